@@ -0,0 +1,110 @@
+//! Deterministic input generators shared by the benchmarks.
+//! A fixed seed keeps runs comparable, so regressions show up as changes in timing rather than in the data.
+
+use fgruc::angles::quaternion::Quaternion;
+use fgruc::geometry::ray::Ray;
+use fgruc::matrix4x4::Matrix4x4;
+use fgruc::rendering::color::UniColor;
+use fgruc::rendering::mesh::Mesh;
+use fgruc::rendering::vertex::Vertex;
+use fgruc::vectors::vector2::Vector2;
+use fgruc::vectors::vector3::Vector3;
+
+/// A small xorshift generator, good enough for benchmark inputs and free of extra dependencies.
+pub struct Rng(u32);
+
+impl Rng {
+
+    /// Creates a new generator from a non-zero seed.
+    pub fn new(seed: u32) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Returns the next random value in [0, 1).
+    pub fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Returns the next random value in [min, max).
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    /// Returns a random vector with every component in [-1, 1).
+    pub fn vector3(&mut self) -> Vector3 {
+        Vector3::new(self.range(-1.0, 1.0), self.range(-1.0, 1.0), self.range(-1.0, 1.0))
+    }
+
+    /// Returns a random unit quaternion.
+    pub fn quaternion(&mut self) -> Quaternion {
+        let q = Quaternion::new(self.range(-1.0, 1.0), self.range(-1.0, 1.0), self.range(-1.0, 1.0), self.range(-1.0, 1.0));
+        let length = q.magnitude_squared().sqrt().max(f32::EPSILON);
+        Quaternion::new(q.w / length, q.x / length, q.y / length, q.z / length)
+    }
+
+    /// Returns a random invertible matrix made of a translation, an axis-angle rotation and a scale.
+    pub fn matrix(&mut self) -> Matrix4x4 {
+        let mut axis = self.vector3();
+        if axis.magnitude_squared() < 1e-4 {
+            axis = Vector3::new(0.0, 1.0, 0.0);
+        }
+        let axis = axis * (1.0 / axis.magnitude_squared().sqrt());
+        let mut rotation = Matrix4x4::identity();
+        rotation.rotate_degrees(self.range(-180.0, 180.0), axis);
+        let scale = Matrix4x4::scale(self.range(0.5, 2.0), self.range(0.5, 2.0), self.range(0.5, 2.0));
+        let translation = Matrix4x4::translate(self.range(-10.0, 10.0), self.range(-10.0, 10.0), self.range(-10.0, 10.0));
+        translation * rotation * scale
+    }
+
+    /// Returns `count` random vectors.
+    pub fn vectors(&mut self, count: usize) -> Vec<Vector3> {
+        (0..count).map(|_| self.vector3()).collect()
+    }
+
+    /// Returns a ray starting somewhere around the unit sphere and pointing roughly at it.
+    pub fn ray_towards_origin(&mut self) -> Ray {
+        let origin = self.vector3() * 3.0;
+        Ray::new(origin, self.vector3() * 0.5 - origin)
+    }
+
+    /// Returns a bumpy heightmap mesh with `resolution` x `resolution` quads.
+    pub fn mesh(&mut self, resolution: u32) -> Mesh {
+        let (a, b) = (self.range(1.0, 4.0), self.range(1.0, 4.0));
+        Mesh::heightmap(Vector2::new(10.0, 10.0), (resolution, resolution), |x, z| (x * a).sin() * (z * b).cos())
+    }
+
+}
+
+/// Returns a unit UV sphere with `segments` around and `rings` from pole to pole,
+/// giving `2 * segments * (rings - 1)` triangles.
+pub fn uv_sphere(segments: u32, rings: u32) -> Mesh {
+    let mut vertices = Vec::new();
+    for ring in 0..=rings {
+        let theta = std::f32::consts::PI * ring as f32 / rings as f32;
+        for segment in 0..=segments {
+            let phi = std::f32::consts::TAU * segment as f32 / segments as f32;
+            let normal = Vector3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin());
+            let uv = Vector2::new(segment as f32 / segments as f32, ring as f32 / rings as f32);
+            vertices.push(Vertex::new(normal, normal, uv, UniColor::from_rgb(255, 255, 255)));
+        }
+    }
+
+    let mut indices = Vec::new();
+    let stride = segments + 1;
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let a = ring * stride + segment;
+            let (b, c, d) = (a + 1, a + stride, a + stride + 1);
+            if ring != 0 {
+                indices.extend_from_slice(&[a, b, c]);
+            }
+            if ring != rings - 1 {
+                indices.extend_from_slice(&[b, d, c]);
+            }
+        }
+    }
+    Mesh::new(vertices, indices)
+}
\ No newline at end of file
@@ -0,0 +1,256 @@
+//! Micro-benchmarks for the core math types and the fast approximations, run with `cargo bench`.
+//!
+//! Every group lives in its own function and is registered in `criterion_group!` at the bottom,
+//! so new features add a function (or a case to an existing group) and a line there.
+
+mod common;
+
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use fgruc::angles::quaternion::Quaternion;
+use fgruc::geometry::ray::Ray;
+use fgruc::math::{fast_acos, fast_atan2, fast_cos, fast_inv_sqrt, fast_sin, fast_tan};
+use fgruc::matrix4x4::Matrix4x4;
+use fgruc::rendering::bvh::MeshBvh;
+use fgruc::rendering::color::{convert_buffer, PixelFormat, UniColor};
+use fgruc::rendering::spatial_grid::SpatialGrid;
+use fgruc::rendering::vertex::{transform_vertices, transform_vertices_par};
+use fgruc::vectors::vector3::Vector3;
+use fgruc::vectors::vector4::Vector4;
+use common::Rng;
+
+/// Number of elements used by the batch benchmarks.
+const BATCH: usize = 1024;
+
+/// A named scalar function benchmarked over a slice of inputs.
+type ScalarCase<'a> = (&'static str, &'a [f32], fn(f32) -> f32);
+
+fn matrix4x4(c: &mut Criterion) {
+    let mut rng = Rng::new(1);
+    let a = rng.matrix();
+    let b = rng.matrix();
+    let points = rng.vectors(BATCH);
+    let v = Vector4::new(0.3, -1.2, 2.5, 1.0);
+    let lhs: Vec<Matrix4x4> = (0..BATCH).map(|_| rng.matrix()).collect();
+    let rhs: Vec<Matrix4x4> = (0..BATCH).map(|_| rng.matrix()).collect();
+    let mut products = vec![Matrix4x4::identity(); BATCH];
+
+    let mut group = c.benchmark_group("matrix4x4");
+    group.bench_function("multiply", |bench| bench.iter(|| black_box(a) * black_box(b)));
+    group.bench_function("multiply_vector4", |bench| bench.iter(|| black_box(a) * black_box(v)));
+    group.bench_function("inverse", |bench| bench.iter(|| black_box(a).inverse()));
+    group.bench_function("determinant", |bench| bench.iter(|| black_box(a).determinant()));
+    group.bench_function("rotate_axis_angle", |bench| {
+        let axis = Vector3::new(0.0, 0.6, 0.8);
+        bench.iter(|| {
+            let mut m = black_box(a);
+            m.rotate_degrees(black_box(37.0), axis);
+            m
+        })
+    });
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("transform_points", |bench| {
+        bench.iter_batched_ref(|| points.clone(), |points| a.transform_points(points), BatchSize::SmallInput)
+    });
+    group.bench_function("mul_many", |bench| {
+        bench.iter(|| Matrix4x4::mul_many(black_box(&mut products), black_box(&lhs), black_box(&rhs)))
+    });
+    group.finish();
+}
+
+fn quaternion(c: &mut Criterion) {
+    let mut rng = Rng::new(2);
+    let a = rng.quaternion();
+    let b = rng.quaternion();
+    let v = rng.vector3();
+    let quats: Vec<Quaternion> = (0..BATCH).map(|_| rng.quaternion()).collect();
+
+    let mut group = c.benchmark_group("quaternion");
+    group.bench_function("slerp", |bench| bench.iter(|| black_box(a).slerp(black_box(b), black_box(0.3))));
+    group.bench_function("lerp", |bench| bench.iter(|| black_box(a).lerp(black_box(b), black_box(0.3))));
+    group.bench_function("multiply", |bench| bench.iter(|| black_box(a) * black_box(b)));
+    group.bench_function("rotate_vector", |bench| bench.iter(|| black_box(a).rotate_vector(black_box(v))));
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("average", |bench| bench.iter(|| Quaternion::average(black_box(&quats))));
+    group.finish();
+}
+
+fn vector3(c: &mut Criterion) {
+    let mut rng = Rng::new(3);
+    let vectors = rng.vectors(BATCH);
+
+    let mut group = c.benchmark_group("vector3");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("normalized_slice", |bench| {
+        bench.iter_batched_ref(
+            || vectors.clone(),
+            |vectors| vectors.iter_mut().for_each(|v| *v = v.normalized()),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("normalized_fast_slice", |bench| {
+        bench.iter_batched_ref(
+            || vectors.clone(),
+            |vectors| vectors.iter_mut().for_each(|v| *v = v.normalized_fast()),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn fast_math(c: &mut Criterion) {
+    let mut rng = Rng::new(4);
+    let angles: Vec<f32> = (0..BATCH).map(|_| rng.range(-3.0, 3.0)).collect();
+    let positive: Vec<f32> = (0..BATCH).map(|_| rng.range(0.01, 100.0)).collect();
+    let cosines: Vec<f32> = (0..BATCH).map(|_| rng.range(-1.0, 1.0)).collect();
+
+    let mut group = c.benchmark_group("fast_math");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    let cases: [ScalarCase; 12] = [
+        ("fast_inv_sqrt", &positive, fast_inv_sqrt),
+        ("std_inv_sqrt", &positive, |x| 1.0 / x.sqrt()),
+        ("fast_sin", &angles, fast_sin),
+        ("std_sin", &angles, f32::sin),
+        ("fast_cos", &angles, fast_cos),
+        ("std_cos", &angles, f32::cos),
+        ("fast_tan", &angles, fast_tan),
+        ("std_tan", &angles, f32::tan),
+        ("fast_acos", &cosines, fast_acos),
+        ("std_acos", &cosines, f32::acos),
+        ("fast_atan2", &angles, |y| fast_atan2(y, 0.5)),
+        ("std_atan2", &angles, |y| y.atan2(0.5)),
+    ];
+    for (name, inputs, f) in cases {
+        group.bench_function(name, |bench| bench.iter(|| black_box(inputs).iter().map(|&x| f(x)).sum::<f32>()));
+    }
+    group.finish();
+}
+
+fn mesh(c: &mut Criterion) {
+    let mut rng = Rng::new(5);
+    let mesh = rng.mesh(64);
+
+    let mut group = c.benchmark_group("mesh");
+    group.throughput(Throughput::Elements(mesh.vertices.len() as u64));
+    group.bench_function("compute_normals", |bench| {
+        bench.iter_batched_ref(|| mesh.clone(), |mesh| mesh.compute_normals(), BatchSize::LargeInput)
+    });
+    group.bench_function("optimize_vertex_cache", |bench| {
+        bench.iter_batched_ref(|| mesh.clone(), |mesh| mesh.optimize_vertex_cache(), BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+fn spatial_grid(c: &mut Criterion) {
+    let mut rng = Rng::new(6);
+    let queries = rng.vectors(64);
+
+    let mut group = c.benchmark_group("spatial_grid");
+    for count in [1_000, 10_000, 100_000] {
+        // Spread the points so the density stays the same and the grid cells hold a few points each.
+        let extent = (count as f32).cbrt();
+        let points: Vec<Vector3> = rng.vectors(count).into_iter().map(|p| p * extent).collect();
+        let grid = SpatialGrid::build(&points, 1.0).unwrap();
+        let queries: Vec<Vector3> = queries.iter().map(|&q| q * extent).collect();
+
+        group.bench_with_input(BenchmarkId::new("nearest_grid", count), &queries, |bench, queries| {
+            bench.iter(|| queries.iter().map(|&q| grid.nearest(q)).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("nearest_brute_force", count), &queries, |bench, queries| {
+            bench.iter(|| {
+                queries.iter()
+                    .map(|q| (0..points.len()).min_by(|&a, &b| points[a].distance_squared(q).total_cmp(&points[b].distance_squared(q))))
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn raycast(c: &mut Criterion) {
+    let mut rng = Rng::new(7);
+    let sphere = common::uv_sphere(160, 157);
+    let bvh = MeshBvh::build(&sphere);
+    let rays: Vec<Ray> = (0..64).map(|_| rng.ray_towards_origin()).collect();
+
+    let mut group = c.benchmark_group("raycast");
+    group.throughput(Throughput::Elements(rays.len() as u64));
+    group.bench_function("mesh_50k_brute_force", |bench| {
+        bench.iter(|| rays.iter().map(|ray| sphere.raycast(ray)).collect::<Vec<_>>())
+    });
+    group.bench_function("mesh_50k_bvh", |bench| {
+        bench.iter(|| rays.iter().map(|ray| bvh.raycast(ray)).collect::<Vec<_>>())
+    });
+    group.finish();
+}
+
+/// Compares `convert_buffer` with the naive conversion going through `UniColor` pixel by pixel.
+fn color(c: &mut Criterion) {
+    let mut rng = Rng::new(9);
+    let pixels = 512 * 512;
+    let src: Vec<u8> = (0..pixels * 4).map(|_| (rng.next_f32() * 256.0) as u8).collect();
+    let mut dst = Vec::with_capacity(src.len());
+
+    let mut group = c.benchmark_group("color");
+    group.throughput(Throughput::Elements(pixels as u64));
+    group.bench_function("convert_buffer_rgba_to_bgra", |bench| {
+        bench.iter(|| convert_buffer(black_box(&src), PixelFormat::Rgba8, &mut dst, PixelFormat::Bgra8))
+    });
+    group.bench_function("unicolor_rgba_to_bgra", |bench| {
+        bench.iter(|| {
+            dst.clear();
+            for p in black_box(&src).chunks_exact(4) {
+                let (r, g, b, a) = UniColor::from_rgba(p[0], p[1], p[2], p[3]).to_rgba();
+                dst.extend_from_slice(&[b, g, r, a]);
+            }
+        })
+    });
+    group.bench_function("convert_buffer_premultiply", |bench| {
+        bench.iter(|| convert_buffer(black_box(&src), PixelFormat::Rgba8, &mut dst, PixelFormat::Rgba8Premultiplied))
+    });
+    group.bench_function("unicolor_premultiply", |bench| {
+        bench.iter(|| {
+            dst.clear();
+            for p in black_box(&src).chunks_exact(4) {
+                let (r, g, b, a) = UniColor::from_rgba(p[0], p[1], p[2], p[3]).to_rgba();
+                let premultiply = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+                dst.extend_from_slice(&[premultiply(r), premultiply(g), premultiply(b), a]);
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Compares the `_par` variants with their serial versions on a large mesh,
+/// run with `cargo bench --features rayon` to see the speedup.
+fn parallel(c: &mut Criterion) {
+    let mut rng = Rng::new(8);
+    let mesh = rng.mesh(512);
+    let matrix = rng.matrix();
+    let points: Vec<Vector3> = mesh.vertices.iter().map(|v| v.position).collect();
+
+    let mut group = c.benchmark_group("parallel");
+    group.throughput(Throughput::Elements(mesh.vertices.len() as u64));
+    group.bench_function("compute_normals", |bench| {
+        bench.iter_batched_ref(|| mesh.clone(), |mesh| mesh.compute_normals(), BatchSize::LargeInput)
+    });
+    group.bench_function("compute_normals_par", |bench| {
+        bench.iter_batched_ref(|| mesh.clone(), |mesh| mesh.compute_normals_par(), BatchSize::LargeInput)
+    });
+    group.bench_function("transform_vertices", |bench| {
+        bench.iter_batched_ref(|| mesh.vertices.clone(), |vertices| transform_vertices(vertices, &matrix), BatchSize::LargeInput)
+    });
+    group.bench_function("transform_vertices_par", |bench| {
+        bench.iter_batched_ref(|| mesh.vertices.clone(), |vertices| transform_vertices_par(vertices, &matrix), BatchSize::LargeInput)
+    });
+    group.bench_function("transform_points", |bench| {
+        bench.iter_batched_ref(|| points.clone(), |points| matrix.transform_points(points), BatchSize::LargeInput)
+    });
+    group.bench_function("transform_points_par", |bench| {
+        bench.iter_batched_ref(|| points.clone(), |points| matrix.transform_points_par(points), BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+criterion_group!(benches, matrix4x4, quaternion, vector3, fast_math, mesh, spatial_grid, raycast, color, parallel);
+criterion_main!(benches);
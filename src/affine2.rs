@@ -0,0 +1,199 @@
+use core::ops::Mul;
+use crate::math::sin_cos;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector2::Vector2;
+
+/// A 2D affine transform stored as a 3x2 matrix, using the same layout as canvas and skia.
+///
+/// A point is transformed as `x' = a * x + c * y + tx` and `y' = b * x + d * y + ty`.
+/// Like [`Matrix4x4`], `a * b` applies `b` first and then `a`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine2 {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f32,
+    pub ty: f32,
+}
+
+impl Affine2 {
+
+    /// Creates a new Affine2 from its six components.
+    #[inline]
+    pub fn new(a: f32, b: f32, c: f32, d: f32, tx: f32, ty: f32) -> Self {
+        Self { a, b, c, d, tx, ty }
+    }
+
+    /// Creates the identity transform.
+    #[inline]
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Creates a translation by `x` and `y`.
+    #[inline]
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Creates a rotation by `radians`, counter-clockwise when y points up.
+    #[inline]
+    pub fn rotate(radians: f32) -> Self {
+        let (sin, cos) = sin_cos(radians);
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Creates a scaling by `x` and `y`.
+    #[inline]
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Creates a shear, moving x by `x` times y and y by `y` times x.
+    #[inline]
+    pub fn shear(x: f32, y: f32) -> Self {
+        Self::new(1.0, y, x, 1.0, 0.0, 0.0)
+    }
+
+    /// Returns the determinant of the linear part.
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Applies this transform to `point`.
+    #[inline]
+    pub fn transform_point(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            self.a * point.x + self.c * point.y + self.tx,
+            self.b * point.x + self.d * point.y + self.ty,
+        )
+    }
+
+    /// Applies only the linear part of this transform to `vector`, ignoring the translation.
+    #[inline]
+    pub fn transform_vector(&self, vector: Vector2) -> Vector2 {
+        Vector2::new(self.a * vector.x + self.c * vector.y, self.b * vector.x + self.d * vector.y)
+    }
+
+    /// Returns the inverse of this transform or `None` if it is singular.
+    pub fn invert(&self) -> Option<Affine2> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let a = self.d * inv_det;
+        let b = -self.b * inv_det;
+        let c = -self.c * inv_det;
+        let d = self.a * inv_det;
+        Some(Affine2::new(a, b, c, d, -(a * self.tx + c * self.ty), -(b * self.tx + d * self.ty)))
+    }
+
+    /// Embeds this transform into a 4x4 matrix acting on the XY plane, leaving z and w untouched.
+    pub fn to_matrix4x4(&self) -> Matrix4x4 {
+        Matrix4x4::from_array([
+            self.a, self.c, 0.0, self.tx,
+            self.b, self.d, 0.0, self.ty,
+            0.0,    0.0,    1.0, 0.0,
+            0.0,    0.0,    0.0, 1.0,
+        ])
+    }
+
+}
+
+impl Default for Affine2 {
+    fn default() -> Self {
+        Affine2::identity()
+    }
+}
+
+impl Mul<Affine2> for Affine2 {
+    type Output = Affine2;
+
+    fn mul(self, other: Affine2) -> Affine2 {
+        Affine2::new(
+            self.a * other.a + self.c * other.b,
+            self.b * other.a + self.d * other.b,
+            self.a * other.c + self.c * other.d,
+            self.b * other.c + self.d * other.d,
+            self.a * other.tx + self.c * other.ty + self.tx,
+            self.b * other.tx + self.d * other.ty + self.ty,
+        )
+    }
+}
+
+impl Mul<Vector2> for Affine2 {
+    type Output = Vector2;
+
+    fn mul(self, point: Vector2) -> Vector2 {
+        self.transform_point(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::vectors::vector3::Vector3;
+    use super::*;
+
+    fn approx_eq(a: &Affine2, b: &Affine2, epsilon: f32) -> bool {
+        [a.a - b.a, a.b - b.b, a.c - b.c, a.d - b.d, a.tx - b.tx, a.ty - b.ty].iter().all(|d| d.abs() <= epsilon)
+    }
+
+    fn sample() -> Affine2 {
+        Affine2::translate(3.0, -2.0) * Affine2::rotate(0.7) * Affine2::scale(2.0, 0.5) * Affine2::shear(0.3, -0.1)
+    }
+
+    #[test]
+    fn round_trips_through_matrix4x4() {
+        let affine = sample();
+        assert_eq!(affine.to_matrix4x4().to_affine2(), Some(affine));
+
+        // Both sides transform points the same way, whatever their z.
+        let matrix = affine.to_matrix4x4();
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.5, -2.0, 0.0), (-3.0, 4.0, 7.0)] {
+            let p = matrix.transform_point(Vector3::new(x, y, z));
+            let q = affine.transform_point(Vector2::new(x, y));
+            assert!(Vector2::new(p.x, p.y).approx_eq(&q, 1e-5) && p.z == z, "{:?} {:?}", p, q);
+        }
+    }
+
+    #[test]
+    fn matches_the_matrix4x4_constructors() {
+        let matrix = Matrix4x4::translate(3.0, -2.0, 5.0) * Matrix4x4::rotation_z(0.7) * Matrix4x4::scale(2.0, 0.5, 4.0);
+        let expected = Affine2::translate(3.0, -2.0) * Affine2::rotate(0.7) * Affine2::scale(2.0, 0.5);
+        assert!(approx_eq(&matrix.to_affine2().unwrap(), &expected, 1e-6), "{:?}", matrix.to_affine2());
+
+        let (a, b) = (sample(), Affine2::rotate(-1.2) * Affine2::translate(0.5, 8.0));
+        let product = (a.to_matrix4x4() * b.to_matrix4x4()).to_affine2().unwrap();
+        assert!(approx_eq(&product, &(a * b), 1e-5), "{:?}", product);
+    }
+
+    #[test]
+    fn non_affine_matrices_are_rejected() {
+        assert!(Matrix4x4::perspective(1.0, 1.5, 0.1, 100.0).unwrap().to_affine2().is_none());
+        // x and y depend on z.
+        assert!(Matrix4x4::rotation_x(0.5).to_affine2().is_none());
+        assert!(Matrix4x4::rotation_y(0.5).to_affine2().is_none());
+        let mut scaled_w = Matrix4x4::identity();
+        scaled_w.data[15] = 2.0;
+        assert!(scaled_w.to_affine2().is_none());
+        // The z row does not matter.
+        assert_eq!(Matrix4x4::scale(1.0, 1.0, 0.0).to_affine2(), Some(Affine2::identity()));
+    }
+
+    #[test]
+    fn invert_undoes_the_transform() {
+        let affine = sample();
+        let inverse = affine.invert().unwrap();
+        assert!(approx_eq(&(affine * inverse), &Affine2::identity(), 1e-5));
+        assert!(approx_eq(&(inverse * affine), &Affine2::identity(), 1e-5));
+        let p = Vector2::new(-4.0, 9.0);
+        assert!((inverse * (affine * p)).approx_eq(&p, 1e-4));
+        assert!(Affine2::scale(0.0, 1.0).invert().is_none());
+        assert!(Affine2::scale(f32::INFINITY, 1.0).invert().is_none());
+    }
+
+}
\ No newline at end of file
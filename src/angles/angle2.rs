@@ -1,7 +1,16 @@
-use crate::math::{fast_cos, fast_sin};
+use core::ops::{Add, Mul, Neg, Sub};
+use crate::angles::quaternion::Quaternion;
+use crate::math::{default_sin_cos, fast_cos, fast_sin, wrap_angle, Lerp};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
 use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
 
 /// A 2D angle for representing a rotation in 2d space.
+///
+/// The arithmetic operators work on the raw radians and never wrap, use `normalized` to bring the angle back into (-π, π].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Angle2 {
     radians: f32,
 }
@@ -28,12 +37,188 @@ impl Angle2 {
         self.radians
     }
 
+    /// Returns the angle of `vector` measured counter-clockwise from the +X axis, in (-π, π].
+    /// The zero vector gives an angle of zero.
+    #[inline]
+    pub fn from_vector(vector: Vector2) -> Self {
+        // Adding zero turns -0.0 into 0.0, for which atan2 would return -π.
+        Self { radians: (vector.y + 0.0).atan2(vector.x) }
+    }
+
+    /// Returns the unit vector pointing in the direction of this angle, the inverse of `from_vector`.
+    #[inline]
+    pub fn to_vector(&self) -> Vector2 {
+        let (sin_theta, cos_theta) = default_sin_cos(self.radians);
+        Vector2::new(cos_theta, sin_theta)
+    }
+
+    /// Returns the same direction wrapped into (-π, π], so -π becomes π.
+    #[inline]
+    pub fn normalized(&self) -> Angle2 {
+        Self { radians: wrap_angle(self.radians) }
+    }
+
+    /// Returns the signed shortest turn from this angle to `other` in (-π, π], positive counter-clockwise.
+    /// Opposite directions give π.
+    #[inline]
+    pub fn angle_between(&self, other: &Angle2) -> Angle2 {
+        (*other - *self).normalized()
+    }
+
+    /// Interpolates along the shortest arc towards `other`, so going from 350° to 10° passes through 0° instead of 180°.
+    /// The result is not normalized, 350° to 10° at `t = 0.5` gives 360°.
+    #[inline]
+    pub fn lerp(&self, other: &Angle2, t: f32) -> Angle2 {
+        *self + self.angle_between(other) * t
+    }
+
     /// Takes `vector` and rotates it by this angle.
     /// Returns the rotated Vector.
     pub fn rotate_vector(&self, vector: Vector2) -> Vector2 {
-        let cos_theta = fast_cos(self.radians);
-        let sin_theta = fast_sin(self.radians);
-        Vector2::new(cos_theta * vector.x - sin_theta * vector.y, sin_theta * vector.x + cos_theta * vector.y)
+        let (sin_theta, cos_theta) = default_sin_cos(self.radians);
+        rotate_with(vector, sin_theta, cos_theta)
+    }
+
+    /// Like `rotate_vector`, but uses `fast_sin` and `fast_cos`, see them for their accuracy.
+    pub fn rotate_vector_fast(&self, vector: Vector2) -> Vector2 {
+        rotate_with(vector, fast_sin(self.radians), fast_cos(self.radians))
+    }
+
+    /// Returns the rotation by this angle around `axis` in 3D space, e.g. to roll a 2D sprite around the view axis.
+    /// The axis gets normalized, positive angles rotate counter-clockwise when looking down the axis towards the origin.
+    pub fn to_quaternion(&self, axis: Vector3) -> Quaternion {
+        Quaternion::from_axis_angle(axis, self.radians)
+    }
+
+}
+
+impl Add for Angle2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self { radians: self.radians + other.radians }
+    }
+}
+
+impl Sub for Angle2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self { radians: self.radians - other.radians }
+    }
+}
+
+impl Mul<f32> for Angle2 {
+    type Output = Self;
+
+    fn mul(self, scalar: f32) -> Self {
+        Self { radians: self.radians * scalar }
+    }
+}
+
+impl Neg for Angle2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { radians: -self.radians }
+    }
+}
+
+impl Lerp for Angle2 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Angle2::lerp(self, other, t)
+    }
+}
+
+/// Rotates `vector` counter-clockwise by the angle with the given sine and cosine.
+#[inline]
+fn rotate_with(vector: Vector2, sin_theta: f32, cos_theta: f32) -> Vector2 {
+    Vector2::new(cos_theta * vector.x - sin_theta * vector.y, sin_theta * vector.x + cos_theta * vector.y)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::f32::consts::PI;
+    use super::*;
+
+    #[test]
+    fn rotate_vector_fast_stays_within_the_fast_sin_error() {
+        let vector = Vector2::new(3.0, -4.0);
+        for i in -1000..1000 {
+            let angle = Angle2::from_radians(i as f32 * 0.031);
+            let (exact, fast) = (angle.rotate_vector(vector), angle.rotate_vector_fast(vector));
+            // Both components mix x and y with a sine and a cosine off by at most 0.0012 each.
+            let bound = 0.0012 * (3.0 + 4.0) + 1e-5;
+            assert!((exact.x - fast.x).abs() <= bound && (exact.y - fast.y).abs() <= bound, "{:?} {:?}", exact, fast);
+        }
+    }
+
+    #[test]
+    fn normalized_wraps_into_the_half_open_range() {
+        assert_eq!(Angle2::from_radians(PI).normalized().to_radians(), PI);
+        assert_eq!(Angle2::from_radians(-PI).normalized().to_radians(), PI);
+        assert_eq!(Angle2::from_radians(0.0).normalized().to_radians(), 0.0);
+        for degrees in [-721.0, -540.0, -181.0, -179.0, 179.0, 181.0, 540.0, 1000.0] {
+            let wrapped = Angle2::from_degrees(degrees).normalized().to_degrees();
+            assert!(wrapped > -180.0 && wrapped <= 180.0, "{} -> {}", degrees, wrapped);
+            assert!(((degrees - wrapped) / 360.0 - ((degrees - wrapped) / 360.0).round()).abs() < 1e-4, "{} -> {}", degrees, wrapped);
+        }
+        // Just past the boundary on either side.
+        assert!((Angle2::from_degrees(181.0).normalized().to_degrees() + 179.0).abs() < 1e-3);
+        assert!((Angle2::from_degrees(-181.0).normalized().to_degrees() - 179.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn angle_between_takes_the_shortest_turn() {
+        let between = |a: f32, b: f32| Angle2::from_degrees(a).angle_between(&Angle2::from_degrees(b)).to_degrees();
+        assert!((between(170.0, -170.0) - 20.0).abs() < 1e-3);
+        assert!((between(-170.0, 170.0) + 20.0).abs() < 1e-3);
+        assert!((between(350.0, 10.0) - 20.0).abs() < 1e-3);
+        assert!((between(10.0, 350.0) + 20.0).abs() < 1e-3);
+        // Opposite directions give a positive half turn either way.
+        assert!((between(0.0, 180.0) - 180.0).abs() < 1e-3);
+        assert!((between(90.0, -90.0) - 180.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn lerp_crosses_zero_instead_of_going_around() {
+        let (from, to) = (Angle2::from_degrees(350.0), Angle2::from_degrees(10.0));
+        for (t, expected) in [(0.0, 350.0), (0.25, 355.0), (0.5, 360.0), (1.0, 370.0)] {
+            let angle = from.lerp(&to, t).to_degrees();
+            assert!((angle - expected).abs() < 1e-3, "{}: {}", t, angle);
+        }
+        assert!(from.lerp(&to, 0.5).normalized().to_degrees().abs() < 1e-3);
+        assert!(to.lerp(&from, 0.5).normalized().to_degrees().abs() < 1e-3);
+        // The `Lerp` trait uses the same arc.
+        assert_eq!(Lerp::lerp(&from, &to, 0.25), from.lerp(&to, 0.25));
+    }
+
+    #[test]
+    fn vectors_and_operators_round_trip() {
+        assert_eq!(Angle2::from_vector(Vector2::new(-1.0, -0.0)).to_radians(), PI);
+        assert_eq!(Angle2::from_vector(Vector2::new(0.0, 0.0)).to_radians(), 0.0);
+        for degrees in [-170.0, -90.0, 0.0, 45.0, 135.0, 180.0] {
+            let angle = Angle2::from_degrees(degrees);
+            let back = Angle2::from_vector(angle.to_vector() * 3.0);
+            // Loose enough for the `fast_math` sine and cosine.
+            assert!(back.angle_between(&angle).to_radians().abs() < 2e-3, "{}: {:?}", degrees, back);
+        }
+        let (a, b) = (Angle2::from_radians(1.0), Angle2::from_radians(0.25));
+        assert_eq!((a + b).to_radians(), 1.25);
+        assert_eq!((a - b).to_radians(), 0.75);
+        assert_eq!((a * 4.0).to_radians(), 4.0);
+        assert_eq!((-a).to_radians(), -1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format_is_stable() {
+        let angle = Angle2::from_radians(1.25);
+        let json = serde_json::to_string(&angle).unwrap();
+        assert_eq!(json, r#"{"radians":1.25}"#);
+        assert_eq!(serde_json::from_str::<Angle2>(&json).unwrap(), angle);
     }
 
 }
\ No newline at end of file
@@ -1,10 +1,18 @@
-use crate::angles::quaternion::Quaternion;
-use crate::math::{fast_cos, fast_sin};
-use crate::types::Axis;
+use core::f32::consts::{FRAC_PI_2, TAU};
+use core::fmt::{Display, Formatter};
+use crate::angles::quaternion::{Quaternion, GIMBAL_LOCK_SIN};
+use crate::math::{default_sin_cos, fast_cos, fast_sin, parse_exact, sin_cos, wrap_angle, Lerp, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::types::{Axis, RotationOrder};
+use crate::vectors::vector3::Vector3;
 
 /// A Euler Angle representing a rotation around the X, Y, and Z axes.
 /// This is just like Quaternion, but less complex.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Euler {
     pub pitch: f32,
     pub yaw: f32,
@@ -19,50 +27,117 @@ impl Euler {
         Self { pitch, yaw, roll }
     }
 
+    /// Creates a new Euler angle struct with the given pitch, yaw, and roll values in degrees.
+    #[inline]
+    pub fn from_degrees(pitch: f32, yaw: f32, roll: f32) -> Self {
+        Self::new(pitch.to_radians(), yaw.to_radians(), roll.to_radians())
+    }
+
+    /// Returns the pitch, yaw, and roll in degrees.
+    #[inline]
+    pub fn to_degrees(&self) -> (f32, f32, f32) {
+        (self.pitch.to_degrees(), self.yaw.to_degrees(), self.roll.to_degrees())
+    }
+
     /// Creates a new Euler angle struct with pitch, yaw, and roll set to 0.
     #[inline]
     pub fn zero() -> Self {
         Self::new(0.0, 0.0, 0.0)
     }
 
+    /// Returns a copy with the pitch replaced by the given value in radians.
+    #[inline]
+    pub fn with_pitch(&self, pitch: f32) -> Self {
+        Self { pitch, ..*self }
+    }
+
+    /// Returns a copy with the pitch replaced by the given value in degrees.
+    #[inline]
+    pub fn with_pitch_degrees(&self, degrees: f32) -> Self {
+        self.with_pitch(degrees.to_radians())
+    }
+
+    /// Returns a copy with the yaw replaced by the given value in radians.
+    #[inline]
+    pub fn with_yaw(&self, yaw: f32) -> Self {
+        Self { yaw, ..*self }
+    }
+
+    /// Returns a copy with the yaw replaced by the given value in degrees.
+    #[inline]
+    pub fn with_yaw_degrees(&self, degrees: f32) -> Self {
+        self.with_yaw(degrees.to_radians())
+    }
+
+    /// Returns a copy with the roll replaced by the given value in radians.
+    #[inline]
+    pub fn with_roll(&self, roll: f32) -> Self {
+        Self { roll, ..*self }
+    }
+
+    /// Returns a copy with the roll replaced by the given value in degrees.
+    #[inline]
+    pub fn with_roll_degrees(&self, degrees: f32) -> Self {
+        self.with_roll(degrees.to_radians())
+    }
+
+    /// Converts the quaternion to euler angles, the inverse of `to_quaternion`.
+    /// The same as `from_quaternion_ordered` with `RotationOrder::XYZ`, see it for the gimbal lock handling.
+    #[inline]
     pub fn from_quaternion(q: &Quaternion) -> Self {
-        let qw = q.w;
-        let qx = q.x;
-        let qy = q.y;
-        let qz = q.z;
-
-        let sinr_cosp = 2.0 * (qw * qx + qy * qz);
-        let cosr_cosp = 1.0 - 2.0 * (qx * qx + qy * qy);
-
-        let sinp = 2.0 * (qw * qy - qz * qx);
-        let pitch: f32;
-        if sinp.abs() >= 1.0 {
-            pitch = (std::f32::consts::PI / 2.0) * sinp.signum();
-        } else {
-            pitch = sinp.asin();
-        }
+        Self::from_quaternion_ordered(q, RotationOrder::XYZ)
+    }
+
+    /// Converts the quaternion to euler angles whose rotations applied in `order` give the same rotation,
+    /// the inverse of `to_quaternion_ordered`. The pitch turns around x, the yaw around y and the roll around z.
+    ///
+    /// The angles of the first and the last axis are in [-π, π], the one of the middle axis in [-π/2, π/2].
+    /// When the middle angle is (within float precision of) ±π/2, the first and the last rotation turn around the same axis
+    /// and only their combination is defined. The middle angle is then snapped to ±π/2, the first angle is set to 0
+    /// and the whole rotation around that axis goes into the last one.
+    #[inline]
+    pub fn from_quaternion_ordered(q: &Quaternion, order: RotationOrder) -> Self {
+        Self::from_quaternion_ordered_checked(q, order).0
+    }
 
-        let siny_cosp = 2.0 * (qw * qz + qx * qy);
-        let cosy_cosp = 1.0 - 2.0 * (qy * qy + qz * qz);
+    /// Like `from_quaternion_ordered`, but also returns whether the conversion hit the singular branch.
+    pub(crate) fn from_quaternion_ordered_checked(q: &Quaternion, order: RotationOrder) -> (Self, bool) {
+        let m = rotation_rows(q);
+        let [i, j, k] = order.axes().map(Axis::index);
+        // The orders going backwards through x, y, z flip the signs of the off-diagonal elements used below.
+        let sign = if order.is_cyclic() { 1.0 } else { -1.0 };
 
-        Self {
-            roll: sinr_cosp.atan2(cosr_cosp),
-            pitch,
-            yaw: siny_cosp.atan2(cosy_cosp),
+        let mut angles = [0.0; 3];
+        let sin_middle = -sign * m[k][i];
+        let locked = sin_middle.abs() >= GIMBAL_LOCK_SIN;
+        if locked {
+            angles[j] = FRAC_PI_2.copysign(sin_middle);
+            angles[k] = (-sign * m[i][j]).atan2(m[j][j]);
+        } else {
+            angles[i] = (sign * m[k][j]).atan2(m[k][k]);
+            angles[j] = sin_middle.asin();
+            angles[k] = (sign * m[j][i]).atan2(m[i][i]);
         }
+        (Euler::new(angles[0], angles[1], angles[2]), locked)
+    }
+
+    /// Returns true if the yaw, the middle angle of `to_quaternion`, is within `epsilon` radians of ±π/2,
+    /// where pitch and roll rotate around the same axis. Angles that differ by a full turn count as well.
+    #[inline]
+    pub fn is_near_gimbal_lock(&self, epsilon: f32) -> bool {
+        self.yaw.cos().abs() <= epsilon.sin()
     }
 
+    /// Converts to a quaternion turning by the pitch around x first, then by the yaw around y and the roll around z,
+    /// the default `RotationOrder::XYZ`. See `to_quaternion_ordered` for the other orders.
     pub fn to_quaternion(&self) -> Quaternion {
         let half_pitch = self.pitch * 0.5;
         let half_yaw = self.yaw * 0.5;
         let half_roll = self.roll * 0.5;
 
-        let sin_pitch = half_pitch.sin();
-        let cos_pitch = half_pitch.cos();
-        let sin_yaw = half_yaw.sin();
-        let cos_yaw = half_yaw.cos();
-        let sin_roll = half_roll.sin();
-        let cos_roll = half_roll.cos();
+        let (sin_pitch, cos_pitch) = sin_cos(half_pitch);
+        let (sin_yaw, cos_yaw) = sin_cos(half_yaw);
+        let (sin_roll, cos_roll) = sin_cos(half_roll);
 
         Quaternion {
             w: cos_pitch * cos_yaw * cos_roll + sin_pitch * sin_yaw * sin_roll,
@@ -72,35 +147,92 @@ impl Euler {
         }
     }
 
-    /// Rotate the Euler angles around the x, y, and z axes by the given angles in radians
+    /// Converts to a quaternion applying the rotations in `order`, with the pitch turning around x, the yaw around y
+    /// and the roll around z. `RotationOrder::XYZ` gives the same rotation as `to_quaternion`.
+    pub fn to_quaternion_ordered(&self, order: RotationOrder) -> Quaternion {
+        let [first, second, third] = order.axes().map(|axis| Quaternion::from_axis(axis, self.angle(axis)));
+        third * second * first
+    }
+
+    /// Returns the angle turning around `axis`.
+    #[inline]
+    fn angle(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::X => self.pitch,
+            Axis::Y => self.yaw,
+            Axis::Z => self.roll,
+        }
+    }
+
+    /// Rotate the Euler angles around the x, y, and z axes by the given angles in radians.
+    /// The angles are not wrapped, so they grow without bounds when rotating repeatedly, see `rotate_normalized`.
     pub fn rotate(&mut self, x_angle: f32, y_angle: f32, z_angle: f32) {
         self.roll += x_angle;
         self.pitch += y_angle;
         self.yaw += z_angle;
     }
 
+    /// Like `rotate`, but wraps every angle into (-π, π] afterwards (see `normalize`), so they stay precise when rotating every frame.
+    pub fn rotate_normalized(&mut self, x_angle: f32, y_angle: f32, z_angle: f32) {
+        self.rotate(x_angle, y_angle, z_angle);
+        self.normalize();
+    }
+
+    /// Wraps every angle into (-π, π] without changing the rotation, like `integrate` and `move_towards` do.
+    /// -π wraps to π, so 3π and -3π both become π, and -0.0 becomes 0.0.
+    pub fn normalize(&mut self) {
+        self.pitch = wrap_angle(self.pitch);
+        self.yaw = wrap_angle(self.yaw);
+        self.roll = wrap_angle(self.roll);
+    }
+
+    /// Returns a copy with every angle wrapped into (-π, π], see `normalize`.
+    #[inline]
+    pub fn normalized(&self) -> Euler {
+        let mut normalized = *self;
+        normalized.normalize();
+        normalized
+    }
+
+    /// Like `normalize`, but wraps the yaw into [0, 2π) instead, like a compass heading. Pitch and roll are wrapped into (-π, π].
+    pub fn wrap_yaw_0_tau(&mut self) {
+        self.normalize();
+        if self.yaw < 0.0 {
+            self.yaw += TAU;
+            // Tiny negative angles round up to exactly 2π, which belongs to 0 instead.
+            if self.yaw >= TAU {
+                self.yaw = 0.0;
+            }
+        }
+    }
+
     /// Rotate the Euler angles around a given `Axis` by a given angle in radians
     pub fn rotate_around(&mut self, axis: Axis, angle: f32) {
+        let (sin_angle, cos_angle) = default_sin_cos(angle);
+        self.rotate_around_with(axis, sin_angle, cos_angle);
+    }
+
+    /// Like `rotate_around`, but uses `fast_sin` and `fast_cos`, see them for their accuracy.
+    pub fn rotate_around_fast(&mut self, axis: Axis, angle: f32) {
+        self.rotate_around_with(axis, fast_sin(angle), fast_cos(angle));
+    }
+
+    /// Rotates the Euler angles around `axis` by the angle with the given sine and cosine.
+    fn rotate_around_with(&mut self, axis: Axis, sin_angle: f32, cos_angle: f32) {
         match axis {
             Axis::X => {
-                let cos_angle = fast_cos(angle);
-                let sin_angle = fast_sin(angle);
                 let new_pitch = self.pitch * cos_angle - self.yaw * sin_angle;
                 let new_yaw = self.pitch * sin_angle + self.yaw * cos_angle;
                 self.pitch = new_pitch;
                 self.yaw = new_yaw;
             },
             Axis::Y => {
-                let cos_angle = fast_cos(angle);
-                let sin_angle = fast_sin(angle);
                 let new_roll = self.roll * cos_angle + self.yaw * sin_angle;
                 let new_yaw = -self.roll * sin_angle + self.yaw * cos_angle;
                 self.roll = new_roll;
                 self.yaw = new_yaw;
             },
             Axis::Z => {
-                let cos_angle = fast_cos(angle);
-                let sin_angle = fast_sin(angle);
                 let new_roll = self.roll * cos_angle - self.pitch * sin_angle;
                 let new_pitch = self.roll * sin_angle + self.pitch * cos_angle;
                 self.roll = new_roll;
@@ -108,4 +240,309 @@ impl Euler {
             },
         }
     }
+
+    /// Turns the angles at `angular_rates` radians per second for `dt` seconds, with x driving the pitch,
+    /// y the yaw and z the roll like in `to_quaternion`. Every angle is wrapped into (-π, π] afterwards.
+    pub fn integrate(&mut self, angular_rates: Vector3, dt: f32) {
+        self.pitch = wrap_angle(self.pitch + angular_rates.x * dt);
+        self.yaw = wrap_angle(self.yaw + angular_rates.y * dt);
+        self.roll = wrap_angle(self.roll + angular_rates.z * dt);
+    }
+
+    /// Turns every angle towards `target` the shortest way around the circle, by at most `max_rates` radians per second
+    /// over `dt` seconds, with x limiting the pitch, y the yaw and z the roll. Never overshoots, angles within reach
+    /// end up exactly at the target. Angles still on their way are wrapped into (-π, π].
+    pub fn move_towards(&self, target: &Euler, max_rates: Vector3, dt: f32) -> Euler {
+        let step = |from: f32, to: f32, rate: f32| {
+            let delta = wrap_angle(to - from);
+            let max_step = (rate * dt).abs();
+            if delta.abs() <= max_step {
+                to
+            } else {
+                wrap_angle(from + max_step.copysign(delta))
+            }
+        };
+        Euler::new(
+            step(self.pitch, target.pitch, max_rates.x),
+            step(self.yaw, target.yaw, max_rates.y),
+            step(self.roll, target.roll, max_rates.z),
+        )
+    }
+
+    /// Formats this rotation in pitch, yaw, roll order as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&[self.pitch, self.yaw, self.roll])
+    }
+
+    /// Parses a rotation in pitch, yaw, roll order written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let [pitch, yaw, roll] = parse_exact::<3>(s)?;
+        Ok(Self::new(pitch, yaw, roll))
+    }
+}
+
+/// Formats the angles in degrees, like `pitch 30.0° yaw -90.0° roll 0.0°`.
+/// Uses one decimal place unless a precision is given, e.g. `{:.3}`.
+impl Display for Euler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision().unwrap_or(1);
+        write!(
+            f,
+            "pitch {:.*}° yaw {:.*}° roll {:.*}°",
+            precision, self.pitch.to_degrees(), precision, self.yaw.to_degrees(), precision, self.roll.to_degrees(),
+        )
+    }
 }
+
+/// Interpolates every angle along the shortest way around the circle, so 170° to -170° passes through 180° instead of 0°.
+impl Lerp for Euler {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let lerp_angle = |a: f32, b: f32| a + wrap_angle(b - a) * t;
+        Euler::new(lerp_angle(self.pitch, other.pitch), lerp_angle(self.yaw, other.yaw), lerp_angle(self.roll, other.roll))
+    }
+}
+
+/// Returns the rows of the rotation matrix of `q`, scaled like `Quaternion::rotate_vector` so it needn't be normalized.
+/// A zero quaternion gives the identity.
+fn rotation_rows(q: &Quaternion) -> [[f32; 3]; 3] {
+    let n = q.magnitude_squared();
+    let s = if n > 0.0 { 2.0 / n } else { 0.0 };
+    let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+    [
+        [1.0 - s * (y * y + z * z), s * (x * y - w * z), s * (x * z + w * y)],
+        [s * (x * y + w * z), 1.0 - s * (x * x + z * z), s * (y * z - w * x)],
+        [s * (x * z - w * y), s * (y * z + w * x), 1.0 - s * (x * x + y * y)],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::f32::consts::PI;
+    use super::*;
+
+    fn set_angle(euler: &mut Euler, axis: Axis, angle: f32) {
+        match axis {
+            Axis::X => euler.pitch = angle,
+            Axis::Y => euler.yaw = angle,
+            Axis::Z => euler.roll = angle,
+        }
+    }
+
+    #[test]
+    fn ordered_round_trip_on_a_grid() {
+        let steps = 12;
+        for order in RotationOrder::ALL {
+            let [first, middle, last] = order.axes();
+            for a in 0..=steps {
+                for b in 1..steps {
+                    for c in 0..=steps {
+                        // Just inside (-π, π) for the outer angles, so they don't flip to the other end.
+                        let outer = |n: i32| (-PI + TAU * n as f32 / steps as f32) * 0.999;
+                        let mut euler = Euler::zero();
+                        set_angle(&mut euler, first, outer(a));
+                        set_angle(&mut euler, middle, -FRAC_PI_2 + PI * b as f32 / steps as f32);
+                        set_angle(&mut euler, last, outer(c));
+
+                        let q = euler.to_quaternion_ordered(order);
+                        let (back, locked) = Euler::from_quaternion_ordered_checked(&q, order);
+                        assert!(!locked, "{:?} {:?}", order, euler);
+                        assert!(
+                            (back.pitch - euler.pitch).abs() < 1e-4 && (back.yaw - euler.yaw).abs() < 1e-4 && (back.roll - euler.roll).abs() < 1e-4,
+                            "{:?}: {:?} came back as {:?}", order, euler, back,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordered_lock_snaps_the_middle_angle() {
+        for order in RotationOrder::ALL {
+            let [first, middle, last] = order.axes();
+            for sign in [1.0, -1.0] {
+                let mut euler = Euler::zero();
+                set_angle(&mut euler, first, 0.4);
+                set_angle(&mut euler, middle, FRAC_PI_2 * sign);
+                set_angle(&mut euler, last, -0.7);
+
+                let q = euler.to_quaternion_ordered(order);
+                let (back, locked) = Euler::from_quaternion_ordered_checked(&q, order);
+                assert!(locked, "{:?} {:?}", order, euler);
+                assert_eq!(back.angle(first), 0.0);
+                assert_eq!(back.angle(middle), FRAC_PI_2 * sign);
+                assert!(back.to_quaternion_ordered(order).approx_eq_rotation(&q, 1e-6), "{:?}: {:?}", order, back);
+            }
+        }
+    }
+
+    #[test]
+    fn xyz_lock_moves_the_pitch_into_the_roll() {
+        let back = Euler::from_quaternion(&Euler::new(0.4, FRAC_PI_2, -0.7).to_quaternion());
+        assert!(back.pitch == 0.0 && back.yaw == FRAC_PI_2 && (back.roll + 1.1).abs() < 1e-5, "{:?}", back);
+        let back = Euler::from_quaternion(&Euler::new(0.4, -FRAC_PI_2, -0.7).to_quaternion());
+        assert!(back.pitch == 0.0 && back.yaw == -FRAC_PI_2 && (back.roll + 0.3).abs() < 1e-5, "{:?}", back);
+    }
+
+    #[test]
+    fn ordered_matches_reference_values() {
+        // Computed as the product of the axis rotations, the first one matches Blender's Euler((0.1, 0.2, 0.3), 'XYZ').
+        let cases = [
+            (Euler::new(0.1, 0.2, 0.3), RotationOrder::XYZ, Quaternion::new(0.9833474, 0.0342708, 0.1060205, 0.1435722)),
+            (Euler::new(0.1, 0.2, 0.3), RotationOrder::ZYX, Quaternion::new(0.9818562, 0.0640713, 0.0911575, 0.1534393)),
+            (Euler::new(0.4, 1.5, -0.7), RotationOrder::XYZ, Quaternion::new(0.6271923, 0.3656245, 0.5777042, -0.3731038)),
+            (Euler::new(0.4, -1.5, -0.7), RotationOrder::ZYX, Quaternion::new(0.6271923, 0.3656245, -0.5777042, -0.3731038)),
+        ];
+        for (euler, order, expected) in cases {
+            let q = euler.to_quaternion_ordered(order);
+            assert!(q.approx_eq(&expected, 1e-6), "{:?} {:?} gave {:?}", euler, order, q);
+            let back = Euler::from_quaternion_ordered(&expected, order);
+            assert!(
+                (back.pitch - euler.pitch).abs() < 1e-5 && (back.yaw - euler.yaw).abs() < 1e-5 && (back.roll - euler.roll).abs() < 1e-5,
+                "{:?} {:?} came back as {:?}", euler, order, back,
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_wraps_into_the_half_open_range() {
+        for (angle, expected) in [(3.0 * PI, PI), (-3.0 * PI, PI), (PI, PI), (-PI, PI), (TAU, 0.0), (-0.5, -0.5), (4.0, 4.0 - TAU)] {
+            let wrapped = Euler::new(angle, angle, angle).normalized();
+            assert!((wrapped.pitch - expected).abs() < 1e-6, "{} wrapped to {}", angle, wrapped.pitch);
+            assert!(wrapped.pitch > -PI && wrapped.pitch <= PI);
+        }
+        // Tiny angles just below 0 and just above -π must not round onto the excluded -π or past π.
+        for angle in [-1e-9, -PI + 1e-7, PI + 1e-7, -PI - 1e-7, 1e20, -1e20] {
+            let wrapped = Euler::new(angle, 0.0, 0.0).normalized().pitch;
+            assert!(wrapped > -PI && wrapped <= PI, "{} wrapped to {}", angle, wrapped);
+        }
+        assert!(Euler::new(-0.0, -0.0, -0.0).normalized().pitch.is_sign_positive());
+    }
+
+    #[test]
+    fn normalize_keeps_the_rotation() {
+        let euler = Euler::new(7.0, -9.5, 3.0 * PI + 0.2);
+        let normalized = euler.normalized();
+        assert!(normalized.to_quaternion().approx_eq_rotation(&euler.to_quaternion(), 1e-5));
+        let back = Euler::from_quaternion(&normalized.to_quaternion());
+        assert!(back.to_quaternion().approx_eq_rotation(&euler.to_quaternion(), 1e-5));
+    }
+
+    #[test]
+    fn wrap_yaw_0_tau_gives_compass_headings() {
+        let mut euler = Euler::new(3.0 * PI, -FRAC_PI_2, 0.0);
+        euler.wrap_yaw_0_tau();
+        assert!((euler.pitch - PI).abs() < 1e-6);
+        assert!((euler.yaw - 1.5 * PI).abs() < 1e-6);
+        let mut euler = Euler::new(0.0, -1e-9, 0.0);
+        euler.wrap_yaw_0_tau();
+        assert!(euler.yaw >= 0.0 && euler.yaw < TAU);
+    }
+
+    #[test]
+    fn from_quaternion_inverts_to_quaternion() {
+        let euler = Euler::new(0.5, -0.3, 1.2);
+        let back = Euler::from_quaternion(&euler.to_quaternion());
+        assert!((back.pitch - 0.5).abs() < 1e-5 && (back.yaw + 0.3).abs() < 1e-5 && (back.roll - 1.2).abs() < 1e-5, "{:?}", back);
+        assert!(euler.to_quaternion().approx_eq_rotation(&euler.to_quaternion_ordered(RotationOrder::XYZ), 1e-6));
+    }
+
+    #[test]
+    fn rotate_around_fast_stays_within_the_fast_sin_error() {
+        for i in -100..100 {
+            let angle = i as f32 * 0.13;
+            for axis in [Axis::X, Axis::Y, Axis::Z] {
+                let (mut exact, mut fast) = (Euler::new(0.4, -0.7, 1.1), Euler::new(0.4, -0.7, 1.1));
+                exact.rotate_around(axis, angle);
+                fast.rotate_around_fast(axis, angle);
+                // Every new angle mixes two old ones, weighted by a sine and a cosine off by at most 0.0012 each.
+                let bound = 0.0012 * (0.7 + 1.1) + 1e-6;
+                let errors = [exact.pitch - fast.pitch, exact.yaw - fast.yaw, exact.roll - fast.roll];
+                assert!(errors.iter().all(|e| e.abs() <= bound), "{:?} {:?}", exact, fast);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format_is_stable() {
+        let json = serde_json::to_string(&Euler::new(0.1, -0.2, 0.3)).unwrap();
+        assert_eq!(json, r#"{"pitch":0.1,"yaw":-0.2,"roll":0.3}"#);
+        let euler: Euler = serde_json::from_str(&json).unwrap();
+        assert_eq!((euler.pitch, euler.yaw, euler.roll), (0.1, -0.2, 0.3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn string_exact_is_in_pitch_yaw_roll_order() {
+        let euler = Euler::new(0.1, -PI, 1e-45);
+        let s = euler.to_string_exact();
+        assert_eq!(s, "0.1,-3.1415927,1e-45");
+        let parsed = Euler::from_string_exact(&s).unwrap();
+        assert_eq!([parsed.pitch, parsed.yaw, parsed.roll], [euler.pitch, euler.yaw, euler.roll]);
+        assert!(Euler::from_string_exact("0.1,x,0").is_err());
+    }
+
+    #[test]
+    fn with_setters_replace_one_angle() {
+        let euler = Euler::new(0.1, 0.2, 0.3);
+        let angles = |e: Euler| [e.pitch, e.yaw, e.roll];
+        assert_eq!(angles(euler.with_pitch(1.0)), [1.0, 0.2, 0.3]);
+        assert_eq!(angles(euler.with_yaw(1.0)), [0.1, 1.0, 0.3]);
+        assert_eq!(angles(euler.with_roll(1.0)), [0.1, 0.2, 1.0]);
+        assert_eq!(angles(euler.with_pitch_degrees(90.0)), [PI / 2.0, 0.2, 0.3]);
+        assert_eq!(angles(euler.with_yaw_degrees(-180.0)), [0.1, -PI, 0.3]);
+        assert_eq!(angles(euler.with_roll_degrees(45.0)), [0.1, 0.2, PI / 4.0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display_shows_degrees() {
+        let euler = Euler::new(PI / 6.0, -PI / 2.0, 0.0);
+        assert_eq!(euler.to_string(), "pitch 30.0° yaw -90.0° roll 0.0°");
+        assert_eq!(format!("{:.3}", euler), "pitch 30.000° yaw -90.000° roll 0.000°");
+        assert_eq!(format!("{:.0}", Euler::new(1.0, 0.0, PI)), "pitch 57° yaw 0° roll 180°");
+    }
+
+    #[test]
+    fn integrate_turns_at_the_rates_and_wraps() {
+        let mut euler = Euler::new(0.0, 3.0, -1.0);
+        euler.integrate(Vector3::new(1.0, 2.0, -0.5), 0.5);
+        assert!((euler.pitch - 0.5).abs() < 1e-6);
+        // 3 + 1 = 4 radians wraps around to 4 - 2π.
+        assert!((euler.yaw - (4.0 - 2.0 * PI)).abs() < 1e-5, "{}", euler.yaw);
+        assert!((euler.roll + 1.25).abs() < 1e-6);
+
+        let mut still = Euler::new(0.1, 0.2, 0.3);
+        still.integrate(Vector3::new(5.0, 5.0, 5.0), 0.0);
+        assert_eq!([still.pitch, still.yaw, still.roll], [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn move_towards_takes_the_short_way_without_overshooting() {
+        let rates = Vector3::new(1.0, 1.0, 1.0);
+        let from = Euler::new(0.0, 3.0, 0.0);
+        let target = Euler::new(0.05, -3.0, -2.0);
+        let moved = from.move_towards(&target, rates, 0.1);
+        // Within reach the angle lands exactly on the target.
+        assert_eq!(moved.pitch, 0.05);
+        // From 3 to -3 the short way crosses π, so the yaw grows instead of shrinking.
+        assert!((moved.yaw - 3.1).abs() < 1e-6, "{}", moved.yaw);
+        assert!((moved.roll + 0.1).abs() < 1e-6, "{}", moved.roll);
+
+        // Crossing π wraps around, and repeated steps arrive exactly.
+        let mut current = from;
+        for _ in 0..20 {
+            current = current.move_towards(&target, rates, 0.1);
+            assert!(current.yaw > -PI && current.yaw <= PI);
+        }
+        assert_eq!([current.pitch, current.yaw, current.roll], [target.pitch, target.yaw, target.roll]);
+
+        // Negative rates and times still move towards the target.
+        let back = from.move_towards(&target, -rates, -0.1);
+        assert_eq!([back.pitch, back.yaw, back.roll], [moved.pitch, moved.yaw, moved.roll]);
+    }
+
+}
\ No newline at end of file
@@ -1,6 +1,17 @@
-use std::f32::consts::FRAC_PI_2;
-use std::ops::{Add, Mul, Sub};
-use crate::math::{fast_inv_sqrt, fast_sin};
+use core::f32::consts::{FRAC_PI_2, PI};
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, Mul, Sub};
+use core::str::FromStr;
+use crate::angles::angle2::Angle2;
+use crate::angles::euler::Euler;
+use crate::math::{default_sqrt, Lerp, fast_inv_sqrt, parse_exact, sin_cos, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::types::{Axis, RotationOrder};
+use crate::vectors::vector3::Vector3;
 
 /// A 3D quaternion with scalar and vector components.
 /// Used to represent angles in 3D space.
@@ -9,7 +20,8 @@ use crate::math::{fast_inv_sqrt, fast_sin};
 /// Maybe use `Euler` struct instead.
 ///
 /// NOTE: Some transformation functions are implemented in `Euler`, so you may need to use `Quaternion::to_euler()`.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quaternion {
     pub w: f32,
     pub x: f32,
@@ -21,15 +33,39 @@ impl Quaternion {
 
     /// Creates a new quaternion with the given scalar (w) and vector (x,y,z) components.
     #[inline]
-    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+    pub const fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
         Self { w, x, y, z }
     }
 
+    /// Creates a quaternion from components in x, y, z, w order, as used by glTF, GLSL and most engines.
+    #[inline]
+    pub const fn from_xyzw(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Creates a quaternion from components in w, x, y, z order, the same order as `new`.
+    #[inline]
+    pub const fn from_wxyz(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Returns the components in x, y, z, w order.
+    #[inline]
+    pub fn to_xyzw_array(&self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Returns the components in w, x, y, z order.
+    #[inline]
+    pub fn to_wxyz_array(&self) -> [f32; 4] {
+        [self.w, self.x, self.y, self.z]
+    }
+
     /// Returns an identity Quaternion.
     /// The identity represents a rotation of zero degrees around the x, y, and z axes.
     /// It is is defined as (1, 0, 0, 0), meaning that it has a scalar part of 1 and a vector part of (0, 0, 0).
     #[inline]
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Quaternion::new(1.0, 0.0, 0.0, 0.0)
     }
 
@@ -42,31 +78,86 @@ impl Quaternion {
     /// Returns the magnitude of the quaternion.
     #[inline]
     pub fn magnitude(&self) -> f32 {
+        default_sqrt(self.magnitude_squared())
+    }
+
+    /// Returns an approximation of the magnitude using `fast_inv_sqrt`, with a relative error below 0.2% and exactly 0 for a zero quaternion.
+    #[inline]
+    pub fn magnitude_fast(&self) -> f32 {
+        let magnitude_squared = self.magnitude_squared();
+        magnitude_squared * fast_inv_sqrt(magnitude_squared)
+    }
+
+    /// Returns an approximation of 1 / magnitude using `fast_inv_sqrt`, with a relative error below 0.2%.
+    #[inline]
+    pub fn inv_magnitude(&self) -> f32 {
         fast_inv_sqrt(self.magnitude_squared())
     }
 
     /// Returns the normalized version of the quaternion.
+    /// A zero quaternion is returned unchanged.
     pub fn normalized(&self) -> Quaternion {
-        let mag = self.magnitude();
+        let magnitude_squared = self.magnitude_squared();
+
+        if magnitude_squared == 0.0 {
+            return *self;
+        }
+        let mag = default_sqrt(magnitude_squared);
+
         Quaternion {
-            x: self.x * mag,
-            y: self.y * mag,
-            z: self.z * mag,
-            w: self.w * mag,
+            x: self.x / mag,
+            y: self.y / mag,
+            z: self.z / mag,
+            w: self.w / mag,
         }
     }
 
-    /// Returns the inverse of the quaternion.
-    pub fn inverse(&self) -> Quaternion {
+    /// Returns the normalized version of the quaternion using `fast_inv_sqrt`, the length of the result is within 0.2% of 1.
+    /// Fine for keeping a quaternion from drifting, but the error compounds if it is renormalized over and over.
+    /// A zero quaternion is returned unchanged.
+    pub fn normalized_fast(&self) -> Quaternion {
         let magnitude_squared = self.magnitude_squared();
+
+        if magnitude_squared == 0.0 {
+            return *self;
+        }
+
+        let inv_mag = fast_inv_sqrt(magnitude_squared);
         Quaternion {
-            x: -self.x / magnitude_squared,
-            y: -self.y / magnitude_squared,
-            z: -self.z / magnitude_squared,
-            w: self.w / magnitude_squared,
+            x: self.x * inv_mag,
+            y: self.y * inv_mag,
+            z: self.z * inv_mag,
+            w: self.w * inv_mag,
         }
     }
 
+    /// Returns the inverse of the quaternion.
+    /// Returns the identity if the quaternion is zero or its inverse is not finite, see `try_inverse`.
+    /// For normalized quaternions `conjugate` gives the same result and is cheaper.
+    pub fn inverse(&self) -> Quaternion {
+        self.try_inverse().unwrap_or_default()
+    }
+
+    /// Returns the inverse of the quaternion, or `None` if it is zero or the inverse is not finite.
+    /// Tiny but nonzero quaternions are rescaled first, so their squared magnitude does not underflow to zero.
+    pub fn try_inverse(&self) -> Option<Quaternion> {
+        let largest = self.x.abs().max(self.y.abs()).max(self.z.abs()).max(self.w.abs());
+        if largest == 0.0 || !largest.is_finite() {
+            return None;
+        }
+        // q^-1 = conj(q) / |q|², computed as conj(q / s) / |q / s|² / s with s being the largest component.
+        let scaled = Quaternion::new(self.w / largest, self.x / largest, self.y / largest, self.z / largest);
+        let factor = 1.0 / (scaled.magnitude_squared() * largest);
+        let inverse = Quaternion::new(scaled.w * factor, -scaled.x * factor, -scaled.y * factor, -scaled.z * factor);
+        inverse.is_finite().then_some(inverse)
+    }
+
+    /// Returns true if no component is infinite or NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
     /// Returns the conjugate of the quaternion.
     #[inline]
     pub fn conjugate(&self) -> Quaternion {
@@ -78,6 +169,37 @@ impl Quaternion {
         }
     }
 
+    /// Returns true if this is the canonical representative of its rotation (see `canonicalized`).
+    pub fn is_canonical(&self) -> bool {
+        if self.w != 0.0 {
+            return self.w > 0.0;
+        }
+        [self.x, self.y, self.z].into_iter()
+            .find(|&c| c != 0.0)
+            .is_none_or(|c| c > 0.0)
+    }
+
+    /// Returns the canonical representative of the rotation this quaternion describes.
+    ///
+    /// `q` and `-q` describe the same rotation, which makes hashing and serializing orientations unstable.
+    /// The canonical form is the one with a positive w. If w is zero, the first non-zero of x, y and z is made positive
+    /// and zeros are made +0.0, so `q` and `-q` always canonicalize to bitwise identical quaternions.
+    pub fn canonicalized(&self) -> Quaternion {
+        let q = if self.is_canonical() {
+            *self
+        } else {
+            Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+        };
+
+        if q.w == 0.0 {
+            // Only signed zeros are left to differ, e.g. for the zero quaternion, which is its own negation.
+            let positive_zero = |c: f32| if c == 0.0 { 0.0 } else { c };
+            Quaternion::new(0.0, positive_zero(q.x), positive_zero(q.y), positive_zero(q.z))
+        } else {
+            q
+        }
+    }
+
     /// Returns a new Quaternion that is a linear interpolation between `self` and `other` by `t`.
     /// `t` should be in the range [0, 1].
     pub fn lerp(&self, other: Quaternion, t: f32) -> Quaternion {
@@ -90,26 +212,87 @@ impl Quaternion {
         }
     }
 
-    /// Returns a new Quaternion that is a spherical linear interpolation between `self` and `other` by `t`.
-    /// `t` should be in the range [0, 1].
-    pub fn slerp(&self, other: Quaternion, t: f32) -> Quaternion {
-        let cos_theta = self.dot(&other);
-        let angle = cos_theta.acos();
-        let sin_theta = fast_sin(angle);
+    /// Returns a normalized linear interpolation between `self` and `other` by `t`, taking the shortest path.
+    ///
+    /// This is a lot cheaper than `slerp` and follows the same path, but not at constant angular speed:
+    /// it moves slightly faster around `t = 0.5`. The difference is negligible for rotations closer than ~30°.
+    pub fn nlerp(&self, other: Quaternion, t: f32) -> Quaternion {
+        let other = if self.dot(&other) < 0.0 { Quaternion::new(-other.w, -other.x, -other.y, -other.z) } else { other };
+        let q = self.lerp(other, t);
+        let length_squared = q.magnitude_squared();
+        if length_squared == 0.0 {
+            return *self;
+        }
+        let inv_length = 1.0 / length_squared.sqrt();
+        Quaternion::new(q.w * inv_length, q.x * inv_length, q.y * inv_length, q.z * inv_length)
+    }
 
-        if sin_theta < 0.001 {
-            // Linear interpolation if angle is small
-            self.lerp(other, t)
+    /// Returns a spherical linear interpolation between `self` and `other` by `t`, rotating at constant angular speed.
+    /// `t` should be in the range [0, 1] and both quaternions should be normalized.
+    ///
+    /// `other` is negated if needed so the interpolation takes the shortest path, which means `slerp(q, -q, t)` is `q`.
+    /// Nearly identical rotations fall back to `nlerp`, where the difference is below float precision.
+    pub fn slerp(&self, other: Quaternion, t: f32) -> Quaternion {
+        let mut cos_theta = self.dot(&other);
+        let other = if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            Quaternion::new(-other.w, -other.x, -other.y, -other.z)
         } else {
-            let self_coeff = (1.0 - t) * fast_sin(angle);
-            let other_coeff = t * fast_sin(angle);
-            Quaternion {
-                x: self_coeff * self.x + other_coeff * other.x,
-                y: self_coeff * self.y + other_coeff * other.y,
-                z: self_coeff * self.z + other_coeff * other.z,
-                w: self_coeff * self.w + other_coeff * other.w,
-            }.normalized()
+            other
+        };
+
+        if cos_theta > 0.9995 {
+            return self.nlerp(other, t);
+        }
+
+        let angle = cos_theta.clamp(-1.0, 1.0).acos();
+        let inv_sin = 1.0 / angle.sin();
+        let self_coeff = ((1.0 - t) * angle).sin() * inv_sin;
+        let other_coeff = (t * angle).sin() * inv_sin;
+        Quaternion {
+            x: self_coeff * self.x + other_coeff * other.x,
+            y: self_coeff * self.y + other_coeff * other.y,
+            z: self_coeff * self.z + other_coeff * other.z,
+            w: self_coeff * self.w + other_coeff * other.w,
+        }
+    }
+
+    /// Returns the weighted average of the given rotations.
+    ///
+    /// Every quaternion is flipped onto the hemisphere of the first one (as `q` and `-q` are the same rotation),
+    /// scaled by its weight and summed up, then the sum is normalized.
+    /// This is accurate for rotations that are reasonably close to each other, like noisy samples or blended poses.
+    ///
+    /// Returns `None` if `quats` is empty, the lengths of `quats` and `weights` differ or the weights sum up to nothing.
+    pub fn weighted_average(quats: &[Quaternion], weights: &[f32]) -> Option<Quaternion> {
+        if quats.len() != weights.len() {
+            return None;
         }
+        Quaternion::normalized_sum(quats.iter().zip(weights.iter().copied()))
+    }
+
+    /// Returns the average of the given rotations, see `weighted_average`.
+    /// Returns `None` if `quats` is empty.
+    pub fn average(quats: &[Quaternion]) -> Option<Quaternion> {
+        Quaternion::normalized_sum(quats.iter().map(|q| (q, 1.0)))
+    }
+
+    /// Sums up the weighted quaternions flipped onto the hemisphere of the first one and normalizes the sum.
+    fn normalized_sum<'a>(weighted: impl Iterator<Item = (&'a Quaternion, f32)>) -> Option<Quaternion> {
+        let mut weighted = weighted.peekable();
+        let first = *weighted.peek()?.0;
+
+        let mut sum = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        for (q, weight) in weighted {
+            let weight = if first.dot(q) < 0.0 { -weight } else { weight };
+            sum = sum + Quaternion::new(q.w * weight, q.x * weight, q.y * weight, q.z * weight);
+        }
+
+        let len = sum.magnitude_squared().sqrt();
+        if len <= f32::EPSILON {
+            return None;
+        }
+        Some(Quaternion::new(sum.w / len, sum.x / len, sum.y / len, sum.z / len))
     }
 
     /// Returns the dot product of this and the other quaternion.
@@ -118,11 +301,14 @@ impl Quaternion {
         self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
     }
 
-    /// Creates a new quaternion from the given euler angles.
+    /// Creates a new quaternion from the given euler angles, turning by the roll around x first, then by the pitch around y
+    /// and the yaw around z. `to_euler` converts back.
+    ///
+    /// Note that this names the axes differently than `Euler`, see `from_euler_ordered` for that.
     pub fn from_euler(pitch: f32, yaw: f32, roll: f32) -> Self {
-        let (sp, cp) = (pitch * 0.5).sin_cos();
-        let (sy, cy) = (yaw * 0.5).sin_cos();
-        let (sr, cr) = (roll * 0.5).sin_cos();
+        let (sp, cp) = sin_cos(pitch * 0.5);
+        let (sy, cy) = sin_cos(yaw * 0.5);
+        let (sr, cr) = sin_cos(roll * 0.5);
 
         let w = cr * cp * cy + sr * sp * sy;
         let x = sr * cp * cy - cr * sp * sy;
@@ -132,24 +318,240 @@ impl Quaternion {
         Self { x, y, z, w }
     }
 
-    /// Converts this quaternion to euler angles.
+    /// Creates the rotation of `euler` with the rotations applied in `order`, the same as `euler.to_quaternion_ordered(order)`.
+    #[inline]
+    pub fn from_euler_ordered(euler: &Euler, order: RotationOrder) -> Self {
+        euler.to_quaternion_ordered(order)
+    }
+
+    /// Creates a rotation of `angle_radians` around `axis`, counter-clockwise when looking down the axis towards the origin.
+    /// The axis gets normalized, a zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vector3, angle_radians: f32) -> Self {
+        let length_squared = axis.magnitude_squared();
+        if length_squared == 0.0 {
+            return Quaternion::identity();
+        }
+        let axis = axis * (1.0 / length_squared.sqrt());
+        let (sin, cos) = sin_cos(angle_radians * 0.5);
+        Quaternion::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+
+    /// Creates a rotation of `angle_radians` around one of the coordinate axes, like `from_axis_angle` with `axis.unit_vector()`
+    /// but without the normalization and the multiplications by zero.
+    #[inline]
+    pub fn from_axis(axis: Axis, angle_radians: f32) -> Self {
+        let (sin, cos) = sin_cos(angle_radians * 0.5);
+        match axis {
+            Axis::X => Quaternion::new(cos, sin, 0.0, 0.0),
+            Axis::Y => Quaternion::new(cos, 0.0, sin, 0.0),
+            Axis::Z => Quaternion::new(cos, 0.0, 0.0, sin),
+        }
+    }
+
+    /// Returns the shortest rotation that turns the direction `from` into the direction `to`,
+    /// e.g. to align a projectile with its velocity. Both vectors get normalized, a zero vector gives the identity.
+    ///
+    /// Equal directions give the identity. Opposite directions have no unique shortest rotation,
+    /// they give a half turn around an arbitrary axis perpendicular to `from`.
+    pub fn from_rotation_arc(from: Vector3, to: Vector3) -> Self {
+        let (from_squared, to_squared) = (from.magnitude_squared(), to.magnitude_squared());
+        if from_squared == 0.0 || to_squared == 0.0 {
+            return Quaternion::identity();
+        }
+        let from = from * (1.0 / from_squared.sqrt());
+        let to = to * (1.0 / to_squared.sqrt());
+        if from == to {
+            return Quaternion::identity();
+        }
+        if from.dot(&to) >= 0.0 {
+            return half_arc(from, to);
+        }
+
+        // The half vector of nearly opposite directions is tiny and imprecise, so turn `from` around
+        // by half a turn first and then take the well conditioned arc from `-from` to `to`.
+        // Around the axis perpendicular to both, the two rotations add up to the shortest arc.
+        // That cross product is tiny as well, so make it exactly perpendicular to `from` again, which is all
+        // the half turn needs to map `from` to `-from`. If it is lost in rounding noise, any perpendicular axis does.
+        let axis = from.cross(&to);
+        let axis = axis - from * from.dot(&axis);
+        let axis_squared = axis.magnitude_squared();
+        let axis = if axis_squared > 1e-12 { axis * (1.0 / axis_squared.sqrt()) } else { from.orthonormal_basis().0 };
+        Quaternion::new(0.0, axis.x, axis.y, axis.z) * half_arc(-from, to)
+    }
+
+    /// Returns the normalized rotation axis and the angle in radians of this rotation.
+    ///
+    /// The angle is always in [0, π], rotations by more than π come back as the shorter rotation around the flipped axis.
+    /// Rotations without an axis (like the identity) return the X axis and an angle of 0.
+    pub fn to_axis_angle(&self) -> (Vector3, f32) {
+        let q = self.canonicalized();
+        let v = Vector3::new(q.x, q.y, q.z);
+        let sin_length = v.magnitude_squared().sqrt();
+        if sin_length == 0.0 {
+            return (Vector3::new(1.0, 0.0, 0.0), 0.0);
+        }
+        // atan2 stays accurate near 0 and π, where acos(w) would lose precision.
+        (v * (1.0 / sin_length), 2.0 * sin_length.atan2(q.w))
+    }
+
+    /// Rotates `v` by this quaternion, computing `q * v * q⁻¹`.
+    /// Uses the cross product form `v + 2 * cross(q.xyz, cross(q.xyz, v) + w * v)`, which is a lot cheaper.
+    ///
+    /// The factor 2 is divided by the squared magnitude, so quaternions that drifted slightly away from unit length
+    /// still give a pure rotation instead of also scaling `v`. A zero quaternion leaves `v` unchanged.
+    #[inline]
+    pub fn rotate_vector(&self, v: Vector3) -> Vector3 {
+        let magnitude_squared = self.magnitude_squared();
+        if magnitude_squared == 0.0 {
+            return v;
+        }
+        let u = Vector3::new(self.x, self.y, self.z);
+        let t = u.cross(&v) + v * self.w;
+        v + u.cross(&t) * (2.0 / magnitude_squared)
+    }
+
+    /// Limits this rotation to at most `max_angle` radians away from `reference_forward`.
+    ///
+    /// The rotation is split into a swing (which moves `reference_forward` somewhere else) and a twist (which spins around it).
+    /// If the swing exceeds `max_angle`, it is shortened to lie exactly on the boundary of the cone, while the twist is kept as is.
+    /// Rotations that are already inside the cone are returned unchanged.
+    ///
+    /// Both this quaternion and `reference_forward` should be normalized, `max_angle` is clamped into [0, π].
+    pub fn clamped_to_cone(&self, reference_forward: Vector3, max_angle: f32) -> Quaternion {
+        let axis = reference_forward * (1.0 / reference_forward.magnitude_squared().sqrt());
+        let max_angle = max_angle.clamp(0.0, PI);
+
+        // Twist is the projection of the vector part onto the axis.
+        let d = self.x * axis.x + self.y * axis.y + self.z * axis.z;
+        let twist_len = (self.w * self.w + d * d).sqrt();
+        let twist = if twist_len < 1e-6 {
+            // The swing is a half turn, so the twist is undefined.
+            Quaternion::identity()
+        } else {
+            Quaternion::new(self.w / twist_len, axis.x * d / twist_len, axis.y * d / twist_len, axis.z * d / twist_len)
+        };
+        let swing = *self * twist.conjugate();
+
+        let swing_angle = 2.0 * swing.w.abs().min(1.0).acos();
+        if swing_angle <= max_angle {
+            return *self;
+        }
+
+        // Keep the swing axis on the w >= 0 hemisphere so the angle stays in [0, π].
+        let swing_axis = Vector3::new(swing.x, swing.y, swing.z) * swing.w.signum();
+        let swing_axis = swing_axis * (1.0 / swing_axis.magnitude_squared().sqrt());
+        let (sin_half, cos_half) = (max_angle * 0.5).sin_cos();
+        let clamped_swing = Quaternion::new(cos_half, swing_axis.x * sin_half, swing_axis.y * sin_half, swing_axis.z * sin_half);
+
+        clamped_swing * twist
+    }
+
+    /// Returns how far this rotation turns around `axis`, ignoring any rotation that tilts the axis itself.
+    /// This is the twist part of the swing-twist decomposition, expressed as an angle in (-π, π].
+    ///
+    /// If the rotation flips the axis around completely, the twist is undefined and 0 is returned.
+    /// A zero `axis` has no direction to twist around, so it returns 0 as well.
+    pub fn twist_around(&self, axis: Vector3) -> Angle2 {
+        let length_squared = axis.magnitude_squared();
+        if length_squared == 0.0 {
+            return Angle2::from_radians(0.0);
+        }
+        let axis = axis * (1.0 / length_squared.sqrt());
+        let d = self.x * axis.x + self.y * axis.y + self.z * axis.z;
+        if d == 0.0 && self.w == 0.0 {
+            return Angle2::from_radians(0.0);
+        }
+
+        // (w, d * axis) is the twist quaternion, its angle is twice the angle of that vector.
+        let angle = 2.0 * d.atan2(self.w);
+        Angle2::from_radians(if angle > PI {
+            angle - 2.0 * PI
+        } else if angle <= -PI {
+            angle + 2.0 * PI
+        } else {
+            angle
+        })
+    }
+
+    /// Converts this quaternion to the (pitch, yaw, roll) angles of `from_euler`.
+    ///
+    /// When the pitch is (within float precision of) ±π/2, yaw and roll rotate around the same axis
+    /// and only their combination is defined. The pitch is then snapped to ±π/2, roll is set to 0
+    /// and the whole rotation around that axis goes into yaw, so the result still describes the same rotation.
     pub fn to_euler(&self) -> (f32, f32, f32) {
+        let sinp = 2.0 * (self.w * self.y - self.z * self.x);
+        if sinp.abs() >= GIMBAL_LOCK_SIN {
+            let sign = sinp.signum();
+            return (FRAC_PI_2 * sign, -2.0 * sign * self.x.atan2(self.w), 0.0);
+        }
+
         let sinr_cosp = 2.0 * (self.w * self.x + self.y * self.z);
         let cosr_cosp = 1.0 - 2.0 * (self.x * self.x + self.y * self.y);
         let roll = sinr_cosp.atan2(cosr_cosp);
 
-        let sinp = 2.0 * (self.w * self.y - self.z * self.x);
-        let pitch = if sinp.abs() >= 1.0 {
-            FRAC_PI_2.copysign(sinp)
-        } else {
-            sinp.asin()
-        };
-
         let siny_cosp = 2.0 * (self.w * self.z + self.x * self.y);
         let cosy_cosp = 1.0 - 2.0 * (self.y * self.y + self.z * self.z);
         let yaw = siny_cosp.atan2(cosy_cosp);
 
-        (pitch, yaw, roll)
+        (sinp.asin(), yaw, roll)
+    }
+
+    /// Converts this quaternion to the euler angles of `Euler::to_quaternion`, the same as `Euler::from_quaternion`,
+    /// and returns whether the conversion hit the singular branch.
+    ///
+    /// When the yaw, the middle angle of `RotationOrder::XYZ`, is (within float precision of) ±π/2,
+    /// pitch and roll rotate around the same axis and only their combination is defined.
+    /// The yaw is then snapped to ±π/2, the pitch is set to 0 and the whole rotation around that axis goes into the roll,
+    /// so the result still describes the same rotation.
+    #[inline]
+    pub fn to_euler_checked(&self) -> (Euler, bool) {
+        Euler::from_quaternion_ordered_checked(self, RotationOrder::XYZ)
+    }
+
+    /// Returns how well-defined the rotation axis of this quaternion is, the sine of half the rotation angle in [0, 1].
+    /// Close to 0 for near-identity rotations, where the axis returned by `to_axis_angle` is mostly noise.
+    pub fn rotation_axis_stability(&self) -> f32 {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return 0.0;
+        }
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt() / magnitude
+    }
+
+    /// Returns true if every component differs from the one of `other` by at most `epsilon`.
+    /// Always false if a component is NaN. See `approx_eq_rotation` to compare the rotations instead.
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.w - other.w).abs() <= epsilon
+            && (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
+
+    /// Like `approx_eq`, but treats `q` and `-q` as equal, since they represent the same rotation.
+    pub fn approx_eq_rotation(&self, other: &Self, epsilon: f32) -> bool {
+        let sign = if self.dot(other) < 0.0 { -1.0 } else { 1.0 };
+        let other = Quaternion::new(other.w * sign, other.x * sign, other.y * sign, other.z * sign);
+        self.approx_eq(&other, epsilon)
+    }
+
+    /// Formats this quaternion in w, x, y, z order as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&[self.w, self.x, self.y, self.z])
+    }
+
+    /// Parses a quaternion in w, x, y, z order written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let [w, x, y, z] = parse_exact::<4>(s)?;
+        Ok(Self::new(w, x, y, z))
+    }
+}
+
+/// The default quaternion is the identity, no rotation.
+impl Default for Quaternion {
+    fn default() -> Self {
+        Quaternion::identity()
     }
 }
 
@@ -179,6 +581,14 @@ impl Mul for Quaternion {
     }
 }
 
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+
+    fn mul(self, v: Vector3) -> Vector3 {
+        self.rotate_vector(v)
+    }
+}
+
 impl Sub for Quaternion {
     type Output = Self;
 
@@ -191,3 +601,492 @@ impl Sub for Quaternion {
         }
     }
 }
+
+/// Interpolates with `slerp`, so the rotation turns at constant speed along the shortest path.
+/// Unlike the inherent `lerp`, which blends the components without normalizing.
+impl Lerp for Quaternion {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self.slerp(*other, t)
+    }
+}
+
+/// Formats the quaternion as `(w, x, y, z)`, forwarding flags like `{:.3}` to the components.
+impl Display for Quaternion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in [self.w, self.x, self.y, self.z].iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            Display::fmt(c, f)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Parses `quat(x, y, z, w)` or a bare list of four floats in w, x, y, z order, like `new` and `Display`.
+///
+/// The bare list may be wrapped in parentheses and separated by commas or whitespace,
+/// so `"(1, 0, 0, 0)"`, `"1, 0, 0, 0"` and `"1 0 0 0"` are all the identity.
+/// Use `from_xyzw` with parsed floats for bare lists in x, y, z, w order.
+impl FromStr for Quaternion {
+    type Err = ParseQuaternionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (list, xyzw) = match s.strip_prefix("quat(").and_then(|rest| rest.strip_suffix(')')) {
+            Some(list) => (list, true),
+            None => (s.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')).unwrap_or(s), false),
+        };
+
+        // Comma separated if there is any comma, whitespace separated otherwise.
+        let commas = list.contains(',');
+        let parts = || list
+            .split(move |c: char| if commas { c == ',' } else { c.is_whitespace() })
+            .map(str::trim)
+            .filter(move |part| commas || !part.is_empty());
+        let found = parts().count();
+        if found != 4 {
+            return Err(ParseQuaternionError::WrongCount { found });
+        }
+
+        let mut values = [0.0; 4];
+        for (index, (value, part)) in values.iter_mut().zip(parts()).enumerate() {
+            *value = part.parse().map_err(|_| ParseQuaternionError::InvalidNumber { index })?;
+        }
+        let [a, b, c, d] = values;
+        Ok(if xyzw { Quaternion::from_xyzw(a, b, c, d) } else { Quaternion::from_wxyz(a, b, c, d) })
+    }
+}
+
+/// Error returned when parsing a `Quaternion` from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseQuaternionError {
+    /// The string did not contain exactly four components.
+    WrongCount { found: usize },
+    /// The component at `index`, counted in the order written, is not a valid float.
+    InvalidNumber { index: usize },
+}
+
+impl Display for ParseQuaternionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseQuaternionError::WrongCount { found } =>
+                write!(f, "expected 4 quaternion components, found {}", found),
+            ParseQuaternionError::InvalidNumber { index } =>
+                write!(f, "quaternion component {} is not a valid float", index),
+        }
+    }
+}
+
+impl Error for ParseQuaternionError {}
+
+/// The sine of the middle angle above which `Quaternion::to_euler` and `Euler::from_quaternion_ordered` take the singular branch.
+/// Just below 1, since the asin of values this close to 1 already loses most of its precision.
+pub(crate) const GIMBAL_LOCK_SIN: f32 = 1.0 - 1e-6;
+
+/// Returns the shortest rotation from the unit vector `from` to the unit vector `to`, which must not point in opposite directions.
+/// The rotation turns around `from × half` by twice the angle between `from` and the half vector.
+#[inline]
+fn half_arc(from: Vector3, to: Vector3) -> Quaternion {
+    let half = from + to;
+    let half = half * (1.0 / half.magnitude_squared().sqrt());
+    let axis = from.cross(&half);
+    Quaternion::new(from.dot(&half), axis.x, axis.y, axis.z)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::matrix4x4::Matrix4x4;
+    use super::*;
+
+    /// Loose enough for the approximated square roots and trigonometry of the `fast_math` feature.
+    const FAST_MATH_TOLERANCE: f32 = 2e-3;
+
+    #[test]
+    fn to_euler_checked_flags_a_middle_angle_of_90_degrees() {
+        for yaw in [FRAC_PI_2, -FRAC_PI_2] {
+            let q = Euler::new(0.3, yaw, -0.2).to_quaternion();
+            let (euler, locked) = q.to_euler_checked();
+            assert!(locked, "yaw {}", yaw);
+            assert_eq!(euler.yaw, yaw);
+            assert_eq!(euler.pitch, 0.0);
+            assert!(euler.to_quaternion().approx_eq_rotation(&q, 1e-6), "{:?}", euler);
+        }
+    }
+
+    #[test]
+    fn to_euler_checked_keeps_small_middle_angles() {
+        for yaw in [0.0, 0.1, -0.5, 1.5] {
+            let q = Euler::new(0.3, yaw, -0.2).to_quaternion();
+            let (euler, locked) = q.to_euler_checked();
+            assert!(!locked, "yaw {}", yaw);
+            assert!((euler.pitch - 0.3).abs() < 1e-5 && (euler.yaw - yaw).abs() < 1e-5 && (euler.roll + 0.2).abs() < 1e-5, "{:?}", euler);
+        }
+    }
+
+    #[test]
+    fn is_near_gimbal_lock_checks_the_yaw() {
+        assert!(Euler::new(0.0, FRAC_PI_2 - 0.001, 0.0).is_near_gimbal_lock(0.01));
+        assert!(Euler::new(0.0, -FRAC_PI_2 + 2.0 * PI, 0.0).is_near_gimbal_lock(0.01));
+        assert!(!Euler::new(0.0, FRAC_PI_2 - 0.1, 0.0).is_near_gimbal_lock(0.01));
+        assert!(!Euler::new(FRAC_PI_2, 0.0, FRAC_PI_2).is_near_gimbal_lock(0.01));
+    }
+
+    #[test]
+    fn to_euler_inverts_from_euler() {
+        let (pitch, yaw, roll) = Quaternion::from_euler(0.4, -1.2, 0.7).to_euler();
+        assert!((pitch - 0.4).abs() < 1e-5 && (yaw + 1.2).abs() < 1e-5 && (roll - 0.7).abs() < 1e-5);
+    }
+
+    #[test]
+    fn rotation_axis_stability_is_low_near_identity() {
+        assert_eq!(Quaternion::identity().rotation_axis_stability(), 0.0);
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0).rotation_axis_stability(), 0.0);
+        let tiny = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0), 1e-4);
+        assert!(tiny.rotation_axis_stability() < 1e-4);
+        let half_turn = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), PI);
+        // The `fast_math` magnitude is only accurate to about 0.2%.
+        assert!((half_turn.rotation_axis_stability() - 1.0).abs() < 2e-3);
+    }
+
+    #[test]
+    fn clamped_to_cone_keeps_rotations_inside() {
+        let forward = Vector3::new(0.0, 0.0, 1.0);
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0), 0.3) * Quaternion::from_axis_angle(forward, 1.0);
+        let clamped = q.clamped_to_cone(forward, 0.5);
+        assert_eq!((clamped.w, clamped.x, clamped.y, clamped.z), (q.w, q.x, q.y, q.z));
+    }
+
+    #[test]
+    fn clamped_to_cone_moves_rotations_onto_the_boundary() {
+        let forward = Vector3::new(0.0, 0.0, 1.0);
+        for (axis, angle) in [(Vector3::new(1.0, 0.0, 0.0), 1.2), (Vector3::new(1.0, -2.0, 0.0), 2.5), (Vector3::new(0.0, 1.0, 0.0), -1.0)] {
+            let swing = Quaternion::from_axis_angle(axis, angle);
+            let clamped = swing.clamped_to_cone(forward, 0.5);
+            let moved = clamped.rotate_vector(forward);
+            assert!((moved.angle_between(&forward) - 0.5).abs() < FAST_MATH_TOLERANCE, "{:?} by {}", axis, angle);
+            // Still swinging towards the same side.
+            let original = swing.rotate_vector(forward);
+            assert!(Vector3::new(moved.x, moved.y, 0.0).dot(&Vector3::new(original.x, original.y, 0.0)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn clamped_to_cone_keeps_the_twist() {
+        let forward = Vector3::new(0.0, 0.0, 1.0);
+        let twist = Quaternion::from_axis_angle(forward, 0.8);
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 1.5) * twist;
+        let clamped = q.clamped_to_cone(forward, 0.4);
+        assert!((clamped.twist_around(forward).to_radians() - 0.8).abs() < FAST_MATH_TOLERANCE);
+        assert!((clamped.rotate_vector(forward).angle_between(&forward) - 0.4).abs() < FAST_MATH_TOLERANCE);
+    }
+
+    #[test]
+    fn twist_around_splits_off_the_swing() {
+        for (axis, swing_axis, swing_angle, twist_angle) in [
+            (Vector3::new(0.0, 0.0, 1.0), Vector3::new(1.0, 0.0, 0.0), 0.7, 1.1),
+            (Vector3::new(1.0, 2.0, -1.0), Vector3::new(2.0, -1.0, 0.0), -1.3, -2.9),
+            (Vector3::new(0.0, 3.0, 0.0), Vector3::new(0.0, 0.0, 1.0), 2.0, 0.0),
+        ] {
+            let twist = Quaternion::from_axis_angle(axis, twist_angle);
+            let q = Quaternion::from_axis_angle(swing_axis, swing_angle) * twist;
+            let angle = q.twist_around(axis).to_radians();
+            assert!((angle - twist_angle).abs() < FAST_MATH_TOLERANCE, "{:?}: {}", axis, angle);
+
+            // The remaining swing turns around an axis perpendicular to `axis`, and swing * twist is q again.
+            let twist = Quaternion::from_axis_angle(axis, angle);
+            let swing = q * twist.conjugate();
+            assert!(Vector3::new(swing.x, swing.y, swing.z).dot(&axis.normalized()).abs() < FAST_MATH_TOLERANCE, "{:?}", swing);
+            assert!((swing * twist).approx_eq_rotation(&q, FAST_MATH_TOLERANCE));
+        }
+    }
+
+    #[test]
+    fn twist_around_handles_degenerate_inputs() {
+        let q = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0), 1.0);
+        assert_eq!(q.twist_around(Vector3::zero()).to_radians(), 0.0);
+        // A half turn around x flips the z axis, leaving no twist around it.
+        let flip = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), PI);
+        assert_eq!(flip.twist_around(Vector3::new(0.0, 0.0, 1.0)).to_radians(), 0.0);
+        // Twists past half a turn wrap into (-π, π].
+        let twist = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 1.5 * PI);
+        assert!((twist.twist_around(Vector3::new(0.0, 1.0, 0.0)).to_radians() + 0.5 * PI).abs() < FAST_MATH_TOLERANCE);
+    }
+
+    #[test]
+    fn fast_variants_stay_within_their_documented_error() {
+        for i in 0..1000 {
+            let t = i as f32 * 0.37;
+            let q = Quaternion::new(t.sin() * 3.0, (t * 1.3).cos(), t.cos() * 0.01, (t * 0.7).sin() * 20.0);
+            let exact = |q: Quaternion| [q.w, q.x, q.y, q.z].iter().map(|&c| c as f64 * c as f64).sum::<f64>().sqrt();
+            assert!((q.magnitude_fast() as f64 / exact(q) - 1.0).abs() < 2e-3, "{:?}", q);
+            assert!((exact(q.normalized_fast()) - 1.0).abs() < 2e-3, "{:?}", q);
+            #[cfg(not(feature = "fast_math"))]
+            assert!((exact(q.normalized()) - 1.0).abs() < 1e-6, "{:?}", q);
+        }
+        let zero = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        assert_eq!((zero.magnitude_fast(), zero.magnitude()), (0.0, 0.0));
+        assert_eq!(zero.normalized_fast(), zero);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format_is_stable() {
+        let q = Quaternion::new(0.5, 0.5, -0.5, 0.5);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, r#"{"w":0.5,"x":0.5,"y":-0.5,"z":0.5}"#);
+        assert_eq!(serde_json::from_str::<Quaternion>(&json).unwrap(), q);
+    }
+
+    #[test]
+    fn slerp_rotates_at_constant_speed() {
+        let from = Quaternion::identity();
+        let to = Quaternion::from_axis_angle(Vector3::UP, FRAC_PI_2);
+        assert!(from.slerp(to, 0.0).approx_eq_rotation(&from, 1e-6));
+        assert!(from.slerp(to, 1.0).approx_eq_rotation(&to, 1e-6));
+        for t in [0.1, 0.25, 0.5, 0.8] {
+            let expected = Quaternion::from_axis_angle(Vector3::UP, FRAC_PI_2 * t);
+            let q = from.slerp(to, t);
+            assert!(q.approx_eq_rotation(&expected, FAST_MATH_TOLERANCE), "{} {:?}", t, q);
+            assert!((q.magnitude_squared() - 1.0).abs() < FAST_MATH_TOLERANCE, "{:?}", q);
+        }
+    }
+
+    #[test]
+    fn slerp_and_nlerp_take_the_shortest_path() {
+        let from = Quaternion::from_axis_angle(Vector3::UP, 0.2);
+        let to = Quaternion::from_axis_angle(Vector3::UP, 1.0);
+        let negated = Quaternion::new(-to.w, -to.x, -to.y, -to.z);
+        let expected = Quaternion::from_axis_angle(Vector3::UP, 0.6);
+        for q in [from.slerp(negated, 0.5), from.nlerp(negated, 0.5), from.nlerp(to, 0.5)] {
+            // The midpoint of nlerp matches slerp, only the speed differs in between.
+            assert!(q.approx_eq_rotation(&expected, FAST_MATH_TOLERANCE), "{:?}", q);
+            assert!(q.w > 0.0, "{:?}", q);
+        }
+        assert!(from.slerp(Quaternion::new(-from.w, -from.x, -from.y, -from.z), 0.5).approx_eq_rotation(&from, 1e-6));
+    }
+
+    #[test]
+    fn slerp_of_nearly_identical_rotations_stays_normalized() {
+        let from = Quaternion::from_axis_angle(Vector3::UP, 0.5);
+        let to = Quaternion::from_axis_angle(Vector3::UP, 0.5 + 1e-4);
+        let q = from.slerp(to, 0.5);
+        assert!(q.approx_eq_rotation(&from, 1e-4), "{:?}", q);
+        assert!((q.magnitude_squared() - 1.0).abs() < 1e-5, "{:?}", q);
+        assert!(from.slerp(from, 0.3).approx_eq_rotation(&from, 1e-6));
+    }
+
+    #[test]
+    fn magnitude_is_the_euclidean_length() {
+        let q = Quaternion::new(0.0, 3.0, 4.0, 0.0);
+        assert!((q.magnitude() - 5.0).abs() < 5.0 * FAST_MATH_TOLERANCE);
+        assert!((Quaternion::new(1.0, 1.0, 1.0, 1.0).magnitude_fast() - 2.0).abs() < 2.0 * FAST_MATH_TOLERANCE);
+        assert!((q.normalized().magnitude() - 1.0).abs() < FAST_MATH_TOLERANCE);
+        #[cfg(not(feature = "fast_math"))]
+        assert_eq!(q.magnitude(), 5.0);
+        // Too small to square, so it counts as zero length like in the vectors.
+        for w in [0.0, 1e-30] {
+            let tiny = Quaternion::new(w, 0.0, 0.0, 0.0);
+            assert_eq!(tiny.normalized(), tiny);
+        }
+    }
+
+    #[test]
+    fn const_constructors() {
+        const IDENTITY: Quaternion = Quaternion::identity();
+        const XYZW: Quaternion = Quaternion::from_xyzw(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(IDENTITY, Quaternion::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(XYZW, Quaternion::from_wxyz(4.0, 1.0, 2.0, 3.0));
+        assert_eq!((XYZW.w, XYZW.x, XYZW.y, XYZW.z), (4.0, 1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn try_inverse_handles_zero_and_tiny_quaternions() {
+        let q = Quaternion::new(1.0, 2.0, -2.0, 4.0);
+        let inverse = q.try_inverse().unwrap();
+        assert!((q * inverse).approx_eq_rotation(&Quaternion::identity(), 1e-6), "{:?}", inverse);
+        assert_eq!(inverse, Quaternion::new(1.0 / 25.0, -2.0 / 25.0, 2.0 / 25.0, -4.0 / 25.0));
+
+        let zero = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        assert_eq!(zero.try_inverse(), None);
+        assert_eq!(zero.inverse(), Quaternion::identity());
+        assert_eq!(Quaternion::new(f32::NAN, 0.0, 0.0, 0.0).try_inverse(), None);
+
+        // The square of 1e-30 underflows, but the inverse is still exact. The inverse of 1e-40 does not fit into `f32`.
+        assert_eq!(Quaternion::new(1e-30, 0.0, 0.0, 0.0).try_inverse(), Some(Quaternion::new(1e30, 0.0, 0.0, 0.0)));
+        assert_eq!(Quaternion::new(0.0, 0.0, -1e-30, 0.0).try_inverse(), Some(Quaternion::new(0.0, 0.0, 1e30, 0.0)));
+        assert_eq!(Quaternion::new(1e-40, 0.0, 0.0, 0.0).try_inverse(), None);
+        assert_eq!(Quaternion::new(1e-40, 0.0, 0.0, 0.0).inverse(), Quaternion::identity());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn string_exact_is_in_wxyz_order() {
+        let q = Quaternion::new(0.1, -0.2, 1.0 / 3.0, -0.0);
+        assert_eq!(q.to_string_exact(), "0.1,-0.2,0.33333334,-0.0");
+        assert_eq!(Quaternion::from_string_exact(&q.to_string_exact()), Ok(q));
+        assert_eq!(Quaternion::from_string_exact("1,0,0"), Err(ParseExactError::WrongCount { expected: 4, found: 3 }));
+    }
+
+    #[test]
+    fn canonicalized_is_the_same_for_q_and_minus_q() {
+        let bits = |q: Quaternion| [q.w, q.x, q.y, q.z].map(f32::to_bits);
+        let cases = [
+            Quaternion::new(0.5, -0.5, 0.5, 0.5),
+            Quaternion::new(-0.8, 0.6, 0.0, 0.0),
+            Quaternion::new(0.0, 0.0, -1.0, 0.0),
+            Quaternion::new(-0.0, -0.0, 0.6, -0.8),
+            Quaternion::new(0.0, 0.0, 0.0, 0.0),
+        ];
+        for q in cases {
+            let minus = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+            let canonical = q.canonicalized();
+            assert_eq!(bits(canonical), bits(minus.canonicalized()), "{:?}", q);
+            assert!(canonical.is_canonical(), "{:?}", canonical);
+            assert_eq!(canonical.canonicalized(), canonical);
+            assert!(canonical.approx_eq_rotation(&q, 0.0) || canonical == Quaternion::new(0.0, 0.0, 0.0, 0.0), "{:?}", q);
+            assert!(canonical.w.is_sign_positive(), "{:?}", canonical);
+        }
+        assert_eq!(Quaternion::new(-0.8, 0.6, 0.0, 0.0).canonicalized(), Quaternion::new(0.8, -0.6, 0.0, 0.0));
+        assert_eq!(Quaternion::new(0.0, 0.0, -1.0, 0.0).canonicalized(), Quaternion::new(0.0, 0.0, 1.0, 0.0));
+        assert!(Quaternion::identity().is_canonical() && !Quaternion::new(-1.0, 0.0, 0.0, 0.0).is_canonical());
+        assert!(!Quaternion::new(0.0, 0.0, -1.0, 0.0).is_canonical());
+    }
+
+    #[test]
+    fn average_of_symmetric_rotations_is_the_middle() {
+        let around = |angle: f32| Quaternion::from_axis_angle(Vector3::UP, angle);
+        let average = Quaternion::average(&[around(0.2), around(0.6), around(0.4)]).unwrap();
+        assert!(average.approx_eq_rotation(&around(0.4), FAST_MATH_TOLERANCE), "{:?}", average);
+
+        // A negated sample is the same rotation and must not cancel the others out.
+        let q = around(0.6);
+        let negated = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+        let average = Quaternion::average(&[around(0.2), negated]).unwrap();
+        assert!(average.approx_eq_rotation(&around(0.4), FAST_MATH_TOLERANCE), "{:?}", average);
+        assert!((average.magnitude_squared() - 1.0).abs() < 1e-5);
+        assert!(Quaternion::average(&[q]).unwrap().approx_eq_rotation(&q, 1e-6));
+    }
+
+    #[test]
+    fn weighted_average_leans_towards_the_heavier_rotation() {
+        let around = |angle: f32| Quaternion::from_axis_angle(Vector3::X, angle);
+        let average = Quaternion::weighted_average(&[around(0.0), around(0.4)], &[1.0, 3.0]).unwrap();
+        // For small angles the weighted sum is close to the weighted angle.
+        assert!(average.approx_eq_rotation(&around(0.3), 5e-3), "{:?}", average);
+        let only_second = Quaternion::weighted_average(&[around(0.0), around(0.4)], &[0.0, 2.0]).unwrap();
+        assert!(only_second.approx_eq_rotation(&around(0.4), 1e-6));
+
+        assert_eq!(Quaternion::average(&[]), None);
+        assert_eq!(Quaternion::weighted_average(&[around(0.1)], &[1.0, 2.0]), None);
+        assert_eq!(Quaternion::weighted_average(&[around(0.1), around(0.2)], &[0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn axis_angle_round_trip() {
+        let axis = Vector3::new(1.0, -2.0, 2.0);
+        let unit = axis * (1.0 / 3.0);
+        for angle in [1e-4, 0.5, 2.0, PI - 1e-3] {
+            let (back_axis, back_angle) = Quaternion::from_axis_angle(axis, angle).to_axis_angle();
+            assert!(back_axis.approx_eq(&unit, FAST_MATH_TOLERANCE), "{} {:?}", angle, back_axis);
+            assert!((back_angle - angle).abs() < FAST_MATH_TOLERANCE * angle.max(1.0), "{} {}", angle, back_angle);
+        }
+
+        // More than half a turn comes back as the shorter turn around the flipped axis.
+        let (back_axis, back_angle) = Quaternion::from_axis_angle(unit, 1.5 * PI).to_axis_angle();
+        assert!(back_axis.approx_eq(&-unit, FAST_MATH_TOLERANCE) && (back_angle - 0.5 * PI).abs() < FAST_MATH_TOLERANCE, "{:?} {}", back_axis, back_angle);
+
+        // A quarter turn around +Y is counter-clockwise seen from above, so it turns +Z into +X.
+        let q = Quaternion::from_axis_angle(Vector3::UP, FRAC_PI_2);
+        assert!(q.rotate_vector(Vector3::Z).approx_eq(&Vector3::X, FAST_MATH_TOLERANCE));
+
+        assert_eq!(Quaternion::from_axis_angle(Vector3::ZERO, 1.0), Quaternion::identity());
+        assert_eq!(Quaternion::identity().to_axis_angle(), (Vector3::X, 0.0));
+        assert_eq!(Quaternion::new(-1.0, 0.0, 0.0, 0.0).to_axis_angle(), (Vector3::X, 0.0));
+    }
+
+    #[test]
+    fn xyzw_and_wxyz_orders() {
+        let q = Quaternion::from_xyzw(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q, Quaternion::new(4.0, 1.0, 2.0, 3.0));
+        assert_eq!(q, Quaternion::from_wxyz(4.0, 1.0, 2.0, 3.0));
+        assert_eq!(q.to_xyzw_array(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(q.to_wxyz_array(), [4.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_str_accepts_both_orders_and_round_trips_display() {
+        let q = Quaternion::new(0.5, -0.25, 1.0, 2.0);
+        for s in ["quat(-0.25, 1, 2, 0.5)", " (0.5, -0.25, 1, 2) ", "0.5,-0.25,1,2", "0.5  -0.25 1\t2"] {
+            assert_eq!(s.parse::<Quaternion>(), Ok(q), "{}", s);
+        }
+        assert_eq!(q.to_string().parse::<Quaternion>(), Ok(q));
+        assert_eq!("1 0 0 0".parse::<Quaternion>(), Ok(Quaternion::identity()));
+
+        assert_eq!("1, 0, 0".parse::<Quaternion>(), Err(ParseQuaternionError::WrongCount { found: 3 }));
+        assert_eq!("quat(1 0 0 0)".parse::<Quaternion>(), Ok(Quaternion::from_xyzw(1.0, 0.0, 0.0, 0.0)));
+        assert_eq!("quat(1, 0)".parse::<Quaternion>(), Err(ParseQuaternionError::WrongCount { found: 2 }));
+        assert_eq!("1, 0, , 0".parse::<Quaternion>(), Err(ParseQuaternionError::InvalidNumber { index: 2 }));
+        assert_eq!("1 0 0 w".parse::<Quaternion>(), Err(ParseQuaternionError::InvalidNumber { index: 3 }));
+        assert_eq!(ParseQuaternionError::InvalidNumber { index: 3 }.to_string(), "quaternion component 3 is not a valid float");
+    }
+
+    #[test]
+    fn from_rotation_arc_turns_from_into_to() {
+        let directions = [
+            Vector3::X, Vector3::new(0.0, 2.0, 0.0), Vector3::new(1.0, 1.0, 0.0), Vector3::new(-3.0, 0.5, 2.0),
+            Vector3::new(0.2, -1.0, -0.1), Vector3::new(-1.0, 1e-4, 0.0), Vector3::new(-1.0, -1e-7, 1e-7),
+        ];
+        for from in directions {
+            for to in directions {
+                let q = Quaternion::from_rotation_arc(from, to);
+                let turned = q.rotate_vector(from.normalized());
+                assert!(turned.approx_eq(&to.normalized(), FAST_MATH_TOLERANCE), "{:?} {:?} {:?}", from, to, turned);
+                assert!((q.magnitude_squared() - 1.0).abs() < FAST_MATH_TOLERANCE, "{:?}", q);
+                // The shortest arc turns by exactly the angle between the directions.
+                let (_, angle) = q.to_axis_angle();
+                assert!((angle - from.angle_between(&to)).abs() < FAST_MATH_TOLERANCE, "{:?} {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn from_rotation_arc_degenerate_inputs() {
+        assert_eq!(Quaternion::from_rotation_arc(Vector3::X, Vector3::X * 3.0), Quaternion::identity());
+        assert_eq!(Quaternion::from_rotation_arc(Vector3::ZERO, Vector3::X), Quaternion::identity());
+        assert_eq!(Quaternion::from_rotation_arc(Vector3::X, Vector3::ZERO), Quaternion::identity());
+
+        // Opposite directions give a half turn around some axis perpendicular to `from`.
+        let q = Quaternion::from_rotation_arc(Vector3::UP, Vector3::DOWN);
+        let (axis, angle) = q.to_axis_angle();
+        assert!((angle - PI).abs() < 1e-5 && axis.dot(&Vector3::UP).abs() < 1e-6, "{:?}", q);
+        assert!(q.rotate_vector(Vector3::UP).approx_eq(&Vector3::DOWN, 1e-6));
+    }
+
+    #[test]
+    fn rotate_vector_matches_the_sandwich_product_and_the_matrix() {
+        let v = Vector3::new(0.5, -2.0, 3.0);
+        for (axis, angle) in [(Vector3::X, 0.3), (Vector3::new(1.0, 1.0, -1.0), 2.0), (Vector3::new(-2.0, 0.5, 1.0), -2.8)] {
+            let q = Quaternion::from_axis_angle(axis, angle);
+            let rotated = q.rotate_vector(v);
+            assert_eq!(q * v, rotated);
+
+            let sandwich = q * Quaternion::new(0.0, v.x, v.y, v.z) * q.conjugate();
+            assert!(rotated.approx_eq(&Vector3::new(sandwich.x, sandwich.y, sandwich.z), 1e-5), "{:?}", rotated);
+            let matrix = Matrix4x4::from_quaternion(&q);
+            assert!(rotated.approx_eq(&matrix.transform_point(v), 1e-5), "{:?}", rotated);
+            assert!((rotated.magnitude_squared() - v.magnitude_squared()).abs() < 1e-4);
+
+            // A quaternion that drifted away from unit length still only rotates.
+            let drifted = Quaternion::new(q.w * 1.1, q.x * 1.1, q.y * 1.1, q.z * 1.1);
+            assert!((drifted * v).approx_eq(&rotated, 1e-5), "{:?}", drifted * v);
+        }
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0) * v, v);
+        assert_eq!(Quaternion::identity() * v, v);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,236 @@
+use crate::angles::angle2::Angle2;
+use crate::angles::euler::Euler;
+use crate::angles::quaternion::Quaternion;
+use crate::math::{cubic_hermite, Lerp};
+use crate::rendering::color::UniColor;
+use crate::transform::Transform;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+use crate::vectors::vector4::Vector4;
+
+/// A track of floats, e.g. for fading an intensity.
+pub type FloatTrack = Track<f32>;
+
+/// A track of positions or scales.
+pub type Vector3Track = Track<Vector3>;
+
+/// A track of rotations, interpolated with `slerp` along the shortest arc.
+pub type QuaternionTrack = Track<Quaternion>;
+
+/// How a `Track` interpolates between two keyframes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Holds the value of the previous key until the next key is reached.
+    Step,
+    /// Interpolates with `Lerp`.
+    #[default]
+    Linear,
+    /// Follows a smooth curve through the keys, see `Animatable::cubic_hermite`.
+    CubicHermite,
+}
+
+/// What a `Track` does with times before its first or after its last key.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Holds the value of the first or last key.
+    #[default]
+    Clamp,
+    /// Repeats the track, so the time wraps around from the last key back to the first.
+    /// Make the last key equal to the first one for a seamless loop.
+    Loop,
+}
+
+/// A value that can be animated by a `Track`.
+pub trait Animatable: Lerp + Clone {
+    /// Interpolates from `b` to `c` by `t` on a cubic curve, using the neighboring keys `a` and `d` for the tangents.
+    /// `times` holds the times of the four keys. At the ends of a track `a` is `b` and `d` is `c`.
+    ///
+    /// The default falls back to `lerp`, which is what types without a meaningful tangent like rotations and colors use.
+    fn cubic_hermite(a: &Self, b: &Self, c: &Self, d: &Self, times: [f32; 4], t: f32) -> Self {
+        let _ = (a, d, times);
+        b.lerp(c, t)
+    }
+}
+
+/// Implements `Animatable` with Catmull-Rom tangents scaled to the uneven key spacing.
+macro_rules! impl_animatable_hermite {
+    ($($t:ty),*) => {
+        $(
+            impl Animatable for $t {
+                fn cubic_hermite(a: &Self, b: &Self, c: &Self, d: &Self, times: [f32; 4], t: f32) -> Self {
+                    let duration = times[2] - times[1];
+                    let m0 = (*c - *a) * (duration / (times[2] - times[0]));
+                    let m1 = (*d - *b) * (duration / (times[3] - times[1]));
+                    cubic_hermite(*b, m0, *c, m1, t)
+                }
+            }
+        )*
+    };
+}
+
+impl_animatable_hermite!(f32, Vector2, Vector3, Vector4);
+
+impl Animatable for Angle2 {}
+
+impl Animatable for Quaternion {}
+
+impl Animatable for Euler {}
+
+impl Animatable for UniColor {}
+
+impl Animatable for Transform {}
+
+/// Keyframes sorted by time, sampled with an `Interpolation` and a `WrapMode`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Track<T> {
+    keys: Vec<(f32, T)>,
+    pub interpolation: Interpolation,
+    pub wrap: WrapMode,
+}
+
+impl<T: Animatable> Track<T> {
+
+    /// Creates a track without keys.
+    pub fn new(interpolation: Interpolation, wrap: WrapMode) -> Self {
+        Self { keys: Vec::new(), interpolation, wrap }
+    }
+
+    /// Adds a key at `time`, keeping the keys sorted. A key already at exactly `time` gets its value replaced.
+    pub fn add_key(&mut self, time: f32, value: T) {
+        let index = self.keys.partition_point(|(key_time, _)| *key_time < time);
+        match self.keys.get_mut(index) {
+            Some(key) if key.0 == time => key.1 = value,
+            _ => self.keys.insert(index, (time, value)),
+        }
+    }
+
+    /// Returns the keys as (time, value) pairs sorted by time.
+    #[inline]
+    pub fn keys(&self) -> &[(f32, T)] {
+        &self.keys
+    }
+
+    /// Returns the time between the first and the last key, zero for tracks with less than two keys.
+    pub fn duration(&self) -> f32 {
+        match (self.keys.first(), self.keys.last()) {
+            (Some(first), Some(last)) => last.0 - first.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the value of the track at time `t`. Sampling exactly at a key returns its value,
+    /// except for the last key of a looping track, where the loop already starts over with the first key.
+    ///
+    /// # Panics
+    /// Panics if the track has no keys.
+    pub fn sample(&self, t: f32) -> T {
+        let first = self.keys.first().expect("sampled a track without keys").0;
+        let duration = self.duration();
+        let t = match self.wrap {
+            WrapMode::Clamp => t,
+            WrapMode::Loop if duration > 0.0 => first + (t - first).rem_euclid(duration),
+            WrapMode::Loop => first,
+        };
+
+        // The first key after `t`, so the segment is from `next - 1` to `next`.
+        let next = self.keys.partition_point(|(key_time, _)| *key_time <= t);
+        if next == 0 {
+            return self.keys[0].1.clone();
+        }
+        if next == self.keys.len() {
+            return self.keys[next - 1].1.clone();
+        }
+
+        let (b, c) = (&self.keys[next - 1], &self.keys[next]);
+        let local = (t - b.0) / (c.0 - b.0);
+        match self.interpolation {
+            Interpolation::Step => b.1.clone(),
+            Interpolation::Linear => b.1.lerp(&c.1, local),
+            Interpolation::CubicHermite => {
+                let a = &self.keys[next.saturating_sub(2)];
+                let d = self.keys.get(next + 1).unwrap_or(c);
+                T::cubic_hermite(&a.1, &b.1, &c.1, &d.1, [a.0, b.0, c.0, d.0], local)
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn float_track(interpolation: Interpolation, wrap: WrapMode) -> FloatTrack {
+        let mut track = Track::new(interpolation, wrap);
+        // Added out of order, the track sorts them.
+        track.add_key(3.0, -2.0);
+        track.add_key(1.0, 4.0);
+        track.add_key(2.0, 10.0);
+        track
+    }
+
+    #[test]
+    fn sampling_at_keys_returns_their_values() {
+        for interpolation in [Interpolation::Step, Interpolation::Linear, Interpolation::CubicHermite] {
+            let track = float_track(interpolation, WrapMode::Clamp);
+            for &(time, value) in track.keys() {
+                assert_eq!(track.sample(time), value, "{:?} at {}", interpolation, time);
+            }
+        }
+
+        let mut track = Vector3Track::new(Interpolation::CubicHermite, WrapMode::Clamp);
+        track.add_key(0.0, Vector3::new(1.0, 2.0, 3.0));
+        track.add_key(0.5, Vector3::new(-1.0, 0.0, 7.0));
+        track.add_key(0.5, Vector3::new(4.0, 4.0, 4.0));
+        assert_eq!(track.keys().len(), 2);
+        assert_eq!(track.sample(0.5).to_array(), [4.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn sampling_between_keys_matches_lerp() {
+        let track = float_track(Interpolation::Linear, WrapMode::Clamp);
+        for t in [1.25f32, 1.5, 1.9, 2.1, 2.75] {
+            let expected = if t < 2.0 { 4.0.lerp(&10.0, t - 1.0) } else { 10.0.lerp(&-2.0, t - 2.0) };
+            assert_eq!(track.sample(t), expected, "{}", t);
+        }
+        // Clamping holds the ends, stepping holds the previous key.
+        assert_eq!(track.sample(-5.0), 4.0);
+        assert_eq!(track.sample(8.0), -2.0);
+        assert_eq!(float_track(Interpolation::Step, WrapMode::Clamp).sample(2.9), 10.0);
+    }
+
+    #[test]
+    fn loop_mode_wraps_the_time() {
+        let track = float_track(Interpolation::Linear, WrapMode::Loop);
+        assert_eq!(track.duration(), 2.0);
+        for t in [1.0f32, 1.5, 2.25, 2.75] {
+            assert_eq!(track.sample(t + 2.0), track.sample(t), "{}", t);
+            assert_eq!(track.sample(t + 20.0), track.sample(t), "{}", t);
+            assert_eq!(track.sample(t - 4.0), track.sample(t), "{}", t);
+        }
+        // The end of the loop is its start again.
+        assert_eq!(track.sample(3.0), 4.0);
+
+        let mut single = FloatTrack::new(Interpolation::Linear, WrapMode::Loop);
+        single.add_key(1.0, 7.0);
+        assert_eq!(single.sample(-3.0), 7.0);
+        assert_eq!(single.sample(50.0), 7.0);
+    }
+
+    #[test]
+    fn quaternion_tracks_take_the_short_arc() {
+        let yaw = |degrees: f32| Quaternion::from_axis_angle(Vector3::UP, degrees.to_radians());
+        let mut track = QuaternionTrack::new(Interpolation::Linear, WrapMode::Clamp);
+        track.add_key(0.0, yaw(10.0));
+        track.add_key(1.0, yaw(350.0));
+
+        // 10° to 350° is 20° backwards through 0°, not 340° forwards through 180°.
+        assert!(track.sample(0.5).approx_eq_rotation(&yaw(0.0), 1e-4), "{:?}", track.sample(0.5));
+        assert!(track.sample(0.25).approx_eq_rotation(&yaw(5.0), 1e-4), "{:?}", track.sample(0.25));
+        // Cubic interpolation falls back to slerp for rotations.
+        track.interpolation = Interpolation::CubicHermite;
+        assert!(track.sample(0.75).approx_eq_rotation(&yaw(355.0), 1e-4), "{:?}", track.sample(0.75));
+    }
+
+}
\ No newline at end of file
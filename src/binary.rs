@@ -0,0 +1,108 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+
+/// Error returned by the `read_binary` functions of `Mesh`, `Transform` and `Aabb`.
+#[derive(Debug)]
+pub enum BinaryReadError {
+    /// The data does not start with the magic bytes of the expected type, so it is something else entirely.
+    WrongMagic { expected: [u8; 4], found: [u8; 4] },
+    /// The data was written by a newer (or unknown) version of the format.
+    UnsupportedVersion { found: u16, supported: u16 },
+    /// The data ended before everything announced by the header was read.
+    Truncated,
+    /// A mesh index points past the end of the vertices.
+    IndexOutOfRange { index: u32, vertex_count: u32 },
+    /// Reading failed for another reason.
+    Io(io::Error),
+}
+
+impl Display for BinaryReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryReadError::WrongMagic { expected, found } =>
+                write!(f, "expected the magic bytes {:?}, found {:?}", String::from_utf8_lossy(expected), String::from_utf8_lossy(found)),
+            BinaryReadError::UnsupportedVersion { found, supported } =>
+                write!(f, "format version {} is not supported, this build reads up to version {}", found, supported),
+            BinaryReadError::Truncated => write!(f, "the data ended unexpectedly"),
+            BinaryReadError::IndexOutOfRange { index, vertex_count } =>
+                write!(f, "index {} is out of range for {} vertices", index, vertex_count),
+            BinaryReadError::Io(error) => write!(f, "reading failed: {}", error),
+        }
+    }
+}
+
+impl Error for BinaryReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BinaryReadError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BinaryReadError {
+    fn from(error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            BinaryReadError::Truncated
+        } else {
+            BinaryReadError::Io(error)
+        }
+    }
+}
+
+/// Writes the 4 magic bytes followed by the little-endian u16 format version.
+pub(crate) fn write_header(w: &mut impl Write, magic: [u8; 4], version: u16) -> io::Result<()> {
+    w.write_all(&magic)?;
+    w.write_all(&version.to_le_bytes())
+}
+
+/// Reads and checks a header written by `write_header`. Versions up to `supported` are accepted and returned.
+pub(crate) fn read_header(r: &mut impl Read, magic: [u8; 4], supported: u16) -> Result<u16, BinaryReadError> {
+    let found: [u8; 4] = read_bytes(r)?;
+    if found != magic {
+        return Err(BinaryReadError::WrongMagic { expected: magic, found });
+    }
+    let version = u16::from_le_bytes(read_bytes(r)?);
+    if version == 0 || version > supported {
+        return Err(BinaryReadError::UnsupportedVersion { found: version, supported });
+    }
+    Ok(version)
+}
+
+/// Writes `value` as 4 little-endian bytes.
+#[inline]
+pub(crate) fn write_u32(w: &mut impl Write, value: u32) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+/// Reads a u32 written by `write_u32`.
+#[inline]
+pub(crate) fn read_u32(r: &mut impl Read) -> Result<u32, BinaryReadError> {
+    Ok(u32::from_le_bytes(read_bytes(r)?))
+}
+
+/// Writes the floats as little-endian bit patterns, so every value including NaN payloads round-trips.
+pub(crate) fn write_f32s(w: &mut impl Write, values: &[f32]) -> io::Result<()> {
+    for value in values {
+        w.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads `N` floats written by `write_f32s`.
+pub(crate) fn read_f32s<const N: usize>(r: &mut impl Read) -> Result<[f32; N], BinaryReadError> {
+    let mut values = [0.0; N];
+    for value in values.iter_mut() {
+        *value = f32::from_le_bytes(read_bytes(r)?);
+    }
+    Ok(values)
+}
+
+/// Reads exactly `N` bytes, running out of data is reported as `BinaryReadError::Truncated`.
+#[inline]
+pub(crate) fn read_bytes<const N: usize>(r: &mut impl Read) -> Result<[u8; N], BinaryReadError> {
+    let mut bytes = [0; N];
+    r.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
\ No newline at end of file
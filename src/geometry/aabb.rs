@@ -0,0 +1,333 @@
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
+use crate::binary::{self, BinaryReadError};
+use crate::matrix4x4::Matrix4x4;
+use crate::rendering::vertex::Vertex;
+use crate::vectors::vector3::Vector3;
+
+#[cfg(feature = "std")]
+const AABB_MAGIC: [u8; 4] = *b"FGAB";
+#[cfg(feature = "std")]
+const AABB_VERSION: u16 = 1;
+
+/// An axis-aligned bounding box described by its `min` and `max` corners.
+/// Used for culling and broad-phase checks, as it is very cheap to test against.
+///
+/// A box with `min` greater than `max` on any axis is empty and contains nothing. [`Aabb::EMPTY`] is the neutral
+/// element of `union`, so bounds can be accumulated starting from it.
+#[derive(Copy, Clone, Debug)]
+pub struct Aabb {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+impl Aabb {
+
+    /// The empty box, with `min` at positive and `max` at negative infinity. Its union with any box is that box.
+    pub const EMPTY: Aabb = Aabb {
+        min: Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+        max: Vector3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY),
+    };
+
+    /// Creates a new Aabb from the given min and max corners.
+    #[inline]
+    pub fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates the smallest Aabb containing all the given points.
+    /// Returns `None` if `points` is empty.
+    pub fn from_points(points: &[Vector3]) -> Option<Self> {
+        let mut builder = AabbBuilder::new();
+        builder.extend(points.iter().copied());
+        builder.build()
+    }
+
+    /// Returns the center of the box.
+    #[inline]
+    pub fn center(&self) -> Vector3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the half size of the box along every axis, the distance from the center to the faces.
+    #[inline]
+    pub fn extents(&self) -> Vector3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Returns true if `min` is greater than `max` on any axis, so the box contains nothing.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z)
+    }
+
+    /// Returns the smallest box containing both boxes. Empty boxes are ignored, inverted ones included,
+    /// so the union of two empty boxes is the first one.
+    #[inline]
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        Aabb::new(self.min.min(&other.min), self.max.max(&other.max))
+    }
+
+    /// Returns true if `point` is inside the box or on its surface.
+    #[inline]
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x
+            && self.min.y <= point.y && point.y <= self.max.y
+            && self.min.z <= point.z && point.z <= self.max.z
+    }
+
+    /// Returns true if `other` lies completely inside this box, touching faces included.
+    /// The empty box is contained in every box.
+    #[inline]
+    pub fn contains_aabb(&self, other: &Aabb) -> bool {
+        other.is_empty() || (self.contains_point(other.min) && self.contains_point(other.max))
+    }
+
+    /// Returns true if the boxes overlap. Boxes that only touch at a face, an edge or a corner count as overlapping.
+    #[inline]
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+            && self.min.z <= other.max.z && other.min.z <= self.max.z
+    }
+
+    /// Returns the 8 corners of the box. Corner `i` takes `max` for x if bit 0 of `i` is set, for y for bit 1 and for z for bit 2,
+    /// so corner 0 is `min` and corner 7 is `max`, like the octants of `octants`.
+    pub fn corners(&self) -> [Vector3; 8] {
+        core::array::from_fn(|i| Vector3::new(
+            if i & 1 == 0 { self.min.x } else { self.max.x },
+            if i & 2 == 0 { self.min.y } else { self.max.y },
+            if i & 4 == 0 { self.min.z } else { self.max.z },
+        ))
+    }
+
+    /// Returns the box grown by `margin` on every side. A negative margin shrinks it, possibly until it is empty.
+    #[inline]
+    pub fn expand(&self, margin: f32) -> Aabb {
+        let margin = Vector3::new(margin, margin, margin);
+        Aabb::new(self.min - margin, self.max + margin)
+    }
+
+    /// Returns the box around the 8 corners transformed by `m`, e.g. to move local bounds into world space.
+    /// The result is larger than the tightest box around the transformed contents if `m` rotates.
+    /// Empty boxes stay empty.
+    pub fn transform(&self, m: &Matrix4x4) -> Aabb {
+        if self.is_empty() {
+            return Aabb::EMPTY;
+        }
+        let mut builder = AabbBuilder::new();
+        builder.extend(self.corners().map(|corner| m.transform_point(corner)));
+        builder.build().unwrap_or(Aabb::EMPTY)
+    }
+
+    /// Splits the box at its center into 8 octants, e.g. for building an octree.
+    ///
+    /// Octant `i` lies on the upper side of the center along x if bit 0 of `i` is set, along y for bit 1 and along z for bit 2,
+    /// so octant 0 touches `min` and octant 7 touches `max`. All octants share the same center, so they tile the box exactly.
+    pub fn octants(&self) -> [Aabb; 8] {
+        let center = self.center();
+        core::array::from_fn(|i| {
+            let pick = |bit: usize, min: f32, center: f32, max: f32| if i & bit == 0 { (min, center) } else { (center, max) };
+            let (min_x, max_x) = pick(1, self.min.x, center.x, self.max.x);
+            let (min_y, max_y) = pick(2, self.min.y, center.y, self.max.y);
+            let (min_z, max_z) = pick(4, self.min.z, center.z, self.max.z);
+            Aabb::new(Vector3::new(min_x, min_y, min_z), Vector3::new(max_x, max_y, max_z))
+        })
+    }
+
+    /// Returns the index of the octant (see `octants`) that `point` falls into.
+    /// Points exactly on a splitting plane go to the upper octant. Points outside the box go to the octant closest to them.
+    #[inline]
+    pub fn child_index_of_point(&self, point: Vector3) -> usize {
+        let center = self.center();
+        (point.x >= center.x) as usize | ((point.y >= center.y) as usize) << 1 | ((point.z >= center.z) as usize) << 2
+    }
+
+    /// Writes the box in fgruc's versioned little-endian binary format, read back by `read_binary`.
+    ///
+    /// The layout of version 1 is the magic bytes `FGAB`, the version as `u16`, then `min` and `max` as 6 `f32`, 30 bytes in total.
+    #[cfg(feature = "std")]
+    pub fn write_binary(&self, w: &mut impl Write) -> io::Result<()> {
+        binary::write_header(w, AABB_MAGIC, AABB_VERSION)?;
+        binary::write_f32s(w, &[self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z])
+    }
+
+    /// Reads a box written by `write_binary`.
+    #[cfg(feature = "std")]
+    pub fn read_binary(r: &mut impl Read) -> Result<Aabb, BinaryReadError> {
+        binary::read_header(r, AABB_MAGIC, AABB_VERSION)?;
+        let [min_x, min_y, min_z, max_x, max_y, max_z] = binary::read_f32s::<6>(r)?;
+        Ok(Aabb::new(Vector3::new(min_x, min_y, min_z), Vector3::new(max_x, max_y, max_z)))
+    }
+
+}
+
+/// Builds an [`Aabb`] incrementally, so points can be streamed in without collecting them first.
+/// Produces exactly the same result as [`Aabb::from_points`] for the same points.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AabbBuilder {
+    bounds: Option<Aabb>,
+}
+
+impl AabbBuilder {
+
+    /// Creates a new builder which has not seen any points yet.
+    #[inline]
+    pub fn new() -> Self {
+        Self { bounds: None }
+    }
+
+    /// Grows the bounds to include `point`.
+    pub fn add_point(&mut self, point: Vector3) {
+        self.bounds = Some(match self.bounds {
+            None => Aabb::new(point, point),
+            Some(bounds) => Aabb::new(bounds.min.min(&point), bounds.max.max(&point)),
+        });
+    }
+
+    /// Grows the bounds to include the position of `vertex`.
+    #[inline]
+    pub fn add_vertex(&mut self, vertex: &Vertex) {
+        self.add_point(vertex.position);
+    }
+
+    /// Grows the bounds to include all the given points.
+    pub fn extend(&mut self, points: impl IntoIterator<Item = Vector3>) {
+        for point in points {
+            self.add_point(point);
+        }
+    }
+
+    /// Returns the built Aabb or `None` if no points were added.
+    #[inline]
+    pub fn build(self) -> Option<Aabb> {
+        self.bounds
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::f32::consts::FRAC_PI_4;
+    use crate::rendering::color::UniColor;
+    use crate::vectors::vector2::Vector2;
+    use super::*;
+
+    fn unit_box(offset: Vector3) -> Aabb {
+        Aabb::new(offset, offset + Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn union_ignores_empty_boxes() {
+        let a = unit_box(Vector3::new(0.0, 0.0, 0.0));
+        let inverted = Aabb::new(Vector3::new(5.0, 5.0, 5.0), Vector3::new(-5.0, 6.0, 6.0));
+        for empty in [Aabb::EMPTY, inverted] {
+            let union = a.union(&empty);
+            assert!(union.min.approx_eq(&a.min, 0.0) && union.max.approx_eq(&a.max, 0.0), "{:?}", union);
+            let union = empty.union(&a);
+            assert!(union.min.approx_eq(&a.min, 0.0) && union.max.approx_eq(&a.max, 0.0), "{:?}", union);
+        }
+        assert!(Aabb::EMPTY.union(&inverted).is_empty());
+
+        let union = a.union(&unit_box(Vector3::new(2.0, -1.0, 0.5)));
+        assert!(union.min.approx_eq(&Vector3::new(0.0, -1.0, 0.0), 0.0) && union.max.approx_eq(&Vector3::new(3.0, 1.0, 1.5), 0.0));
+    }
+
+    #[test]
+    fn intersects_overlapping_touching_and_disjoint_boxes() {
+        let a = unit_box(Vector3::new(0.0, 0.0, 0.0));
+        assert!(a.intersects(&unit_box(Vector3::new(0.5, 0.5, 0.5))));
+        assert!(a.intersects(&unit_box(Vector3::new(1.0, 0.0, 0.0))));
+        assert!(!a.intersects(&unit_box(Vector3::new(1.5, 0.0, 0.0))));
+        assert!(!a.intersects(&Aabb::EMPTY));
+        assert!(a.contains_aabb(&Aabb::new(Vector3::new(0.25, 0.25, 0.25), Vector3::new(1.0, 1.0, 1.0))));
+        assert!(!a.contains_aabb(&unit_box(Vector3::new(0.5, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn transform_refits_a_rotated_box() {
+        let a = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let rotated = a.transform(&Matrix4x4::rotation_z(FRAC_PI_4));
+        let half_diagonal = 2.0f32.sqrt();
+        assert!(rotated.extents().approx_eq(&Vector3::new(half_diagonal, half_diagonal, 1.0), 1e-5), "{:?}", rotated);
+        assert!(rotated.center().approx_eq(&Vector3::new(0.0, 0.0, 0.0), 1e-6));
+        assert!(Aabb::EMPTY.transform(&Matrix4x4::rotation_z(FRAC_PI_4)).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_round_trips_and_is_stable() {
+        let aabb = Aabb::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(2.0, 2.0, 0.25));
+        let mut bytes = Vec::new();
+        aabb.write_binary(&mut bytes).unwrap();
+        let expected: [u8; 30] = [
+            b'F', b'G', b'A', b'B', 1, 0,
+            0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x3f,
+            0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x80, 0x3e,
+        ];
+        assert_eq!(bytes, expected);
+        let read = Aabb::read_binary(&mut bytes.as_slice()).unwrap();
+        assert!(read.min.approx_eq(&aabb.min, 0.0) && read.max.approx_eq(&aabb.max, 0.0));
+        for length in 0..bytes.len() {
+            assert!(matches!(Aabb::read_binary(&mut &bytes[..length]), Err(BinaryReadError::Truncated)));
+        }
+    }
+
+    #[test]
+    fn builder_matches_from_points() {
+        let points: Vec<Vector3> = (0..100).map(|i| {
+            let t = i as f32;
+            Vector3::new((t * 0.37).sin() * 5.0, (t * 1.1).cos() * 3.0 - 1.0, (t * 0.13).sin() * 8.0)
+        }).collect();
+        let bounds = Aabb::from_points(&points).unwrap();
+        let fold = |f: fn(f32, f32) -> f32, start: f32| points.iter().fold(Vector3::from_one(start), |acc, p| Vector3::new(f(acc.x, p.x), f(acc.y, p.y), f(acc.z, p.z)));
+        assert_eq!((bounds.min, bounds.max), (fold(f32::min, f32::INFINITY), fold(f32::max, f32::NEG_INFINITY)));
+
+        // Streaming the points in pieces or as vertices gives the same box.
+        let mut builder = AabbBuilder::new();
+        builder.extend(points[..40].iter().copied());
+        for &point in &points[40..70] {
+            builder.add_point(point);
+        }
+        for &point in &points[70..] {
+            builder.add_vertex(&Vertex::new(point, Vector3::UP, Vector2::new(0.0, 0.0), UniColor::WHITE));
+        }
+        let built = builder.build().unwrap();
+        assert_eq!((built.min, built.max), (bounds.min, bounds.max));
+
+        assert!(Aabb::from_points(&[]).is_none());
+        assert!(AabbBuilder::new().build().is_none());
+        let single = Aabb::from_points(&[Vector3::new(1.0, -2.0, 3.0)]).unwrap();
+        assert_eq!((single.min, single.max), (Vector3::new(1.0, -2.0, 3.0), Vector3::new(1.0, -2.0, 3.0)));
+    }
+
+    #[test]
+    fn octants_tile_the_box() {
+        let aabb = Aabb::new(Vector3::new(0.1, -0.7, 0.3), Vector3::new(1.3, 0.2, 2.9));
+        let center = aabb.center();
+        let octants = aabb.octants();
+        for (i, octant) in octants.iter().enumerate() {
+            for (axis, bit) in [1, 2, 4].into_iter().enumerate() {
+                let (min, max) = if i & bit == 0 { (aabb.min[axis], center[axis]) } else { (center[axis], aabb.max[axis]) };
+                assert_eq!((octant.min[axis], octant.max[axis]), (min, max), "{} {}", i, axis);
+            }
+            // The center of each octant lies in it.
+            assert_eq!(aabb.child_index_of_point(octant.center()), i);
+        }
+        assert_eq!(octants[0].min, aabb.min);
+        assert_eq!(octants[7].max, aabb.max);
+
+        // Splitting planes belong to the upper octants, points outside to the closest one.
+        assert_eq!(aabb.child_index_of_point(center), 7);
+        assert_eq!(aabb.child_index_of_point(Vector3::new(-5.0, 5.0, -5.0)), 2);
+    }
+
+}
\ No newline at end of file
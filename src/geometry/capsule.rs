@@ -0,0 +1,187 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::sphere::Sphere;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector3::Vector3;
+
+/// A capsule described by the segment from `a` to `b`, swept by a sphere of `radius`.
+/// Commonly used as a collision volume for characters.
+#[derive(Copy, Clone, Debug)]
+pub struct Capsule {
+    pub a: Vector3,
+    pub b: Vector3,
+    pub radius: f32,
+}
+
+impl Capsule {
+
+    /// Creates a new Capsule from the two segment end points and the radius.
+    #[inline]
+    pub fn new(a: Vector3, b: Vector3, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    /// Returns true if `point` lies inside or on the surface of this capsule.
+    #[inline]
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        closest_point_on_segment(self.a, self.b, point).distance_squared(&point) <= self.radius * self.radius
+    }
+
+    /// Returns the point on or inside this capsule closest to `point`.
+    /// Points inside the capsule are returned unchanged.
+    pub fn closest_point(&self, point: Vector3) -> Vector3 {
+        let on_segment = closest_point_on_segment(self.a, self.b, point);
+        let offset = point - on_segment;
+        let distance_squared = offset.magnitude_squared();
+        if distance_squared <= self.radius * self.radius {
+            point
+        } else {
+            on_segment + offset * (self.radius / distance_squared.sqrt())
+        }
+    }
+
+    /// Returns true if this capsule and `sphere` overlap or touch.
+    #[inline]
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let radius = self.radius + sphere.radius;
+        closest_point_on_segment(self.a, self.b, sphere.center).distance_squared(&sphere.center) <= radius * radius
+    }
+
+    /// Returns true if this capsule and `other` overlap or touch.
+    pub fn intersects_capsule(&self, other: &Capsule) -> bool {
+        let (p, q) = closest_points_between_segments(self.a, self.b, other.a, other.b);
+        let radius = self.radius + other.radius;
+        p.distance_squared(&q) <= radius * radius
+    }
+
+    /// Returns the smallest Aabb containing this capsule, for broad-phase checks.
+    pub fn aabb(&self) -> Aabb {
+        let r = Vector3::from_one(self.radius);
+        Aabb::new(self.a.min(&self.b) - r, self.a.max(&self.b) + r)
+    }
+
+}
+
+/// Returns the point on the segment from `a` to `b` closest to `point`.
+fn closest_point_on_segment(a: Vector3, b: Vector3, point: Vector3) -> Vector3 {
+    let ab = b - a;
+    let length_squared = ab.magnitude_squared();
+    if is_degenerate(a, b, length_squared) {
+        return a;
+    }
+    a + ab * ((point - a).dot(&ab) / length_squared).clamp(0.0, 1.0)
+}
+
+/// Returns the closest points between the segments `p1`-`q1` and `p2`-`q2`, one on each segment.
+/// Handles degenerate segments and parallel segments, where any pair of closest points may be returned.
+fn closest_points_between_segments(p1: Vector3, q1: Vector3, p2: Vector3, q2: Vector3) -> (Vector3, Vector3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.magnitude_squared();
+    let e = d2.magnitude_squared();
+    let f = d2.dot(&r);
+
+    let (degenerate1, degenerate2) = (is_degenerate(p1, q1, a), is_degenerate(p2, q2, e));
+    if degenerate1 && degenerate2 {
+        return (p1, p2);
+    }
+
+    let (s, t) = if degenerate1 {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(&r);
+        if degenerate2 {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            // a * e * sin² of the angle between the segments, compared relative to a * e so the scale doesn't matter.
+            let denom = a * e - b * b;
+            // Parallel segments have no unique closest pair, so any s works; 0 is picked.
+            let s = if denom > f32::EPSILON * a * e { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Returns true if the segment from `p` to `q` with the squared length `length_squared` is too short to have a direction,
+/// i.e. its length is within the rounding error of its coordinates. Unlike an absolute threshold this works at any scale.
+#[inline]
+fn is_degenerate(p: Vector3, q: Vector3, length_squared: f32) -> bool {
+    length_squared <= f32::EPSILON * f32::EPSILON * p.magnitude_squared().max(q.magnitude_squared())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn capsule(a: [f32; 3], b: [f32; 3], radius: f32) -> Capsule {
+        Capsule::new(Vector3::new(a[0], a[1], a[2]), Vector3::new(b[0], b[1], b[2]), radius)
+    }
+
+    #[test]
+    fn contains_and_closest_point() {
+        let c = capsule([0.0, 0.0, 0.0], [0.0, 2.0, 0.0], 0.5);
+        assert!(c.contains_point(Vector3::new(0.5, 1.0, 0.0)));
+        assert!(c.contains_point(Vector3::new(0.0, 2.5, 0.0)));
+        assert!(!c.contains_point(Vector3::new(0.4, 2.4, 0.0)));
+        assert_eq!(c.closest_point(Vector3::new(0.1, 0.3, 0.1)), Vector3::new(0.1, 0.3, 0.1));
+        assert!(c.closest_point(Vector3::new(3.0, 1.0, 0.0)).approx_eq(&Vector3::new(0.5, 1.0, 0.0), 1e-6));
+        assert!(c.closest_point(Vector3::new(0.0, -4.0, 0.0)).approx_eq(&Vector3::new(0.0, -0.5, 0.0), 1e-6));
+        let aabb = c.aabb();
+        assert_eq!((aabb.min, aabb.max), (Vector3::new(-0.5, -0.5, -0.5), Vector3::new(0.5, 2.5, 0.5)));
+    }
+
+    #[test]
+    fn intersects_spheres_and_capsules() {
+        let c = capsule([0.0, 0.0, 0.0], [4.0, 0.0, 0.0], 1.0);
+        assert!(c.intersects_sphere(&Sphere::new(Vector3::new(2.0, 1.5, 0.0), 0.5)));
+        assert!(!c.intersects_sphere(&Sphere::new(Vector3::new(2.0, 1.6, 0.0), 0.5)));
+        assert!(c.intersects_sphere(&Sphere::new(Vector3::new(-1.4, 0.0, 0.0), 0.5)));
+
+        // Crossing above each other, parallel, end to end and a capsule shrunk to a sphere.
+        assert!(c.intersects_capsule(&capsule([2.0, -3.0, 1.9], [2.0, 3.0, 1.9], 1.0)));
+        assert!(!c.intersects_capsule(&capsule([2.0, -3.0, 2.1], [2.0, 3.0, 2.1], 1.0)));
+        assert!(c.intersects_capsule(&capsule([1.0, 1.9, 0.0], [9.0, 1.9, 0.0], 1.0)));
+        assert!(!c.intersects_capsule(&capsule([1.0, 2.1, 0.0], [9.0, 2.1, 0.0], 1.0)));
+        assert!(c.intersects_capsule(&capsule([5.9, 0.0, 0.0], [9.0, 0.0, 0.0], 1.0)));
+        assert!(!c.intersects_capsule(&capsule([6.1, 0.0, 0.0], [9.0, 0.0, 0.0], 1.0)));
+        assert!(c.intersects_capsule(&capsule([3.0, 1.5, 0.0], [3.0, 1.5, 0.0], 0.6)));
+        assert!(capsule([3.0, 1.5, 0.0], [3.0, 1.5, 0.0], 0.6).intersects_capsule(&c));
+        assert!(!c.intersects_capsule(&capsule([3.0, 1.7, 0.0], [3.0, 1.7, 0.0], 0.6)));
+    }
+
+    #[test]
+    fn small_capsules_are_not_treated_as_points() {
+        // A millimeter-sized capsule, whose squared length is below `f32::EPSILON`.
+        let tiny = capsule([0.0, 0.0, 0.0], [1e-4, 0.0, 0.0], 1e-5);
+        assert!(tiny.contains_point(Vector3::new(1e-4, 5e-6, 0.0)));
+        assert!(tiny.contains_point(Vector3::new(5e-5, 0.0, 5e-6)));
+        assert!(!tiny.contains_point(Vector3::new(5e-5, 2e-5, 0.0)));
+
+        // Crossing 1e-4 apart, which a segment treated as a point or as parallel misses.
+        let a = capsule([0.0, 0.0, 0.0], [1e-3, 0.0, 0.0], 6e-5);
+        let b = capsule([5e-4, -5e-4, 1e-4], [5e-4, 5e-4, 1e-4], 6e-5);
+        assert!(a.intersects_capsule(&b) && b.intersects_capsule(&a));
+        let b = capsule([5e-4, -5e-4, 1.3e-4], [5e-4, 5e-4, 1.3e-4], 6e-5);
+        assert!(!a.intersects_capsule(&b) && !b.intersects_capsule(&a));
+
+        // The same configuration scaled up and moved far from the origin.
+        let offset = Vector3::new(1000.0, -2000.0, 500.0);
+        let scaled = |c: Capsule| Capsule::new(c.a * 1e4 + offset, c.b * 1e4 + offset, c.radius * 1e4);
+        let a = scaled(capsule([0.0, 0.0, 0.0], [1e-3, 0.0, 0.0], 6e-5));
+        assert!(a.intersects_capsule(&scaled(capsule([5e-4, -5e-4, 1e-4], [5e-4, 5e-4, 1e-4], 6e-5))));
+        assert!(!a.intersects_capsule(&scaled(capsule([5e-4, -5e-4, 1.3e-4], [5e-4, 5e-4, 1.3e-4], 6e-5))));
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::plane::Plane;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector3::Vector3;
+use crate::vectors::vector4::Vector4;
+
+/// How a volume lies relative to a `Frustum`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Containment {
+    /// Completely inside, e.g. nothing needs to be clipped.
+    Inside,
+    /// Partly inside, or too close to a corner of the frustum to tell.
+    Intersecting,
+    /// Completely outside, so it can be culled.
+    Outside,
+}
+
+/// The view volume of a camera as six normalized planes facing inwards, used for frustum culling.
+///
+/// The planes are ordered left, right, bottom, top, near, far. They are extracted from a view-projection matrix
+/// with the Gribb/Hartmann method, so perspective and orthographic projections work alike.
+/// For the reverse-Z `Matrix4x4::perspective_infinite` near and far swap places and the far plane at infinity
+/// has a zero normal and a positive `d`, so nothing is ever behind it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+
+    /// Extracts the frustum of a view-projection matrix with the OpenGL clip-space depth range of [-1, 1],
+    /// e.g. `projection * view` with a projection from `Matrix4x4::perspective` or `Matrix4x4::orthographic`.
+    /// Using the projection alone gives the frustum in view space. Same as [`Frustum::from_matrix_gl`].
+    #[inline]
+    pub fn from_matrix(view_projection: &Matrix4x4) -> Self {
+        Self::from_matrix_gl(view_projection)
+    }
+
+    /// Extracts the frustum of a view-projection matrix with the OpenGL clip-space depth range of [-1, 1].
+    /// Same as [`Frustum::from_matrix`].
+    pub fn from_matrix_gl(view_projection: &Matrix4x4) -> Self {
+        let [x, y, z, w] = clip_rows(view_projection);
+        Self::from_rows([w + x, w - x, w + y, w - y, w + z, w - z])
+    }
+
+    /// Extracts the frustum of a view-projection matrix with the clip-space depth range of [0, 1] used by wgpu, Vulkan,
+    /// Metal and Direct3D, e.g. with a projection from `Matrix4x4::perspective_wgpu` or `Matrix4x4::orthographic_wgpu`.
+    pub fn from_matrix_wgpu(view_projection: &Matrix4x4) -> Self {
+        let [x, y, z, w] = clip_rows(view_projection);
+        Self::from_rows([w + x, w - x, w + y, w - y, z, w - z])
+    }
+
+    /// Turns the plane equations as `(a, b, c, d)` rows into normalized planes.
+    fn from_rows(rows: [Vector4; 6]) -> Self {
+        Self {
+            planes: rows.map(|row| {
+                let mut plane = Plane::new(Vector3::new(row.x, row.y, row.z), row.w);
+                plane.normalize();
+                plane
+            }),
+        }
+    }
+
+    /// Returns true if `point` lies inside the frustum or on its boundary.
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.planes.iter().all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// Tests the sphere around `center` with `radius` against the frustum.
+    /// Spheres touching a plane from the inside count as inside, spheres touching it from the outside as intersecting.
+    ///
+    /// Like most culling tests this is conservative: a sphere just outside a corner of the frustum,
+    /// where it is in front of every single plane, is reported as intersecting.
+    pub fn intersects_sphere(&self, center: Vector3, radius: f32) -> Containment {
+        let mut containment = Containment::Inside;
+        for plane in &self.planes {
+            let distance = plane.signed_distance(center);
+            if distance < -radius {
+                return Containment::Outside;
+            }
+            if distance < radius {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+
+    /// Tests the box against the frustum using its corners closest to and farthest from every plane.
+    /// An empty box is outside.
+    ///
+    /// Like `intersects_sphere` this is conservative, boxes just outside a corner of the frustum are reported as intersecting.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> Containment {
+        if aabb.is_empty() {
+            return Containment::Outside;
+        }
+
+        let mut containment = Containment::Inside;
+        for plane in &self.planes {
+            let n = plane.normal;
+            // The corner farthest along the normal is the last to leave the inside, the nearest one the first.
+            let farthest = Vector3::new(
+                if n.x >= 0.0 { aabb.max.x } else { aabb.min.x },
+                if n.y >= 0.0 { aabb.max.y } else { aabb.min.y },
+                if n.z >= 0.0 { aabb.max.z } else { aabb.min.z },
+            );
+            let nearest = Vector3::new(
+                if n.x >= 0.0 { aabb.min.x } else { aabb.max.x },
+                if n.y >= 0.0 { aabb.min.y } else { aabb.max.y },
+                if n.z >= 0.0 { aabb.min.z } else { aabb.max.z },
+            );
+            if plane.signed_distance(farthest) < 0.0 {
+                return Containment::Outside;
+            }
+            if plane.signed_distance(nearest) < 0.0 {
+                containment = Containment::Intersecting;
+            }
+        }
+        containment
+    }
+
+}
+
+/// Returns the rows of `m`, which compute the clip-space x, y, z and w of a point.
+#[inline]
+fn clip_rows(m: &Matrix4x4) -> [Vector4; 4] {
+    [m.row(0), m.row(1), m.row(2), m.row(3)]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::f32::consts::FRAC_PI_2;
+    use super::*;
+
+    /// A 90 degree view down -Z from the origin between 1 and 10 units away, so inside |x| <= -z and |y| <= -z.
+    fn frustum() -> Frustum {
+        Frustum::from_matrix(&Matrix4x4::perspective(FRAC_PI_2, 1.0, 1.0, 10.0).unwrap())
+    }
+
+    fn assert_plane(plane: &Plane, normal: Vector3, d: f32) {
+        // Loose enough for the `fast_math` magnitude.
+        assert!(plane.normal.approx_eq(&normal.normalized(), 2e-3) && (plane.d - d).abs() < 2e-3, "{:?}", plane);
+    }
+
+    #[test]
+    fn planes_of_a_perspective_matrix() {
+        for frustum in [frustum(), Frustum::from_matrix_wgpu(&Matrix4x4::perspective_wgpu(FRAC_PI_2, 1.0, 1.0, 10.0).unwrap())] {
+            let [left, right, bottom, top, near, far] = frustum.planes;
+            assert_plane(&left, Vector3::new(1.0, 0.0, -1.0), 0.0);
+            assert_plane(&right, Vector3::new(-1.0, 0.0, -1.0), 0.0);
+            assert_plane(&bottom, Vector3::new(0.0, 1.0, -1.0), 0.0);
+            assert_plane(&top, Vector3::new(0.0, -1.0, -1.0), 0.0);
+            assert_plane(&near, Vector3::new(0.0, 0.0, -1.0), -1.0);
+            assert_plane(&far, Vector3::new(0.0, 0.0, 1.0), 10.0);
+        }
+
+        // With a view matrix the planes move along with the camera.
+        let view = Matrix4x4::look_at(Vector3::new(0.0, 0.0, 5.0), Vector3::zero(), Vector3::UP);
+        let moved = Frustum::from_matrix(&(Matrix4x4::perspective(FRAC_PI_2, 1.0, 1.0, 10.0).unwrap() * view));
+        assert_plane(&moved.planes[4], Vector3::new(0.0, 0.0, -1.0), 4.0);
+        assert!(moved.contains_point(Vector3::zero()) && !moved.contains_point(Vector3::new(0.0, 0.0, 4.5)));
+    }
+
+    #[test]
+    fn contains_point_inside_outside_and_on_the_boundary() {
+        let frustum = frustum();
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -5.0)));
+        assert!(frustum.contains_point(Vector3::new(4.9, -4.9, -5.0)));
+        assert!(!frustum.contains_point(Vector3::new(5.1, 0.0, -5.0)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -0.5)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -10.5)));
+        assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 5.0)));
+    }
+
+    #[test]
+    fn spheres_and_boxes_inside_outside_and_straddling() {
+        let frustum = frustum();
+        let cases = [
+            (Vector3::new(0.0, 0.0, -5.0), 1.0, Containment::Inside),
+            (Vector3::new(0.0, 0.0, -5.0), 20.0, Containment::Intersecting),
+            // Across the near, left and far planes.
+            (Vector3::new(0.0, 0.0, -1.0), 0.5, Containment::Intersecting),
+            (Vector3::new(-5.0, 0.0, -5.0), 0.5, Containment::Intersecting),
+            (Vector3::new(0.0, 0.0, -10.0), 0.5, Containment::Intersecting),
+            // Behind the camera, past the far plane and beside the frustum.
+            (Vector3::new(0.0, 0.0, 5.0), 1.0, Containment::Outside),
+            (Vector3::new(0.0, 0.0, -12.0), 1.0, Containment::Outside),
+            (Vector3::new(9.0, 0.0, -5.0), 1.0, Containment::Outside),
+        ];
+        for (center, radius, expected) in cases {
+            assert_eq!(frustum.intersects_sphere(center, radius), expected, "{:?} {}", center, radius);
+            // The box around the sphere lies the same way.
+            let aabb = Aabb::new(center - Vector3::from_one(radius), center + Vector3::from_one(radius));
+            assert_eq!(frustum.intersects_aabb(&aabb), expected, "{:?}", aabb);
+        }
+        assert_eq!(frustum.intersects_aabb(&Aabb::EMPTY), Containment::Outside);
+    }
+
+}
\ No newline at end of file
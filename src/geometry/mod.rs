@@ -0,0 +1,8 @@
+pub mod aabb;
+pub mod sphere;
+pub mod rect;
+pub mod capsule;
+pub mod ray;
+pub mod triangle;
+pub mod plane;
+pub mod frustum;
\ No newline at end of file
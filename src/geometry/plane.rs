@@ -0,0 +1,169 @@
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector3::Vector3;
+
+/// The plane of all points `x` with `dot(normal, x) + d = 0`, the same convention as `sdf::plane` and `Ray::intersect_plane`.
+///
+/// The side `normal` points to is the front (positive) side. If `normal` is unit length, `d` is the signed distance
+/// from the plane to the origin and `signed_distance` returns true distances. Planes built by `from_point_normal` and
+/// `from_points` are normalized, planes from `new` keep their scale until `normalize` is called.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Plane {
+    pub normal: Vector3,
+    pub d: f32,
+}
+
+impl Plane {
+
+    /// Creates a new Plane from the given normal and offset, without normalizing them.
+    #[inline]
+    pub fn new(normal: Vector3, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    /// Creates the plane through `point` facing `normal`, which is normalized first.
+    #[inline]
+    pub fn from_point_normal(point: Vector3, normal: Vector3) -> Self {
+        let normal = normal.normalized();
+        Self { normal, d: -normal.dot(&point) }
+    }
+
+    /// Creates the plane through the three points, facing the side from which they appear counter-clockwise.
+    /// Returns `None` if the points are collinear or coincident.
+    pub fn from_points(a: Vector3, b: Vector3, c: Vector3) -> Option<Self> {
+        let (ab, ac) = (b - a, c - a);
+        let cross = ab.cross(&ac);
+        // Relative to the edge lengths, so the test does not depend on the size of the triangle.
+        let length_squared = cross.magnitude_squared();
+        if length_squared <= f32::EPSILON * f32::EPSILON * ab.magnitude_squared() * ac.magnitude_squared() || !length_squared.is_finite() {
+            return None;
+        }
+        Some(Plane::from_point_normal(a, cross))
+    }
+
+    /// Returns the signed distance from the plane to `p`: positive in front of the plane, negative behind it and zero on it.
+    /// The distance is scaled by the length of `normal`, so it is only a true distance for normalized planes.
+    #[inline]
+    pub fn signed_distance(&self, p: Vector3) -> f32 {
+        self.normal.dot(&p) + self.d
+    }
+
+    /// Rescales the plane so its normal is unit length, keeping the same points on it.
+    /// A plane with a zero normal is left unchanged.
+    pub fn normalize(&mut self) {
+        let length_squared = self.normal.magnitude_squared();
+        if length_squared > 0.0 {
+            let inv_length = 1.0 / length_squared.sqrt();
+            self.normal *= inv_length;
+            self.d *= inv_length;
+        }
+    }
+
+    /// Returns the point on the plane closest to `p`.
+    pub fn project_point(&self, p: Vector3) -> Vector3 {
+        p - self.normal * self.normal_factor(self.signed_distance(p))
+    }
+
+    /// Mirrors `p` at the plane, so it ends up at the same distance on the other side.
+    pub fn reflect_point(&self, p: Vector3) -> Vector3 {
+        p - self.normal * (2.0 * self.normal_factor(self.signed_distance(p)))
+    }
+
+    /// Mirrors the direction `v` at the plane, flipping its component along the normal. Unlike `reflect_point`, `d` is ignored.
+    pub fn reflect_vector(&self, v: Vector3) -> Vector3 {
+        v - self.normal * (2.0 * self.normal_factor(self.normal.dot(&v)))
+    }
+
+    /// Returns the point where the infinite line through `a` and `b` crosses the plane, which can lie outside the segment.
+    /// Compare the signs of `signed_distance` at `a` and `b` first to clip a segment.
+    /// Returns `None` if the line is parallel to the plane or `a` equals `b`.
+    pub fn intersect_line(&self, a: Vector3, b: Vector3) -> Option<Vector3> {
+        let (distance_a, distance_b) = (self.signed_distance(a), self.signed_distance(b));
+        let denominator = distance_a - distance_b;
+        if denominator == 0.0 {
+            return None;
+        }
+        let t = distance_a / denominator;
+        t.is_finite().then(|| a + (b - a) * t)
+    }
+
+    /// Divides `x` by the squared length of the normal, so the projections work for planes which are not normalized.
+    #[inline]
+    fn normal_factor(&self, x: f32) -> f32 {
+        let length_squared = self.normal.magnitude_squared();
+        if length_squared > 0.0 { x / length_squared } else { 0.0 }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// The plane y = 2 facing +Y, once normalized and once scaled by 4.
+    fn planes() -> [Plane; 2] {
+        [Plane::new(Vector3::new(0.0, 1.0, 0.0), -2.0), Plane::new(Vector3::new(0.0, 4.0, 0.0), -8.0)]
+    }
+
+    #[test]
+    fn signed_distance_is_positive_in_front() {
+        let [plane, scaled] = planes();
+        assert_eq!(plane.signed_distance(Vector3::new(3.0, 5.0, -1.0)), 3.0);
+        assert_eq!(plane.signed_distance(Vector3::new(3.0, -1.0, -1.0)), -3.0);
+        assert_eq!(plane.signed_distance(Vector3::new(7.0, 2.0, 9.0)), 0.0);
+        // Scaled planes scale the distance until they are normalized.
+        assert_eq!(scaled.signed_distance(Vector3::new(0.0, 5.0, 0.0)), 12.0);
+        let mut normalized = scaled;
+        normalized.normalize();
+        assert_eq!(normalized, plane);
+        let mut zero = Plane::new(Vector3::zero(), 1.0);
+        zero.normalize();
+        assert_eq!(zero, Plane::new(Vector3::zero(), 1.0));
+    }
+
+    #[test]
+    fn constructors_agree() {
+        let a = Vector3::new(1.0, 2.0, 0.0);
+        let from_points = Plane::from_points(a, Vector3::new(1.0, 2.0, 5.0), Vector3::new(4.0, 2.0, 0.0)).unwrap();
+        let from_point_normal = Plane::from_point_normal(Vector3::new(-3.0, 2.0, 8.0), Vector3::new(0.0, 0.5, 0.0));
+        // Loose enough for the `fast_math` magnitude.
+        for plane in [from_points, from_point_normal] {
+            assert!(plane.normal.approx_eq(&Vector3::new(0.0, 1.0, 0.0), 2e-3) && (plane.d + 2.0).abs() < 4e-3, "{:?}", plane);
+        }
+        assert!(Plane::from_points(a, a, Vector3::new(4.0, 2.0, 0.0)).is_none());
+        assert!(Plane::from_points(a, a * 2.0, a * 3.0).is_none());
+    }
+
+    #[test]
+    fn projection_and_reflection() {
+        let p = Vector3::new(3.0, 5.0, -1.0);
+        for plane in planes() {
+            assert_eq!(plane.project_point(p), Vector3::new(3.0, 2.0, -1.0));
+            assert_eq!(plane.reflect_point(p), Vector3::new(3.0, -1.0, -1.0));
+            assert_eq!(plane.reflect_point(plane.reflect_point(p)), p);
+            // Directions ignore the offset of the plane.
+            assert_eq!(plane.reflect_vector(Vector3::new(1.0, -2.0, 3.0)), Vector3::new(1.0, 2.0, 3.0));
+        }
+        let tilted = Plane::from_point_normal(Vector3::new(1.0, 1.0, 1.0), Vector3::new(1.0, 1.0, 0.0));
+        let projected = tilted.project_point(Vector3::new(5.0, 0.0, 2.0));
+        assert!(tilted.signed_distance(projected).abs() < 1e-5, "{:?}", projected);
+        assert!(projected.approx_eq(&Vector3::new(3.5, -1.5, 2.0), 1e-5), "{:?}", projected);
+    }
+
+    #[test]
+    fn intersect_line_finds_the_crossing() {
+        for plane in planes() {
+            let hit = plane.intersect_line(Vector3::new(1.0, 0.0, 1.0), Vector3::new(3.0, 4.0, 1.0)).unwrap();
+            assert_eq!(hit, Vector3::new(2.0, 2.0, 1.0));
+            // The line continues past the segment.
+            let hit = plane.intersect_line(Vector3::new(0.0, 5.0, 0.0), Vector3::new(0.0, 4.0, 1.0)).unwrap();
+            assert_eq!(hit, Vector3::new(0.0, 2.0, 3.0));
+            // Parallel lines and single points have no crossing, even on the plane itself.
+            assert!(plane.intersect_line(Vector3::new(0.0, 3.0, 0.0), Vector3::new(5.0, 3.0, 1.0)).is_none());
+            assert!(plane.intersect_line(Vector3::new(0.0, 2.0, 0.0), Vector3::new(5.0, 2.0, 1.0)).is_none());
+            assert!(plane.intersect_line(Vector3::new(1.0, 1.0, 1.0), Vector3::new(1.0, 1.0, 1.0)).is_none());
+        }
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,219 @@
+use crate::geometry::aabb::Aabb;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector3::Vector3;
+
+/// A half-line starting at `origin` and going along `direction`. Used for picking and other raycasts.
+/// The direction does not need to be normalized, but hit distances `t` are measured in multiples of it.
+#[derive(Copy, Clone, Debug)]
+pub struct Ray {
+    pub origin: Vector3,
+    pub direction: Vector3,
+}
+
+impl Ray {
+
+    /// Creates a new Ray from the given origin and direction.
+    #[inline]
+    pub fn new(origin: Vector3, direction: Vector3) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Returns the point at `origin + direction * t`.
+    #[inline]
+    pub fn point_at(&self, t: f32) -> Vector3 {
+        self.origin + self.direction * t
+    }
+
+    /// Returns the distance `t >= 0` at which this ray hits the sphere around `center`.
+    /// A ray starting inside the sphere reports where it leaves it, a ray just touching the sphere counts as a hit.
+    pub fn intersect_sphere(&self, center: Vector3, radius: f32) -> Option<f32> {
+        let a = self.direction.magnitude_squared();
+        if a == 0.0 {
+            return None;
+        }
+        let to_origin = self.origin - center;
+        let half_b = to_origin.dot(&self.direction);
+        let c = to_origin.magnitude_squared() - radius * radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let (near, far) = ((-half_b - root) / a, (-half_b + root) / a);
+        if near >= 0.0 {
+            Some(near)
+        } else {
+            (far >= 0.0).then_some(far)
+        }
+    }
+
+    /// Returns the distance `t >= 0` at which this ray hits `aabb`, using the slab method.
+    /// A ray starting inside the box reports where it leaves it. Direction components of zero are fine,
+    /// such a ray hits if its origin is between the two slabs of that axis, borders included.
+    /// A zero direction never hits, like in `intersect_sphere`.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        if aabb.is_empty() || self.direction.magnitude_squared() == 0.0 {
+            return None;
+        }
+        let inv_direction = Vector3::new(1.0 / self.direction.x, 1.0 / self.direction.y, 1.0 / self.direction.z);
+        let (entry, exit) = slab_interval(aabb, self.origin, inv_direction);
+        if exit < entry.max(0.0) {
+            None
+        } else if entry >= 0.0 {
+            Some(entry)
+        } else {
+            Some(exit)
+        }
+    }
+
+    /// Returns the distance `t >= 0` at which this ray hits the plane `dot(normal, x) + d = 0`, like `sdf::plane`.
+    /// Both sides of the plane are hit. A ray parallel to the plane never hits it, even if it lies in the plane.
+    pub fn intersect_plane(&self, normal: Vector3, d: f32) -> Option<f32> {
+        let denominator = normal.dot(&self.direction);
+        if denominator == 0.0 {
+            return None;
+        }
+        let t = -(normal.dot(&self.origin) + d) / denominator;
+        (t >= 0.0 && t.is_finite()).then_some(t)
+    }
+
+    /// Returns the distance `t >= 0` at which this ray hits the triangle `a`, `b`, `c`, using Möller–Trumbore.
+    /// If `cull_backfaces` is true, triangles whose counter-clockwise side faces away from the ray are ignored.
+    /// Hits exactly on an edge or a corner count.
+    #[inline]
+    pub fn intersect_triangle(&self, a: Vector3, b: Vector3, c: Vector3, cull_backfaces: bool) -> Option<f32> {
+        self.intersect_triangle_barycentric(a, b, c, cull_backfaces).map(|(t, _, _)| t)
+    }
+
+    /// Like `intersect_triangle`, but also returns the barycentric weights `u` of `b` and `v` of `c`.
+    pub(crate) fn intersect_triangle_barycentric(
+        &self,
+        a: Vector3,
+        b: Vector3,
+        c: Vector3,
+        cull_backfaces: bool,
+    ) -> Option<(f32, f32, f32)> {
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(&edge2);
+        let det = edge1.dot(&p);
+        if det == 0.0 || (cull_backfaces && det < 0.0) || !det.is_finite() {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(&p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&edge1);
+        let v = self.direction.dot(&q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(&q) * inv_det;
+        (t >= 0.0).then_some((t, u, v))
+    }
+
+}
+
+/// Returns the distances at which a ray enters and leaves the slabs of `bounds`, with `entry > exit` if it misses.
+/// Negative distances are behind the origin. Works with infinite inverse direction components for rays parallel to an axis.
+#[inline]
+pub(crate) fn slab_interval(bounds: &Aabb, origin: Vector3, inv_direction: Vector3) -> (f32, f32) {
+    let mut entry = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    for (o, inv, lo, hi) in [
+        (origin.x, inv_direction.x, bounds.min.x, bounds.max.x),
+        (origin.y, inv_direction.y, bounds.min.y, bounds.max.y),
+        (origin.z, inv_direction.z, bounds.min.z, bounds.max.z),
+    ] {
+        if inv.is_infinite() {
+            // The ray is parallel to the slab, so it is between its borders everywhere or nowhere.
+            // Checked explicitly, as a ray starting exactly on a border would produce 0 * inf = NaN.
+            if o < lo || o > hi {
+                return (f32::INFINITY, f32::NEG_INFINITY);
+            }
+            continue;
+        }
+        let (t1, t2) = ((lo - o) * inv, (hi - o) * inv);
+        entry = entry.max(t1.min(t2));
+        exit = exit.min(t1.max(t2));
+    }
+    (entry, exit)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn unit_box() -> Aabb {
+        Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn aabb_axis_aligned_hits_and_misses() {
+        let ray = Ray::new(Vector3::new(-5.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(4.0));
+        // Starting inside reports the exit.
+        let ray = Ray::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(0.5));
+        // Pointing away.
+        let ray = Ray::new(Vector3::new(-5.0, 0.0, 0.0), Vector3::new(-1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+    }
+
+    #[test]
+    fn aabb_rays_parallel_to_a_slab() {
+        // Sliding along the top face counts, just above it misses.
+        let ray = Ray::new(Vector3::new(-5.0, 1.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some(4.0));
+        let ray = Ray::new(Vector3::new(-5.0, 1.0001, 0.0), Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+        let ray = Ray::new(Vector3::new(-5.0, 1.0001, 0.0), Vector3::new(1.0, -0.001, 0.0));
+        assert!(ray.intersect_aabb(&unit_box()).is_some());
+    }
+
+    #[test]
+    fn zero_direction_never_hits() {
+        for origin in [Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0), Vector3::new(5.0, 0.0, 0.0)] {
+            let ray = Ray::new(origin, Vector3::new(0.0, 0.0, 0.0));
+            assert_eq!(ray.intersect_aabb(&unit_box()), None);
+            assert_eq!(ray.intersect_sphere(Vector3::new(0.0, 0.0, 0.0), 2.0), None);
+        }
+    }
+
+    #[test]
+    fn sphere_hits_grazes_and_exits() {
+        let ray = Ray::new(Vector3::new(0.0, 0.0, -5.0), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(ray.intersect_sphere(Vector3::new(0.0, 0.0, 0.0), 1.0), Some(4.0));
+        assert_eq!(ray.intersect_sphere(Vector3::new(0.0, 1.0001, 0.0), 1.0), None);
+        let inside = Ray::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(inside.intersect_sphere(Vector3::new(0.0, 0.0, 0.0), 2.0), Some(2.0));
+    }
+
+    #[test]
+    fn plane_and_triangle_hits() {
+        let ray = Ray::new(Vector3::new(0.0, 3.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+        assert_eq!(ray.intersect_plane(Vector3::new(0.0, 1.0, 0.0), -1.0), Some(2.0));
+        assert_eq!(ray.intersect_plane(Vector3::new(1.0, 0.0, 0.0), 0.0), None);
+
+        let (a, b, c) = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 2.0));
+        // Exactly on the edge from b to c, from above and below.
+        let down = Ray::new(Vector3::new(1.0, 3.0, 1.0), Vector3::new(0.0, -1.0, 0.0));
+        assert_eq!(down.intersect_triangle(a, b, c, false), Some(3.0));
+        let up = Ray::new(Vector3::new(1.0, -3.0, 1.0), Vector3::new(0.0, 1.0, 0.0));
+        assert_eq!(up.intersect_triangle(a, b, c, false), Some(3.0));
+        // a, b, c winds counter-clockwise seen from below, so culling drops the ray from above.
+        assert_eq!(down.intersect_triangle(a, b, c, true), None);
+        assert_eq!(up.intersect_triangle(a, b, c, true), Some(3.0));
+        let miss = Ray::new(Vector3::new(1.5, 3.0, 1.5), Vector3::new(0.0, -1.0, 0.0));
+        assert_eq!(miss.intersect_triangle(a, b, c, false), None);
+    }
+
+}
\ No newline at end of file
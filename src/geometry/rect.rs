@@ -0,0 +1,199 @@
+use crate::vectors::vector2::Vector2;
+
+/// An axis-aligned 2D rectangle described by its top-left `position` and its `size`.
+/// Used for UI layout and screen-space bounds, so y usually points down.
+#[derive(Copy, Clone, Debug)]
+pub struct Rect {
+    pub position: Vector2,
+    pub size: Vector2,
+}
+
+impl Rect {
+
+    /// Creates a new Rect from the given position and size.
+    #[inline]
+    pub fn new(position: Vector2, size: Vector2) -> Self {
+        Self { position, size }
+    }
+
+    /// Creates a new Rect spanning from `min` to `max`.
+    #[inline]
+    pub fn from_min_max(min: Vector2, max: Vector2) -> Self {
+        Self::new(min, max - min)
+    }
+
+    /// Returns the corner with the smallest coordinates.
+    #[inline]
+    pub fn min(&self) -> Vector2 {
+        self.position
+    }
+
+    /// Returns the corner with the largest coordinates.
+    #[inline]
+    pub fn max(&self) -> Vector2 {
+        self.position + self.size
+    }
+
+    /// Returns this Rect aligned to the device pixel grid, with `device_pixel_ratio` device pixels per unit.
+    ///
+    /// The position and the size are rounded separately instead of rounding both corners,
+    /// so a rect moving by sub-pixel amounts keeps its size instead of jittering by a pixel.
+    #[inline]
+    pub fn snapped_to_pixels(&self, device_pixel_ratio: f32) -> Rect {
+        Rect::new(self.position.snapped_to_pixels(device_pixel_ratio), self.size.snapped_to_pixels(device_pixel_ratio))
+    }
+
+    /// Returns this Rect shrunk by `margin` on every side, or grown if `margin` is negative.
+    /// The size never goes below zero, a rect that is too small collapses onto its center.
+    pub fn inset(&self, margin: f32) -> Rect {
+        let size = Vector2::new((self.size.x - 2.0 * margin).max(0.0), (self.size.y - 2.0 * margin).max(0.0));
+        let center = self.position + self.size * 0.5;
+        Rect::new(center - size * 0.5, size)
+    }
+
+    /// Returns the center of this Rect.
+    #[inline]
+    pub fn center(&self) -> Vector2 {
+        self.position + self.size * 0.5
+    }
+
+    /// Splits this Rect at its center into 4 quadrants, e.g. for building a quadtree.
+    ///
+    /// Quadrant `i` lies on the right half if bit 0 of `i` is set and on the bottom half (larger y) if bit 1 is set,
+    /// so the order is top-left, top-right, bottom-left, bottom-right. The inner edges of neighboring quadrants match exactly.
+    pub fn quadrants(&self) -> [Rect; 4] {
+        let half = self.size * 0.5;
+        let center = self.position + half;
+        core::array::from_fn(|i| {
+            let x = if i & 1 == 0 { self.position.x } else { center.x };
+            let y = if i & 2 == 0 { self.position.y } else { center.y };
+            Rect::new(Vector2::new(x, y), half)
+        })
+    }
+
+    /// Returns the index of the quadrant (see `quadrants`) that `point` falls into.
+    /// Points exactly on a splitting line go to the right or bottom quadrant. Points outside go to the quadrant closest to them.
+    #[inline]
+    pub fn child_index_of_point(&self, point: Vector2) -> usize {
+        let center = self.center();
+        (point.x >= center.x) as usize | ((point.y >= center.y) as usize) << 1
+    }
+
+    /// Splits this Rect into a left and a right part, the left one taking `fraction` (clamped to [0, 1]) of the width.
+    /// Both parts share the split edge exactly, so they tile the original without gaps or overlap.
+    pub fn split_h(&self, fraction: f32) -> (Rect, Rect) {
+        let width = self.size.x * fraction.clamp(0.0, 1.0);
+        (
+            Rect::new(self.position, Vector2::new(width, self.size.y)),
+            Rect::new(Vector2::new(self.position.x + width, self.position.y), Vector2::new(self.size.x - width, self.size.y)),
+        )
+    }
+
+    /// Splits this Rect into a top and a bottom part, the top one taking `fraction` (clamped to [0, 1]) of the height.
+    /// Both parts share the split edge exactly, so they tile the original without gaps or overlap.
+    pub fn split_v(&self, fraction: f32) -> (Rect, Rect) {
+        let height = self.size.y * fraction.clamp(0.0, 1.0);
+        (
+            Rect::new(self.position, Vector2::new(self.size.x, height)),
+            Rect::new(Vector2::new(self.position.x, self.position.y + height), Vector2::new(self.size.x, self.size.y - height)),
+        )
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn corners_of_new_and_from_min_max() {
+        let rect = Rect::new(Vector2::new(10.0, 20.0), Vector2::new(30.0, 40.0));
+        assert_eq!(rect.min(), Vector2::new(10.0, 20.0));
+        assert_eq!(rect.max(), Vector2::new(40.0, 60.0));
+
+        let rect = Rect::from_min_max(Vector2::new(-5.0, 2.0), Vector2::new(5.0, 3.0));
+        assert_eq!(rect.position, Vector2::new(-5.0, 2.0));
+        assert_eq!(rect.size, Vector2::new(10.0, 1.0));
+        assert_eq!(rect.max(), Vector2::new(5.0, 3.0));
+    }
+
+    #[test]
+    fn snapped_to_pixels_keeps_the_size_while_moving() {
+        // At 2 device pixels per unit the grid is 0.5 units apart.
+        let size = Vector2::new(10.2, 5.0);
+        for x in [0.0, 0.1, 0.2, 0.3, 0.4] {
+            let snapped = Rect::new(Vector2::new(x, 0.0), size).snapped_to_pixels(2.0);
+            assert_eq!(snapped.size, Vector2::new(10.0, 5.0), "{}", x);
+            assert_eq!(snapped.position.x, if x < 0.25 { 0.0 } else { 0.5 }, "{}", x);
+        }
+        assert_eq!(Vector2::new(1.26, -0.74).snapped_to_pixels(4.0), Vector2::new(1.25, -0.75));
+    }
+
+    #[test]
+    fn inset_shrinks_around_the_center() {
+        let rect = Rect::new(Vector2::new(10.0, 10.0), Vector2::new(20.0, 6.0));
+        let inset = rect.inset(2.0);
+        assert_eq!((inset.min(), inset.max()), (Vector2::new(12.0, 12.0), Vector2::new(28.0, 14.0)));
+        let grown = rect.inset(-1.0);
+        assert_eq!((grown.min(), grown.max()), (Vector2::new(9.0, 9.0), Vector2::new(31.0, 17.0)));
+
+        // Too large a margin collapses the short side onto the center.
+        let collapsed = rect.inset(4.0);
+        assert_eq!(collapsed.size, Vector2::new(12.0, 0.0));
+        assert_eq!(collapsed.center(), rect.center());
+        assert_eq!(rect.center(), Vector2::new(20.0, 13.0));
+    }
+
+    #[test]
+    fn splits_share_the_edge() {
+        let rect = Rect::new(Vector2::new(1.0, 2.0), Vector2::new(10.0, 4.0));
+        let (left, right) = rect.split_h(0.3);
+        assert_eq!(left.max().x, right.min().x);
+        assert_eq!((left.min(), right.max()), (rect.min(), rect.max()));
+        assert_eq!((left.size.y, right.size.y), (4.0, 4.0));
+        assert!((left.size.x - 3.0).abs() < 1e-6);
+
+        let (top, bottom) = rect.split_v(0.25);
+        assert_eq!(top.max().y, bottom.min().y);
+        assert_eq!((top.min(), bottom.max()), (rect.min(), rect.max()));
+        assert_eq!((top.size.y, bottom.size.y), (1.0, 3.0));
+
+        // The fraction is clamped.
+        let (all, none) = rect.split_h(1.5);
+        assert_eq!((all.size, none.size.x), (rect.size, 0.0));
+        let (none, all) = rect.split_v(-1.0);
+        assert_eq!((none.size.y, all.size), (0.0, rect.size));
+    }
+
+    #[test]
+    fn quadrants_tile_the_rect() {
+        let rect = Rect::new(Vector2::new(-3.0, 1.0), Vector2::new(8.0, 6.0));
+        let [top_left, top_right, bottom_left, bottom_right] = rect.quadrants();
+        assert_eq!((top_left.min(), top_left.max()), (rect.min(), rect.center()));
+        assert_eq!((bottom_right.min(), bottom_right.max()), (rect.center(), rect.max()));
+        assert_eq!((top_right.min(), top_right.max()), (Vector2::new(1.0, 1.0), Vector2::new(5.0, 4.0)));
+        assert_eq!((bottom_left.min(), bottom_left.max()), (Vector2::new(-3.0, 4.0), Vector2::new(1.0, 7.0)));
+
+        // The inner edges match exactly even where the halves round.
+        let rect = Rect::new(Vector2::new(0.1, 0.7), Vector2::new(0.3, 1.1));
+        let [top_left, top_right, bottom_left, _] = rect.quadrants();
+        assert_eq!(top_left.max().x, top_right.min().x);
+        assert_eq!(top_left.max().y, bottom_left.min().y);
+    }
+
+    #[test]
+    fn child_index_of_point_picks_the_containing_quadrant() {
+        let rect = Rect::new(Vector2::new(0.0, 0.0), Vector2::new(4.0, 4.0));
+        let quadrants = rect.quadrants();
+        for point in [Vector2::new(1.0, 1.0), Vector2::new(3.0, 1.0), Vector2::new(1.0, 3.0), Vector2::new(3.0, 3.0)] {
+            let quadrant = quadrants[rect.child_index_of_point(point)];
+            assert!(quadrant.min().x <= point.x && point.x <= quadrant.max().x && quadrant.min().y <= point.y && point.y <= quadrant.max().y, "{:?}", point);
+        }
+        // The splitting lines belong to the right and bottom quadrants, points outside to the closest one.
+        assert_eq!(rect.child_index_of_point(rect.center()), 3);
+        assert_eq!(rect.child_index_of_point(Vector2::new(2.0, 1.0)), 1);
+        assert_eq!(rect.child_index_of_point(Vector2::new(-10.0, 10.0)), 2);
+    }
+
+}
\ No newline at end of file
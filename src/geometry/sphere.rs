@@ -0,0 +1,136 @@
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::rendering::vertex::Vertex;
+use crate::vectors::vector3::Vector3;
+
+/// A bounding sphere described by its `center` and `radius`.
+#[derive(Copy, Clone, Debug)]
+pub struct Sphere {
+    pub center: Vector3,
+    pub radius: f32,
+}
+
+impl Sphere {
+
+    /// Creates a new Sphere from the given center and radius.
+    #[inline]
+    pub fn new(center: Vector3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Creates a Sphere containing all the given points.
+    /// Returns `None` if `points` is empty.
+    ///
+    /// The sphere is grown point by point (see [`SphereBuilder`]), so it is not always the minimal bounding sphere,
+    /// but it is computed in a single pass.
+    pub fn from_points(points: &[Vector3]) -> Option<Self> {
+        let mut builder = SphereBuilder::new();
+        builder.extend(points.iter().copied());
+        builder.build()
+    }
+
+}
+
+/// Builds a [`Sphere`] incrementally, so points can be streamed in without collecting them first.
+/// Produces exactly the same result as [`Sphere::from_points`] for the same points.
+///
+/// Every point outside the current sphere grows it just enough to contain both the old sphere and the new point.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SphereBuilder {
+    sphere: Option<Sphere>,
+}
+
+impl SphereBuilder {
+
+    /// Creates a new builder which has not seen any points yet.
+    #[inline]
+    pub fn new() -> Self {
+        Self { sphere: None }
+    }
+
+    /// Grows the sphere to include `point`.
+    pub fn add_point(&mut self, point: Vector3) {
+        self.sphere = Some(match self.sphere {
+            None => Sphere::new(point, 0.0),
+            Some(sphere) => {
+                let distance = sphere.center.distance_squared(&point).sqrt();
+                if distance <= sphere.radius {
+                    sphere
+                } else {
+                    let radius = (sphere.radius + distance) * 0.5;
+                    let center = sphere.center + (point - sphere.center) * ((radius - sphere.radius) / distance);
+                    Sphere::new(center, radius)
+                }
+            }
+        });
+    }
+
+    /// Grows the sphere to include the position of `vertex`.
+    #[inline]
+    pub fn add_vertex(&mut self, vertex: &Vertex) {
+        self.add_point(vertex.position);
+    }
+
+    /// Grows the sphere to include all the given points.
+    pub fn extend(&mut self, points: impl IntoIterator<Item = Vector3>) {
+        for point in points {
+            self.add_point(point);
+        }
+    }
+
+    /// Returns the built Sphere or `None` if no points were added.
+    #[inline]
+    pub fn build(self) -> Option<Sphere> {
+        self.sphere
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::rendering::color::UniColor;
+    use crate::vectors::vector2::Vector2;
+    use super::*;
+
+    fn points() -> Vec<Vector3> {
+        (0..100).map(|i| {
+            let t = i as f32;
+            Vector3::new((t * 0.37).sin() * 5.0, (t * 1.1).cos() * 3.0 - 1.0, (t * 0.13).sin() * 8.0)
+        }).collect()
+    }
+
+    #[test]
+    fn builder_matches_from_points() {
+        let points = points();
+        let sphere = Sphere::from_points(&points).unwrap();
+        let mut builder = SphereBuilder::new();
+        builder.extend(points[..40].iter().copied());
+        for &point in &points[40..70] {
+            builder.add_point(point);
+        }
+        for &point in &points[70..] {
+            builder.add_vertex(&Vertex::new(point, Vector3::UP, Vector2::new(0.0, 0.0), UniColor::WHITE));
+        }
+        let built = builder.build().unwrap();
+        assert_eq!((built.center, built.radius), (sphere.center, sphere.radius));
+
+        assert!(Sphere::from_points(&[]).is_none());
+        assert!(SphereBuilder::new().build().is_none());
+        let single = Sphere::from_points(&[Vector3::new(1.0, -2.0, 3.0)]).unwrap();
+        assert_eq!((single.center, single.radius), (Vector3::new(1.0, -2.0, 3.0), 0.0));
+    }
+
+    #[test]
+    fn from_points_contains_every_point() {
+        let points = points();
+        let sphere = Sphere::from_points(&points).unwrap();
+        for point in &points {
+            assert!(sphere.center.distance(point) <= sphere.radius * (1.0 + 1e-5), "{:?}", point);
+        }
+        // Two points give the sphere around them.
+        let pair = Sphere::from_points(&[Vector3::new(-1.0, 0.0, 0.0), Vector3::new(3.0, 0.0, 0.0)]).unwrap();
+        assert_eq!((pair.center, pair.radius), (Vector3::new(1.0, 0.0, 0.0), 2.0));
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,201 @@
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::rendering::color::UniColor;
+use crate::rendering::vertex::Vertex;
+use crate::vectors::vector3::Vector3;
+
+/// The tolerance of `Triangle::contains_point` on the barycentric weights, so points on an edge count despite rounding.
+const CONTAINS_EPSILON: f32 = 1e-5;
+
+/// A triangle given by its three corners, counter-clockwise when looking at its front side.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Triangle {
+    pub a: Vector3,
+    pub b: Vector3,
+    pub c: Vector3,
+}
+
+impl Triangle {
+
+    /// Creates a new Triangle from the given corners.
+    #[inline]
+    pub fn new(a: Vector3, b: Vector3, c: Vector3) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Returns the unit normal of the front side, or zero for a degenerate triangle.
+    pub fn normal(&self) -> Vector3 {
+        let cross = (self.b - self.a).cross(&(self.c - self.a));
+        let length_squared = cross.magnitude_squared();
+        if length_squared > 0.0 {
+            cross * (1.0 / length_squared.sqrt())
+        } else {
+            Vector3::zero()
+        }
+    }
+
+    /// Returns the area of the triangle, zero if its corners are collinear.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        (self.b - self.a).cross(&(self.c - self.a)).magnitude_squared().sqrt() * 0.5
+    }
+
+    /// Returns the average of the three corners.
+    #[inline]
+    pub fn centroid(&self) -> Vector3 {
+        (self.a + self.b + self.c) * (1.0 / 3.0)
+    }
+
+    /// Returns the barycentric weights of `a`, `b` and `c` for `point`, which sum up to one.
+    /// Points off the plane of the triangle are projected onto it first.
+    ///
+    /// Returns `None` for degenerate triangles, whose weights are not unique.
+    pub fn barycentric(&self, point: Vector3) -> Option<(f32, f32, f32)> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = point - self.a;
+        let (d00, d01, d11) = (ab.dot(&ab), ab.dot(&ac), ac.dot(&ac));
+        let denominator = d00 * d11 - d01 * d01;
+        // Relative to the edge lengths, so the test does not depend on the size of the triangle.
+        if denominator <= f32::EPSILON * d00 * d11 || !denominator.is_finite() {
+            return None;
+        }
+
+        let (d20, d21) = (ap.dot(&ab), ap.dot(&ac));
+        let v = (d11 * d20 - d01 * d21) / denominator;
+        let w = (d00 * d21 - d01 * d20) / denominator;
+        Some((1.0 - v - w, v, w))
+    }
+
+    /// Returns true if `point`, which should lie in the plane of the triangle, is inside it or on its border.
+    /// Degenerate triangles contain no points.
+    pub fn contains_point(&self, point: Vector3) -> bool {
+        self.barycentric(point)
+            .is_some_and(|(u, v, w)| u >= -CONTAINS_EPSILON && v >= -CONTAINS_EPSILON && w >= -CONTAINS_EPSILON)
+    }
+
+}
+
+/// A triangle made of three vertices, for blending their attributes at a point like a rasterizer does.
+#[derive(Copy, Clone, Debug)]
+pub struct VertexTriangle {
+    pub a: Vertex,
+    pub b: Vertex,
+    pub c: Vertex,
+}
+
+impl VertexTriangle {
+
+    /// Creates a new VertexTriangle from the given vertices.
+    #[inline]
+    pub fn new(a: Vertex, b: Vertex, c: Vertex) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Returns the triangle of the vertex positions.
+    #[inline]
+    pub fn triangle(&self) -> Triangle {
+        Triangle::new(self.a.position, self.b.position, self.c.position)
+    }
+
+    /// Blends the attributes of the vertices with the barycentric weights `bary` of `a`, `b` and `c`, e.g. from `Triangle::barycentric`.
+    /// The normal is renormalized, the color is blended per sRGB channel like `UniColor::lerp`.
+    pub fn interpolate(&self, bary: (f32, f32, f32)) -> Vertex {
+        let (u, v, w) = bary;
+        let normal = self.a.normal * u + self.b.normal * v + self.c.normal * w;
+        let length_squared = normal.magnitude_squared();
+
+        let [ca, cb, cc] = [self.a.color, self.b.color, self.c.color].map(|c| c.to_f32_array());
+        let color = core::array::from_fn(|i| ca[i] * u + cb[i] * v + cc[i] * w);
+
+        Vertex::new(
+            self.a.position * u + self.b.position * v + self.c.position * w,
+            if length_squared > 0.0 { normal * (1.0 / length_squared.sqrt()) } else { normal },
+            self.a.tex_coords * u + self.b.tex_coords * v + self.c.tex_coords * w,
+            UniColor::from_f32_array(color),
+        )
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::vectors::vector2::Vector2;
+    use super::*;
+
+    fn triangle() -> Triangle {
+        Triangle::new(Vector3::zero(), Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0))
+    }
+
+    fn assert_weights(actual: Option<(f32, f32, f32)>, expected: (f32, f32, f32)) {
+        let (u, v, w) = actual.unwrap();
+        assert!((u - expected.0).abs() < 1e-5 && (v - expected.1).abs() < 1e-5 && (w - expected.2).abs() < 1e-5, "{:?}", (u, v, w));
+    }
+
+    #[test]
+    fn normal_area_and_centroid() {
+        let triangle = triangle();
+        assert_eq!(triangle.normal(), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(triangle.area(), 2.0);
+        assert!(triangle.centroid().approx_eq(&Vector3::new(2.0 / 3.0, 2.0 / 3.0, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn barycentric_at_corners_edges_and_inside() {
+        let triangle = triangle();
+        assert_weights(triangle.barycentric(triangle.a), (1.0, 0.0, 0.0));
+        assert_weights(triangle.barycentric(triangle.b), (0.0, 1.0, 0.0));
+        assert_weights(triangle.barycentric(triangle.c), (0.0, 0.0, 1.0));
+        assert_weights(triangle.barycentric(Vector3::new(1.0, 1.0, 0.0)), (0.0, 0.5, 0.5));
+        assert_weights(triangle.barycentric(triangle.centroid()), (1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0));
+        // Points off the plane are projected onto it, points outside get negative weights.
+        assert_weights(triangle.barycentric(Vector3::new(0.5, 0.5, 3.0)), (0.5, 0.25, 0.25));
+        assert_weights(triangle.barycentric(Vector3::new(3.0, 0.0, 0.0)), (-0.5, 1.5, 0.0));
+    }
+
+    #[test]
+    fn contains_point_includes_the_border() {
+        let triangle = triangle();
+        for point in [triangle.a, triangle.b, Vector3::new(1.0, 1.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.5, 0.5, 0.0)] {
+            assert!(triangle.contains_point(point), "{:?}", point);
+        }
+        for point in [Vector3::new(1.1, 1.0, 0.0), Vector3::new(-0.1, 1.0, 0.0), Vector3::new(3.0, 0.0, 0.0)] {
+            assert!(!triangle.contains_point(point), "{:?}", point);
+        }
+    }
+
+    #[test]
+    fn degenerate_triangles() {
+        let collinear = Triangle::new(Vector3::zero(), Vector3::new(1.0, 1.0, 0.0), Vector3::new(2.0, 2.0, 0.0));
+        let point = Triangle::new(Vector3::from_one(1.0), Vector3::from_one(1.0), Vector3::from_one(1.0));
+        for triangle in [collinear, point] {
+            assert_eq!(triangle.normal(), Vector3::zero());
+            assert_eq!(triangle.area(), 0.0);
+            assert_eq!(triangle.barycentric(Vector3::new(1.0, 1.0, 0.0)), None);
+            assert!(!triangle.contains_point(triangle.a));
+        }
+        // Small triangles are not degenerate.
+        let small = Triangle::new(Vector3::zero(), Vector3::new(1e-4, 0.0, 0.0), Vector3::new(0.0, 1e-4, 0.0));
+        assert_weights(small.barycentric(Vector3::new(5e-5, 5e-5, 0.0)), (0.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn interpolate_blends_every_attribute() {
+        let vertex = |position: Vector3, normal: Vector3, u: f32, color: UniColor| Vertex::new(position, normal, Vector2::new(u, 0.0), color);
+        let triangle = VertexTriangle::new(
+            vertex(Vector3::zero(), Vector3::new(1.0, 0.0, 0.0), 0.0, UniColor::BLACK),
+            vertex(Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), 1.0, UniColor::WHITE),
+            vertex(Vector3::new(0.0, 2.0, 0.0), Vector3::new(0.0, 1.0, 0.0), 1.0, UniColor::WHITE),
+        );
+        assert_eq!(triangle.triangle(), Triangle::new(Vector3::zero(), Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 2.0, 0.0)));
+
+        let blended = triangle.interpolate((0.5, 0.25, 0.25));
+        assert!(blended.position.approx_eq(&Vector3::new(0.5, 0.5, 0.0), 1e-6));
+        // The normal is renormalized.
+        assert!(blended.normal.approx_eq(&(Vector3::new(1.0, 1.0, 0.0) * core::f32::consts::FRAC_1_SQRT_2), 1e-6), "{:?}", blended.normal);
+        assert!((blended.tex_coords.x - 0.5).abs() < 1e-6);
+        assert_eq!(blended.color.to_rgb(), (128, 128, 128));
+    }
+
+}
\ No newline at end of file
@@ -1,3 +1,9 @@
+// Tests always link `std`, its float functions then take precedence over the `libm` backed `math::Float`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("fgruc needs either the `std` or the `libm` feature for its float functions");
+
 /// Collection of Vectors and Utilities
 pub mod vectors;
 
@@ -10,8 +16,37 @@ pub mod angles;
 /// Contains the Matrix struct and its implementations
 pub mod matrix4x4;
 
+/// Contains the Affine2 struct for 2D transforms
+pub mod affine2;
+
+/// Contains the Transform struct combining position, rotation and scale
+pub mod transform;
+
+/// Keyframe animation tracks for floats, vectors, rotations and everything else implementing `Lerp`.
+#[cfg(feature = "std")]
+pub mod animation;
+
 /// Contains some types and enums like Axis
 pub mod types;
 
 /// Contains structs and functions for rendering engines.
-pub mod rendering;
\ No newline at end of file
+pub mod rendering;
+
+/// The error type and helpers of the versioned binary formats written by `write_binary`.
+#[cfg(feature = "std")]
+pub mod binary;
+
+/// Bounding volumes and other geometric primitives.
+pub mod geometry;
+
+/// Assertion macros like `assert_matrix_eq!` for testing code built on fgruc.
+#[cfg(feature = "std")]
+pub mod macros;
+
+/// SSE fast paths of the `Matrix4x4` products and inverse, other targets use the scalar code in `matrix4x4`.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod simd;
+
+/// `From` conversions to and from the `mint` interop types.
+#[cfg(feature = "mint")]
+mod mint_interop;
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::angles::quaternion::Quaternion;
+use crate::matrix4x4::Matrix4x4;
+
+/// Asserts that two `Matrix4x4`s are equal within `epsilon` for every element.
+/// On failure, both matrices are printed row by row together with the worst element and its indices.
+#[macro_export]
+macro_rules! assert_matrix_eq {
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+        if let Some(message) = $crate::macros::matrix_mismatch(&$left, &$right, $epsilon) {
+            panic!("{}", message);
+        }
+    };
+}
+
+/// Asserts that two `Quaternion`s describe the same rotation within `epsilon` for every component.
+/// `q` and `-q` are treated as equal, since they are the same rotation.
+/// On failure, both quaternions are printed together with the worst component.
+#[macro_export]
+macro_rules! assert_quat_eq {
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+        if let Some(message) = $crate::macros::quat_mismatch(&$left, &$right, $epsilon) {
+            panic!("{}", message);
+        }
+    };
+}
+
+/// Returns the failure message of `assert_matrix_eq!` or `None` if the matrices are equal within `epsilon`.
+#[doc(hidden)]
+pub fn matrix_mismatch(left: &Matrix4x4, right: &Matrix4x4, epsilon: f32) -> Option<String> {
+    let (index, diff) = (0..16)
+        .map(|i| (i, (left.data[i] - right.data[i]).abs()))
+        .fold((0, 0.0), |worst, (i, diff)| if diff > worst.1 || diff.is_nan() { (i, diff) } else { worst });
+
+    if diff <= epsilon {
+        return None;
+    }
+    Some(format!(
+        "assertion failed: matrices differ by more than {}\nworst element: row {}, column {} (data[{}]) differs by {}\nleft:\n{}right:\n{}",
+        epsilon, index / 4, index % 4, index, diff, format_rows(left), format_rows(right),
+    ))
+}
+
+/// Returns the failure message of `assert_quat_eq!` or `None` if the quaternions are equal within `epsilon`.
+#[doc(hidden)]
+pub fn quat_mismatch(left: &Quaternion, right: &Quaternion, epsilon: f32) -> Option<String> {
+    const NAMES: [&str; 4] = ["w", "x", "y", "z"];
+
+    let l = [left.w, left.x, left.y, left.z];
+    // Compare against whichever sign of right is closer, as q and -q are the same rotation.
+    let sign = if left.dot(right) < 0.0 { -1.0 } else { 1.0 };
+    let r = [right.w * sign, right.x * sign, right.y * sign, right.z * sign];
+
+    let (index, diff) = (0..4)
+        .map(|i| (i, (l[i] - r[i]).abs()))
+        .fold((0, 0.0), |worst, (i, diff)| if diff > worst.1 || diff.is_nan() { (i, diff) } else { worst });
+
+    if diff <= epsilon {
+        return None;
+    }
+    Some(format!(
+        "assertion failed: quaternions differ by more than {}\nworst component: {} differs by {}\nleft:  ({}, {}, {}, {})\nright: ({}, {}, {}, {})",
+        epsilon, NAMES[index], diff, left.w, left.x, left.y, left.z, right.w, right.x, right.y, right.z,
+    ))
+}
+
+/// Formats the matrix as four aligned rows.
+fn format_rows(m: &Matrix4x4) -> String {
+    m.data.chunks_exact(4)
+        .map(|row| format!("[{:>12.6} {:>12.6} {:>12.6} {:>12.6}]\n", row[0], row[1], row[2], row[3]))
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+//! Deterministic math functions, built only from IEEE 754 basic arithmetic (+, -, *, /) and bit operations.
+//! Unlike the std functions, which may call into different libm implementations, these return bit-identical
+//! results on every platform, which is what lockstep networking and replays need.
+//!
+//! Accuracy: `det_sin` and `det_cos` are within 1e-7 of the exact result for |x| up to a few hundred radians
+//! (larger inputs lose precision in the range reduction, like any f32 implementation),
+//! `det_atan2` is within 3e-7 and `det_sqrt` within 1 ulp.
+
+use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+
+/// π/2 split into a part that is exactly representable with few bits and the remainder,
+/// so `k * PI_2_HI` is exact and range reduction keeps most of its precision.
+const PI_2_HI: f32 = 1.570_312_5;
+const PI_2_LO: f32 = 4.838_268e-4;
+
+/// Reduces `x` into [-π/4, π/4] and returns it together with the quadrant (0 to 3) it came from.
+#[inline]
+fn reduce(x: f32) -> (f32, i32) {
+    let k = (x * (2.0 / PI)).round();
+    let r = (x - k * PI_2_HI) - k * PI_2_LO;
+    (r, (k as i64).rem_euclid(4) as i32)
+}
+
+/// Taylor polynomial of sin on [-π/4, π/4], exact to well below f32 precision there.
+#[inline]
+fn sin_poly(x: f32) -> f32 {
+    let x2 = x * x;
+    x * (1.0 + x2 * (-1.0 / 6.0 + x2 * (1.0 / 120.0 + x2 * (-1.0 / 5040.0 + x2 * (1.0 / 362_880.0)))))
+}
+
+/// Taylor polynomial of cos on [-π/4, π/4], exact to well below f32 precision there.
+#[inline]
+fn cos_poly(x: f32) -> f32 {
+    let x2 = x * x;
+    1.0 + x2 * (-0.5 + x2 * (1.0 / 24.0 + x2 * (-1.0 / 720.0 + x2 * (1.0 / 40_320.0 + x2 * (-1.0 / 3_628_800.0)))))
+}
+
+/// Deterministic sine of `x` (in radians).
+pub fn det_sin(x: f32) -> f32 {
+    let (r, quadrant) = reduce(x);
+    match quadrant {
+        0 => sin_poly(r),
+        1 => cos_poly(r),
+        2 => -sin_poly(r),
+        _ => -cos_poly(r),
+    }
+}
+
+/// Deterministic cosine of `x` (in radians).
+pub fn det_cos(x: f32) -> f32 {
+    let (r, quadrant) = reduce(x);
+    match quadrant {
+        0 => cos_poly(r),
+        1 => -sin_poly(r),
+        2 => -cos_poly(r),
+        _ => sin_poly(r),
+    }
+}
+
+/// Deterministic arctangent of `y / x`, returning the angle in (-π, π] like `f32::atan2`.
+/// Returns 0 if both inputs are 0.
+pub fn det_atan2(y: f32, x: f32) -> f32 {
+    if x == 0.0 && y == 0.0 {
+        return 0.0;
+    }
+
+    // atan of the smaller over the larger magnitude is in [0, π/4], the rest follows from symmetry.
+    let (ax, ay) = (x.abs(), y.abs());
+    let (small, large) = if ay > ax { (ax, ay) } else { (ay, ax) };
+    let mut angle = atan_unit(small / large);
+    if ay > ax {
+        angle = FRAC_PI_2 - angle;
+    }
+    if x < 0.0 {
+        angle = PI - angle;
+    }
+    if y < 0.0 {
+        -angle
+    } else {
+        angle
+    }
+}
+
+/// atan of `z` in [0, 1].
+#[inline]
+fn atan_unit(z: f32) -> f32 {
+    // tan(π/8), above it atan(z) = π/4 + atan((z - 1) / (z + 1)) brings the argument below it again.
+    const TAN_PI_8: f32 = 0.414_213_57;
+
+    let (offset, z) = if z > TAN_PI_8 {
+        (FRAC_PI_4, (z - 1.0) / (z + 1.0))
+    } else {
+        (0.0, z)
+    };
+    let z2 = z * z;
+    let series = z * (1.0 + z2 * (-1.0 / 3.0 + z2 * (1.0 / 5.0 + z2 * (-1.0 / 7.0 + z2 * (1.0 / 9.0
+        + z2 * (-1.0 / 11.0 + z2 * (1.0 / 13.0 + z2 * (-1.0 / 15.0))))))));
+    offset + series
+}
+
+/// Deterministic square root of `x`, returning NaN for negative inputs.
+/// Starts from the bit-level estimate also used by `fast_inv_sqrt` and refines it with Newton's method.
+pub fn det_sqrt(x: f32) -> f32 {
+    if x < 0.0 || x.is_nan() {
+        return f32::NAN;
+    }
+    if x == 0.0 || x.is_infinite() {
+        return x;
+    }
+
+    let mut y = f32::from_bits((x.to_bits() >> 1) + 0x1fbd_1df5);
+    for _ in 0..4 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Inputs with the expected bit patterns of `det_sin` and `det_cos`. A change in any of them breaks lockstep
+    /// replays recorded with an earlier version or on another platform.
+    const SIN_COS: [(f32, u32, u32); 12] = [
+        (0.0, 0x0000_0000, 0x3f80_0000),
+        (0.5, 0x3ef5_7744, 0x3f60_a940),
+        (-0.5, 0xbef5_7744, 0x3f60_a940),
+        (1.0, 0x3f57_6aa4, 0x3f0a_5140),
+        (FRAC_PI_4, 0x3f35_04f4, 0x3f35_04f3),
+        (FRAC_PI_2, 0x3f80_0000, 0xb33b_c000),
+        (PI, 0xb3bb_c000, 0xbf80_0000),
+        (2.0, 0x3f68_c7b7, 0xbed5_1132),
+        (-3.0, 0xbe10_81c4, 0xbf7d_7026),
+        (10.0, 0xbf0b_44f7, 0xbf56_cd64),
+        (100.0, 0xbf01_a12e, 0x3f5c_c0ee),
+        (-250.0, 0x3f78_7486, 0x3e76_c5a3),
+    ];
+
+    const ATAN2: [(f32, f32, u32); 8] = [
+        (0.0, 1.0, 0x0000_0000),
+        (1.0, 1.0, 0x3f49_0fdb),
+        (1.0, -2.0, 0x402b_6374),
+        (-3.0, -0.5, 0xbfde_3372),
+        (0.3, 0.7, 0x3ecf_4dfc),
+        (-2.0, 5.0, 0xbec2_d1bb),
+        (1.0, 0.0, 0x3fc9_0fdb),
+        (0.0, -1.0, 0x4049_0fdb),
+    ];
+
+    const SQRT: [(f32, u32); 6] = [
+        (0.25, 0x3f00_0000),
+        (2.0, 0x3fb5_04f3),
+        (3.0, 0x3fdd_b3d8),
+        (10.0, 0x404a_62c2),
+        (1e-6, 0x3a83_126e),
+        (12345.678, 0x42de_38e3),
+    ];
+
+    #[test]
+    fn golden_bit_patterns() {
+        for (x, sin, cos) in SIN_COS {
+            assert_eq!(det_sin(x).to_bits(), sin, "det_sin({})", x);
+            assert_eq!(det_cos(x).to_bits(), cos, "det_cos({})", x);
+        }
+        for (y, x, atan2) in ATAN2 {
+            assert_eq!(det_atan2(y, x).to_bits(), atan2, "det_atan2({}, {})", y, x);
+        }
+        for (x, sqrt) in SQRT {
+            assert_eq!(det_sqrt(x).to_bits(), sqrt, "det_sqrt({})", x);
+        }
+    }
+
+    #[test]
+    fn accuracy_within_the_documented_bounds() {
+        for i in -30_000..=30_000 {
+            let x = i as f32 * 0.01;
+            assert!((det_sin(x) as f64 - (x as f64).sin()).abs() <= 1e-7, "det_sin({})", x);
+            assert!((det_cos(x) as f64 - (x as f64).cos()).abs() <= 1e-7, "det_cos({})", x);
+        }
+        for i in 0..3600 {
+            let angle = i as f64 * core::f64::consts::TAU / 3600.0;
+            let (y, x) = ((angle.sin() * 3.0) as f32, (angle.cos() * 3.0) as f32);
+            assert!((det_atan2(y, x) as f64 - (y as f64).atan2(x as f64)).abs() <= 3e-7, "det_atan2({}, {})", y, x);
+        }
+        for i in 1..10_000 {
+            let x = i as f32 * 0.37;
+            assert!((det_sqrt(x) - x.sqrt()).abs() <= f32::EPSILON * x.sqrt(), "det_sqrt({})", x);
+        }
+        assert!(det_sqrt(-1.0).is_nan());
+        assert_eq!(det_atan2(0.0, 0.0), 0.0);
+    }
+
+}
\ No newline at end of file
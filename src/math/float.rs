@@ -0,0 +1,115 @@
+//! Without `std`, `f32` lacks the methods calling into the platform's math library, like `sqrt` and `sin`.
+//! `Float` provides them under the same names, so every module only imports it and keeps calling the methods as before.
+
+/// The `f32` methods of `std` which `core` doesn't have, implemented with `libm`.
+pub(crate) trait Float: Sized {
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+impl Float for f32 {
+
+    #[inline]
+    fn sqrt(self) -> f32 {
+        libm::sqrtf(self)
+    }
+
+    #[inline]
+    fn cbrt(self) -> f32 {
+        libm::cbrtf(self)
+    }
+
+    #[inline]
+    fn powf(self, n: f32) -> f32 {
+        libm::powf(self, n)
+    }
+
+    /// Goes through `powf`, which returns exactly `self * self` for squares, the only power used in the crate.
+    #[inline]
+    fn powi(self, n: i32) -> f32 {
+        libm::powf(self, n as f32)
+    }
+
+    #[inline]
+    fn exp(self) -> f32 {
+        libm::expf(self)
+    }
+
+    #[inline]
+    fn exp2(self) -> f32 {
+        libm::exp2f(self)
+    }
+
+    #[inline]
+    fn sin(self) -> f32 {
+        libm::sinf(self)
+    }
+
+    #[inline]
+    fn cos(self) -> f32 {
+        libm::cosf(self)
+    }
+
+    #[inline]
+    fn tan(self) -> f32 {
+        libm::tanf(self)
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (f32, f32) {
+        libm::sincosf(self)
+    }
+
+    #[inline]
+    fn asin(self) -> f32 {
+        libm::asinf(self)
+    }
+
+    #[inline]
+    fn acos(self) -> f32 {
+        libm::acosf(self)
+    }
+
+    #[inline]
+    fn atan2(self, other: f32) -> f32 {
+        libm::atan2f(self, other)
+    }
+
+    #[inline]
+    fn floor(self) -> f32 {
+        libm::floorf(self)
+    }
+
+    #[inline]
+    fn ceil(self) -> f32 {
+        libm::ceilf(self)
+    }
+
+    #[inline]
+    fn round(self) -> f32 {
+        libm::roundf(self)
+    }
+
+    /// Same as `std`: the remainder of `self / rhs`, made non-negative by adding `|rhs|`.
+    #[inline]
+    fn rem_euclid(self, rhs: f32) -> f32 {
+        let r = self % rhs;
+        if r < 0.0 { r + rhs.abs() } else { r }
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,626 @@
+use core::error::Error;
+use core::f32::consts::{FRAC_PI_2, PI, TAU};
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, Mul};
+
+/// Deterministic versions of the trigonometric functions, giving the same bits on every platform.
+pub mod det;
+
+/// Signed distance functions for procedural modeling and raymarching.
+pub mod sdf;
+
+/// Direction sampling for path tracing and lightmap baking, driven by explicit uniform random numbers.
+pub mod sampling;
+
+/// Catmull-Rom and cubic Bézier curves, and the `Spline` type chaining them into paths.
+pub mod spline;
+
+/// The float functions of `std` backed by `libm`, for builds without `std`.
+#[cfg(all(not(feature = "std"), not(test)))]
+mod float;
+#[cfg(all(not(feature = "std"), not(test)))]
+pub(crate) use float::Float;
+
+/// Fast inverse square root implementation.
+/// Note that this returns a less approximate value than the default inv sqrt method, so it sacrifices accuracy for speed.
+/// It should only be used in specific cases like the calculation of a vector magnitude.
+#[inline]
+pub fn fast_inv_sqrt(x: f32) -> f32 {
+    let y = f32::from_bits(0x5f3759df - (x.to_bits() >> 1));
+    y * (1.5 - 0.5 * x * y * y)
+}
+
+/// A faster implementation of sin() function.
+/// Sacrifices accuracy for speed: the absolute error is below 0.0012 and the result stays within [-1, 1].
+/// The input is wrapped into [-π, π] first, so any angle works, but like every `f32` angle
+/// the precision drops for very large inputs, as the spacing between floats grows.
+pub fn fast_sin(x: f32) -> f32 {
+    sin_reduced(reduce_angle(x))
+}
+
+/// A faster implementation of cos() function.
+/// Sacrifices accuracy for speed, with the same error bound as `fast_sin`. `fast_cos(0.0)` is exactly 1.
+pub fn fast_cos(x: f32) -> f32 {
+    // cos(x) = sin(π/2 - |x|), which keeps the argument in [-π/2, π/2].
+    sin_reduced(FRAC_PI_2 - reduce_angle(x).abs())
+}
+
+/// A faster implementation of tan() function.
+/// Sacrifices accuracy for speed. The error grows towards the poles at ±π/2, where the result goes to infinity.
+#[inline]
+pub fn fast_tan(x: f32) -> f32 {
+    fast_sin(x) / fast_cos(x)
+}
+
+/// A faster implementation of acos() function, using the polynomial from Abramowitz and Stegun.
+/// Sacrifices accuracy for speed: the absolute error is below 0.0001. Inputs are clamped into [-1, 1],
+/// so dot products slightly above 1 from rounding give 0 instead of NaN.
+pub fn fast_acos(x: f32) -> f32 {
+    let x = x.clamp(-1.0, 1.0);
+    let a = x.abs();
+    let acos = (((-0.018_729_3 * a + 0.074_261) * a - 0.212_114_4) * a + 1.570_728_8) * (1.0 - a).sqrt();
+    if x < 0.0 { PI - acos } else { acos }
+}
+
+/// A faster implementation of atan2() function, the angle of the point (`x`, `y`) in [-π, π].
+/// Sacrifices accuracy for speed: the absolute error is below 0.00002. `fast_atan2(0.0, 0.0)` is 0.
+pub fn fast_atan2(y: f32, x: f32) -> f32 {
+    let (abs_x, abs_y) = (x.abs(), y.abs());
+    let (min, max) = if abs_x < abs_y { (abs_x, abs_y) } else { (abs_y, abs_x) };
+    if max == 0.0 {
+        return 0.0;
+    }
+
+    // atan on [0, 1], then mirrored into the other octants.
+    let z = min / max;
+    let z2 = z * z;
+    let mut angle = z * (0.999_866 + z2 * (-0.330_299_5 + z2 * (0.180_141 + z2 * (-0.085_133 + z2 * 0.020_835_1))));
+    if abs_y > abs_x {
+        angle = FRAC_PI_2 - angle;
+    }
+    if x < 0.0 {
+        angle = PI - angle;
+    }
+    angle.copysign(y)
+}
+
+/// Wraps `x` into [-π, π] for the fast trigonometric functions.
+#[inline]
+fn reduce_angle(x: f32) -> f32 {
+    x - TAU * (x * (1.0 / TAU)).round()
+}
+
+/// Approximates sin(x) for `x` in [-π, π] with a parabola, refined by blending it with its square.
+#[inline]
+fn sin_reduced(x: f32) -> f32 {
+    const B: f32 = 4.0 / PI;
+    const C: f32 = -4.0 / (PI * PI);
+    const P: f32 = 0.225;
+    let y = B * x + C * x * x.abs();
+    (P * (y * y.abs() - y) + y).clamp(-1.0, 1.0)
+}
+
+/// Encodes a value in [0, 1] as an unsigned normalized byte, clamping values outside the range.
+#[inline]
+pub(crate) fn to_unorm8(x: f32) -> u8 {
+    (x.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Decodes an unsigned normalized byte into [0, 1].
+#[inline]
+pub(crate) fn from_unorm8(x: u8) -> f32 {
+    x as f32 / 255.0
+}
+
+/// Encodes a value in [-1, 1] as a signed normalized byte, clamping values outside the range.
+/// Only -127 to 127 are used, so -1, 0 and 1 survive the round trip exactly.
+#[inline]
+pub(crate) fn to_snorm8(x: f32) -> i8 {
+    (x.clamp(-1.0, 1.0) * 127.0).round() as i8
+}
+
+/// Decodes a signed normalized byte into [-1, 1], mapping both -128 and -127 to -1.
+#[inline]
+pub(crate) fn from_snorm8(x: i8) -> f32 {
+    (x as f32 / 127.0).max(-1.0)
+}
+
+/// Returns the sine and cosine of `x`.
+/// With the `deterministic` feature enabled, this uses the `det` functions so rotations are bit-identical across platforms.
+#[inline]
+pub(crate) fn sin_cos(x: f32) -> (f32, f32) {
+    #[cfg(feature = "deterministic")]
+    {
+        (det::det_sin(x), det::det_cos(x))
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.sin_cos()
+    }
+}
+
+/// Returns the square root of `x`, used by the `magnitude` methods.
+/// With the `fast_math` feature enabled, this uses `fast_inv_sqrt` like the `_fast` variants do.
+#[inline]
+pub(crate) fn default_sqrt(x: f32) -> f32 {
+    #[cfg(feature = "fast_math")]
+    {
+        x * fast_inv_sqrt(x)
+    }
+    #[cfg(not(feature = "fast_math"))]
+    {
+        x.sqrt()
+    }
+}
+
+/// Returns the sine and cosine of `x` for the methods that have a `_fast` variant.
+/// With the `fast_math` feature enabled, this uses `fast_sin` and `fast_cos`, otherwise it is `sin_cos`.
+#[inline]
+pub(crate) fn default_sin_cos(x: f32) -> (f32, f32) {
+    #[cfg(feature = "fast_math")]
+    {
+        (fast_sin(x), fast_cos(x))
+    }
+    #[cfg(not(feature = "fast_math"))]
+    {
+        sin_cos(x)
+    }
+}
+
+/// Linear interpolation between two values of the same type, the building block for animation tracks, gradients and splines.
+///
+/// `lerp(a, b, 0.0)` is `a` and `lerp(a, b, 1.0)` is `b`. Values of `t` outside [0, 1] extrapolate,
+/// unless the type cannot represent the result (colors clamp `t`, rotations stay normalized).
+pub trait Lerp {
+    /// Returns the value `t` of the way from `self` to `other`.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+/// Evaluates the cubic Hermite curve from `p0` with tangent `m0` to `p1` with tangent `m1` at `t` in [0, 1].
+/// The tangents are derivatives with respect to `t`, so scale them by the segment duration for curves over time.
+#[inline]
+pub fn cubic_hermite<T: Add<Output = T> + Mul<f32, Output = T>>(p0: T, m0: T, p1: T, m1: T, t: f32) -> T {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    p0 * (2.0 * t3 - 3.0 * t2 + 1.0) + m0 * (t3 - 2.0 * t2 + t) + p1 * (-2.0 * t3 + 3.0 * t2) + m1 * (t3 - t2)
+}
+
+/// Moves `current` towards `target` by exponential decay, covering the fraction `1 - e^(-lambda * dt)` of the way.
+///
+/// Unlike lerping by a fixed factor every frame, this is framerate independent: two steps of `dt` end up
+/// in the same place as one step of `2 * dt`. A larger `lambda` follows more tightly, after `1 / lambda` seconds
+/// about 63% of the distance is covered. An infinite `lambda` (or one large enough that the decay underflows) returns `target` exactly.
+pub fn damp<T: Lerp + Clone>(current: &T, target: &T, lambda: f32, dt: f32) -> T {
+    let t = 1.0 - (-lambda * dt).exp();
+    if t >= 1.0 || lambda == f32::INFINITY {
+        target.clone()
+    } else {
+        current.lerp(target, t)
+    }
+}
+
+/// Returns the value `t` of the way from `a` to `b`, like `Lerp` for `f32`. `t = 0` gives exactly `a`.
+#[inline]
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Returns how far `v` is along the way from `a` to `b`, the inverse of `lerp`, so `a` gives 0 and `b` gives 1.
+/// Values outside the range give results outside [0, 1]. If `a == b` there is no way to go, which gives 0.
+#[inline]
+pub fn inverse_lerp(a: f32, b: f32, v: f32) -> f32 {
+    if a == b {
+        0.0
+    } else {
+        (v - a) / (b - a)
+    }
+}
+
+/// Maps `v` from the range `in_min` to `in_max` onto the range `out_min` to `out_max`, without clamping.
+/// An empty input range maps everything to `out_min`, see `inverse_lerp`.
+#[inline]
+pub fn remap(v: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    lerp(out_min, out_max, inverse_lerp(in_min, in_max, v))
+}
+
+/// Clamps `x` into [0, 1].
+#[inline]
+pub fn clamp01(x: f32) -> f32 {
+    x.clamp(0.0, 1.0)
+}
+
+/// Returns 0 below `edge0`, 1 above `edge1` and a smooth cubic (with zero slope at both edges) in between.
+/// Swapped edges give the mirrored curve. Equal edges give a hard step, 0 below the edge and 1 from the edge on.
+#[inline]
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = step_fraction(edge0, edge1, x);
+    (t * t * (3.0 - 2.0 * t)) as f32
+}
+
+/// Like `smoothstep`, but with Perlin's quintic, which also has zero curvature at both edges.
+#[inline]
+pub fn smootherstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = step_fraction(edge0, edge1, x);
+    (t * t * t * (t * (t * 6.0 - 15.0) + 10.0)) as f32
+}
+
+/// Returns the clamped position of `x` between the edges for `smoothstep` and `smootherstep`.
+///
+/// The polynomials are evaluated in `f64`, since their `f32` rounding errors let the curves dip by a few ulps
+/// and overshoot 1 close to the upper edge. Rounded back to `f32`, they are monotonic for every input.
+#[inline]
+fn step_fraction(edge0: f32, edge1: f32, x: f32) -> f64 {
+    if edge0 == edge1 {
+        return if x < edge0 { 0.0 } else { 1.0 };
+    }
+    clamp01(inverse_lerp(edge0, edge1, x)) as f64
+}
+
+/// Moves `current` towards `target` by at most `max_delta`, never overshooting. Targets within reach are returned exactly.
+/// The sign of `max_delta` is ignored.
+#[inline]
+pub fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    let delta = target - current;
+    let max_delta = max_delta.abs();
+    if delta.abs() <= max_delta {
+        target
+    } else {
+        current + max_delta.copysign(delta)
+    }
+}
+
+/// Wraps the angle `x` in radians into (-π, π] without changing the direction it points in, so -π becomes π.
+#[inline]
+pub fn wrap_angle(x: f32) -> f32 {
+    // Angles already in range come back unchanged, the remainder below would round their last bits.
+    // Adding zero turns -0.0 into 0.0 like the remainder does.
+    if x > -PI && x <= PI {
+        return x + 0.0;
+    }
+    let wrapped = PI - (PI - x).rem_euclid(TAU);
+    // `rem_euclid` rounds tiny negative remainders up to exactly 2π, which belongs to π instead.
+    if wrapped <= -PI { PI } else { wrapped }
+}
+
+/// Error returned by the `from_string_exact` parsers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseExactError {
+    /// The string did not contain the number of components the type needs.
+    WrongCount { expected: usize, found: usize },
+    /// The component at `index` is not a valid float.
+    InvalidNumber { index: usize },
+}
+
+impl Display for ParseExactError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseExactError::WrongCount { expected, found } =>
+                write!(f, "expected {} comma separated components, found {}", expected, found),
+            ParseExactError::InvalidNumber { index } =>
+                write!(f, "component {} is not a valid float", index),
+        }
+    }
+}
+
+impl Error for ParseExactError {}
+
+/// Formats the values as comma separated floats using the shortest representation that parses back to the same bits.
+/// Negative zero, subnormals and infinities round-trip, NaN is written as `NaN` and loses its payload.
+#[cfg(feature = "std")]
+pub(crate) fn format_exact(values: &[f32]) -> String {
+    values.iter()
+        .map(|v| format!("{:?}", v))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses exactly `N` comma separated floats written by `format_exact`. Whitespace around the components is ignored.
+pub(crate) fn parse_exact<const N: usize>(s: &str) -> Result<[f32; N], ParseExactError> {
+    let found = s.split(',').count();
+    if found != N {
+        return Err(ParseExactError::WrongCount { expected: N, found });
+    }
+
+    let mut values = [0.0; N];
+    for (index, (value, part)) in values.iter_mut().zip(s.split(',').map(str::trim)).enumerate() {
+        *value = part.parse().map_err(|_| ParseExactError::InvalidNumber { index })?;
+    }
+    Ok(values)
+}
+
+/// 64-bit FNV-1a hasher used for the stable content hashes.
+/// Values are fed in little-endian byte order so the hash is the same on every host.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+
+    /// Creates a new hasher starting at the FNV-1a 64-bit offset basis.
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Feeds the bytes into the hash.
+    #[inline]
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// Feeds the little-endian bytes of `value`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Feeds the little-endian bytes of `value`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Feeds the little-endian bit pattern of every value, so 0.0 and -0.0 hash differently.
+    #[inline]
+    pub(crate) fn write_f32s(&mut self, values: &[f32]) {
+        for value in values {
+            self.write(&value.to_bits().to_le_bytes());
+        }
+    }
+
+    /// Returns the final hash.
+    #[inline]
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Pairs of range ends, from tiny to large and with either sign.
+    const ENDS: [(f32, f32); 6] = [(0.0, 1.0), (-3.0, 7.5), (100.0, -250.0), (1e-3, 2e-3), (-1e4, 1e4), (5.0, 5.0 + 1e-2)];
+
+    #[test]
+    fn lerp_hits_both_ends() {
+        for (a, b) in ENDS {
+            assert_eq!(lerp(a, b, 0.0), a);
+            assert!((lerp(a, b, 1.0) - b).abs() <= b.abs() * 1e-6);
+        }
+    }
+
+    #[test]
+    fn inverse_lerp_undoes_lerp() {
+        for (a, b) in ENDS {
+            for i in 0..=100 {
+                let t = i as f32 / 100.0;
+                let back = inverse_lerp(a, b, lerp(a, b, t));
+                // Relative to the size of the values compared with their distance.
+                let tolerance = 1e-6 * (1.0 + a.abs().max(b.abs()) / (b - a).abs());
+                assert!((back - t).abs() <= tolerance, "{} to {} at {} gave {}", a, b, t, back);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_ranges_give_defined_results() {
+        assert_eq!(inverse_lerp(2.0, 2.0, 5.0), 0.0);
+        assert_eq!(remap(5.0, 2.0, 2.0, -1.0, 1.0), -1.0);
+        assert_eq!(remap(0.5, 0.0, 1.0, 10.0, 20.0), 15.0);
+        assert_eq!(smoothstep(1.0, 1.0, 0.5), 0.0);
+        assert_eq!(smoothstep(1.0, 1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn clamp01_clamps() {
+        assert_eq!(clamp01(-0.5), 0.0);
+        assert_eq!(clamp01(0.25), 0.25);
+        assert_eq!(clamp01(7.0), 1.0);
+    }
+
+    #[test]
+    fn smoothsteps_are_monotonic() {
+        for (edge0, edge1) in [(0.0, 1.0), (-2.0, 3.0), (1.0, 1.0 + 1e-4)] {
+            let (mut previous_smooth, mut previous_smoother) = (0.0, 0.0);
+            for i in -100..=10100 {
+                let x = edge0 + (edge1 - edge0) * i as f32 / 10000.0;
+                let (smooth, smoother) = (smoothstep(edge0, edge1, x), smootherstep(edge0, edge1, x));
+                assert!(smooth >= previous_smooth && smoother >= previous_smoother, "{} between {} and {}", x, edge0, edge1);
+                assert!((0.0..=1.0).contains(&smooth) && (0.0..=1.0).contains(&smoother));
+                (previous_smooth, previous_smoother) = (smooth, smoother);
+            }
+            assert_eq!(smoothstep(edge0, edge1, edge1), 1.0);
+            assert_eq!(smootherstep(edge0, edge1, edge0), 0.0);
+        }
+        // Every float just below the upper edge.
+        let mut x = 1.0f32;
+        let mut previous = smoothstep(0.0, 1.0, x);
+        for _ in 0..10000 {
+            x = f32::from_bits(x.to_bits() - 1);
+            let value = smoothstep(0.0, 1.0, x);
+            assert!(value <= previous, "{}", x);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn move_towards_never_overshoots() {
+        assert_eq!(move_towards(0.0, 10.0, 3.0), 3.0);
+        assert_eq!(move_towards(0.0, -10.0, 3.0), -3.0);
+        assert_eq!(move_towards(9.0, 10.0, 3.0), 10.0);
+        assert_eq!(move_towards(0.0, 10.0, -3.0), 3.0);
+    }
+
+    #[test]
+    fn wrap_angle_maps_into_the_half_open_range() {
+        assert_eq!(wrap_angle(PI), PI);
+        assert_eq!(wrap_angle(-PI), PI);
+        assert_eq!(wrap_angle(0.5), 0.5);
+        for x in [0.1, -0.3, 3.0, -3.1, 1e-30] {
+            assert_eq!(wrap_angle(x), x);
+        }
+        assert!(wrap_angle(-0.0).is_sign_positive());
+        assert!((wrap_angle(TAU + 0.5) - 0.5).abs() < 1e-6);
+        assert!((wrap_angle(-FRAC_PI_2 - TAU) + FRAC_PI_2).abs() < 1e-6);
+        for i in -10000..=10000 {
+            let x = i as f32 * 0.0137 * PI;
+            let wrapped = wrap_angle(x);
+            assert!(wrapped > -PI && wrapped <= PI, "{} wrapped to {}", x, wrapped);
+            assert!((wrapped.sin() - x.sin()).abs() < 1e-4 && (wrapped.cos() - x.cos()).abs() < 1e-4, "{} wrapped to {}", x, wrapped);
+        }
+        for x in [-1e-9, 1e-9 - PI, -PI - 1e-7, PI + 1e-7] {
+            let wrapped = wrap_angle(x);
+            assert!(wrapped > -PI && wrapped <= PI, "{} wrapped to {}", x, wrapped);
+        }
+    }
+
+    #[test]
+    fn fnv1a_matches_the_reference_vectors() {
+        for (input, expected) in [(&b""[..], 0xcbf2_9ce4_8422_2325), (b"a", 0xaf63_dc4c_8601_ec8c), (b"foobar", 0x8594_4171_f739_67e8)] {
+            let mut hasher = Fnv1a::new();
+            hasher.write(input);
+            assert_eq!(hasher.finish(), expected);
+        }
+    }
+
+    #[test]
+    fn fast_trig_stays_within_its_documented_error() {
+        // Dense over [-10π, 10π], so every wrap of the range reduction is crossed several times.
+        let steps = 200_000;
+        for i in 0..=steps {
+            let x = (i as f64 / steps as f64 * 2.0 - 1.0) * 10.0 * core::f64::consts::PI;
+            let x32 = x as f32;
+            let (sin, cos) = (fast_sin(x32), fast_cos(x32));
+            assert!((sin as f64 - (x32 as f64).sin()).abs() < 0.0012, "fast_sin({})", x32);
+            assert!((cos as f64 - (x32 as f64).cos()).abs() < 0.0012, "fast_cos({})", x32);
+            assert!((-1.0..=1.0).contains(&sin) && (-1.0..=1.0).contains(&cos), "{} {} at {}", sin, cos, x32);
+        }
+        assert_eq!(fast_cos(0.0), 1.0);
+        assert!((-1.0..=1.0).contains(&fast_sin(7.0)));
+        assert!((fast_sin(7.0) - 7.0f32.sin()).abs() < 0.0012);
+
+        for i in 0..=20_000 {
+            let x = i as f32 / 10_000.0 - 1.0;
+            assert!((fast_acos(x) as f64 - (x as f64).acos()).abs() < 1e-4, "fast_acos({})", x);
+        }
+        assert_eq!(fast_acos(1.000_001), 0.0);
+
+        for i in 0..3600 {
+            let angle = i as f64 * core::f64::consts::TAU / 3600.0;
+            for radius in [1e-3, 1.0, 250.0] {
+                let (y, x) = ((angle.sin() * radius) as f32, (angle.cos() * radius) as f32);
+                assert!((fast_atan2(y, x) as f64 - (y as f64).atan2(x as f64)).abs() < 2e-5, "fast_atan2({}, {})", y, x);
+            }
+        }
+        assert_eq!(fast_atan2(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn norm8_endpoints_and_round_trips() {
+        assert_eq!((to_unorm8(0.0), to_unorm8(0.5), to_unorm8(1.0)), (0, 128, 255));
+        assert_eq!((to_snorm8(-1.0), to_snorm8(0.0), to_snorm8(1.0)), (-127, 0, 127));
+        assert_eq!((from_snorm8(-127), from_snorm8(0), from_snorm8(127)), (-1.0, 0.0, 1.0));
+        assert_eq!(from_snorm8(-128), -1.0);
+        // Out of range values are clamped.
+        assert_eq!((to_unorm8(-0.5), to_unorm8(2.0)), (0, 255));
+        assert_eq!((to_snorm8(-2.0), to_snorm8(2.0)), (-127, 127));
+
+        for byte in 0..=255u8 {
+            assert_eq!(to_unorm8(from_unorm8(byte)), byte);
+        }
+        for byte in -127..=127i8 {
+            assert_eq!(to_snorm8(from_snorm8(byte)), byte);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn format_exact_round_trips_every_bit() {
+        let values = [1.0, -0.0, 0.1, 1.0 / 3.0, 1e-45, f32::MIN_POSITIVE, f32::MAX, f32::NEG_INFINITY, 16777217.0];
+        let s = format_exact(&values);
+        assert_eq!(s, "1.0,-0.0,0.1,0.33333334,1e-45,1.1754944e-38,3.4028235e38,-inf,16777216.0");
+        let parsed = parse_exact::<9>(&s).unwrap();
+        assert_eq!(parsed.map(f32::to_bits), values.map(f32::to_bits));
+
+        // Any float between neighbors of 1.0 survives.
+        for bits in (0x3f7f_fff0..0x3f80_0010u32).chain([0x0000_0001, 0x7f7f_ffff, 0x8000_0000]) {
+            let value = f32::from_bits(bits);
+            assert_eq!(parse_exact::<1>(&format_exact(&[value])).unwrap()[0].to_bits(), bits);
+        }
+        assert_eq!(format_exact(&[f32::NAN]), "NaN");
+        assert!(parse_exact::<1>("NaN").unwrap()[0].is_nan());
+    }
+
+    #[test]
+    fn parse_exact_reports_the_bad_component() {
+        assert_eq!(parse_exact::<3>(" 1.5, -2 ,3e2"), Ok([1.5, -2.0, 300.0]));
+        assert_eq!(parse_exact::<3>("1,2"), Err(ParseExactError::WrongCount { expected: 3, found: 2 }));
+        assert_eq!(parse_exact::<2>("1,2,3"), Err(ParseExactError::WrongCount { expected: 2, found: 3 }));
+        assert_eq!(parse_exact::<3>("1,x,3"), Err(ParseExactError::InvalidNumber { index: 1 }));
+        assert_eq!(parse_exact::<2>("1,"), Err(ParseExactError::InvalidNumber { index: 1 }));
+        assert_eq!(parse_exact::<1>(""), Err(ParseExactError::InvalidNumber { index: 0 }));
+    }
+
+    /// Interpolates halfway through the trait only, like generic code would.
+    fn mix<T: Lerp>(a: &T, b: &T) -> T {
+        a.lerp(b, 0.5)
+    }
+
+    #[test]
+    fn lerp_trait_matches_the_inherent_methods() {
+        use crate::angles::angle2::Angle2;
+        use crate::angles::euler::Euler;
+        use crate::angles::quaternion::Quaternion;
+        use crate::matrix4x4::Matrix4x4;
+        use crate::rendering::color::UniColor;
+        use crate::rendering::vertex::Vertex;
+        use crate::vectors::vector2::Vector2;
+        use crate::vectors::vector3::Vector3;
+        use crate::vectors::vector4::Vector4;
+
+        assert_eq!(mix(&-3.0f32, &7.0), lerp(-3.0, 7.0, 0.5));
+
+        let (a2, b2) = (Vector2::new(1.0, -2.0), Vector2::new(5.0, 4.0));
+        assert_eq!(mix(&a2, &b2), Vector2::lerp(&a2, &b2, 0.5));
+        let (a3, b3) = (Vector3::new(1.0, -2.0, 3.0), Vector3::new(5.0, 4.0, -1.0));
+        assert_eq!(mix(&a3, &b3), Vector3::lerp(&a3, &b3, 0.5));
+        let (a4, b4) = (Vector4::new(1.0, -2.0, 3.0, 0.0), Vector4::new(5.0, 4.0, -1.0, 2.0));
+        assert_eq!(mix(&a4, &b4), Vector4::lerp(&a4, &b4, 0.5));
+
+        let qa = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), 0.4);
+        let qb = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 1.2);
+        assert_eq!(mix(&qa, &qb), qa.slerp(qb, 0.5));
+
+        let (aa, ab) = (Angle2::from_degrees(170.0), Angle2::from_degrees(-170.0));
+        assert_eq!(mix(&aa, &ab), Angle2::lerp(&aa, &ab, 0.5));
+
+        let (ca, cb) = (UniColor::from_rgba(0, 100, 255, 10), UniColor::from_rgba(200, 50, 0, 250));
+        assert_eq!(mix(&ca, &cb).as_bytes(), UniColor::lerp(&ca, &cb, 0.5).as_bytes());
+
+        let va = Vertex::new(a3, Vector3::new(0.0, 1.0, 0.0), a2, ca);
+        let vb = Vertex::new(b3, Vector3::new(1.0, 0.0, 0.0), b2, cb);
+        assert_eq!(mix(&va, &vb).as_bytes(), Vertex::lerp(&va, &vb, 0.5).as_bytes());
+
+        let ma = Matrix4x4::from_array(core::array::from_fn(|i| i as f32));
+        let mb = Matrix4x4::from_array(core::array::from_fn(|i| -(i as f32) * 3.0));
+        assert_eq!(mix(&ma, &mb), Matrix4x4::from_array(core::array::from_fn(|i| lerp(ma[i], mb[i], 0.5))));
+
+        // Euler has no inherent lerp, it takes the short way around every angle.
+        let e = mix(&Euler::new(0.1, 3.0, -0.2), &Euler::new(0.3, -3.0, 0.2));
+        assert!((e.pitch - 0.2).abs() < 1e-6 && (wrap_angle(e.yaw) - PI).abs() < 1e-5 && e.roll.abs() < 1e-6, "{:?}", e);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,139 @@
+//! Sampling functions mapping two uniform random numbers `u1` and `u2` in [0, 1) to unit directions,
+//! e.g. for path tracing or baking lightmaps. They take the random numbers as arguments, so they work with any RNG
+//! and with stratified or low-discrepancy sequences.
+//!
+//! The samples are in a local frame with +Z as the central direction, `to_world` maps them around a surface normal.
+
+use core::f32::consts::{PI, TAU};
+use crate::math::sin_cos;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector3::Vector3;
+
+/// Returns a direction on the +Z hemisphere distributed proportionally to its cosine with +Z,
+/// which is the ideal distribution for diffuse lighting. Its density is `cosine_hemisphere_pdf`.
+pub fn cosine_hemisphere(u1: f32, u2: f32) -> Vector3 {
+    // Uniform points on the unit disk, lifted up onto the hemisphere (Malley's method).
+    let r = u1.sqrt();
+    let (sin, cos) = sin_cos(TAU * u2);
+    Vector3::new(r * cos, r * sin, (1.0 - u1).max(0.0).sqrt())
+}
+
+/// Returns the density of `cosine_hemisphere` for a direction whose z component is `cos_theta`.
+#[inline]
+pub fn cosine_hemisphere_pdf(cos_theta: f32) -> f32 {
+    cos_theta.max(0.0) / PI
+}
+
+/// Returns a direction uniformly distributed over the whole sphere.
+pub fn uniform_sphere(u1: f32, u2: f32) -> Vector3 {
+    let z = 1.0 - 2.0 * u1;
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let (sin, cos) = sin_cos(TAU * u2);
+    Vector3::new(r * cos, r * sin, z)
+}
+
+/// Returns the density of `uniform_sphere`, the same for every direction.
+#[inline]
+pub fn uniform_sphere_pdf() -> f32 {
+    1.0 / (4.0 * PI)
+}
+
+/// Returns a direction uniformly distributed in the cone around +Z whose half angle has the cosine `cos_theta_max`,
+/// e.g. for sampling a spherical light, which covers such a cone seen from the shaded point.
+/// A `cos_theta_max` of 1 always returns +Z, -1 gives `uniform_sphere`.
+pub fn uniform_cone(u1: f32, u2: f32, cos_theta_max: f32) -> Vector3 {
+    let z = 1.0 + u1 * (cos_theta_max - 1.0);
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let (sin, cos) = sin_cos(TAU * u2);
+    Vector3::new(r * cos, r * sin, z)
+}
+
+/// Returns the density of `uniform_cone`, the inverse of the solid angle of the cone.
+/// Infinite for a cone of zero width.
+#[inline]
+pub fn uniform_cone_pdf(cos_theta_max: f32) -> f32 {
+    1.0 / (TAU * (1.0 - cos_theta_max))
+}
+
+/// Maps the local direction `local`, as returned by the sampling functions, into the frame around the unit vector `normal`,
+/// so local +Z becomes `normal`. The frame is built with `Vector3::orthonormal_basis`.
+pub fn to_world(local: Vector3, normal: Vector3) -> Vector3 {
+    let (tangent, bitangent) = normal.orthonormal_basis();
+    tangent * local.x + bitangent * local.y + normal * local.z
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// The centers of a `n` x `n` grid of strata over [0, 1)², plus the corners of the range.
+    fn stratified(n: u32) -> impl Iterator<Item = (f32, f32)> {
+        let below_one = 1.0 - f32::EPSILON / 2.0;
+        (0..n * n)
+            .map(move |i| ((i % n) as f32 + 0.5, (i / n) as f32 + 0.5))
+            .map(move |(x, y)| (x / n as f32, y / n as f32))
+            .chain([(0.0, 0.0), (below_one, 0.0), (0.0, below_one), (below_one, below_one)])
+    }
+
+    #[test]
+    fn samples_are_unit_length() {
+        for (u1, u2) in stratified(32) {
+            for cos_theta_max in [-1.0, -0.3, 0.0, 0.5, 0.99, 1.0] {
+                let samples = [cosine_hemisphere(u1, u2), uniform_sphere(u1, u2), uniform_cone(u1, u2, cos_theta_max)];
+                for sample in samples {
+                    assert!((sample.magnitude_squared() - 1.0).abs() < 1e-5, "{:?} from ({}, {})", sample, u1, u2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cosine_hemisphere_leans_towards_z() {
+        let samples: Vec<Vector3> = stratified(32).map(|(u1, u2)| cosine_hemisphere(u1, u2)).collect();
+        assert!(samples.iter().all(|sample| sample.z >= 0.0));
+        // The mean cosine of a cosine-weighted hemisphere is 2/3, a uniform one would give 1/2.
+        let mean_z = samples.iter().map(|sample| sample.z).sum::<f32>() / samples.len() as f32;
+        assert!((mean_z - 2.0 / 3.0).abs() < 0.01, "{}", mean_z);
+        assert_eq!(cosine_hemisphere_pdf(-0.5), 0.0);
+        assert!((cosine_hemisphere_pdf(1.0) - 1.0 / PI).abs() < 1e-7);
+
+        let mean_z = stratified(32).map(|(u1, u2)| uniform_sphere(u1, u2).z).sum::<f32>() / (32 * 32 + 4) as f32;
+        assert!(mean_z.abs() < 0.01, "{}", mean_z);
+    }
+
+    #[test]
+    fn uniform_cone_stays_inside_the_cone() {
+        for cos_theta_max in [-0.9, 0.0, 0.3, 0.9, 0.999, 1.0] {
+            for (u1, u2) in stratified(24) {
+                let sample = uniform_cone(u1, u2, cos_theta_max);
+                assert!(sample.z >= cos_theta_max, "{:?} is outside {}", sample, cos_theta_max);
+            }
+        }
+        assert_eq!(uniform_cone(0.7, 0.2, 1.0).to_array(), [0.0, 0.0, 1.0]);
+        assert!((uniform_cone_pdf(-1.0) - uniform_sphere_pdf()).abs() < 1e-7);
+    }
+
+    #[test]
+    fn to_world_maps_z_onto_the_normal() {
+        let normals = [
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.6, -0.8, 0.0),
+            Vector3::new(2.0, -1.0, 3.0) * (1.0 / 14f32.sqrt()),
+        ];
+        for normal in normals {
+            assert!(to_world(Vector3::new(0.0, 0.0, 1.0), normal).approx_eq(&normal, 1e-6), "{:?}", normal);
+            // The frame is orthonormal, so cosines with the normal and lengths are kept.
+            for (u1, u2) in stratified(8) {
+                let local = cosine_hemisphere(u1, u2);
+                let world = to_world(local, normal);
+                assert!((world.dot(&normal) - local.z).abs() < 1e-5);
+                assert!((world.magnitude_squared() - 1.0).abs() < 1e-5);
+            }
+        }
+    }
+
+}
\ No newline at end of file
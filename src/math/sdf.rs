@@ -0,0 +1,157 @@
+//! Signed distance functions return the distance from `p` to the surface of a shape,
+//! negative inside and positive outside. They can be combined with `union`, `intersect`, `subtract`
+//! and `smooth_union` for procedural modeling and raymarching.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+
+/// The step used by `gradient` for the central differences.
+const GRADIENT_EPSILON: f32 = 1e-3;
+
+/// Distance to a sphere of `radius` centered at the origin.
+#[inline]
+pub fn sphere(p: Vector3, radius: f32) -> f32 {
+    p.magnitude_squared().sqrt() - radius
+}
+
+/// Distance to a box centered at the origin with the given half extents.
+/// Named `cuboid` since `box` is a reserved keyword.
+#[inline]
+pub fn cuboid(p: Vector3, half_extents: Vector3) -> f32 {
+    let q = Vector3::new(p.x.abs() - half_extents.x, p.y.abs() - half_extents.y, p.z.abs() - half_extents.z);
+    let outside = Vector3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0));
+    outside.magnitude_squared().sqrt() + q.x.max(q.y).max(q.z).min(0.0)
+}
+
+/// Distance to a capsule around the segment from `a` to `b` with the given radius.
+#[inline]
+pub fn capsule(p: Vector3, a: Vector3, b: Vector3, radius: f32) -> f32 {
+    let pa = p - a;
+    let ba = b - a;
+    let length_squared = ba.magnitude_squared();
+    let h = if length_squared > 0.0 { (pa.dot(&ba) / length_squared).clamp(0.0, 1.0) } else { 0.0 };
+    (pa - ba * h).magnitude_squared().sqrt() - radius
+}
+
+/// Distance to the plane `dot(normal, x) + d = 0`, positive on the side `normal` points to.
+/// `normal` has to be normalized.
+#[inline]
+pub fn plane(p: Vector3, normal: Vector3, d: f32) -> f32 {
+    p.dot(&normal) + d
+}
+
+/// Distance to a torus around the Y axis centered at the origin,
+/// with `radii.x` as the radius of the ring and `radii.y` as the radius of the tube.
+#[inline]
+pub fn torus(p: Vector3, radii: Vector2) -> f32 {
+    let ring = Vector2::new(p.xz().magnitude_squared().sqrt() - radii.x, p.y);
+    ring.magnitude_squared().sqrt() - radii.y
+}
+
+/// Combines two shapes into one covering both.
+#[inline]
+pub fn union(a: f32, b: f32) -> f32 {
+    a.min(b)
+}
+
+/// Combines two shapes into their overlap.
+#[inline]
+pub fn intersect(a: f32, b: f32) -> f32 {
+    a.max(b)
+}
+
+/// Cuts shape `b` out of shape `a`.
+#[inline]
+pub fn subtract(a: f32, b: f32) -> f32 {
+    a.max(-b)
+}
+
+/// Like `union`, but blends the shapes together over a distance of about `k`, using the polynomial smooth minimum.
+/// A `k` of zero or less is a plain `union`.
+#[inline]
+pub fn smooth_union(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return union(a, b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+/// Returns the gradient of the distance function `f` at `p`, estimated with central differences.
+/// For exact distance functions it has unit length and normalizing it gives the surface normal.
+pub fn gradient(p: Vector3, f: impl Fn(Vector3) -> f32) -> Vector3 {
+    let (dx, dy, dz) = (
+        Vector3::new(GRADIENT_EPSILON, 0.0, 0.0),
+        Vector3::new(0.0, GRADIENT_EPSILON, 0.0),
+        Vector3::new(0.0, 0.0, GRADIENT_EPSILON),
+    );
+    Vector3::new(f(p + dx) - f(p - dx), f(p + dy) - f(p - dy), f(p + dz) - f(p - dz)) * (0.5 / GRADIENT_EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sphere_is_negative_radius_at_the_center_and_zero_on_the_surface() {
+        assert_eq!(sphere(Vector3::zero(), 2.5), -2.5);
+        for p in [Vector3::new(2.5, 0.0, 0.0), Vector3::new(0.0, -2.5, 0.0), Vector3::new(1.5, 2.0, 0.0)] {
+            assert!(sphere(p, 2.5).abs() < 1e-6, "{:?}", p);
+        }
+        assert!((sphere(Vector3::new(0.0, 0.0, 4.0), 2.5) - 1.5).abs() < 1e-6);
+        assert!((sphere(Vector3::new(0.0, 0.0, 1.0), 2.5) + 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn shapes_are_zero_on_their_surface() {
+        let half = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(cuboid(Vector3::new(1.0, 0.5, -1.0), half), 0.0);
+        assert_eq!(cuboid(Vector3::zero(), half), -1.0);
+        assert!((cuboid(Vector3::new(4.0, 6.0, 0.0), half) - 5.0).abs() < 1e-6);
+
+        let (a, b) = (Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 4.0, 0.0));
+        assert_eq!(capsule(Vector3::new(0.5, 2.0, 0.0), a, b, 0.5), 0.0);
+        assert_eq!(capsule(Vector3::new(0.0, 5.0, 0.0), a, b, 0.5), 0.5);
+        assert_eq!(capsule(Vector3::new(0.0, 5.0, 0.0), b, b, 0.5), 0.5);
+
+        assert_eq!(plane(Vector3::new(3.0, 2.0, -1.0), Vector3::new(0.0, 1.0, 0.0), -2.0), 0.0);
+        assert_eq!(torus(Vector3::new(3.0, 0.0, 0.0), Vector2::new(2.0, 1.0)), 0.0);
+        assert_eq!(torus(Vector3::new(0.0, 0.0, 2.0), Vector2::new(2.0, 1.0)), -1.0);
+    }
+
+    #[test]
+    fn combinations_pick_the_right_side() {
+        assert_eq!(union(-1.0, 2.0), -1.0);
+        assert_eq!(intersect(-1.0, 2.0), 2.0);
+        assert_eq!(subtract(-1.0, -3.0), 3.0);
+        assert_eq!(subtract(-1.0, 3.0), -1.0);
+    }
+
+    #[test]
+    fn smooth_union_approaches_union_for_small_k() {
+        for (a, b) in [(-1.0f32, 2.0f32), (0.3, 0.25), (1.0, 1.0), (-0.5, -0.49)] {
+            assert_eq!(smooth_union(a, b, 0.0), union(a, b));
+            assert_eq!(smooth_union(a, b, -1.0), union(a, b));
+            for k in [1e-2f32, 1e-4, 1e-6] {
+                // The blend never goes above the union and deviates from it by at most k / 4.
+                let blended = smooth_union(a, b, k);
+                assert!(blended <= union(a, b) && union(a, b) - blended <= k * 0.25 + 1e-7, "{} {} {}", a, b, k);
+            }
+        }
+        // Far apart shapes are not blended at all.
+        assert_eq!(smooth_union(-1.0, 2.0, 0.5), -1.0);
+    }
+
+    #[test]
+    fn gradient_of_a_sphere_points_away_from_its_center() {
+        for p in [Vector3::new(3.0, 0.0, 0.0), Vector3::new(1.0, -2.0, 0.5), Vector3::new(-0.3, 0.2, 0.9), Vector3::new(0.0, 0.0, -7.0)] {
+            let gradient = gradient(p, |q| sphere(q, 1.0));
+            let radial = p * (1.0 / p.magnitude_squared().sqrt());
+            assert!(gradient.approx_eq(&radial, 1e-3), "{:?} at {:?}", gradient, p);
+        }
+    }
+
+}
\ No newline at end of file
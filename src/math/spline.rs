@@ -0,0 +1,315 @@
+//! Cubic curves through or along control points, e.g. for camera paths.
+//! The free functions evaluate a single segment and work for `f32`, `Vector2`, `Vector3` and `Vector4`,
+//! `Spline` chains segments of `Vector3` control points into a whole curve.
+
+use core::ops::{Add, Mul, Sub};
+use crate::math::cubic_hermite;
+#[cfg(feature = "std")]
+use crate::vectors::vector3::Vector3;
+
+/// Evaluates the uniform Catmull-Rom segment from `p1` to `p2` at `t` in [0, 1], using `p0` and `p3` for the tangents.
+/// The curve passes through `p1` at `t = 0` and `p2` at `t = 1`.
+#[inline]
+pub fn catmull_rom<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T {
+    cubic_hermite(p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5, t)
+}
+
+/// Returns the derivative of `catmull_rom` with respect to `t`, the direction and speed of the curve at `t`.
+#[inline]
+pub fn catmull_rom_derivative<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T {
+    cubic_hermite_derivative(p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5, t)
+}
+
+/// Evaluates the cubic Bézier curve with the control points `p0` to `p3` at `t` in [0, 1].
+/// The curve starts at `p0`, ends at `p3` and stays inside the convex hull of the control points.
+#[inline]
+pub fn cubic_bezier<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T {
+    let s = 1.0 - t;
+    p0 * (s * s * s) + p1 * (3.0 * s * s * t) + p2 * (3.0 * s * t * t) + p3 * (t * t * t)
+}
+
+/// Returns the derivative of `cubic_bezier` with respect to `t`. At `t = 0` it points from `p0` to `p1`.
+#[inline]
+pub fn cubic_bezier_derivative<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>>(p0: T, p1: T, p2: T, p3: T, t: f32) -> T {
+    let s = 1.0 - t;
+    (p1 - p0) * (3.0 * s * s) + (p2 - p1) * (6.0 * s * t) + (p3 - p2) * (3.0 * t * t)
+}
+
+/// Returns the derivative of `cubic_hermite` with respect to `t`.
+#[inline]
+fn cubic_hermite_derivative<T: Add<Output = T> + Mul<f32, Output = T>>(p0: T, m0: T, p1: T, m1: T, t: f32) -> T {
+    let t2 = t * t;
+    p0 * (6.0 * t2 - 6.0 * t) + m0 * (3.0 * t2 - 4.0 * t + 1.0) + p1 * (6.0 * t - 6.0 * t2) + m1 * (3.0 * t2 - 2.0 * t)
+}
+
+/// How a `Spline` turns its control points into a curve.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplineKind {
+    /// A Catmull-Rom curve passing through every control point.
+    ///
+    /// The first and the last point are duplicated to get the tangents at the ends,
+    /// so the curve starts heading from the first towards the second point and ends heading from the second to last to the last.
+    CatmullRom,
+    /// Cubic Bézier segments sharing their end points: points 0 to 3 are the first segment, 3 to 6 the second and so on.
+    /// The curve passes through every third point, the others pull it towards them.
+    Bezier,
+}
+
+/// A curve made of cubic segments through or along `Vector3` control points, e.g. for camera paths.
+///
+/// `sample` and `sample_derivative` take a `t` in [0, 1] over the whole curve, every segment gets an equal share of it.
+/// Since segments differ in length, the speed along the curve varies, use an `ArcLengthTable` for constant speed.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spline {
+    points: Vec<Vector3>,
+    kind: SplineKind,
+}
+
+#[cfg(feature = "std")]
+impl Spline {
+
+    /// Creates a Catmull-Rom spline through all `points`, see `SplineKind::CatmullRom`.
+    /// Returns `None` for less than 2 points.
+    pub fn catmull_rom(points: Vec<Vector3>) -> Option<Self> {
+        (points.len() >= 2).then_some(Self { points, kind: SplineKind::CatmullRom })
+    }
+
+    /// Creates a spline of cubic Bézier segments, see `SplineKind::Bezier`.
+    /// Returns `None` unless there are `3 * n + 1` points for some `n >= 1`.
+    pub fn bezier(points: Vec<Vector3>) -> Option<Self> {
+        (points.len() >= 4 && (points.len() - 1).is_multiple_of(3)).then_some(Self { points, kind: SplineKind::Bezier })
+    }
+
+    /// Returns the control points.
+    #[inline]
+    pub fn points(&self) -> &[Vector3] {
+        &self.points
+    }
+
+    /// Returns how the control points are turned into a curve.
+    #[inline]
+    pub fn kind(&self) -> SplineKind {
+        self.kind
+    }
+
+    /// Returns the number of cubic segments.
+    #[inline]
+    pub fn segment_count(&self) -> usize {
+        match self.kind {
+            SplineKind::CatmullRom => self.points.len() - 1,
+            SplineKind::Bezier => (self.points.len() - 1) / 3,
+        }
+    }
+
+    /// Returns the point on the curve at `t`, which is clamped to [0, 1].
+    /// `t = 0` gives exactly the first and `t = 1` exactly the last control point.
+    pub fn sample(&self, t: f32) -> Vector3 {
+        let (segment, t) = self.locate(t);
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        match self.kind {
+            SplineKind::CatmullRom => catmull_rom(p0, p1, p2, p3, t),
+            SplineKind::Bezier => cubic_bezier(p0, p1, p2, p3, t),
+        }
+    }
+
+    /// Returns the derivative of `sample` with respect to `t`, which is clamped to [0, 1].
+    /// Its direction is the direction the curve is heading in, e.g. for orienting a camera, and its length the speed.
+    /// It is zero where the curve stops, e.g. for duplicated control points.
+    pub fn sample_derivative(&self, t: f32) -> Vector3 {
+        let (segment, t) = self.locate(t);
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        let derivative = match self.kind {
+            SplineKind::CatmullRom => catmull_rom_derivative(p0, p1, p2, p3, t),
+            SplineKind::Bezier => cubic_bezier_derivative(p0, p1, p2, p3, t),
+        };
+        // Every segment covers 1 / segment_count of the whole curve.
+        derivative * self.segment_count() as f32
+    }
+
+    /// Measures the curve by sampling it at `samples + 1` evenly spaced values of `t`, at least 2.
+    /// The result maps distances along the curve back to `t`, so `sample` can move along the curve at constant speed.
+    /// More samples give a more precise length and speed, a few per segment are usually enough.
+    pub fn arc_length_parameterize(&self, samples: usize) -> ArcLengthTable {
+        let samples = samples.max(1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut previous = self.sample(0.0);
+        let mut length = 0.0;
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let point = self.sample(i as f32 / samples as f32);
+            length += previous.distance_squared(&point).sqrt();
+            lengths.push(length);
+            previous = point;
+        }
+        ArcLengthTable { lengths }
+    }
+
+    /// Splits the global `t` into the index of a segment and the `t` within it.
+    fn locate(&self, t: f32) -> (usize, f32) {
+        let segments = self.segment_count();
+        let scaled = t.clamp(0.0, 1.0) * segments as f32;
+        // A NaN `t` goes to the start of the curve.
+        if scaled.is_nan() {
+            return (0, 0.0);
+        }
+        let segment = (scaled as usize).min(segments - 1);
+        (segment, scaled - segment as f32)
+    }
+
+    /// Returns the four points evaluated for `segment`.
+    fn segment_points(&self, segment: usize) -> [Vector3; 4] {
+        match self.kind {
+            SplineKind::CatmullRom => {
+                let last = self.points.len() - 1;
+                let point = |i: usize| self.points[i.min(last)];
+                [point(segment.saturating_sub(1)), point(segment), point(segment + 1), point(segment + 2)]
+            }
+            SplineKind::Bezier => {
+                let start = segment * 3;
+                [self.points[start], self.points[start + 1], self.points[start + 2], self.points[start + 3]]
+            }
+        }
+    }
+
+}
+
+/// The length of a `Spline` measured at evenly spaced values of `t`, created by `Spline::arc_length_parameterize`.
+/// Converts distances along the curve into values of `t`, interpolating linearly between the measured samples.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthTable {
+    /// The length of the curve from the start up to every sample.
+    lengths: Vec<f32>,
+}
+
+#[cfg(feature = "std")]
+impl ArcLengthTable {
+
+    /// Returns the measured length of the whole curve.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Returns the `t` at which the curve is `distance` long, with `distance` clamped to [0, `length`].
+    /// Sampling at evenly spaced distances moves along the curve at constant speed.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let samples = self.lengths.len() - 1;
+        // The first sample at least `distance` along, so the distance lies between it and the sample before.
+        let next = self.lengths.partition_point(|&length| length < distance).clamp(1, samples);
+        let (start, end) = (self.lengths[next - 1], self.lengths[next]);
+        let fraction = if end > start { ((distance - start) / (end - start)).clamp(0.0, 1.0) } else { 0.0 };
+        (next - 1) as f32 / samples as f32 + fraction / samples as f32
+    }
+
+    /// Like `t_at_distance`, but takes the distance as a fraction of the whole length in [0, 1].
+    #[inline]
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.length())
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::vectors::vector3::Vector3;
+    use super::*;
+
+    #[test]
+    fn bezier_with_collinear_points_stays_on_the_line() {
+        let (origin, direction) = (Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.6, 0.0, -0.8));
+        let on_line = |s: f32| origin + direction * s;
+        // Control points out of order along the line, so the curve even turns back.
+        let (p0, p1, p2, p3) = (on_line(0.0), on_line(5.0), on_line(-2.0), on_line(3.0));
+        for i in 0..=50 {
+            let t = i as f32 / 50.0;
+            let offset = cubic_bezier(p0, p1, p2, p3, t) - origin;
+            assert!(offset.cross(&direction).magnitude() < 1e-5, "{} is off the line by {:?}", t, offset);
+            assert!(cubic_bezier_derivative(p0, p1, p2, p3, t).cross(&direction).magnitude() < 1e-4);
+        }
+        assert!(cubic_bezier_derivative(p0, p1, p2, p3, 0.0).approx_eq(&((p1 - p0) * 3.0), 1e-5));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_its_middle_points() {
+        let [p0, p1, p2, p3] = [Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 2.0, 0.0), Vector3::new(3.0, 2.0, 1.0), Vector3::new(4.0, 0.0, 1.0)];
+        assert_eq!(catmull_rom(p0, p1, p2, p3, 0.0).to_array(), p1.to_array());
+        assert!(catmull_rom(p0, p1, p2, p3, 1.0).approx_eq(&p2, 1e-6));
+        assert!(catmull_rom_derivative(p0, p1, p2, p3, 0.0).approx_eq(&((p2 - p0) * 0.5), 1e-6));
+        assert!(catmull_rom_derivative(p0, p1, p2, p3, 1.0).approx_eq(&((p3 - p1) * 0.5), 1e-6));
+    }
+
+    #[cfg(feature = "std")]
+    fn path() -> Vec<Vector3> {
+        vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(2.0, 1.0, 0.0),
+            Vector3::new(3.0, 1.0, 4.0),
+            Vector3::new(-1.0, 0.0, 6.0),
+            Vector3::new(-2.0, 3.0, 5.0),
+            Vector3::new(0.0, 2.0, 1.0),
+            Vector3::new(1.0, -1.0, 2.0),
+        ]
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn splines_start_and_end_at_their_end_points() {
+        let points = path();
+        let (first, last) = (points[0].to_array(), points[points.len() - 1].to_array());
+        for spline in [Spline::catmull_rom(points.clone()).unwrap(), Spline::bezier(points.clone()).unwrap()] {
+            assert_eq!(spline.sample(0.0).to_array(), first, "{:?}", spline.kind());
+            assert_eq!(spline.sample(1.0).to_array(), last, "{:?}", spline.kind());
+            // Out of range and NaN values are clamped.
+            assert_eq!(spline.sample(-3.0).to_array(), first);
+            assert_eq!(spline.sample(2.0).to_array(), last);
+            assert_eq!(spline.sample(f32::NAN).to_array(), first);
+        }
+        assert!(Spline::bezier(points[..6].to_vec()).is_none());
+        assert!(Spline::catmull_rom(points[..1].to_vec()).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn catmull_rom_starts_heading_towards_the_second_point() {
+        let points = path();
+        let spline = Spline::catmull_rom(points.clone()).unwrap();
+        let start = spline.sample_derivative(0.0);
+        let expected = points[1] - points[0];
+        assert!(start.cross(&expected).magnitude() < 1e-4, "{:?}", start);
+        assert!(start.dot(&expected) > 0.0);
+        let end = spline.sample_derivative(1.0);
+        let expected = points[6] - points[5];
+        assert!(end.cross(&expected).magnitude() < 1e-4 && end.dot(&expected) > 0.0, "{:?}", end);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn t_at_distance_is_monotonic_and_spans_the_curve() {
+        let spline = Spline::catmull_rom(path()).unwrap();
+        let table = spline.arc_length_parameterize(200);
+        assert_eq!(table.t_at_distance(0.0), 0.0);
+        assert_eq!(table.t_at_distance(-1.0), 0.0);
+        assert_eq!(table.t_at_distance(table.length()), 1.0);
+        assert_eq!(table.t_at_distance(table.length() * 2.0), 1.0);
+
+        let steps = 500;
+        let mut previous = 0.0;
+        let mut positions = Vec::new();
+        for i in 0..=steps {
+            let t = table.t_at_fraction(i as f32 / steps as f32);
+            assert!(t >= previous, "t went back from {} to {}", previous, t);
+            previous = t;
+            positions.push(spline.sample(t));
+        }
+        // Even distances move along the curve at a nearly constant speed.
+        let step = table.length() / steps as f32;
+        for pair in positions.windows(2) {
+            let distance = pair[0].distance_squared(&pair[1]).sqrt();
+            assert!((distance - step).abs() < step * 0.1, "{} instead of {}", distance, step);
+        }
+    }
+
+}
\ No newline at end of file
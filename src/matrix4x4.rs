@@ -1,25 +1,45 @@
-use std::f32::consts::PI;
-use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+use core::f32::consts::PI;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+use crate::affine2::Affine2;
+use crate::angles::euler::Euler;
+use crate::angles::quaternion::Quaternion;
+use crate::math::{Lerp, parse_exact, sin_cos, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::transform::Transform;
+use crate::types::Axis;
 use crate::vectors::vector3::Vector3;
+use crate::vectors::vector4::Vector4;
 
-/// A 4x4 matrix with 16 `f32` elements stored in column-major order.
-#[derive(Clone, Copy)]
+/// A 4x4 matrix with 16 `f32` elements stored in row-major order, so `data[row * 4 + column]`.
+///
+/// Points are transformed as column vectors, so the translation sits in the last column at `data[3]`, `data[7]` and `data[11]`.
+/// GLSL and WGSL expect column-major uniforms, so upload `to_cols_array` or `as_bytes_column_major` instead of `data`.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct Matrix4x4 {
     pub data: [f32; 16],
 }
 
+const _: () = assert!(core::mem::size_of::<Matrix4x4>() == 64 && core::mem::align_of::<Matrix4x4>() == 4);
+
 impl Matrix4x4 {
 
     /// Creates a new identity Matrix.
     /// This is basically just `Matrix::identity()`.
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Matrix4x4::identity()
     }
 
     /// Creates a new identity matrix.
     ///
     /// An identity matrix is a matrix in which all the elements of the main diagonal are 1, and all other elements are 0.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Matrix4x4 {
             data: [
                 1.0, 0.0, 0.0, 0.0,
@@ -54,13 +74,348 @@ impl Matrix4x4 {
         }
     }
 
-    /// Create a new Matrix from a float array.
+    /// Creates a right-handed perspective projection with the OpenGL clip-space depth range of [-1, 1].
+    /// The camera looks down -Z, `fov_y_radians` is the vertical field of view and `aspect` is width / height.
+    /// Points are transformed as column vectors, so `projection * view * model` applies the model matrix first.
+    ///
+    /// A point on the near plane maps to a clip-space depth of -w and a point on the far plane to +w.
+    /// Returns `None` if the field of view is not in (0, PI), `aspect` is not positive and finite,
+    /// `near` is not positive or `near == far`.
+    #[inline]
+    pub fn perspective(fov_y_radians: f32, aspect: f32, near: f32, far: f32) -> Option<Self> {
+        Self::perspective_gl(fov_y_radians, aspect, near, far)
+    }
+
+    /// Creates a right-handed perspective projection with the OpenGL clip-space depth range of [-1, 1].
+    /// Same as [`Matrix4x4::perspective`].
+    pub fn perspective_gl(fov_y_radians: f32, aspect: f32, near: f32, far: f32) -> Option<Self> {
+        let f = perspective_focal_length(fov_y_radians, aspect, near, far)?;
+        let range = near - far;
+        Some(Matrix4x4 {
+            data: [
+                f / aspect, 0.0, 0.0,                 0.0,
+                0.0,        f,   0.0,                 0.0,
+                0.0,        0.0, (far + near) / range, 2.0 * far * near / range,
+                0.0,        0.0, -1.0,                0.0,
+            ],
+        })
+    }
+
+    /// Creates a right-handed perspective projection with the clip-space depth range of [0, 1] used by wgpu, Vulkan,
+    /// Metal and Direct3D. The near plane maps to a depth of 0 and the far plane to 1.
+    /// Returns `None` in the same cases as [`Matrix4x4::perspective`].
+    pub fn perspective_wgpu(fov_y_radians: f32, aspect: f32, near: f32, far: f32) -> Option<Self> {
+        let f = perspective_focal_length(fov_y_radians, aspect, near, far)?;
+        let range = near - far;
+        Some(Matrix4x4 {
+            data: [
+                f / aspect, 0.0, 0.0,         0.0,
+                0.0,        f,   0.0,         0.0,
+                0.0,        0.0, far / range, far * near / range,
+                0.0,        0.0, -1.0,        0.0,
+            ],
+        })
+    }
+
+    /// Creates a right-handed reverse-Z perspective projection with an infinitely far plane and a depth range of [0, 1].
+    /// The near plane maps to a depth of 1 and depth approaches 0 towards infinity,
+    /// which spreads float precision evenly over the whole view distance. Use it with a `Greater` depth test.
+    /// Returns `None` if the field of view is not in (0, PI), `aspect` is not positive and finite or `near` is not positive.
+    pub fn perspective_infinite(fov_y_radians: f32, aspect: f32, near: f32) -> Option<Self> {
+        let f = perspective_focal_length(fov_y_radians, aspect, near, f32::INFINITY)?;
+        Some(Matrix4x4 {
+            data: [
+                f / aspect, 0.0, 0.0,  0.0,
+                0.0,        f,   0.0,  0.0,
+                0.0,        0.0, 0.0,  near,
+                0.0,        0.0, -1.0, 0.0,
+            ],
+        })
+    }
+
+    /// Creates a right-handed orthographic projection with the OpenGL clip-space depth range of [-1, 1].
+    /// The camera looks down -Z, so `near` and `far` are distances in front of it. `w` stays 1 for every point.
+    /// Returns `None` if `left == right`, `bottom == top` or `near == far`, or any bound is not finite.
+    #[inline]
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Option<Self> {
+        Self::orthographic_gl(left, right, bottom, top, near, far)
+    }
+
+    /// Creates a right-handed orthographic projection with the OpenGL clip-space depth range of [-1, 1].
+    /// Same as [`Matrix4x4::orthographic`].
+    pub fn orthographic_gl(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Option<Self> {
+        if !orthographic_bounds_valid([left, right, bottom, top, near, far]) {
+            return None;
+        }
+        let (width, height, depth) = (right - left, top - bottom, far - near);
+        Some(Matrix4x4 {
+            data: [
+                2.0 / width, 0.0,          0.0,          -(right + left) / width,
+                0.0,         2.0 / height, 0.0,          -(top + bottom) / height,
+                0.0,         0.0,          -2.0 / depth, -(far + near) / depth,
+                0.0,         0.0,          0.0,          1.0,
+            ],
+        })
+    }
+
+    /// Creates a right-handed orthographic projection with the clip-space depth range of [0, 1] used by wgpu, Vulkan,
+    /// Metal and Direct3D. Returns `None` in the same cases as [`Matrix4x4::orthographic`].
+    pub fn orthographic_wgpu(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Option<Self> {
+        if !orthographic_bounds_valid([left, right, bottom, top, near, far]) {
+            return None;
+        }
+        let (width, height, depth) = (right - left, top - bottom, far - near);
+        Some(Matrix4x4 {
+            data: [
+                2.0 / width, 0.0,          0.0,          -(right + left) / width,
+                0.0,         2.0 / height, 0.0,          -(top + bottom) / height,
+                0.0,         0.0,          -1.0 / depth, -near / depth,
+                0.0,         0.0,          0.0,          1.0,
+            ],
+        })
+    }
+
+    /// Creates a right-handed view matrix for a camera at `eye` looking at `target`, with -Z as the view direction.
+    /// The target ends up on the -Z axis at its distance from the eye.
+    ///
+    /// If the view direction is parallel to `up`, the world axis least aligned with it is used as up instead.
+    /// If `eye == target`, the camera looks down -Z.
+    pub fn look_at(eye: Vector3, target: Vector3, up: Vector3) -> Self {
+        let (forward, right, up) = look_at_basis(eye, target, up);
+        Matrix4x4 {
+            data: [
+                right.x,    right.y,    right.z,    -right.dot(&eye),
+                up.x,       up.y,       up.z,       -up.dot(&eye),
+                -forward.x, -forward.y, -forward.z, forward.dot(&eye),
+                0.0,        0.0,        0.0,        1.0,
+            ],
+        }
+    }
+
+    /// Creates a left-handed view matrix for a camera at `eye` looking at `target`, with +Z as the view direction.
+    /// The target ends up on the +Z axis at its distance from the eye. Degenerate inputs are handled like in [`Matrix4x4::look_at`].
+    pub fn look_at_lh(eye: Vector3, target: Vector3, up: Vector3) -> Self {
+        let (forward, right, up) = look_at_basis(eye, target, up);
+        let left = right * -1.0;
+        Matrix4x4 {
+            data: [
+                left.x,    left.y,    left.z,    -left.dot(&eye),
+                up.x,      up.y,      up.z,      -up.dot(&eye),
+                forward.x, forward.y, forward.z, -forward.dot(&eye),
+                0.0,       0.0,       0.0,       1.0,
+            ],
+        }
+    }
+
+    /// Creates a pure rotation matrix from `q`, so that `Matrix4x4::from_quaternion(&q).transform_point(v)`
+    /// rotates `v` like `q` does. `q` is normalized first, a zero quaternion gives the identity.
+    pub fn from_quaternion(q: &Quaternion) -> Self {
+        let length_squared = q.magnitude_squared();
+        if length_squared <= f32::EPSILON * f32::EPSILON {
+            return Matrix4x4::identity();
+        }
+        let s = 2.0 / length_squared;
+        let (x, y, z, w) = (q.x, q.y, q.z, q.w);
+        let (xx, yy, zz) = (x * x * s, y * y * s, z * z * s);
+        let (xy, xz, yz) = (x * y * s, x * z * s, y * z * s);
+        let (wx, wy, wz) = (w * x * s, w * y * s, w * z * s);
+        Matrix4x4 {
+            data: [
+                1.0 - yy - zz, xy - wz,       xz + wy,       0.0,
+                xy + wz,       1.0 - xx - zz, yz - wx,       0.0,
+                xz - wy,       yz + wx,       1.0 - xx - yy, 0.0,
+                0.0,           0.0,           0.0,           1.0,
+            ],
+        }
+    }
+
+    /// Creates a pure rotation of `angle_radians` around one of the coordinate axes, counter-clockwise when looking
+    /// down the axis towards the origin like `Quaternion::from_axis`. Note that the deprecated `rotate` turns the other way.
+    pub fn rotation_about_axis(axis: Axis, angle_radians: f32) -> Self {
+        let (s, c) = sin_cos(angle_radians);
+        let data = match axis {
+            Axis::X => [
+                1.0, 0.0, 0.0, 0.0,
+                0.0, c,   -s,  0.0,
+                0.0, s,   c,   0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+            Axis::Y => [
+                c,   0.0, s,   0.0,
+                0.0, 1.0, 0.0, 0.0,
+                -s,  0.0, c,   0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+            Axis::Z => [
+                c,   -s,  0.0, 0.0,
+                s,   c,   0.0, 0.0,
+                0.0, 0.0, 1.0, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+        };
+        Matrix4x4 { data }
+    }
+
+    /// Creates a pure rotation of `angle_radians` around the x axis, so a quarter turn maps +Y to +Z.
+    #[inline]
+    pub fn rotation_x(angle_radians: f32) -> Self {
+        Matrix4x4::rotation_about_axis(Axis::X, angle_radians)
+    }
+
+    /// Creates a pure rotation of `angle_radians` around the y axis, so a quarter turn maps +Z to +X.
+    #[inline]
+    pub fn rotation_y(angle_radians: f32) -> Self {
+        Matrix4x4::rotation_about_axis(Axis::Y, angle_radians)
+    }
+
+    /// Creates a pure rotation of `angle_radians` around the z axis, so a quarter turn maps +X to +Y.
+    #[inline]
+    pub fn rotation_z(angle_radians: f32) -> Self {
+        Matrix4x4::rotation_about_axis(Axis::Z, angle_radians)
+    }
+
+    /// Creates a pure rotation of `angle_radians` around `axis`, counter-clockwise when looking down the axis towards the origin,
+    /// matching `Quaternion::from_axis_angle`. The axis gets normalized, a zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vector3, angle_radians: f32) -> Self {
+        let length_squared = axis.magnitude_squared();
+        if length_squared == 0.0 {
+            return Matrix4x4::identity();
+        }
+        let Vector3 { x, y, z } = axis * (1.0 / length_squared.sqrt());
+        let (sin, cos) = sin_cos(angle_radians);
+        let t = 1.0 - cos;
+        Matrix4x4 {
+            data: [
+                cos + x * x * t,     x * y * t - z * sin, x * z * t + y * sin, 0.0,
+                x * y * t + z * sin, cos + y * y * t,     y * z * t - x * sin, 0.0,
+                x * z * t - y * sin, y * z * t + x * sin, cos + z * z * t,     0.0,
+                0.0,                 0.0,                 0.0,                 1.0,
+            ],
+        }
+    }
+
+    /// Creates the pure rotation of `euler`, the same as `from_quaternion(&euler.to_quaternion())`:
+    /// the pitch turns around x first, then the yaw around y and the roll around z.
+    pub fn from_euler(euler: &Euler) -> Self {
+        Matrix4x4::rotation_z(euler.roll) * Matrix4x4::rotation_y(euler.yaw) * Matrix4x4::rotation_x(euler.pitch)
+    }
+
+    /// Create a new Matrix from a float array in row-major order, like `from_rows_array`.
     pub fn from_array(data: [f32; 16]) -> Self {
         Matrix4x4 {
             data
         }
     }
 
+    /// Returns true if every element differs from the one of `other` by at most `epsilon`.
+    /// Always false if an element is NaN.
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        self.data.iter().zip(other.data.iter()).all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+
+    /// Returns true if `other` is approximately this matrix times some nonzero factor, e.g. two projection matrices
+    /// whose homogeneous results are the same after the divide by w. The factor is fitted by least squares
+    /// and `epsilon` is relative to the largest element of this matrix. Two zero matrices are equal, a zero and a nonzero one are not.
+    pub fn approx_eq_up_to_scale(&self, other: &Self, epsilon: f32) -> bool {
+        let dot = |a: &Matrix4x4, b: &Matrix4x4| a.data.iter().zip(b.data.iter()).map(|(x, y)| x * y).sum::<f32>();
+        let (self_squared, other_squared) = (dot(self, self), dot(other, other));
+        if self_squared == 0.0 || other_squared == 0.0 {
+            return self_squared == other_squared;
+        }
+        let factor = dot(self, other) / other_squared;
+        let largest = self.data.iter().fold(0.0f32, |m, e| m.max(e.abs()));
+        factor != 0.0 && self.approx_eq(&(*other * factor), epsilon * largest)
+    }
+
+    /// Compares this matrix with `other` for debugging two code paths which should produce the same transform.
+    ///
+    /// Besides the largest element difference, both matrices are decomposed like `Transform::from_matrix` to report
+    /// how far the translation, the rotation and the scale of `other` are off from this matrix.
+    /// Print the result to get a summary like `translation off by 0.020, rotation off by 1.30°, scale equal`.
+    pub fn compare(&self, other: &Matrix4x4) -> MatrixDiff {
+        let max_element_difference = self.data.iter().zip(other.data.iter()).fold(0.0f32, |m, (a, b)| m.max((a - b).abs()));
+        let (a, b) = (Transform::from_matrix(self), Transform::from_matrix(other));
+
+        // Identical parts are reported as exactly zero or one, instead of the rounding noise of the formulas.
+        let rotation_degrees = if a.rotation == b.rotation {
+            0.0
+        } else {
+            let delta = a.rotation.conjugate() * b.rotation;
+            let sin_half = Vector3::new(delta.x, delta.y, delta.z).magnitude_squared().sqrt();
+            2.0 * sin_half.atan2(delta.w.abs()).to_degrees()
+        };
+        let ratio = |a: f32, b: f32| if a == b { 1.0 } else { b / a };
+
+        MatrixDiff {
+            max_element_difference,
+            translation_delta: b.position - a.position,
+            rotation_degrees,
+            scale_ratio: Vector3::new(ratio(a.scale.x, b.scale.x), ratio(a.scale.y, b.scale.y), ratio(a.scale.z, b.scale.z)),
+        }
+    }
+
+    /// Returns `data` as four arrays of four consecutive elements, so `to_2d_array()[i][j]` is `data[i * 4 + j]`.
+    pub fn to_2d_array(&self) -> [[f32; 4]; 4] {
+        let d = &self.data;
+        [
+            [d[0], d[1], d[2], d[3]],
+            [d[4], d[5], d[6], d[7]],
+            [d[8], d[9], d[10], d[11]],
+            [d[12], d[13], d[14], d[15]],
+        ]
+    }
+
+    /// Creates a matrix from 16 elements in row-major order, the layout of `data`.
+    #[inline]
+    pub const fn from_rows_array(data: [f32; 16]) -> Self {
+        Matrix4x4 { data }
+    }
+
+    /// Creates a matrix from 16 elements in column-major order, like a GLSL or WGSL `mat4x4<f32>`.
+    pub fn from_cols_array(data: [f32; 16]) -> Self {
+        let mut matrix = Matrix4x4 { data };
+        matrix.transpose();
+        matrix
+    }
+
+    /// Returns the 16 elements in row-major order, the layout of `data`.
+    #[inline]
+    pub const fn to_rows_array(&self) -> [f32; 16] {
+        self.data
+    }
+
+    /// Returns the 16 elements in column-major order, ready to upload as a GLSL or WGSL `mat4x4<f32>`.
+    pub fn to_cols_array(&self) -> [f32; 16] {
+        let mut matrix = *self;
+        matrix.transpose();
+        matrix.data
+    }
+
+    /// Returns the column-major elements as bytes in native endianness, for direct uniform upload.
+    pub fn as_bytes_column_major(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        for (chunk, element) in bytes.chunks_exact_mut(4).zip(self.to_cols_array()) {
+            chunk.copy_from_slice(&element.to_ne_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the row at `index`, panicking if it is 4 or more.
+    #[inline]
+    pub fn row(&self, index: usize) -> Vector4 {
+        assert!(index < 4, "Matrix4x4 row out of range: the index is {index} but there are only 4 rows");
+        let d = &self.data;
+        Vector4::new(d[index * 4], d[index * 4 + 1], d[index * 4 + 2], d[index * 4 + 3])
+    }
+
+    /// Returns the column at `index`, panicking if it is 4 or more. The last column holds the translation.
+    #[inline]
+    pub fn col(&self, index: usize) -> Vector4 {
+        assert!(index < 4, "Matrix4x4 column out of range: the index is {index} but there are only 4 columns");
+        let d = &self.data;
+        Vector4::new(d[index], d[index + 4], d[index + 8], d[index + 12])
+    }
+
     /// Transposes the matrix in-place.
     pub fn transpose(&mut self) {
         let mut temp: f32;
@@ -101,91 +456,256 @@ impl Matrix4x4 {
     }
 
     /// Calculates the inverse of the matrix.
+    /// Returns `None` if the determinant is 0. Uses SSE where available, with the same results as the scalar code.
+    #[inline]
     pub fn inverse(&self) -> Option<Matrix4x4> {
-        let mut result = Matrix4x4::new();
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+        {
+            crate::simd::inverse(self)
+        }
+        #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+        {
+            inverse_scalar(self)
+        }
+    }
 
-        let a = self[0];
-        let b = self[1];
-        let c = self[2];
-        let d = self[3];
-        let e = self[4];
-        let f = self[5];
-        let g = self[6];
-        let h = self[7];
-        let i = self[8];
-        let j = self[9];
-        let k = self[10];
-        let l = self[11];
-        let m = self[12];
-        let n = self[13];
-        let o = self[14];
-        let p = self[15];
-
-        let q = a * f - b * e;
-        let r = a * g - c * e;
-        let s = a * h - d * e;
-        let t = b * g - c * f;
-        let u = b * h - d * f;
-        let v = c * h - d * g;
-        let w = i * n - j * m;
-        let x = i * o - k * m;
-        let y = i * p - l * m;
-        let z = j * o - k * n;
-        let aa = j * p - l * n;
-        let bb = k * p - l * o;
-
-        let det = q * bb - r * aa + s * z + t * y - u * x + v * w;
-
-        if det == 0.0 {
-            return None;
+    /// Multiplies the matrices of `a` and `b` pair by pair into `out`, so `out[i] = a[i] * b[i]`, e.g. for skinning palettes.
+    /// Gives the same results as multiplying every pair on its own, but keeps the loop free of copies.
+    ///
+    /// # Panics
+    /// Panics if the three slices differ in length.
+    pub fn mul_many(out: &mut [Matrix4x4], a: &[Matrix4x4], b: &[Matrix4x4]) {
+        assert!(
+            out.len() == a.len() && a.len() == b.len(),
+            "Matrix4x4::mul_many needs slices of equal length, but got {}, {} and {}", out.len(), a.len(), b.len(),
+        );
+        for ((out, a), b) in out.iter_mut().zip(a).zip(b) {
+            *out = mul_matrices(a, b);
         }
+    }
 
-        let inv_det = 1.0 / det;
-
-        result[0] = (f * bb - g * aa + h * z) * inv_det;
-        result[1] = (-b * bb + c * aa - d * z) * inv_det;
-        result[2] = (n * v - o * u + p * t) * inv_det;
-        result[3] = (-j * v + k * u - l * t) * inv_det;
-        result[4] = (-e * bb + g * y - h * x) * inv_det;
-        result[5] = (a * bb - c * y + d * x) * inv_det;
-        result[6] = (-m * v + o * s - p * r) * inv_det;
-        result[7] = (i * v - k * s + l * r) * inv_det;
-        result[8] = (e * aa - f * y + h * w) * inv_det;
-        result[9] = (-a * aa + b * y - d * w) * inv_det;
-        result[10] = (m * u - n * s + p * q) * inv_det;
-        result[11] = (-i * u + j * s - l * q) * inv_det;
-        result[12] = (-e * z + f * x - g * w) * inv_det;
-        result[13] = (a * z - b * x + c * w) * inv_det;
-        result[14] = (-m * t + n * r - o * q) * inv_det;
-        result[15] = (i * t - j * r + k * q) * inv_det;
+    /// Returns true if the bottom row of the matrix is (0, 0, 0, 1).
+    /// Affine matrices never change w, so transformed points don't need a perspective divide.
+    #[inline]
+    pub fn is_affine(&self) -> bool {
+        self.data[12] == 0.0 && self.data[13] == 0.0 && self.data[14] == 0.0 && self.data[15] == 1.0
+    }
 
-        Some(result)
+    /// Transforms `point` by this matrix, treating it as a position (w = 1).
+    /// If the resulting w is not 1 (e.g. for projection matrices), the result is divided by w.
+    pub fn transform_point(&self, point: Vector3) -> Vector3 {
+        let d = &self.data;
+        let x = d[0] * point.x + d[1] * point.y + d[2] * point.z + d[3];
+        let y = d[4] * point.x + d[5] * point.y + d[6] * point.z + d[7];
+        let z = d[8] * point.x + d[9] * point.y + d[10] * point.z + d[11];
+        let w = d[12] * point.x + d[13] * point.y + d[14] * point.z + d[15];
+
+        if w == 1.0 {
+            Vector3::new(x, y, z)
+        } else {
+            let inv_w = 1.0 / w;
+            Vector3::new(x * inv_w, y * inv_w, z * inv_w)
+        }
+    }
+
+    /// Transforms `direction` by this matrix, treating it as a direction (w = 0), so translation is ignored.
+    /// No perspective divide is done. Note that normals need the inverse transpose instead if the matrix has non-uniform scale.
+    #[inline]
+    pub fn transform_direction(&self, direction: Vector3) -> Vector3 {
+        let d = &self.data;
+        Vector3::new(
+            d[0] * direction.x + d[1] * direction.y + d[2] * direction.z,
+            d[4] * direction.x + d[5] * direction.y + d[6] * direction.z,
+            d[8] * direction.x + d[9] * direction.y + d[10] * direction.z,
+        )
+    }
+
+    /// Transforms all `points` in-place, giving the same results as calling `transform_point` on each of them.
+    ///
+    /// This is a lot faster for big slices, as the matrix is only loaded once
+    /// and the perspective divide is skipped entirely if the matrix is affine.
+    pub fn transform_points(&self, points: &mut [Vector3]) {
+        let [m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, ..] = self.data;
+
+        if self.is_affine() {
+            for p in points.iter_mut() {
+                *p = Vector3::new(
+                    m0 * p.x + m1 * p.y + m2 * p.z + m3,
+                    m4 * p.x + m5 * p.y + m6 * p.z + m7,
+                    m8 * p.x + m9 * p.y + m10 * p.z + m11,
+                );
+            }
+        } else {
+            for p in points.iter_mut() {
+                *p = self.transform_point(*p);
+            }
+        }
+    }
+
+    /// Like `transform_points`, but splits the points over all cores with the `rayon` feature.
+    /// The results are identical to `transform_points`. Without the feature this is `transform_points`.
+    pub fn transform_points_par(&self, points: &mut [Vector3]) {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            // Chunks big enough that the per-task overhead disappears next to the affine fast path.
+            points.par_chunks_mut(4096).for_each(|chunk| self.transform_points(chunk));
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.transform_points(points);
     }
 
+    /// Transforms all points of `src` and writes them into `dst`.
+    /// `dst` is cleared first, so it can be reused between calls without reallocating.
+    #[cfg(feature = "std")]
+    pub fn transform_points_into(&self, src: &[Vector3], dst: &mut Vec<Vector3>) {
+        dst.clear();
+        dst.extend_from_slice(src);
+        self.transform_points(dst);
+    }
+
+    /// Treats this matrix as a view-projection matrix and returns the eight world-space corners of its frustum.
+    /// Returns `None` if the matrix is not invertible.
+    ///
+    /// The corners are ordered near quad first, then far quad,
+    /// each going counter-clockwise as seen from the camera: bottom-left, bottom-right, top-right, top-left.
+    /// This assumes OpenGL-style clip space, where depth ranges from -1 (near) to 1 (far).
+    pub fn frustum_corners(&self) -> Option<[Vector3; 8]> {
+        const NDC: [(f32, f32, f32); 8] = [
+            (-1.0, -1.0, -1.0), (1.0, -1.0, -1.0), (1.0, 1.0, -1.0), (-1.0, 1.0, -1.0),
+            (-1.0, -1.0, 1.0), (1.0, -1.0, 1.0), (1.0, 1.0, 1.0), (-1.0, 1.0, 1.0),
+        ];
+
+        let inverse = self.inverse()?;
+        Some(NDC.map(|(x, y, z)| inverse.transform_point(Vector3::new(x, y, z))))
+    }
+
+    /// Like `frustum_corners`, but only returns a slice of the frustum, e.g. for cascaded shadow maps.
+    ///
+    /// `near` and `far` are fractions of the distance between the near and the far plane,
+    /// so `(0.0, 1.0)` is the whole frustum and `(0.0, 0.25)` is the first quarter of it.
+    pub fn frustum_slice_corners(&self, near: f32, far: f32) -> Option<[Vector3; 8]> {
+        let corners = self.frustum_corners()?;
+        let mut slice = corners;
+        for i in 0..4 {
+            slice[i] = corners[i].lerp(&corners[i + 4], near);
+            slice[i + 4] = corners[i].lerp(&corners[i + 4], far);
+        }
+        Some(slice)
+    }
+
+    /// Rotates this matrix by `angle` in degrees around `axis`, clockwise when looking down the axis towards the origin.
+    /// This is the same as `rotate_degrees(-angle, axis)`.
+    #[deprecated(note = "takes degrees and turns clockwise, use `rotate_degrees` or `rotate_radians` with the negated angle")]
     pub fn rotate(&mut self, angle: f32, axis: Vector3) {
-        let rad = angle * PI / 180.0;
-        let cos = rad.cos();
-        let sin = rad.sin();
-        let one_minus_cos = 1.0 - cos;
+        self.rotate_degrees(-angle, axis);
+    }
+
+    /// Rotates this matrix by `angle_radians` around `axis` like `from_axis_angle`, applied before this matrix,
+    /// so `self * Matrix4x4::from_axis_angle(axis, angle_radians)`. The axis gets normalized.
+    #[inline]
+    pub fn rotate_radians(&mut self, angle_radians: f32, axis: Vector3) {
+        *self *= Matrix4x4::from_axis_angle(axis, angle_radians);
+    }
+
+    /// Like `rotate_radians`, but takes the angle in degrees.
+    #[inline]
+    pub fn rotate_degrees(&mut self, angle_degrees: f32, axis: Vector3) {
+        self.rotate_radians(angle_degrees.to_radians(), axis);
+    }
 
-        let x = axis.x;
-        let y = axis.y;
-        let z = axis.z;
+    /// Formats this matrix as its 16 elements in `data` order as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&self.data)
+    }
 
-        let mut r = Matrix4x4::new();
-        r[0] = cos + x * x * one_minus_cos;
-        r[1] = x * y * one_minus_cos + z * sin;
-        r[2] = x * z * one_minus_cos - y * sin;
-        r[4] = x * y * one_minus_cos - z * sin;
-        r[5] = cos + y * y * one_minus_cos;
-        r[6] = y * z * one_minus_cos + x * sin;
-        r[8] = x * z * one_minus_cos + y * sin;
-        r[9] = y * z * one_minus_cos - x * sin;
-        r[10] = cos + z * z * one_minus_cos;
+    /// Parses a matrix as its 16 elements in `data` order written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let data = parse_exact::<16>(s)?;
+        Ok(Self { data })
+    }
 
-        *self = *self * r;
+    /// Returns the 2D affine transform this matrix applies to the XY plane,
+    /// or `None` if x and y depend on z or the matrix has a perspective row.
+    /// The z row is ignored, since it does not affect the resulting x and y.
+    pub fn to_affine2(&self) -> Option<Affine2> {
+        let d = &self.data;
+        if d[2] != 0.0 || d[6] != 0.0 || d[12] != 0.0 || d[13] != 0.0 || d[14] != 0.0 || d[15] != 1.0 {
+            return None;
+        }
+        Some(Affine2::new(d[0], d[4], d[1], d[5], d[3], d[7]))
     }
 
+    /// Extracts the rotation of the upper-left 3x3 part as a normalized quaternion.
+    ///
+    /// The basis vectors are normalized first, so uniform scale and scale applied before the rotation are removed.
+    /// With shear or scale applied after the rotation the basis is no longer orthogonal and the result is only approximate.
+    /// Mirroring (a negative determinant) cannot be expressed as a rotation and gives a meaningless result.
+    pub fn to_quaternion(&self) -> Quaternion {
+        let d = &self.data;
+        let column = |i: usize| {
+            let v = Vector3::new(d[i], d[4 + i], d[8 + i]);
+            let length_squared = v.magnitude_squared();
+            if length_squared > 0.0 { v * (1.0 / length_squared.sqrt()) } else { v }
+        };
+        let (c0, c1, c2) = (column(0), column(1), column(2));
+        let (m00, m10, m20) = (c0.x, c0.y, c0.z);
+        let (m01, m11, m21) = (c1.x, c1.y, c1.z);
+        let (m02, m12, m22) = (c2.x, c2.y, c2.z);
+
+        // Branch on the largest diagonal term so the square root is always taken of a value of at least 1.
+        let trace = m00 + m11 + m22;
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(0.25 * s, (m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0;
+            Quaternion::new((m21 - m12) / s, 0.25 * s, (m01 + m10) / s, (m02 + m20) / s)
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0;
+            Quaternion::new((m02 - m20) / s, (m01 + m10) / s, 0.25 * s, (m12 + m21) / s)
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0;
+            Quaternion::new((m10 - m01) / s, (m02 + m20) / s, (m12 + m21) / s, 0.25 * s)
+        };
+
+        let length = q.magnitude_squared().sqrt();
+        Quaternion::new(q.w / length, q.x / length, q.y / length, q.z / length)
+    }
+
+    /// Returns this matrix multiplied with itself `n` times, using binary exponentiation.
+    /// `powi(0)` is the identity and negative powers raise the inverse,
+    /// so `None` is only returned for a negative `n` when the matrix is not invertible.
+    pub fn powi(&self, n: i32) -> Option<Matrix4x4> {
+        let mut base = if n < 0 { self.inverse()? } else { *self };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Matrix4x4::identity();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+
+        Some(result)
+    }
+
+    /// Returns an iterator over the first `count` powers of this matrix: identity, M, M², ...
+    /// Useful for placing repeated instances where each one is one step further along, like stairs or railings.
+    pub fn iter_powers(&self, count: usize) -> impl Iterator<Item = Matrix4x4> {
+        let step = *self;
+        core::iter::successors(Some(Matrix4x4::identity()), move |previous| Some(*previous * step)).take(count)
+    }
+
+}
+
+impl Default for Matrix4x4 {
+    fn default() -> Self {
+        Matrix4x4::identity()
+    }
 }
 
 impl Add<Matrix4x4> for Matrix4x4 {
@@ -212,22 +732,30 @@ impl Sub<Matrix4x4> for Matrix4x4 {
     }
 }
 
+/// Uses SSE where available, with the same results as the scalar code.
 impl Mul<Matrix4x4> for Matrix4x4 {
     type Output = Matrix4x4;
 
+    #[inline]
     fn mul(self, other: Matrix4x4) -> Matrix4x4 {
-        let mut result = Matrix4x4::new();
-        for i in (0..16).step_by(4) {
-            let a = self[i];
-            let b = self[i + 1];
-            let c = self[i + 2];
-            let d = self[i + 3];
-            result[i] = a * other[0] + b * other[4] + c * other[8] + d * other[12];
-            result[i + 1] = a * other[1] + b * other[5] + c * other[9] + d * other[13];
-            result[i + 2] = a * other[2] + b * other[6] + c * other[10] + d * other[14];
-            result[i + 3] = a * other[3] + b * other[7] + c * other[11] + d * other[15];
+        mul_matrices(&self, &other)
+    }
+}
+
+/// Uses SSE where available, with the same results as the scalar code.
+impl Mul<Vector4> for Matrix4x4 {
+    type Output = Vector4;
+
+    #[inline]
+    fn mul(self, v: Vector4) -> Vector4 {
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+        {
+            crate::simd::mul_vector4(&self, v)
+        }
+        #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+        {
+            mul_vector4_scalar(&self, v)
         }
-        result
     }
 }
 
@@ -256,6 +784,36 @@ impl Div<f32> for Matrix4x4 {
     }
 }
 
+impl AddAssign for Matrix4x4 {
+    fn add_assign(&mut self, other: Matrix4x4) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Matrix4x4 {
+    fn sub_assign(&mut self, other: Matrix4x4) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<Matrix4x4> for Matrix4x4 {
+    fn mul_assign(&mut self, other: Matrix4x4) {
+        *self = *self * other;
+    }
+}
+
+impl MulAssign<f32> for Matrix4x4 {
+    fn mul_assign(&mut self, other: f32) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<f32> for Matrix4x4 {
+    fn div_assign(&mut self, other: f32) {
+        *self = *self / other;
+    }
+}
+
 impl Index<usize> for Matrix4x4 {
     type Output = f32;
 
@@ -268,4 +826,685 @@ impl IndexMut<usize> for Matrix4x4 {
     fn index_mut(&mut self, i: usize) -> &mut Self::Output {
         &mut self.data[i]
     }
+}
+
+/// Builds a matrix from four arrays of four consecutive elements, the inverse of `to_2d_array`.
+impl From<[[f32; 4]; 4]> for Matrix4x4 {
+    fn from(rows: [[f32; 4]; 4]) -> Self {
+        let mut data = [0.0; 16];
+        for (i, row) in rows.iter().enumerate() {
+            data[i * 4..i * 4 + 4].copy_from_slice(row);
+        }
+        Matrix4x4 { data }
+    }
+}
+
+impl From<[f32; 16]> for Matrix4x4 {
+    fn from(data: [f32; 16]) -> Self {
+        Matrix4x4 { data }
+    }
+}
+
+/// Interpolates every element on its own. The result is only a valid rigid transform if the matrices differ
+/// by translation or scale, interpolate a `Transform` to blend rotations.
+impl Lerp for Matrix4x4 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mut data = self.data;
+        for (a, b) in data.iter_mut().zip(other.data.iter()) {
+            *a = a.lerp(b, t);
+        }
+        Matrix4x4 { data }
+    }
+}
+
+/// Prints the matrix as its rows, i.e. `data` in groups of four, so the translation shows up in the last column.
+/// Standard flags are forwarded to the elements, e.g. `{:.3?}` or `{:#?}`.
+impl Debug for Matrix4x4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Matrix4x4").field("rows", &self.to_2d_array()).finish()
+    }
+}
+
+/// Prints a `Matrix4x4 (row-major)` header, then four lines, one per row (`data` in groups of four), with the columns aligned.
+/// The translation shows up in the last column. A precision flag like `{:.3}` is applied to every element.
+impl Display for Matrix4x4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = f.precision();
+        // Every element is formatted twice, once into the counter to find the widest one, then padded to it.
+        let mut width = 0;
+        for e in &self.data {
+            let mut counter = CharCounter(0);
+            match precision {
+                Some(precision) => write!(counter, "{:.*}", precision, e)?,
+                None => write!(counter, "{}", e)?,
+            }
+            width = width.max(counter.0);
+        }
+
+        write!(f, "Matrix4x4 (row-major)")?;
+        for row in self.data.chunks_exact(4) {
+            write!(f, "\n[")?;
+            for (j, e) in row.iter().enumerate() {
+                let separator = if j > 0 { " " } else { "" };
+                match precision {
+                    Some(precision) => write!(f, "{}{:>w$.*}", separator, precision, e, w = width)?,
+                    None => write!(f, "{}{:>w$}", separator, e, w = width)?,
+                }
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts the characters written to it, used to align the columns of `Matrix4x4`'s `Display` without allocating.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// The result of `Matrix4x4::compare`, describing how far a matrix is off from another one.
+/// `Display` summarizes it in one line, treating differences that would print as zero as equal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MatrixDiff {
+    /// The largest absolute difference of two corresponding elements.
+    pub max_element_difference: f32,
+    /// The translation of the other matrix minus the translation of this one.
+    pub translation_delta: Vector3,
+    /// The angle of the rotation between the two rotations in degrees, in [0, 180].
+    pub rotation_degrees: f32,
+    /// The scale of the other matrix divided by the scale of this one per axis, one where they match.
+    pub scale_ratio: Vector3,
+}
+
+impl MatrixDiff {
+
+    /// The translation distance below which `Display` reports the translations as equal,
+    /// half of the last of the 3 decimals it prints.
+    pub const TRANSLATION_EPSILON: f32 = 5e-4;
+
+    /// The rotation angle in degrees below which `Display` reports the rotations as equal,
+    /// half of the last of the 2 decimals it prints.
+    pub const ROTATION_EPSILON_DEGREES: f32 = 0.005;
+
+    /// The difference of a scale ratio from one below which `Display` reports the scales as equal,
+    /// half of the last of the 3 decimals it prints.
+    pub const SCALE_EPSILON: f32 = 5e-4;
+
+    /// Returns true if the translation, the rotation and the scale are all equal within their tolerances,
+    /// so `Display` reports all of them as equal.
+    pub fn is_equal(&self) -> bool {
+        self.translation_equal() && self.rotation_equal() && self.scale_equal()
+    }
+
+    /// Returns true if the translations match within `MatrixDiff::TRANSLATION_EPSILON`.
+    #[inline]
+    fn translation_equal(&self) -> bool {
+        self.translation_delta.magnitude_squared().sqrt() < Self::TRANSLATION_EPSILON
+    }
+
+    /// Returns true if the rotations match within `MatrixDiff::ROTATION_EPSILON_DEGREES`.
+    #[inline]
+    fn rotation_equal(&self) -> bool {
+        self.rotation_degrees < Self::ROTATION_EPSILON_DEGREES
+    }
+
+    /// Returns true if the scales match within `MatrixDiff::SCALE_EPSILON` on every axis.
+    #[inline]
+    fn scale_equal(&self) -> bool {
+        let r = self.scale_ratio;
+        (r.x - 1.0).abs() < Self::SCALE_EPSILON && (r.y - 1.0).abs() < Self::SCALE_EPSILON && (r.z - 1.0).abs() < Self::SCALE_EPSILON
+    }
+
+}
+
+/// Prints a summary like `translation off by 0.020, rotation off by 1.30°, scale equal`.
+impl Display for MatrixDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.translation_equal() {
+            write!(f, "translation equal, ")?;
+        } else {
+            write!(f, "translation off by {:.3}, ", self.translation_delta.magnitude_squared().sqrt())?;
+        }
+        if self.rotation_equal() {
+            write!(f, "rotation equal, ")?;
+        } else {
+            write!(f, "rotation off by {:.2}°, ", self.rotation_degrees)?;
+        }
+        if self.scale_equal() {
+            write!(f, "scale equal")
+        } else {
+            let r = self.scale_ratio;
+            write!(f, "scale off by a factor of ({:.3}, {:.3}, {:.3})", r.x, r.y, r.z)
+        }
+    }
+}
+
+/// Validates the perspective parameters and returns the focal length `1 / tan(fov_y / 2)`.
+fn perspective_focal_length(fov_y: f32, aspect: f32, near: f32, far: f32) -> Option<f32> {
+    let valid = fov_y > 0.0 && fov_y < PI
+        && aspect > 0.0 && aspect.is_finite()
+        && near > 0.0 && near.is_finite()
+        && !far.is_nan() && far != near;
+    valid.then(|| 1.0 / (fov_y * 0.5).tan())
+}
+
+/// Returns true if the orthographic bounds are finite and span a non-empty volume on every axis.
+fn orthographic_bounds_valid([left, right, bottom, top, near, far]: [f32; 6]) -> bool {
+    [left, right, bottom, top, near, far].iter().all(|v| v.is_finite())
+        && left != right && bottom != top && near != far
+}
+
+/// Returns the normalized forward, right and up axes of a right-handed camera at `eye` looking at `target`.
+/// Falls back to -Z as forward if `eye == target`, and to the world axis least aligned with forward if it is parallel to `up`.
+fn look_at_basis(eye: Vector3, target: Vector3, up: Vector3) -> (Vector3, Vector3, Vector3) {
+    let direction = target - eye;
+    let length_squared = direction.magnitude_squared();
+    let forward = if length_squared > f32::EPSILON * f32::EPSILON {
+        direction * (1.0 / length_squared.sqrt())
+    } else {
+        Vector3::new(0.0, 0.0, -1.0)
+    };
+
+    let mut right = forward.cross(&up);
+    if right.magnitude_squared() <= 1e-12 * up.magnitude_squared().max(1.0) {
+        let fallback = if forward.x.abs() <= forward.y.abs() && forward.x.abs() <= forward.z.abs() {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else if forward.y.abs() <= forward.z.abs() {
+            Vector3::new(0.0, 1.0, 0.0)
+        } else {
+            Vector3::new(0.0, 0.0, 1.0)
+        };
+        right = forward.cross(&fallback);
+    }
+    let right = right * (1.0 / right.magnitude_squared().sqrt());
+    (forward, right, right.cross(&forward))
+}
+
+/// Returns `a * b`, with SSE where available.
+#[inline]
+fn mul_matrices(a: &Matrix4x4, b: &Matrix4x4) -> Matrix4x4 {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+    {
+        crate::simd::mul(a, b)
+    }
+    #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+    {
+        mul_scalar(a, b)
+    }
+}
+
+// The scalar fallbacks for targets without SSE. They stay compiled everywhere, so they can't rot unnoticed,
+// and `simd` mirrors their exact order of operations.
+
+/// Returns `a * b`. Row i of the product sums the rows of `b` weighted by row i of `a` from left to right.
+#[cfg_attr(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"), allow(dead_code))]
+pub(crate) fn mul_scalar(a: &Matrix4x4, b: &Matrix4x4) -> Matrix4x4 {
+    let (a, o) = (&a.data, &b.data);
+    let mut data = [0.0; 16];
+    for (row, out) in a.chunks_exact(4).zip(data.chunks_exact_mut(4)) {
+        for (column, out) in out.iter_mut().enumerate() {
+            *out = row[0] * o[column] + row[1] * o[column + 4] + row[2] * o[column + 8] + row[3] * o[column + 12];
+        }
+    }
+    Matrix4x4 { data }
+}
+
+/// Returns `m * v`, every component is a row of `m` times `v` summed from left to right.
+#[cfg_attr(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"), allow(dead_code))]
+pub(crate) fn mul_vector4_scalar(m: &Matrix4x4, v: Vector4) -> Vector4 {
+    let d = &m.data;
+    Vector4::new(
+        d[0] * v.x + d[1] * v.y + d[2] * v.z + d[3] * v.w,
+        d[4] * v.x + d[5] * v.y + d[6] * v.z + d[7] * v.w,
+        d[8] * v.x + d[9] * v.y + d[10] * v.z + d[11] * v.w,
+        d[12] * v.x + d[13] * v.y + d[14] * v.z + d[15] * v.w,
+    )
+}
+
+/// Returns the inverse of `matrix` from the 2x2 determinants of its top and bottom two rows, or `None` if its determinant is 0.
+#[cfg_attr(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"), allow(dead_code))]
+pub(crate) fn inverse_scalar(matrix: &Matrix4x4) -> Option<Matrix4x4> {
+    let mut result = Matrix4x4::new();
+
+    let a = matrix[0];
+    let b = matrix[1];
+    let c = matrix[2];
+    let d = matrix[3];
+    let e = matrix[4];
+    let f = matrix[5];
+    let g = matrix[6];
+    let h = matrix[7];
+    let i = matrix[8];
+    let j = matrix[9];
+    let k = matrix[10];
+    let l = matrix[11];
+    let m = matrix[12];
+    let n = matrix[13];
+    let o = matrix[14];
+    let p = matrix[15];
+
+    let q = a * f - b * e;
+    let r = a * g - c * e;
+    let s = a * h - d * e;
+    let t = b * g - c * f;
+    let u = b * h - d * f;
+    let v = c * h - d * g;
+    let w = i * n - j * m;
+    let x = i * o - k * m;
+    let y = i * p - l * m;
+    let z = j * o - k * n;
+    let aa = j * p - l * n;
+    let bb = k * p - l * o;
+
+    let det = q * bb - r * aa + s * z + t * y - u * x + v * w;
+
+    if det == 0.0 {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+
+    result[0] = (f * bb - g * aa + h * z) * inv_det;
+    result[1] = (-b * bb + c * aa - d * z) * inv_det;
+    result[2] = (n * v - o * u + p * t) * inv_det;
+    result[3] = (-j * v + k * u - l * t) * inv_det;
+    result[4] = (-e * bb + g * y - h * x) * inv_det;
+    result[5] = (a * bb - c * y + d * x) * inv_det;
+    result[6] = (-m * v + o * s - p * r) * inv_det;
+    result[7] = (i * v - k * s + l * r) * inv_det;
+    result[8] = (e * aa - f * y + h * w) * inv_det;
+    result[9] = (-a * aa + b * y - d * w) * inv_det;
+    result[10] = (m * u - n * s + p * q) * inv_det;
+    result[11] = (-i * u + j * s - l * q) * inv_det;
+    result[12] = (-e * z + f * x - g * w) * inv_det;
+    result[13] = (a * z - b * x + c * w) * inv_det;
+    result[14] = (-m * t + n * r - o * q) * inv_det;
+    result[15] = (i * t - j * r + k * q) * inv_det;
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn compare_identical_matrices_reports_zeros() {
+        let m = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotation_y(0.7) * Matrix4x4::scale(2.0, 2.0, 2.0);
+        let diff = m.compare(&m);
+        assert_eq!(diff.max_element_difference, 0.0);
+        assert_eq!(diff.rotation_degrees, 0.0);
+        assert!(diff.is_equal());
+        assert_eq!(diff.to_string(), "translation equal, rotation equal, scale equal");
+    }
+
+    #[test]
+    fn compare_reports_a_5_degree_rotation() {
+        let a = Matrix4x4::translate(4.0, 0.0, 0.0) * Matrix4x4::rotation_z(0.3);
+        let b = Matrix4x4::translate(4.0, 0.0, 0.0) * Matrix4x4::rotation_z(0.3 + 5f32.to_radians());
+        let diff = a.compare(&b);
+        assert!((diff.rotation_degrees - 5.0).abs() < 1e-3, "{:?}", diff);
+        assert!(diff.translation_delta.magnitude() < 1e-5);
+        assert!(diff.scale_ratio.approx_eq(&Vector3::new(1.0, 1.0, 1.0), 1e-5));
+        assert!(!diff.is_equal());
+        assert_eq!(diff.to_string(), "translation equal, rotation off by 5.00°, scale equal");
+    }
+
+    #[test]
+    fn compare_treats_differences_that_print_as_zero_as_equal() {
+        let a = Matrix4x4::translate(100.0, 0.0, 0.0);
+        let noise = a.compare(&Matrix4x4::translate(100.0001, 0.0, 0.0));
+        assert!(noise.is_equal(), "{:?}", noise);
+        let off = a.compare(&Matrix4x4::translate(100.02, 0.0, 0.0));
+        assert_eq!(off.to_string(), "translation off by 0.020, rotation equal, scale equal");
+        let scaled = Matrix4x4::scale(1.0, 1.0, 1.0).compare(&Matrix4x4::scale(1.0, 1.5, 1.0));
+        assert_eq!(scaled.to_string(), "translation equal, rotation equal, scale off by a factor of (1.000, 1.500, 1.000)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_writes_a_flat_row_major_array() {
+        let m = Matrix4x4::translate(1.0, 2.0, 3.0);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, "[1.0,0.0,0.0,1.0,0.0,1.0,0.0,2.0,0.0,0.0,1.0,3.0,0.0,0.0,0.0,1.0]");
+        assert!(serde_json::from_str::<Matrix4x4>(&json).unwrap() == m);
+        assert!(serde_json::from_str::<Matrix4x4>("[1.0,2.0]").is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts_matrices_to_their_floats() {
+        let matrices = [Matrix4x4::translate(1.0, 2.0, 3.0), Matrix4x4::identity()];
+        let floats: &[f32] = bytemuck::cast_slice(&matrices);
+        assert_eq!(floats.len(), 32);
+        assert_eq!(&floats[..16], &matrices[0].data);
+        assert_eq!(bytemuck::cast::<Matrix4x4, [f32; 16]>(matrices[1]), Matrix4x4::identity().data);
+    }
+
+    #[test]
+    fn transform_points_par_matches_the_serial_version() {
+        let affine = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotation_y(0.4);
+        let projective = Matrix4x4::perspective(1.0, 1.5, 0.1, 100.0).unwrap() * affine;
+        for matrix in [affine, projective] {
+            let mut serial: Vec<Vector3> = (0..20_000)
+                .map(|i| Vector3::new((i as f32 * 0.37).sin(), (i as f32 * 0.11).cos(), -(i % 97) as f32))
+                .collect();
+            let mut parallel = serial.clone();
+            matrix.transform_points(&mut serial);
+            matrix.transform_points_par(&mut parallel);
+            let bits = |v: &Vector3| v.to_array().map(f32::to_bits);
+            assert!(serial.iter().zip(&parallel).all(|(a, b)| bits(a) == bits(b)));
+        }
+    }
+
+    #[test]
+    fn display_labels_the_rows() {
+        assert_eq!(Matrix4x4::identity().to_string(), "Matrix4x4 (row-major)\n[1 0 0 0]\n[0 1 0 0]\n[0 0 1 0]\n[0 0 0 1]");
+        assert_eq!(
+            format!("{}", Matrix4x4::translate(1.5, -20.0, 3.0)),
+            "Matrix4x4 (row-major)\n[  1   0   0 1.5]\n[  0   1   0 -20]\n[  0   0   1   3]\n[  0   0   0   1]",
+        );
+        assert_eq!(
+            format!("{:.2}", Matrix4x4::translate(1.5, -20.0, 3.0)),
+            concat!(
+                "Matrix4x4 (row-major)\n",
+                "[  1.00   0.00   0.00   1.50]\n",
+                "[  0.00   1.00   0.00 -20.00]\n",
+                "[  0.00   0.00   1.00   3.00]\n",
+                "[  0.00   0.00   0.00   1.00]",
+            ),
+        );
+    }
+
+    /// Points spread around the origin, some of them behind the camera of a projection.
+    fn scattered_points() -> Vec<Vector3> {
+        (0..500).map(|i| Vector3::new((i as f32 * 0.37).sin() * 5.0, (i as f32 * 0.11).cos() * 3.0, -(i % 41) as f32 + 5.0)).collect()
+    }
+
+    #[test]
+    fn transform_points_matches_transform_point() {
+        let affine = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotation_y(0.4) * Matrix4x4::scale(2.0, 0.5, 1.0);
+        let projective = Matrix4x4::perspective(1.0, 1.5, 0.1, 100.0).unwrap() * affine;
+        assert!(affine.is_affine() && !projective.is_affine());
+        for matrix in [affine, projective] {
+            let mut points = scattered_points();
+            matrix.transform_points(&mut points);
+            for (point, transformed) in scattered_points().into_iter().zip(points) {
+                let expected = matrix.transform_point(point);
+                assert_eq!(transformed.to_array().map(f32::to_bits), expected.to_array().map(f32::to_bits), "{:?}", point);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transform_points_into_matches_transform_point() {
+        let projective = Matrix4x4::perspective(1.0, 1.5, 0.1, 100.0).unwrap() * Matrix4x4::rotation_x(0.3);
+        let src = scattered_points();
+        let mut dst = vec![Vector3::zero(); 3];
+        projective.transform_points_into(&src, &mut dst);
+        assert_eq!(dst.len(), src.len());
+        for (point, transformed) in src.iter().zip(&dst) {
+            assert_eq!(transformed.to_array().map(f32::to_bits), projective.transform_point(*point).to_array().map(f32::to_bits));
+        }
+    }
+
+    #[test]
+    fn array_conversions_are_row_major() {
+        let rows = [[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0], [9.0, 10.0, 11.0, 12.0], [13.0, 14.0, 15.0, 16.0]];
+        let m = Matrix4x4::from(rows);
+        assert_eq!(m.data, core::array::from_fn(|i| i as f32 + 1.0));
+        assert_eq!(m.to_2d_array(), rows);
+        assert_eq!(Matrix4x4::from(m.data).to_2d_array(), rows);
+        assert_eq!(Matrix4x4::translate(1.0, 2.0, 3.0).to_2d_array()[0][3], 1.0);
+    }
+
+    #[test]
+    fn assign_operators_match_the_plain_ones() {
+        let a = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotation_y(0.7);
+        let b = Matrix4x4::scale(2.0, 3.0, 4.0);
+        let mut m = a;
+        m += b;
+        assert_eq!(m.data, (a + b).data);
+        m -= b;
+        assert_eq!(m.data, (a + b - b).data);
+        let mut m = a;
+        m *= b;
+        assert_eq!(m.data, (a * b).data);
+        let mut m = a;
+        m *= 2.0;
+        assert_eq!(m.data, (a * 2.0).data);
+        m /= 2.0;
+        assert_eq!(m.data, a.data);
+    }
+
+    #[test]
+    fn const_constructors() {
+        const IDENTITY: Matrix4x4 = Matrix4x4::new();
+        assert_eq!(IDENTITY, Matrix4x4::identity());
+        assert_eq!(IDENTITY.data, core::array::from_fn(|i| if i % 5 == 0 { 1.0 } else { 0.0 }));
+        // Looking along `Vector3::FORWARD` with `Vector3::UP` up is the identity view.
+        assert_eq!(Matrix4x4::look_at(Vector3::ZERO, Vector3::FORWARD, Vector3::UP), IDENTITY);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn string_exact_round_trips_in_data_order() {
+        let m = Matrix4x4::translate(0.1, -2.0, 3.0) * Matrix4x4::rotation_y(0.7);
+        let s = m.to_string_exact();
+        assert!(s.starts_with(&format!("{:?},", m.data[0])) && s.ends_with(",1.0"), "{}", s);
+        assert_eq!(s.split(',').nth(3), Some("0.1"));
+        assert_eq!(Matrix4x4::from_string_exact(&s).map(|m| m.data.map(f32::to_bits)), Ok(m.data.map(f32::to_bits)));
+        assert!(Matrix4x4::from_string_exact("1,2,3").is_err());
+    }
+
+    #[test]
+    fn frustum_corners_of_a_perspective_view() {
+        // 90 degrees vertically with twice the width, so the half extents at distance d are (2d, d).
+        let projection = Matrix4x4::perspective(core::f32::consts::FRAC_PI_2, 2.0, 1.0, 10.0).unwrap();
+        let corners = projection.frustum_corners().unwrap();
+        let expected = [
+            Vector3::new(-2.0, -1.0, -1.0), Vector3::new(2.0, -1.0, -1.0), Vector3::new(2.0, 1.0, -1.0), Vector3::new(-2.0, 1.0, -1.0),
+            Vector3::new(-20.0, -10.0, -10.0), Vector3::new(20.0, -10.0, -10.0), Vector3::new(20.0, 10.0, -10.0), Vector3::new(-20.0, 10.0, -10.0),
+        ];
+        for (corner, expected) in corners.iter().zip(expected) {
+            assert!(corner.approx_eq(&expected, 1e-3), "{:?} {:?}", corner, expected);
+        }
+
+        // The corners move along with the camera.
+        let view = Matrix4x4::translate(0.0, 0.0, -5.0);
+        let moved = (projection * view).frustum_corners().unwrap();
+        assert!(moved[0].approx_eq(&Vector3::new(-2.0, -1.0, 4.0), 1e-3), "{:?}", moved[0]);
+
+        assert!(Matrix4x4::scale(1.0, 0.0, 1.0).frustum_corners().is_none());
+    }
+
+    #[test]
+    fn frustum_slice_corners_interpolate_between_the_planes() {
+        let projection = Matrix4x4::perspective(core::f32::consts::FRAC_PI_2, 2.0, 1.0, 10.0).unwrap();
+        let corners = projection.frustum_corners().unwrap();
+        let whole = projection.frustum_slice_corners(0.0, 1.0).unwrap();
+        assert_eq!(whole, corners);
+
+        let slice = projection.frustum_slice_corners(0.5, 1.0).unwrap();
+        assert!(slice[0].approx_eq(&Vector3::new(-11.0, -5.5, -5.5), 1e-3), "{:?}", slice[0]);
+        assert_eq!(slice[4..], corners[4..]);
+        let first_quarter = projection.frustum_slice_corners(0.0, 0.25).unwrap();
+        assert_eq!(first_quarter[..4], corners[..4]);
+        assert!(first_quarter[6].approx_eq(&Vector3::new(6.5, 3.25, -3.25), 1e-3), "{:?}", first_quarter[6]);
+    }
+
+    #[test]
+    fn quaternion_round_trip_covers_every_branch() {
+        // Half turns have a trace of -1 and take the branch of their axis, the others take the trace branch.
+        let axes = [Vector3::X, Vector3::Y, Vector3::Z, Vector3::new(1.0, 2.0, -3.0), Vector3::new(-1.0, 1.0, 1.0)];
+        for axis in axes {
+            for angle in [0.0, 0.3, 1.5, 2.5, core::f32::consts::PI, 4.0, 6.0] {
+                let q = Quaternion::from_axis_angle(axis.normalized(), angle);
+                let m = Matrix4x4::from_quaternion(&q);
+                assert!(m.to_quaternion().approx_eq_rotation(&q, 1e-5), "{:?} {}", axis, angle);
+
+                let v = Vector3::new(0.5, -2.0, 3.0);
+                assert!(m.transform_point(v).approx_eq(&q.rotate_vector(v), 1e-4), "{:?} {}", axis, angle);
+            }
+        }
+    }
+
+    #[test]
+    fn quaternion_conversions_ignore_length_and_scale() {
+        let q = Quaternion::from_axis_angle(Vector3::Y, 0.8);
+        let scaled = Quaternion::new(q.w * 3.0, q.x * 3.0, q.y * 3.0, q.z * 3.0);
+        let m = Matrix4x4::from_quaternion(&q);
+        for (a, b) in Matrix4x4::from_quaternion(&scaled).data.iter().zip(m.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+        assert_eq!(Matrix4x4::from_quaternion(&Quaternion::new(0.0, 0.0, 0.0, 0.0)), Matrix4x4::identity());
+
+        // Scale applied before the rotation and translation do not change the extracted rotation.
+        let trs = Matrix4x4::translate(1.0, 2.0, 3.0) * m * Matrix4x4::scale(2.0, 5.0, 0.5);
+        assert!(trs.to_quaternion().approx_eq_rotation(&q, 1e-5), "{:?}", trs.to_quaternion());
+        assert!((trs.to_quaternion().magnitude_squared() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn mul_vector4_and_transform_direction() {
+        let m = Matrix4x4::translate(1.0, 2.0, 3.0) * Matrix4x4::scale(2.0, 3.0, 4.0);
+        // Points (w = 1) are translated, directions (w = 0) are not.
+        assert_eq!(m * Vector4::new(1.0, 1.0, 1.0, 1.0), Vector4::new(3.0, 5.0, 7.0, 1.0));
+        assert_eq!(m * Vector4::new(1.0, 1.0, 1.0, 0.0), Vector4::new(2.0, 3.0, 4.0, 0.0));
+        assert_eq!(m.transform_direction(Vector3::new(1.0, 1.0, 1.0)), Vector3::new(2.0, 3.0, 4.0));
+        assert_eq!(m.transform_point(Vector3::new(1.0, 1.0, 1.0)), Vector3::new(3.0, 5.0, 7.0));
+
+        // No perspective divide: w comes out of the last row.
+        let projection = Matrix4x4::perspective(core::f32::consts::FRAC_PI_2, 1.0, 1.0, 10.0).unwrap();
+        let clip = projection * Vector4::new(0.0, 0.0, -5.0, 1.0);
+        assert_eq!(clip.w, 5.0);
+        assert_eq!((m * m) * Vector4::new(1.0, -1.0, 2.0, 1.0), m * (m * Vector4::new(1.0, -1.0, 2.0, 1.0)));
+    }
+
+    #[test]
+    fn powi_matches_repeated_multiplication() {
+        let step = Matrix4x4::translate(1.0, 0.5, 0.0) * Matrix4x4::scale(2.0, 1.0, 1.0);
+        assert_eq!(step.powi(0), Some(Matrix4x4::identity()));
+        assert_eq!(step.powi(1), Some(step));
+        let mut repeated = Matrix4x4::identity();
+        for n in 0..10 {
+            assert_eq!(step.powi(n).unwrap().data, repeated.data, "{}", n);
+            repeated *= step;
+        }
+
+        // Negative powers raise the inverse.
+        let back = step.powi(-3).unwrap();
+        let product = back * step.powi(3).unwrap();
+        for (a, b) in product.data.iter().zip(Matrix4x4::identity().data) {
+            assert!((a - b).abs() < 1e-5, "{:?}", product);
+        }
+        let singular = Matrix4x4::scale(1.0, 0.0, 1.0);
+        assert_eq!(singular.powi(-1), None);
+        assert_eq!(singular.powi(2), Some(singular * singular));
+    }
+
+    #[test]
+    fn iter_powers_steps_one_power_at_a_time() {
+        let step = Matrix4x4::translate(0.0, 0.25, -1.0);
+        let powers: Vec<Matrix4x4> = step.iter_powers(5).collect();
+        assert_eq!(powers.len(), 5);
+        for (n, power) in powers.iter().enumerate() {
+            assert_eq!(power.data, step.powi(n as i32).unwrap().data, "{}", n);
+            assert_eq!(power.transform_point(Vector3::ZERO), Vector3::new(0.0, 0.25 * n as f32, -(n as f32)));
+        }
+        assert_eq!(step.iter_powers(0).count(), 0);
+    }
+
+    #[test]
+    fn single_axis_rotations_turn_counter_clockwise() {
+        let quarter = core::f32::consts::FRAC_PI_2;
+        // Each quarter turn maps the next axis onto the one after it, like the cross product does.
+        for (axis, from, to) in [(Axis::X, Vector3::Y, Vector3::Z), (Axis::Y, Vector3::Z, Vector3::X), (Axis::Z, Vector3::X, Vector3::Y)] {
+            let m = Matrix4x4::rotation_about_axis(axis, quarter);
+            assert!(m.transform_point(from).approx_eq(&to, 1e-6), "{:?}", axis);
+            assert_eq!(axis.unit_vector().cross(&from), to);
+
+            for angle in [0.4, -2.0, 3.0] {
+                let m = Matrix4x4::rotation_about_axis(axis, angle);
+                let q = Quaternion::from_axis(axis, angle);
+                assert!(q.approx_eq_rotation(&Quaternion::from_axis_angle(axis.unit_vector(), angle), 1e-6), "{:?}", axis);
+                assert!(m.to_quaternion().approx_eq_rotation(&q, 1e-5), "{:?} {}", axis, angle);
+            }
+        }
+        assert_eq!(Matrix4x4::rotation_x(0.3), Matrix4x4::rotation_about_axis(Axis::X, 0.3));
+        assert_eq!(Matrix4x4::rotation_about_axis(Axis::Z, 0.0), Matrix4x4::identity());
+    }
+
+    #[test]
+    fn row_and_column_layouts() {
+        let m = Matrix4x4::translate(1.0, 2.0, 3.0);
+        // Row-major: the translation is the last column, so it comes last in each row but together in the column-major array.
+        assert_eq!(m.to_rows_array(), m.data);
+        assert_eq!(m.to_cols_array()[12..], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(m.row(0), Vector4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(m.col(3), Vector4::new(1.0, 2.0, 3.0, 1.0));
+        assert_eq!(Matrix4x4::from_cols_array(m.to_cols_array()), m);
+        assert_eq!(Matrix4x4::from_rows_array(m.to_rows_array()), m);
+
+        let counting = Matrix4x4::from(core::array::from_fn::<f32, 16, _>(|i| i as f32));
+        for i in 0..4 {
+            assert_eq!(counting.row(i).to_array(), core::array::from_fn(|j| (i * 4 + j) as f32));
+            assert_eq!(counting.col(i).to_array(), core::array::from_fn(|j| (j * 4 + i) as f32));
+        }
+        let bytes = counting.as_bytes_column_major();
+        for (i, element) in counting.to_cols_array().into_iter().enumerate() {
+            assert_eq!(bytes[i * 4..i * 4 + 4], element.to_ne_bytes());
+        }
+        assert_eq!(f32::from_ne_bytes(bytes[4..8].try_into().unwrap()), 4.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Matrix4x4 row out of range: the index is 4 but there are only 4 rows")]
+    fn row_out_of_range_panics() {
+        Matrix4x4::identity().row(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Matrix4x4 column out of range: the index is 5 but there are only 4 columns")]
+    fn col_out_of_range_panics() {
+        Matrix4x4::identity().col(5);
+    }
+
+    #[test]
+    fn radian_rotation_constructors_agree() {
+        let axis = Vector3::new(1.0, -2.0, 0.5);
+        let close = |a: &Matrix4x4, b: &Matrix4x4| a.data.iter().zip(b.data).all(|(a, b)| (a - b).abs() < 1e-5);
+        for angle in [0.0, 0.5, -1.2, 3.0] {
+            let m = Matrix4x4::from_axis_angle(axis, angle);
+            assert!(close(&m, &Matrix4x4::from_quaternion(&Quaternion::from_axis_angle(axis.normalized(), angle))), "{}", angle);
+
+            let mut radians = Matrix4x4::translate(1.0, 2.0, 3.0);
+            radians.rotate_radians(angle, axis);
+            assert!(close(&radians, &(Matrix4x4::translate(1.0, 2.0, 3.0) * m)), "{}", angle);
+            let mut degrees = Matrix4x4::translate(1.0, 2.0, 3.0);
+            degrees.rotate_degrees(angle.to_degrees(), axis);
+            assert!(close(&degrees, &radians), "{}", angle);
+
+            // The deprecated `rotate` takes degrees and turns the other way.
+            let mut deprecated = Matrix4x4::translate(1.0, 2.0, 3.0);
+            #[allow(deprecated)]
+            deprecated.rotate(-angle.to_degrees(), axis);
+            assert!(close(&deprecated, &radians), "{}", angle);
+        }
+        assert!(close(&Matrix4x4::from_axis_angle(Vector3::X, 0.7), &Matrix4x4::rotation_x(0.7)));
+        assert!(close(&Matrix4x4::from_axis_angle(Vector3::Y, 0.7), &Matrix4x4::rotation_y(0.7)));
+        assert!(close(&Matrix4x4::from_axis_angle(Vector3::Z, 0.7), &Matrix4x4::rotation_z(0.7)));
+        assert_eq!(Matrix4x4::from_axis_angle(Vector3::ZERO, 1.0), Matrix4x4::identity());
+
+        let euler = Euler::new(0.3, -0.8, 1.1);
+        assert!(close(&Matrix4x4::from_euler(&euler), &Matrix4x4::from_quaternion(&euler.to_quaternion())));
+    }
+
 }
\ No newline at end of file
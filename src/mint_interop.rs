@@ -0,0 +1,163 @@
+use crate::angles::quaternion::Quaternion;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+use crate::vectors::vector4::Vector4;
+
+impl From<mint::Vector2<f32>> for Vector2 {
+    fn from(v: mint::Vector2<f32>) -> Self {
+        Vector2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector2> for mint::Vector2<f32> {
+    fn from(v: Vector2) -> Self {
+        mint::Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Point2<f32>> for Vector2 {
+    fn from(p: mint::Point2<f32>) -> Self {
+        Vector2::new(p.x, p.y)
+    }
+}
+
+impl From<Vector2> for mint::Point2<f32> {
+    fn from(v: Vector2) -> Self {
+        mint::Point2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Vector3<f32>> for Vector3 {
+    fn from(v: mint::Vector3<f32>) -> Self {
+        Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for mint::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        mint::Vector3 { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+impl From<mint::Point3<f32>> for Vector3 {
+    fn from(p: mint::Point3<f32>) -> Self {
+        Vector3::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<Vector3> for mint::Point3<f32> {
+    fn from(v: Vector3) -> Self {
+        mint::Point3 { x: v.x, y: v.y, z: v.z }
+    }
+}
+
+impl From<mint::Vector4<f32>> for Vector4 {
+    fn from(v: mint::Vector4<f32>) -> Self {
+        Vector4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Vector4> for mint::Vector4<f32> {
+    fn from(v: Vector4) -> Self {
+        mint::Vector4 { x: v.x, y: v.y, z: v.z, w: v.w }
+    }
+}
+
+/// mint stores the vector part in `v` and the scalar part in `s`.
+impl From<mint::Quaternion<f32>> for Quaternion {
+    fn from(q: mint::Quaternion<f32>) -> Self {
+        Quaternion::from_xyzw(q.v.x, q.v.y, q.v.z, q.s)
+    }
+}
+
+impl From<Quaternion> for mint::Quaternion<f32> {
+    fn from(q: Quaternion) -> Self {
+        mint::Quaternion { v: mint::Vector3 { x: q.x, y: q.y, z: q.z }, s: q.w }
+    }
+}
+
+// `data` holds the rows one after another, the translation being `data[3]`, `data[7]` and `data[11]`.
+// So a `RowMatrix4` maps onto it directly and a `ColumnMatrix4` is its transpose.
+
+impl From<mint::RowMatrix4<f32>> for Matrix4x4 {
+    fn from(m: mint::RowMatrix4<f32>) -> Self {
+        let rows: [[f32; 4]; 4] = m.into();
+        Matrix4x4::from(rows)
+    }
+}
+
+impl From<Matrix4x4> for mint::RowMatrix4<f32> {
+    fn from(m: Matrix4x4) -> Self {
+        m.to_2d_array().into()
+    }
+}
+
+impl From<mint::ColumnMatrix4<f32>> for Matrix4x4 {
+    fn from(m: mint::ColumnMatrix4<f32>) -> Self {
+        let columns: [[f32; 4]; 4] = m.into();
+        let mut matrix = Matrix4x4::from(columns);
+        matrix.transpose();
+        matrix
+    }
+}
+
+impl From<Matrix4x4> for mint::ColumnMatrix4<f32> {
+    fn from(mut m: Matrix4x4) -> Self {
+        m.transpose();
+        m.to_2d_array().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn vectors_and_points_round_trip() {
+        let v2 = Vector2::new(1.0, -2.0);
+        assert_eq!(Vector2::from(mint::Vector2::from(v2)), v2);
+        assert_eq!(Vector2::from(mint::Point2::from(v2)), v2);
+        assert_eq!(mint::Vector2::from(v2), mint::Vector2 { x: 1.0, y: -2.0 });
+
+        let v3 = Vector3::new(1.0, -2.0, 3.5);
+        assert_eq!(Vector3::from(mint::Vector3::from(v3)).to_array(), v3.to_array());
+        assert_eq!(Vector3::from(mint::Point3::from(v3)).to_array(), v3.to_array());
+        assert_eq!(mint::Point3::from(v3), mint::Point3 { x: 1.0, y: -2.0, z: 3.5 });
+
+        let v4 = Vector4::new(1.0, -2.0, 3.5, -4.25);
+        let m4 = mint::Vector4::from(v4);
+        assert_eq!(m4, mint::Vector4 { x: 1.0, y: -2.0, z: 3.5, w: -4.25 });
+        let back = Vector4::from(m4);
+        assert_eq!([back.x, back.y, back.z, back.w], [1.0, -2.0, 3.5, -4.25]);
+    }
+
+    #[test]
+    fn quaternions_keep_the_scalar_part_apart() {
+        let q = Quaternion::new(0.5, -0.1, 0.7, 0.3);
+        let m: mint::Quaternion<f32> = q.into();
+        assert_eq!(m.s, 0.5);
+        assert_eq!(m.v, mint::Vector3 { x: -0.1, y: 0.7, z: 0.3 });
+        assert_eq!(Quaternion::from(m).to_wxyz_array(), q.to_wxyz_array());
+    }
+
+    #[test]
+    fn matrices_put_the_translation_where_mint_expects_it() {
+        let m = Matrix4x4::translate(1.0, 2.0, 3.0) * Matrix4x4::rotation_z(0.5) * Matrix4x4::scale(2.0, 3.0, 4.0);
+
+        let rows: mint::RowMatrix4<f32> = m.into();
+        assert_eq!([rows.x.w, rows.y.w, rows.z.w], [1.0, 2.0, 3.0]);
+        assert_eq!([rows.w.x, rows.w.y, rows.w.z, rows.w.w], [0.0, 0.0, 0.0, 1.0]);
+        let array: [[f32; 4]; 4] = rows.into();
+        assert_eq!(array, m.to_2d_array());
+        assert_eq!(Matrix4x4::from(rows).data, m.data);
+
+        let columns: mint::ColumnMatrix4<f32> = m.into();
+        assert_eq!([columns.w.x, columns.w.y, columns.w.z, columns.w.w], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!([columns.x.w, columns.y.w, columns.z.w], [0.0, 0.0, 0.0]);
+        assert_eq!([columns.y.x, columns.y.y], [m.data[1], m.data[5]]);
+        assert_eq!(Matrix4x4::from(columns).data, m.data);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::rendering::mesh::Mesh;
+
+/// Connectivity information of a `Mesh`, needed by algorithms like smoothing, border detection or decimation.
+///
+/// Adjacency is built from the indices only, so vertices that share a position but are separate
+/// in the vertex buffer (e.g. at UV seams) are not connected. Degenerate edges (from a vertex to itself) are ignored.
+#[derive(Clone, Debug)]
+pub struct MeshAdjacency {
+    vertex_neighbors: Vec<Vec<u32>>,
+    triangle_neighbors: Vec<[Option<u32>; 3]>,
+    boundary_edges: Vec<(u32, u32)>,
+    boundary_vertices: Vec<bool>,
+}
+
+impl MeshAdjacency {
+
+    /// Builds the adjacency of `mesh`.
+    /// Returns an error with the offending edge if more than two triangles share an edge,
+    /// since such a mesh has no well-defined neighborhood.
+    ///
+    /// Only edges are checked. A bowtie vertex, where two fans of triangles touch in a single vertex without sharing
+    /// an edge, is accepted: its neighbors are the union of both fans and it is a boundary vertex if either fan is open.
+    pub fn build(mesh: &Mesh) -> Result<MeshAdjacency, NonManifoldError> {
+        let triangle_count = mesh.indices.len() / 3;
+
+        // The triangles using every (undirected) edge, keyed by the sorted vertex pair.
+        let mut edges: HashMap<(u32, u32), Vec<u32>> = HashMap::new();
+        for (t, triangle) in mesh.indices.chunks_exact(3).enumerate() {
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                if a == b {
+                    continue;
+                }
+                let triangles = edges.entry((a.min(b), a.max(b))).or_default();
+                triangles.push(t as u32);
+                if triangles.len() > 2 {
+                    return Err(NonManifoldError { edge: (a.min(b), a.max(b)) });
+                }
+            }
+        }
+
+        let mut vertex_neighbors = vec![Vec::new(); mesh.vertices.len()];
+        for &(a, b) in edges.keys() {
+            vertex_neighbors[a as usize].push(b);
+            vertex_neighbors[b as usize].push(a);
+        }
+        for neighbors in vertex_neighbors.iter_mut() {
+            neighbors.sort_unstable();
+        }
+
+        let mut triangle_neighbors = vec![[None; 3]; triangle_count];
+        let mut boundary_edges = Vec::new();
+        let mut boundary_vertices = vec![false; mesh.vertices.len()];
+        for (t, triangle) in mesh.indices.chunks_exact(3).enumerate() {
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                if a == b {
+                    continue;
+                }
+                let triangles = &edges[&(a.min(b), a.max(b))];
+                triangle_neighbors[t][i] = triangles.iter().copied().find(|&other| other != t as u32);
+                if triangles.len() == 1 {
+                    boundary_edges.push((a, b));
+                    boundary_vertices[a as usize] = true;
+                    boundary_vertices[b as usize] = true;
+                }
+            }
+        }
+
+        Ok(MeshAdjacency {
+            vertex_neighbors,
+            triangle_neighbors,
+            boundary_edges,
+            boundary_vertices,
+        })
+    }
+
+    /// Returns the vertices connected to `vertex` by an edge, in ascending order.
+    pub fn vertex_neighbors(&self, vertex: u32) -> impl Iterator<Item = u32> + '_ {
+        self.vertex_neighbors[vertex as usize].iter().copied()
+    }
+
+    /// Returns the triangles sharing an edge with `triangle`.
+    pub fn triangle_neighbors(&self, triangle: u32) -> impl Iterator<Item = u32> + '_ {
+        self.triangle_neighbors[triangle as usize].iter().flatten().copied()
+    }
+
+    /// Returns all edges used by only one triangle, directed as in that triangle,
+    /// so following them walks along the border loops of the mesh.
+    #[inline]
+    pub fn boundary_edges(&self) -> &[(u32, u32)] {
+        &self.boundary_edges
+    }
+
+    /// Returns true if `vertex` lies on a boundary edge.
+    #[inline]
+    pub fn is_boundary_vertex(&self, vertex: u32) -> bool {
+        self.boundary_vertices[vertex as usize]
+    }
+
+    /// Returns true if the mesh has no boundary edges, i.e. it is watertight.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.boundary_edges.is_empty()
+    }
+
+}
+
+/// Returned by `MeshAdjacency::build` if more than two triangles share an edge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NonManifoldError {
+    /// The vertex indices of the offending edge, smaller index first.
+    pub edge: (u32, u32),
+}
+
+impl Display for NonManifoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "edge ({}, {}) is shared by more than two triangles", self.edge.0, self.edge.1)
+    }
+}
+
+impl Error for NonManifoldError {}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::rendering::color::UniColor;
+    use crate::rendering::vertex::Vertex;
+    use crate::vectors::vector2::Vector2;
+    use crate::vectors::vector3::Vector3;
+    use super::*;
+
+    fn mesh(vertex_count: u32, indices: Vec<u32>) -> Mesh {
+        let vertices = (0..vertex_count)
+            .map(|i| Vertex::new(Vector3::new(i as f32, 0.0, 0.0), Vector3::UP, Vector2::new(0.0, 0.0), UniColor::WHITE))
+            .collect();
+        Mesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn edges_shared_by_three_triangles_are_rejected() {
+        // Three triangles hinged on the edge (0, 1), like pages of a book.
+        let book = mesh(5, vec![0, 1, 2, 1, 0, 3, 0, 1, 4]);
+        let error = MeshAdjacency::build(&book).unwrap_err();
+        assert_eq!(error, NonManifoldError { edge: (0, 1) });
+        assert_eq!(error.to_string(), "edge (0, 1) is shared by more than two triangles");
+
+        // Two of them are fine.
+        let adjacency = MeshAdjacency::build(&mesh(4, vec![0, 1, 2, 1, 0, 3])).unwrap();
+        assert_eq!(adjacency.triangle_neighbors(0).collect::<Vec<_>>(), [1]);
+        assert_eq!(adjacency.vertex_neighbors(0).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(adjacency.boundary_edges(), [(1, 2), (2, 0), (0, 3), (3, 1)]);
+        assert!(!adjacency.is_closed());
+    }
+
+    #[test]
+    fn bowtie_vertices_are_accepted() {
+        // Two triangles touching only in vertex 0.
+        let bowtie = mesh(5, vec![0, 1, 2, 0, 3, 4]);
+        let adjacency = MeshAdjacency::build(&bowtie).unwrap();
+        assert_eq!(adjacency.vertex_neighbors(0).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(adjacency.triangle_neighbors(0).count(), 0);
+        assert!(adjacency.is_boundary_vertex(0));
+        assert_eq!(adjacency.boundary_edges().len(), 6);
+
+        // A closed tetrahedron has no boundary at all.
+        let tetrahedron = mesh(4, vec![0, 1, 2, 0, 3, 1, 1, 3, 2, 2, 3, 0]);
+        let adjacency = MeshAdjacency::build(&tetrahedron).unwrap();
+        assert!(adjacency.is_closed() && (0..4).all(|v| !adjacency.is_boundary_vertex(v)));
+        assert!((0..4).all(|t| adjacency.triangle_neighbors(t).count() == 3));
+    }
+
+}
\ No newline at end of file
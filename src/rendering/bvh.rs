@@ -0,0 +1,233 @@
+use crate::geometry::aabb::Aabb;
+use crate::geometry::ray::{slab_interval, Ray};
+use crate::rendering::mesh::{Mesh, MeshHit};
+use crate::vectors::vector3::Vector3;
+
+/// The maximum number of triangles in a leaf node.
+const MAX_LEAF_TRIANGLES: usize = 4;
+
+/// A bounding volume hierarchy over the triangles of a `Mesh`, making raycasts logarithmic instead of linear
+/// in the triangle count. Gives exactly the same results as [`Mesh::raycast`].
+///
+/// The tree is built by splitting the triangles at the median of their centroids along the longest axis.
+/// It borrows the mesh, so it has to be rebuilt when the mesh changes.
+#[derive(Clone, Debug)]
+pub struct MeshBvh<'a> {
+    mesh: &'a Mesh,
+    nodes: Vec<BvhNode>,
+    triangles: Vec<u32>,
+}
+
+/// A node of the tree. Leaves hold `count` triangles starting at `first` in the triangle list,
+/// inner nodes have a `count` of 0 and their two children at `first` and `first + 1`.
+#[derive(Copy, Clone, Debug)]
+struct BvhNode {
+    bounds: Aabb,
+    first: u32,
+    count: u32,
+}
+
+impl<'a> MeshBvh<'a> {
+
+    /// Builds the hierarchy over the triangles of `mesh`.
+    pub fn build(mesh: &'a Mesh) -> Self {
+        let triangle_count = mesh.indices.len() / 3;
+        let mut bounds = Vec::with_capacity(triangle_count);
+        let mut centroids = Vec::with_capacity(triangle_count);
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize].position);
+            bounds.push(Aabb::new(a.min(&b).min(&c), a.max(&b).max(&c)));
+            centroids.push((a + b + c) * (1.0 / 3.0));
+        }
+
+        let mut bvh = Self {
+            mesh,
+            nodes: Vec::with_capacity(triangle_count.max(1) * 2 / MAX_LEAF_TRIANGLES + 1),
+            triangles: (0..triangle_count as u32).collect(),
+        };
+        if triangle_count > 0 {
+            bvh.nodes.push(BvhNode { bounds: bounds[0], first: 0, count: triangle_count as u32 });
+            bvh.split(0, &bounds, &centroids);
+        }
+        bvh
+    }
+
+    /// Returns the mesh this hierarchy was built for.
+    #[inline]
+    pub fn mesh(&self) -> &'a Mesh {
+        self.mesh
+    }
+
+    /// Returns the closest hit of `ray` with the mesh, exactly like [`Mesh::raycast`] but a lot faster for big meshes.
+    pub fn raycast(&self, ray: &Ray) -> Option<MeshHit> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let inv_direction = Vector3::new(1.0 / ray.direction.x, 1.0 / ray.direction.y, 1.0 / ray.direction.z);
+        let mut best: Option<MeshHit> = None;
+        let mut stack = vec![0u32];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+            let limit = best.map_or(f32::INFINITY, |hit| hit.t);
+            // Nodes starting exactly at the best distance may still hold a tie with a smaller triangle index.
+            if slab_entry(&node.bounds, ray.origin, inv_direction).is_none_or(|entry| entry > limit) {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &triangle in &self.triangles[node.first as usize..(node.first + node.count) as usize] {
+                    if let Some(hit) = self.mesh.raycast_triangle(ray, triangle as usize) {
+                        if best.is_none_or(|best| hit.is_closer_than(&best)) {
+                            best = Some(hit);
+                        }
+                    }
+                }
+            } else {
+                // Visit the nearer child first, so the farther one can often be skipped.
+                let (left, right) = (node.first, node.first + 1);
+                let left_entry = slab_entry(&self.nodes[left as usize].bounds, ray.origin, inv_direction);
+                let right_entry = slab_entry(&self.nodes[right as usize].bounds, ray.origin, inv_direction);
+                if left_entry.unwrap_or(f32::INFINITY) <= right_entry.unwrap_or(f32::INFINITY) {
+                    stack.extend([right, left]);
+                } else {
+                    stack.extend([left, right]);
+                }
+            }
+        }
+        best
+    }
+
+    /// Fits the bounds of the node at `index` and splits it recursively until the leaves are small enough.
+    fn split(&mut self, index: usize, bounds: &[Aabb], centroids: &[Vector3]) {
+        let BvhNode { first, count, .. } = self.nodes[index];
+        let range = first as usize..(first + count) as usize;
+
+        let mut node_bounds = bounds[self.triangles[range.start] as usize];
+        let (mut centroid_min, mut centroid_max) = (centroids[self.triangles[range.start] as usize], centroids[self.triangles[range.start] as usize]);
+        for &triangle in &self.triangles[range.clone()] {
+            let (b, c) = (bounds[triangle as usize], centroids[triangle as usize]);
+            node_bounds = node_bounds.union(&b);
+            centroid_min = centroid_min.min(&c);
+            centroid_max = centroid_max.max(&c);
+        }
+        self.nodes[index].bounds = node_bounds;
+
+        let extent = centroid_max - centroid_min;
+        if (count as usize) <= MAX_LEAF_TRIANGLES || extent.x.max(extent.y).max(extent.z) <= 0.0 {
+            return;
+        }
+
+        let axis = if extent.x >= extent.y && extent.x >= extent.z { 0 } else if extent.y >= extent.z { 1 } else { 2 };
+        let key = |triangle: &u32| {
+            let c = centroids[*triangle as usize];
+            [c.x, c.y, c.z][axis]
+        };
+        let mid = count as usize / 2;
+        self.triangles[range].select_nth_unstable_by(mid, |a, b| key(a).total_cmp(&key(b)));
+
+        let left = self.nodes.len();
+        let placeholder = node_bounds;
+        self.nodes.push(BvhNode { bounds: placeholder, first, count: mid as u32 });
+        self.nodes.push(BvhNode { bounds: placeholder, first: first + mid as u32, count: count - mid as u32 });
+        self.nodes[index] = BvhNode { bounds: node_bounds, first: left as u32, count: 0 };
+        self.split(left, bounds, centroids);
+        self.split(left + 1, bounds, centroids);
+    }
+
+}
+
+/// Returns the distance at which a ray enters `bounds` (0 if it starts inside), or `None` if it misses.
+#[inline]
+fn slab_entry(bounds: &Aabb, origin: Vector3, inv_direction: Vector3) -> Option<f32> {
+    let (entry, exit) = slab_interval(bounds, origin, inv_direction);
+    let entry = entry.max(0.0);
+    (entry <= exit).then_some(entry)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::rendering::convex_hull::convex_hull;
+    use crate::vectors::vector2::Vector2;
+    use super::*;
+
+    /// A small xorshift generator, so the rays are the same on every run.
+    struct Rng(u32);
+
+    impl Rng {
+
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 >> 8) as f32 / (1 << 24) as f32
+        }
+
+        fn point(&mut self, scale: f32) -> Vector3 {
+            Vector3::new(self.next_f32() - 0.5, self.next_f32() - 0.5, self.next_f32() - 0.5) * scale
+        }
+
+    }
+
+    /// The closest hit as (t, triangle) from testing every triangle, the smaller index winning ties.
+    fn brute_force(mesh: &Mesh, ray: &Ray) -> Option<(f32, usize)> {
+        let mut best: Option<(f32, usize)> = None;
+        for (triangle, corners) in mesh.indices.chunks_exact(3).enumerate() {
+            let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[corners[k] as usize].position);
+            if let Some(t) = ray.intersect_triangle(a, b, c, false) {
+                if best.is_none_or(|(best_t, _)| t < best_t) {
+                    best = Some((t, triangle));
+                }
+            }
+        }
+        best
+    }
+
+    /// Casts every ray against the brute force, `Mesh::raycast` and the BVH and returns the number of hits.
+    fn assert_matches_brute_force(mesh: &Mesh, rays: &[Ray]) -> usize {
+        let bvh = MeshBvh::build(mesh);
+        let mut hits = 0;
+        for ray in rays {
+            let expected = brute_force(mesh, ray);
+            assert_eq!(mesh.raycast(ray).map(|hit| (hit.t, hit.triangle)), expected, "{:?}", ray);
+            assert_eq!(bvh.raycast(ray).map(|hit| (hit.t, hit.triangle)), expected, "{:?}", ray);
+            hits += expected.is_some() as usize;
+        }
+        hits
+    }
+
+    #[test]
+    fn raycast_matches_brute_force_on_a_heightmap() {
+        let mesh = Mesh::heightmap(Vector2::new(10.0, 10.0), (40, 40), |x, z| (x * 1.3).sin() * (z * 0.7).cos());
+        let mut rng = Rng(0x1234_5678);
+        // From all around the box of the mesh towards random points, many of them past the border.
+        let rays: Vec<Ray> = (0..2_000)
+            .map(|_| {
+                let origin = rng.point(30.0);
+                Ray::new(origin, rng.point(14.0) - origin)
+            })
+            .collect();
+        let hits = assert_matches_brute_force(&mesh, &rays);
+        assert!(hits > 200 && hits < rays.len() - 200, "{} hits", hits);
+    }
+
+    #[test]
+    fn raycast_matches_brute_force_inside_and_outside_a_closed_mesh() {
+        let mut rng = Rng(0x9e37_79b9);
+        let points: Vec<Vector3> = (0..300).map(|_| rng.point(4.0)).collect();
+        let hull = convex_hull(&points).unwrap();
+
+        // Rays starting near the center always hit the hull from inside, rays from outside pointing away never do.
+        let inside: Vec<Ray> = (0..500).map(|_| Ray::new(rng.point(0.5), rng.point(1.0))).collect();
+        assert_eq!(assert_matches_brute_force(&hull, &inside), inside.len());
+        let away: Vec<Ray> = (0..500)
+            .map(|_| {
+                let direction = rng.point(1.0);
+                Ray::new(direction * (10.0 / direction.magnitude()), direction)
+            })
+            .collect();
+        assert_eq!(assert_matches_brute_force(&hull, &away), 0);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,294 @@
+use crate::angles::quaternion::Quaternion;
+use crate::geometry::aabb::Aabb;
+use crate::geometry::rect::Rect;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+
+/// The projection a `Camera` uses.
+#[derive(Copy, Clone, Debug)]
+pub enum Projection {
+    /// A perspective projection with the vertical field of view in radians.
+    Perspective { fov_y: f32 },
+    /// An orthographic projection with the visible height in world units.
+    Orthographic { height: f32 },
+}
+
+/// A camera placed in 3D space.
+/// In its local space the camera looks down -Z, with +Y being up (right-handed).
+#[derive(Copy, Clone, Debug)]
+pub struct Camera {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    pub projection: Projection,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Camera {
+
+    /// Creates a new camera from the given values.
+    pub fn new(position: Vector3, rotation: Quaternion, projection: Projection, near: f32, far: f32) -> Self {
+        Self {
+            position,
+            rotation,
+            projection,
+            near,
+            far,
+        }
+    }
+
+    /// Returns the direction the camera is looking at.
+    #[inline]
+    pub fn forward(&self) -> Vector3 {
+        self.rotation.rotate_vector(Vector3::new(0.0, 0.0, -1.0))
+    }
+
+    /// Sets the vertical field of view to `fov` clamped into [`min`, `max`] (all in radians).
+    /// Does nothing for orthographic cameras.
+    pub fn set_fov_clamped(&mut self, fov: f32, min: f32, max: f32) {
+        if let Projection::Perspective { fov_y } = &mut self.projection {
+            *fov_y = fov.clamp(min, max);
+        }
+    }
+
+    /// Zooms an orthographic camera by dividing its visible height by `factor`,
+    /// so a factor above 1 zooms in and a factor below 1 zooms out.
+    /// The resulting height is clamped into [`min_height`, `max_height`]. Does nothing for perspective cameras.
+    pub fn zoom_orthographic(&mut self, factor: f32, min_height: f32, max_height: f32) {
+        if let Projection::Orthographic { height } = &mut self.projection {
+            *height = (*height / factor).clamp(min_height, max_height);
+        }
+    }
+
+    /// Changes the field of view to `new_fov` while moving the camera along its forward axis,
+    /// so that everything at `target_distance` in front of the camera keeps the same size on screen (the "vertigo" effect).
+    ///
+    /// The visible height at distance `d` is `2 * d * tan(fov / 2)`, so keeping it constant means
+    /// moving to `d * tan(fov / 2) / tan(new_fov / 2)` away from the focus point. Does nothing for orthographic cameras.
+    pub fn dolly_zoom(&mut self, target_distance: f32, new_fov: f32) {
+        if let Projection::Perspective { fov_y } = &mut self.projection {
+            let forward = self.rotation.rotate_vector(Vector3::new(0.0, 0.0, -1.0));
+            let focus = self.position + forward * target_distance;
+            let new_distance = target_distance * (*fov_y * 0.5).tan() / (new_fov * 0.5).tan();
+
+            self.position = focus - forward * new_distance;
+            *fov_y = new_fov;
+        }
+    }
+
+}
+
+/// A camera orbiting around a `target` point at a given `distance`, like the viewport camera of most editors.
+#[derive(Copy, Clone, Debug)]
+pub struct OrbitCamera {
+    pub target: Vector3,
+    pub distance: f32,
+    pub rotation: Quaternion,
+    pub projection: Projection,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl OrbitCamera {
+
+    /// Creates a new orbit camera looking at `target` from `distance` units away along +Z.
+    pub fn new(target: Vector3, distance: f32, projection: Projection, near: f32, far: f32) -> Self {
+        Self {
+            target,
+            distance,
+            rotation: Quaternion::identity(),
+            projection,
+            near,
+            far,
+        }
+    }
+
+    /// Orbits around the target, `screen_delta.x` turns around the world Y axis and `screen_delta.y` around the camera's X axis.
+    /// Both are in radians, so pixel deltas should be scaled by some sensitivity first.
+    pub fn rotate(&mut self, screen_delta: Vector2) {
+        let yaw = Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), -screen_delta.x);
+        let pitch = Quaternion::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), -screen_delta.y);
+        let rotation = yaw * self.rotation * pitch;
+
+        // Renormalize so errors don't pile up over many small rotations.
+        let inv_len = 1.0 / rotation.magnitude_squared().sqrt();
+        self.rotation = Quaternion::new(rotation.w * inv_len, rotation.x * inv_len, rotation.y * inv_len, rotation.z * inv_len);
+    }
+
+    /// Moves the target (and with it the camera) along the camera's right and up axes by `delta` world units.
+    pub fn pan(&mut self, delta: Vector2) {
+        let right = self.rotation.rotate_vector(Vector3::new(1.0, 0.0, 0.0));
+        let up = self.rotation.rotate_vector(Vector3::new(0.0, 1.0, 0.0));
+        self.target = self.target + right * delta.x + up * delta.y;
+    }
+
+    /// Moves the camera towards the target by dividing the distance by `factor`,
+    /// so a factor above 1 zooms in and a factor below 1 zooms out.
+    #[inline]
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance /= factor;
+    }
+
+    /// Returns the `Camera` described by the current orbit state.
+    pub fn camera(&self) -> Camera {
+        let back = self.rotation.rotate_vector(Vector3::new(0.0, 0.0, 1.0));
+        Camera::new(self.target + back * self.distance, self.rotation, self.projection, self.near, self.far)
+    }
+
+}
+
+/// Returns the rotation of an arcball dragged from `from` to `to`, both given in pixels with y pointing down.
+///
+/// This is Shoemake's arcball: both points are mapped onto a sphere filling the viewport,
+/// points outside of it are projected onto a hyperbola instead so the rotation stays smooth at the border.
+/// The rotation is in view space and turns by twice the arc between the two mapped points, as in the original arcball.
+pub fn arcball_rotation(from: Vector2, to: Vector2, viewport_size: Vector2) -> Quaternion {
+    let a = arcball_point(from, viewport_size);
+    let b = arcball_point(to, viewport_size);
+    let axis = a.cross(&b);
+    Quaternion::new(a.dot(&b), axis.x, axis.y, axis.z)
+}
+
+/// Projects the eight corners of `aabb` with `view_proj` and returns the 2D rect enclosing them in pixels (y pointing down).
+///
+/// Edges crossing behind the camera are clipped against the camera plane (clip-space w slightly above 0),
+/// so a box straddling the near plane still returns a finite rect, which extends past the viewport as expected.
+/// Returns `None` if the box is completely behind the camera.
+pub fn project_aabb_to_screen(aabb: &Aabb, view_proj: &Matrix4x4, viewport: Vector2) -> Option<Rect> {
+    const MIN_W: f32 = 1e-5;
+    const EDGES: [(usize, usize); 12] = [
+        (0, 1), (2, 3), (4, 5), (6, 7),
+        (0, 2), (1, 3), (4, 6), (5, 7),
+        (0, 4), (1, 5), (2, 6), (3, 7),
+    ];
+
+    let d = &view_proj.data;
+    let clip = |p: Vector3| [
+        d[0] * p.x + d[1] * p.y + d[2] * p.z + d[3],
+        d[4] * p.x + d[5] * p.y + d[6] * p.z + d[7],
+        d[8] * p.x + d[9] * p.y + d[10] * p.z + d[11],
+        d[12] * p.x + d[13] * p.y + d[14] * p.z + d[15],
+    ];
+
+    // Corner i takes max for x if bit 0 is set, for y if bit 1 is set and for z if bit 2 is set.
+    let corners: [[f32; 4]; 8] = core::array::from_fn(|i| clip(Vector3::new(
+        if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+        if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+        if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+    )));
+
+    // At most the 8 corners and a point on each of the 12 edges.
+    let mut points = [[0.0; 4]; 20];
+    let mut count = 0;
+    for corner in corners.iter().filter(|c| c[3] >= MIN_W) {
+        points[count] = *corner;
+        count += 1;
+    }
+    for (a, b) in EDGES {
+        let (a, b) = (corners[a], corners[b]);
+        if (a[3] >= MIN_W) != (b[3] >= MIN_W) {
+            let t = (MIN_W - a[3]) / (b[3] - a[3]);
+            points[count] = core::array::from_fn(|k| a[k] + (b[k] - a[k]) * t);
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+
+    let mut min = Vector2::new(f32::INFINITY, f32::INFINITY);
+    let mut max = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for p in &points[..count] {
+        let x = (p[0] / p[3] * 0.5 + 0.5) * viewport.x;
+        let y = (0.5 - p[1] / p[3] * 0.5) * viewport.y;
+        min = Vector2::new(min.x.min(x), min.y.min(y));
+        max = Vector2::new(max.x.max(x), max.y.max(y));
+    }
+    Some(Rect::from_min_max(min, max))
+}
+
+/// Maps a point in pixels onto the unit arcball sphere (or the hyperbola outside of it).
+fn arcball_point(point: Vector2, viewport_size: Vector2) -> Vector3 {
+    let radius = viewport_size.x.min(viewport_size.y) * 0.5;
+    let x = (point.x - viewport_size.x * 0.5) / radius;
+    let y = (viewport_size.y * 0.5 - point.y) / radius;
+    let d = x * x + y * y;
+
+    let z = if d <= 0.5 {
+        (1.0 - d).sqrt()
+    } else {
+        0.5 / d.sqrt()
+    };
+    let inv_len = 1.0 / (d + z * z).sqrt();
+    Vector3::new(x * inv_len, y * inv_len, z * inv_len)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::f32::consts::FRAC_PI_2;
+    use super::*;
+
+    const VIEWPORT: Vector2 = Vector2::new(200.0, 200.0);
+
+    /// Looks from (0, 0, 5) at the origin with a 90 degree field of view.
+    fn view_proj() -> Matrix4x4 {
+        let view = Matrix4x4::look_at(Vector3::new(0.0, 0.0, 5.0), Vector3::zero(), Vector3::UP);
+        Matrix4x4::perspective(FRAC_PI_2, 1.0, 0.1, 100.0).unwrap() * view
+    }
+
+    #[test]
+    fn arcball_without_drag_is_the_identity() {
+        for point in [Vector2::new(100.0, 100.0), Vector2::new(30.0, 170.0), Vector2::new(-50.0, 400.0)] {
+            let rotation = arcball_rotation(point, point, VIEWPORT);
+            assert!(rotation.approx_eq_rotation(&Quaternion::identity(), 1e-6), "{:?}", rotation);
+        }
+    }
+
+    #[test]
+    fn arcball_drag_across_the_center_turns_around_screen_up() {
+        // Half the radius left of the center to half the radius right of it spans 60 degrees on the sphere,
+        // the arcball turns twice as far.
+        let rotation = arcball_rotation(Vector2::new(50.0, 100.0), Vector2::new(150.0, 100.0), VIEWPORT);
+        let expected = Quaternion::from_axis_angle(Vector3::UP, 120f32.to_radians());
+        assert!(rotation.approx_eq_rotation(&expected, 1e-5), "{:?}", rotation);
+        assert!((rotation.magnitude_squared() - 1.0).abs() < 1e-5);
+
+        // Dragging back undoes it, dragging upwards (y pointing down) turns around -X.
+        let back = arcball_rotation(Vector2::new(150.0, 100.0), Vector2::new(50.0, 100.0), VIEWPORT);
+        assert!((rotation * back).approx_eq_rotation(&Quaternion::identity(), 1e-5));
+        let up = arcball_rotation(Vector2::new(100.0, 150.0), Vector2::new(100.0, 50.0), VIEWPORT);
+        let expected = Quaternion::from_axis_angle(Vector3::new(-1.0, 0.0, 0.0), 120f32.to_radians());
+        assert!(up.approx_eq_rotation(&expected, 1e-5), "{:?}", up);
+    }
+
+    #[test]
+    fn project_aabb_to_screen_encloses_a_visible_box() {
+        // The front face is 4 units away, where the half height of the view is 4, so it covers a quarter of the viewport.
+        let aabb = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let rect = project_aabb_to_screen(&aabb, &view_proj(), VIEWPORT).unwrap();
+        assert!(rect.min().approx_eq(&Vector2::new(75.0, 75.0), 1e-3), "{:?}", rect);
+        assert!(rect.max().approx_eq(&Vector2::new(125.0, 125.0), 1e-3), "{:?}", rect);
+
+        // Y points down on screen.
+        let above = Aabb::new(Vector3::new(-1.0, 1.0, -1.0), Vector3::new(1.0, 2.0, 1.0));
+        let rect = project_aabb_to_screen(&above, &view_proj(), VIEWPORT).unwrap();
+        assert!(rect.max().y < 100.0, "{:?}", rect);
+    }
+
+    #[test]
+    fn project_aabb_to_screen_clips_at_the_camera() {
+        let behind = Aabb::new(Vector3::new(-1.0, -1.0, 6.0), Vector3::new(1.0, 1.0, 8.0));
+        assert!(project_aabb_to_screen(&behind, &view_proj(), VIEWPORT).is_none());
+
+        // Around the camera, so part of the box is behind it.
+        let straddling = Aabb::new(Vector3::new(-1.0, -1.0, 4.0), Vector3::new(1.0, 1.0, 6.0));
+        let rect = project_aabb_to_screen(&straddling, &view_proj(), VIEWPORT).unwrap();
+        assert!(rect.min().x.is_finite() && rect.min().y.is_finite() && rect.max().x.is_finite() && rect.max().y.is_finite(), "{:?}", rect);
+        assert!(rect.min().x < 0.0 && rect.min().y < 0.0 && rect.max().x > VIEWPORT.x && rect.max().y > VIEWPORT.y, "{:?}", rect);
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,1532 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign, Mul, MulAssign};
+use crate::math::Lerp;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+
+/// Tone-mapping operators bringing HDR `ColorF` values into the displayable [0, 1] range.
+pub mod tonemap;
+
+mod named;
+
+/// A struct for to and from conversion of most color formats including hex, rgb, rgba, etc.
+/// The Color Data is actually stored as a RGBA8888 u32.
+#[derive(Copy, Clone, Debug)]
+#[repr(transparent)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct UniColor(u32);
+
+// The bytes are the RGBA8888 value in native endianness, exactly like `as_bytes`.
+const _: () = assert!(core::mem::size_of::<UniColor>() == 4 && core::mem::align_of::<UniColor>() == 4);
+
+impl UniColor {
+
+    /// Opaque white.
+    pub const WHITE: UniColor = UniColor(0xFFFFFFFF);
+    /// Opaque black.
+    pub const BLACK: UniColor = UniColor(0x000000FF);
+    /// Fully transparent black.
+    pub const TRANSPARENT: UniColor = UniColor(0x00000000);
+
+    /// Creates a new UniColor from an RGB tuple.
+    pub const fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        let u32_color = ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | 0xFF;
+        UniColor(u32_color)
+    }
+
+    /// Converts a UniColor to an RGB tuple.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let r = (self.0 >> 24) as u8;
+        let g = (self.0 >> 16) as u8;
+        let b = (self.0 >> 8) as u8;
+        (r, g, b)
+    }
+
+    /// Creates a new UniColor from an RGBA tuple.
+    pub const fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let u32_color = ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | (a as u32);
+        UniColor(u32_color)
+    }
+
+    /// Converts a UniColor to an RGBA tuple.
+    pub fn to_rgba(&self) -> (u8, u8, u8, u8) {
+        let r = (self.0 >> 24) as u8;
+        let g = (self.0 >> 16) as u8;
+        let b = (self.0 >> 8) as u8;
+        let a = (self.0 & 0xFF) as u8;
+        (r, g, b, a)
+    }
+
+    /// Creates a new UniColor from a hex string in the `rgb`, `rgba`, `rrggbb` or `rrggbbaa` form.
+    /// The digits may be prefixed with `#` or `0x` and are case-insensitive, forms without alpha are opaque.
+    /// Returns `None` for other lengths or any character that is not a hex digit.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#')
+            .or_else(|| hex.strip_prefix("0x"))
+            .or_else(|| hex.strip_prefix("0X"))
+            .unwrap_or(hex);
+        let mut buffer = [0u8; 8];
+        let mut len = 0;
+        for c in digits.chars() {
+            // More than 8 digits never make a color.
+            *buffer.get_mut(len)? = c.to_digit(16)? as u8;
+            len += 1;
+        }
+        let nibbles = &buffer[..len];
+
+        let [r, g, b, a] = match nibbles.len() {
+            3 | 4 => {
+                let channel = |i: usize| nibbles.get(i).map_or(0xFF, |&n| n * 17);
+                [channel(0), channel(1), channel(2), channel(3)]
+            }
+            6 | 8 => {
+                let channel = |i: usize| nibbles.get(i * 2).map_or(0xFF, |&n| n << 4 | nibbles[i * 2 + 1]);
+                [channel(0), channel(1), channel(2), channel(3)]
+            }
+            _ => return None,
+        };
+        Some(UniColor::from_rgba(r, g, b, a))
+    }
+
+    /// Parses a color as written in CSS: a color name like `"cornflowerblue"`, `"transparent"`,
+    /// the functional `rgb(r, g, b)` and `rgba(r, g, b, a)` notations or any hex form accepted by `from_hex`.
+    ///
+    /// Names and function names are case-insensitive and surrounding whitespace is ignored.
+    /// The color channels are numbers in [0, 255] or percentages, alpha is a number in [0, 1] or a percentage.
+    /// Out of range values are clamped like in CSS. Returns `None` for anything else.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let arguments = strip_prefix_ignore_case(s, "rgba").or_else(|| strip_prefix_ignore_case(s, "rgb"))
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'));
+        if let Some(arguments) = arguments {
+            let count = arguments.split(',').count();
+            if count != 3 && count != 4 {
+                return None;
+            }
+            let mut parts = arguments.split(',').map(str::trim);
+            let r = parse_css_component(parts.next()?, 255.0)?;
+            let g = parse_css_component(parts.next()?, 255.0)?;
+            let b = parse_css_component(parts.next()?, 255.0)?;
+            let a = match parts.next() {
+                Some(alpha) => parse_css_component(alpha, 1.0)?,
+                None => 255,
+            };
+            return Some(UniColor::from_rgba(r, g, b, a));
+        }
+
+        if s.eq_ignore_ascii_case("transparent") {
+            return Some(UniColor::TRANSPARENT);
+        }
+        named::by_name(s).or_else(|| UniColor::from_hex(s))
+    }
+
+    /// Like `from_hex`, but usable in constants and statics, e.g. `const ACCENT: UniColor = UniColor::from_hex_const(b"#ff8000");`.
+    /// Accepts the `rgb`, `rgba`, `rrggbb` and `rrggbbaa` forms with an optional `#`.
+    ///
+    /// # Panics
+    /// Panics on any other input, which is a compile error when evaluated in a const context.
+    pub const fn from_hex_const(hex: &[u8]) -> Self {
+        let (digits, start) = match hex {
+            [b'#', ..] => (hex.len() - 1, 1),
+            _ => (hex.len(), 0),
+        };
+
+        let mut value = 0u32;
+        let mut i = 0;
+        while i < digits {
+            let nibble = match hex[start + i] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("invalid hex digit in color"),
+            } as u32;
+            value = match digits {
+                // Short forms repeat every digit, so `f` becomes `ff`.
+                3 | 4 => (value << 8) | (nibble * 17),
+                _ => (value << 4) | nibble,
+            };
+            i += 1;
+        }
+
+        match digits {
+            3 | 6 => UniColor(value << 8 | 0xFF),
+            4 | 8 => UniColor(value),
+            _ => panic!("a hex color needs 3, 4, 6 or 8 digits"),
+        }
+    }
+
+    /// Converts a UniColor to a lowercase `#rrggbb` hex string, dropping alpha. `from_hex` parses it back.
+    #[cfg(feature = "std")]
+    pub fn to_hex(&self) -> String {
+        format!("#{:06x}", self.0 >> 8)
+    }
+
+    /// Converts a UniColor to a lowercase `#rrggbbaa` hex string, including alpha.
+    #[cfg(feature = "std")]
+    pub fn to_hex_alpha(&self) -> String {
+        format!("#{:08x}", self.0)
+    }
+
+    /// Like `to_hex`, but writes the ASCII bytes of `#rrggbb` into an array instead of allocating.
+    #[inline]
+    pub const fn to_hex_bytes(&self) -> [u8; 7] {
+        hex_bytes(self.0 >> 8)
+    }
+
+    /// Like `to_hex_alpha`, but writes the ASCII bytes of `#rrggbbaa` into an array instead of allocating.
+    #[inline]
+    pub const fn to_hex_alpha_bytes(&self) -> [u8; 9] {
+        hex_bytes(self.0)
+    }
+
+    /// Creates a new UniColor from a CMYK tuple.
+    pub fn from_cmyk(c: f32, m: f32, y: f32, k: f32) -> Self {
+        let r = (1.0 - c) * (1.0 - k);
+        let g = (1.0 - m) * (1.0 - k);
+        let b = (1.0 - y) * (1.0 - k);
+        Self::from_rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
+    }
+
+    /// Converts a UniColor to a CMYK tuple.
+    pub fn to_cmyk(&self) -> (f32, f32, f32, f32) {
+        let r = (self.0 >> 16) as f32 / 255.0;
+        let g = (self.0 >> 8 & 0xff) as f32 / 255.0;
+        let b = (self.0 & 0xff) as f32 / 255.0;
+
+        let k = 1.0 - r.max(g).max(b);
+        let c = (1.0 - r - k) / (1.0 - k);
+        let m = (1.0 - g - k) / (1.0 - k);
+        let y = (1.0 - b - k) / (1.0 - k);
+
+        (c, m, y, k)
+    }
+
+    /// Converts a UniColor to its HSL representation.
+    /// Hue is returned in degrees in [0, 360), saturation and lightness in [0, 1].
+    /// Grays (including black and white) have a hue and saturation of 0.
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.to_rgb();
+        let r = r as f32 / 255.0;
+        let g = g as f32 / 255.0;
+        let b = b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+        // The denominator reaches 0 for black and white, where chroma is 0 anyway.
+        let denominator = 1.0 - (2.0 * lightness - 1.0).abs();
+        let saturation = if chroma == 0.0 || denominator <= 0.0 {
+            0.0
+        } else {
+            (chroma / denominator).min(1.0)
+        };
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            ((b - r) / chroma) + 2.0
+        } else {
+            ((r - g) / chroma) + 4.0
+        };
+        let hue = hue * 60.0;
+        if hue >= 360.0 {
+            (hue - 360.0, saturation, lightness)
+        } else {
+            (hue, saturation, lightness)
+        }
+    }
+
+    /// Creates a UniColor from its HSL representation.
+    /// The hue (in degrees) is wrapped into [0, 360), saturation and lightness are clamped into [0, 1].
+    /// A NaN or infinite hue is treated as 0, so it gives red tones or, without saturation, a gray.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let h = if h.is_finite() { h.rem_euclid(360.0) } else { 0.0 };
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h / 60.0;
+        let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
+        let (r1, g1, b1) = if h < 1.0 {
+            (c, x, 0.0)
+        } else if h < 2.0 {
+            (x, c, 0.0)
+        } else if h < 3.0 {
+            (0.0, c, x)
+        } else if h < 4.0 {
+            (0.0, x, c)
+        } else if h < 5.0 {
+            (x, 0.0, c)
+        } else {
+            (c, 0.0, x)
+        };
+        let m = l - c / 2.0;
+        let r = ((r1 + m) * 255.0).round().clamp(0.0, 255.0);
+        let g = ((g1 + m) * 255.0).round().clamp(0.0, 255.0);
+        let b = ((b1 + m) * 255.0).round().clamp(0.0, 255.0);
+        UniColor::from_rgb(r as u8, g as u8, b as u8)
+    }
+
+    /// Returns the channels as floats in [0, 1], in r, g, b, a order and still sRGB encoded.
+    pub fn to_f32_array(&self) -> [f32; 4] {
+        let (r, g, b, a) = self.to_rgba();
+        [r, g, b, a].map(|c| c as f32 / 255.0)
+    }
+
+    /// Creates a UniColor from sRGB encoded channels in [0, 1], in r, g, b, a order.
+    /// Values outside the range are clamped, the rest is rounded to the nearest byte.
+    pub fn from_f32_array(rgba: [f32; 4]) -> Self {
+        let [r, g, b, a] = rgba.map(to_channel);
+        UniColor::from_rgba(r, g, b, a)
+    }
+
+    /// Returns the channels decoded from sRGB into linear light, as expected by shaders doing lighting math.
+    /// Uses the exact piecewise sRGB curve, alpha is not encoded and only rescaled to [0, 1].
+    pub fn to_linear(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_f32_array();
+        [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+    }
+
+    /// Creates a UniColor from linear light channels in r, g, b, a order, the inverse of `to_linear`.
+    /// Values outside [0, 1] are clamped.
+    pub fn from_linear(rgba: [f32; 4]) -> Self {
+        let [r, g, b, a] = rgba;
+        let encode = |c: f32| linear_to_srgb(c.clamp(0.0, 1.0));
+        UniColor::from_f32_array([encode(r), encode(g), encode(b), a])
+    }
+
+    pub fn as_bytes(&self) -> [u8; 4] {
+        self.0.to_ne_bytes()
+    }
+
+    /// Computes the midpoint between two colors.
+    pub fn midpoint(&self, other: &Self) -> Self {
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+
+        let r = (r1 as f32 + r2 as f32) / 2.0;
+        let g = (g1 as f32 + g2 as f32) / 2.0;
+        let b = (b1 as f32 + b2 as f32) / 2.0;
+        let a = (a1 as f32 + a2 as f32) / 2.0;
+
+        UniColor::from_rgba(
+            r.round() as u8,
+            g.round() as u8,
+            b.round() as u8,
+            a.round() as u8,
+        )
+    }
+
+    /// Computes a linear interpolation between two colors.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        // A NaN `t` keeps this color.
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+
+        let r = (r1 as f32 * (1.0 - t) + r2 as f32 * t).round() as u8;
+        let g = (g1 as f32 * (1.0 - t) + g2 as f32 * t).round() as u8;
+        let b = (b1 as f32 * (1.0 - t) + b2 as f32 * t).round() as u8;
+        let a = (a1 as f32 * (1.0 - t) + a2 as f32 * t).round() as u8;
+
+        UniColor::from_rgba(r, g, b, a)
+    }
+
+    /// Computes a linear interpolation between two colors in linear light instead of on the gamma-encoded bytes.
+    /// The RGB channels are decoded from sRGB, interpolated and encoded again, alpha is interpolated directly.
+    ///
+    /// This avoids the dark mid-tones `lerp` produces, e.g. 50% between black and white is 188 instead of 128.
+    pub fn lerp_linear(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+
+        let mix = |c1: u8, c2: u8| {
+            let linear = srgb_to_linear(c1 as f32 / 255.0) * (1.0 - t) + srgb_to_linear(c2 as f32 / 255.0) * t;
+            (linear_to_srgb(linear) * 255.0).round() as u8
+        };
+        let a = (a1 as f32 * (1.0 - t) + a2 as f32 * t).round() as u8;
+
+        UniColor::from_rgba(mix(r1, r2), mix(g1, g2), mix(b1, b2), a)
+    }
+
+    /// Computes an interpolation between two colors in HSL space, clamping `t` to [0, 1].
+    /// The hue turns along the shorter way around the color wheel, saturation, lightness and alpha are interpolated linearly.
+    ///
+    /// This keeps blends across hues vivid, e.g. 50% between red and blue is magenta instead of the grayish purple of `lerp`.
+    /// Grays have no hue of their own, so they take the hue of the other color and only fade in saturation.
+    pub fn lerp_hsl(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let (mut h1, s1, l1) = self.to_hsl();
+        let (mut h2, s2, l2) = other.to_hsl();
+        if s1 == 0.0 {
+            h1 = h2;
+        } else if s2 == 0.0 {
+            h2 = h1;
+        }
+
+        let hue_delta = (h2 - h1 + 180.0).rem_euclid(360.0) - 180.0;
+        let (_, _, _, a1) = self.to_rgba();
+        let (_, _, _, a2) = other.to_rgba();
+        let a = (a1 as f32 * (1.0 - t) + a2 as f32 * t).round() as u8;
+
+        UniColor::from_hsl(h1 + hue_delta * t, s1 + (s2 - s1) * t, l1 + (l2 - l1) * t).with_alpha(a)
+    }
+
+    /// Computes the squared distance between two colors in RGBA space.
+    pub fn distance_squared(&self, other: &Self) -> u32 {
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+
+        let dr = r1 as i32 - r2 as i32;
+        let dg = g1 as i32 - g2 as i32;
+        let db = b1 as i32 - b2 as i32;
+        let da = a1 as i32 - a2 as i32;
+
+        (dr * dr + dg * dg + db * db + da * da) as u32
+    }
+
+    /// Returns the perceptual difference to `other` as the CIE76 delta E, the euclidean distance in CIELAB (D65).
+    /// A value around 2.3 is just noticeable, alpha is ignored.
+    pub fn delta_e(&self, other: &Self) -> f32 {
+        let (l1, a1, b1) = to_lab(self);
+        let (l2, a2, b2) = to_lab(other);
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// Simulates how this color looks with the color vision deficiency `kind`, using the full severity matrices
+    /// of Machado et al. (2009) on linear RGB. Grays are left unchanged, alpha is kept.
+    pub fn simulate_cvd(&self, kind: CvdKind) -> Self {
+        let m = kind.matrix();
+        let (r, g, b, a) = self.to_rgba();
+        let linear = [r, g, b].map(|c| srgb_to_linear(c as f32 / 255.0));
+        let encode = |row: [f32; 3]| {
+            let c = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+            (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0).round() as u8
+        };
+        UniColor::from_rgba(encode(m[0]), encode(m[1]), encode(m[2]), a)
+    }
+
+    /// Returns this color with `f` applied to the RGB channels as values in [0, 1].
+    /// The results are clamped to [0, 1] and rounded to the nearest byte, alpha is left untouched.
+    pub fn map_rgb(&self, f: impl Fn(f32) -> f32) -> Self {
+        let (r, g, b, a) = self.to_rgba();
+        UniColor::from_rgba(map_channel(r, &f), map_channel(g, &f), map_channel(b, &f), a)
+    }
+
+    /// Like `map_rgb`, but applies `f` to alpha as well.
+    pub fn map_rgba(&self, f: impl Fn(f32) -> f32) -> Self {
+        let (r, g, b, a) = self.to_rgba();
+        UniColor::from_rgba(map_channel(r, &f), map_channel(g, &f), map_channel(b, &f), map_channel(a, &f))
+    }
+
+    /// Combines the RGB channels of this color and `other` with `f`, as values in [0, 1] like in `map_rgb`.
+    /// The alpha of this color is kept, e.g. `zip_rgb(&other, |a, b| a * b)` is a multiply blend.
+    pub fn zip_rgb(&self, other: &Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        let (r1, g1, b1, a) = self.to_rgba();
+        let (r2, g2, b2, _) = other.to_rgba();
+        let zip = |c1: u8, c2: u8| to_channel(f(c1 as f32 / 255.0, c2 as f32 / 255.0));
+        UniColor::from_rgba(zip(r1, r2), zip(g1, g2), zip(b1, b2), a)
+    }
+
+    /// Applies a levels adjustment to the RGB channels: values at or below `black_point` become black, values at
+    /// or above `white_point` become white and the range between is stretched and raised to the power of `gamma`.
+    /// Points are in [0, 1], gamma above 1 darkens the midtones and below 1 brightens them.
+    /// `levels(0.0, 1.0, 1.0)` leaves the color unchanged. Alpha is left untouched.
+    pub fn levels(&self, black_point: f32, white_point: f32, gamma: f32) -> Self {
+        let range = (white_point - black_point).max(f32::EPSILON);
+        self.map_rgb(|c| ((c - black_point) / range).clamp(0.0, 1.0).powf(gamma))
+    }
+
+    /// Returns this color with `amount` added to its HSL lightness, clamped to [0, 1].
+    /// `lighten(1.0)` is white, a negative amount darkens. Alpha is kept.
+    pub fn lighten(&self, amount: f32) -> Self {
+        self.map_hsl(|h, s, l| (h, s, l + amount))
+    }
+
+    /// Returns this color with `amount` subtracted from its HSL lightness, clamped to [0, 1].
+    /// `darken(1.0)` is black. Alpha is kept.
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Returns this color with `amount` added to its HSL saturation, clamped to [0, 1].
+    /// Grays have no hue, so they stay gray. Alpha is kept.
+    pub fn saturate(&self, amount: f32) -> Self {
+        // A gray has a saturation of 0 and a meaningless hue of 0, saturating it would turn it red.
+        self.map_hsl(|h, s, l| if s == 0.0 { (h, s, l) } else { (h, s + amount, l) })
+    }
+
+    /// Returns this color with `amount` subtracted from its HSL saturation, clamped to [0, 1].
+    /// `desaturate(1.0)` is the gray of the same HSL lightness. Alpha is kept.
+    pub fn desaturate(&self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Returns this color with the alpha replaced by `a`.
+    #[inline]
+    pub const fn with_alpha(&self, a: u8) -> Self {
+        UniColor(self.0 & 0xFFFFFF00 | a as u32)
+    }
+
+    /// Returns this color with the alpha replaced by `a` in [0, 1], clamped and rounded to the nearest byte.
+    #[inline]
+    pub fn with_alpha_f32(&self, a: f32) -> Self {
+        self.with_alpha(to_channel(a))
+    }
+
+    /// Returns the negative of this color, every RGB channel `c` becomes `255 - c`. Alpha is kept.
+    pub fn invert(&self) -> Self {
+        let (r, g, b, a) = self.to_rgba();
+        UniColor::from_rgba(255 - r, 255 - g, 255 - b, a)
+    }
+
+    /// Returns the gray of the same luma, weighting the gamma-encoded channels with the Rec. 709 coefficients
+    /// (0.2126 red, 0.7152 green and 0.0722 blue). Alpha is kept.
+    pub fn grayscale(&self) -> Self {
+        let (r, g, b, a) = self.to_rgba();
+        let luma = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32).round().min(255.0) as u8;
+        UniColor::from_rgba(luma, luma, luma, a)
+    }
+
+    /// Returns the relative luminance as defined by WCAG 2, from 0 for black to 1 for white.
+    /// The channels are decoded into linear light and weighted with the Rec. 709 coefficients, alpha is ignored.
+    pub fn luminance(&self) -> f32 {
+        let [r, g, b, _] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Returns the WCAG 2 contrast ratio between this color and `other`, from 1 for equal luminance to 21 for black on white.
+    /// The order doesn't matter. WCAG AA asks for at least 4.5 for normal text and 3 for large text.
+    pub fn contrast_ratio(&self, other: &Self) -> f32 {
+        let l1 = self.luminance() as f64;
+        let l2 = other.luminance() as f64;
+        // Divided in f64, so black on white comes out as exactly 21 instead of 20.999998.
+        ((l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)) as f32
+    }
+
+    /// Applies `f` to the hue, saturation and lightness as returned by `to_hsl` and converts back with `from_hsl`.
+    /// Alpha is kept.
+    fn map_hsl(&self, f: impl Fn(f32, f32, f32) -> (f32, f32, f32)) -> Self {
+        let (h, s, l) = self.to_hsl();
+        let (h, s, l) = f(h, s, l);
+        let (_, _, _, a) = self.to_rgba();
+        UniColor::from_hsl(h, s, l).with_alpha(a)
+    }
+
+}
+
+/// Multiplies two colors per channel in [0, 1] space, alpha included, e.g. to tint a texture color.
+/// White is the identity and black absorbs everything.
+impl Mul for UniColor {
+    type Output = UniColor;
+
+    fn mul(self, other: UniColor) -> UniColor {
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+        UniColor::from_rgba(multiply_channel(r1, r2), multiply_channel(g1, g2), multiply_channel(b1, b2), multiply_channel(a1, a2))
+    }
+}
+
+/// Scales the RGB channels by `scalar`, rounding and clamping to [0, 255]. Alpha is left untouched.
+impl Mul<f32> for UniColor {
+    type Output = UniColor;
+
+    fn mul(self, scalar: f32) -> UniColor {
+        let (r, g, b, a) = self.to_rgba();
+        let scale = |c: u8| (c as f32 * scalar).round().clamp(0.0, 255.0) as u8;
+        UniColor::from_rgba(scale(r), scale(g), scale(b), a)
+    }
+}
+
+/// Adds two colors per channel, alpha included, saturating at 255.
+impl Add for UniColor {
+    type Output = UniColor;
+
+    fn add(self, other: UniColor) -> UniColor {
+        let (r1, g1, b1, a1) = self.to_rgba();
+        let (r2, g2, b2, a2) = other.to_rgba();
+        UniColor::from_rgba(r1.saturating_add(r2), g1.saturating_add(g2), b1.saturating_add(b2), a1.saturating_add(a2))
+    }
+}
+
+impl MulAssign for UniColor {
+    fn mul_assign(&mut self, other: UniColor) {
+        *self = *self * other;
+    }
+}
+
+impl MulAssign<f32> for UniColor {
+    fn mul_assign(&mut self, scalar: f32) {
+        *self = *self * scalar;
+    }
+}
+
+impl AddAssign for UniColor {
+    fn add_assign(&mut self, other: UniColor) {
+        *self = *self + other;
+    }
+}
+
+/// Serializes as a `"#rrggbbaa"` hex string, see `to_hex_alpha`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UniColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex = self.to_hex_alpha_bytes();
+        serializer.serialize_str(core::str::from_utf8(&hex).map_err(serde::ser::Error::custom)?)
+    }
+}
+
+/// Deserializes from any hex string accepted by `from_hex`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UniColor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // A visitor instead of deserializing a `String`, so this works without an allocator.
+        struct HexVisitor;
+
+        impl serde::de::Visitor<'_> for HexVisitor {
+            type Value = UniColor;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a hex color string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<UniColor, E> {
+                UniColor::from_hex(s).ok_or_else(|| E::custom(format_args!("invalid hex color {:?}", s)))
+            }
+        }
+
+        deserializer.deserialize_str(HexVisitor)
+    }
+}
+
+/// Interpolates the gamma-encoded bytes like the inherent `lerp`, clamping `t` to [0, 1].
+impl Lerp for UniColor {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        UniColor::lerp(self, other, t)
+    }
+}
+
+/// A color with linear light float channels, which may exceed 1 for HDR values.
+/// Use it for lighting and blending math, then tone-map (see [`tonemap`]) and convert to a `UniColor` for display.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorF {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl ColorF {
+
+    /// Creates a new ColorF from linear channels.
+    #[inline]
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Decodes a sRGB UniColor into linear light, alpha is only rescaled to [0, 1].
+    pub fn from_unicolor(color: UniColor) -> Self {
+        let (r, g, b, a) = color.to_rgba();
+        Self::new(
+            srgb_to_linear(r as f32 / 255.0),
+            srgb_to_linear(g as f32 / 255.0),
+            srgb_to_linear(b as f32 / 255.0),
+            a as f32 / 255.0,
+        )
+    }
+
+    /// Encodes this color as a sRGB UniColor, clamping every channel into [0, 1] first.
+    /// Tone-map HDR colors before, otherwise everything above 1 is clipped to white.
+    pub fn to_unicolor(&self) -> UniColor {
+        let encode = |c: f32| (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0).round() as u8;
+        UniColor::from_rgba(encode(self.r), encode(self.g), encode(self.b), (self.a.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Returns this color with `f` applied to the RGB channels, alpha is left untouched.
+    #[inline]
+    pub fn map_rgb(&self, f: impl Fn(f32) -> f32) -> Self {
+        Self::new(f(self.r), f(self.g), f(self.b), self.a)
+    }
+
+}
+
+/// A kind of color vision deficiency, simulated by `UniColor::simulate_cvd`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CvdKind {
+    /// Missing long-wavelength (red) cones.
+    Protanopia,
+    /// Missing medium-wavelength (green) cones, the most common kind.
+    Deuteranopia,
+    /// Missing short-wavelength (blue) cones.
+    Tritanopia,
+}
+
+impl CvdKind {
+
+    /// Returns the linear RGB simulation matrix of Machado et al. (2009) at full severity.
+    fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            CvdKind::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            CvdKind::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            CvdKind::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+
+}
+
+/// Returns all pairs of `colors` (as index pairs `(i, j)` with `i < j`) whose `delta_e` drops below `min_delta_e`
+/// when seen with the color vision deficiency `kind`, i.e. the pairs a palette should not rely on telling apart.
+#[cfg(feature = "std")]
+pub fn palette_distinguishable(colors: &[UniColor], kind: CvdKind, min_delta_e: f32) -> Vec<(usize, usize)> {
+    let simulated: Vec<UniColor> = colors.iter().map(|c| c.simulate_cvd(kind)).collect();
+    let mut pairs = Vec::new();
+    for i in 0..simulated.len() {
+        for j in i + 1..simulated.len() {
+            if simulated[i].delta_e(&simulated[j]) < min_delta_e {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// The memory layout of the pixels in a raw 8-bit image buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// Red, green, blue and alpha, one byte each.
+    Rgba8,
+    /// Blue, green, red and alpha, one byte each. Common for window surfaces.
+    Bgra8,
+    /// Red, green and blue, one byte each, without alpha (treated as fully opaque).
+    Rgb8,
+    /// Like `Rgba8`, but the color channels are already multiplied by alpha.
+    Rgba8Premultiplied,
+}
+
+impl PixelFormat {
+
+    /// Returns the number of bytes a single pixel takes up in this format.
+    #[inline]
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            PixelFormat::Rgb8 => 3,
+            PixelFormat::Rgba8 | PixelFormat::Bgra8 | PixelFormat::Rgba8Premultiplied => 4,
+        }
+    }
+
+    /// Reads a single pixel in this format as straight (not premultiplied) RGBA.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn read(&self, p: &[u8]) -> [u8; 4] {
+        match self {
+            PixelFormat::Rgba8 => [p[0], p[1], p[2], p[3]],
+            PixelFormat::Bgra8 => [p[2], p[1], p[0], p[3]],
+            PixelFormat::Rgb8 => [p[0], p[1], p[2], 0xFF],
+            PixelFormat::Rgba8Premultiplied => {
+                let a = p[3] as u32;
+                let unpremultiply = |c: u8| (c as u32 * 255 + a / 2).checked_div(a).map_or(0, |v| v.min(255) as u8);
+                [unpremultiply(p[0]), unpremultiply(p[1]), unpremultiply(p[2]), p[3]]
+            }
+        }
+    }
+
+    /// Appends a single straight RGBA pixel to `dst` in this format.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn write(&self, rgba: [u8; 4], dst: &mut Vec<u8>) {
+        let [r, g, b, a] = rgba;
+        match self {
+            PixelFormat::Rgba8 => dst.extend_from_slice(&rgba),
+            PixelFormat::Bgra8 => dst.extend_from_slice(&[b, g, r, a]),
+            PixelFormat::Rgb8 => dst.extend_from_slice(&[r, g, b]),
+            PixelFormat::Rgba8Premultiplied => {
+                let premultiply = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+                dst.extend_from_slice(&[premultiply(r), premultiply(g), premultiply(b), a]);
+            }
+        }
+    }
+
+}
+
+/// Returned by `convert_buffer` if the source buffer doesn't hold a whole number of pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BufferLengthError {
+    /// The length of the source buffer in bytes.
+    pub len: usize,
+    /// The number of bytes per pixel of the source format.
+    pub bytes_per_pixel: usize,
+}
+
+impl Display for BufferLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "buffer length {} is not a multiple of {} bytes per pixel", self.len, self.bytes_per_pixel)
+    }
+}
+
+impl Error for BufferLengthError {}
+
+/// Converts a whole buffer of pixels from `src_format` to `dst_format`, e.g. RGBA from an image decoder to BGRA for a window surface.
+/// `dst` is cleared first, so it can be reused between calls without reallocating.
+///
+/// This skips the round trip through `UniColor` for every pixel, the `color` benchmark group compares the two.
+/// Converting to `Rgb8` drops alpha, converting from a premultiplied format rounds the divided channels.
+#[cfg(feature = "std")]
+pub fn convert_buffer(src: &[u8], src_format: PixelFormat, dst: &mut Vec<u8>, dst_format: PixelFormat) -> Result<(), BufferLengthError> {
+    let src_bpp = src_format.bytes_per_pixel();
+    if !src.len().is_multiple_of(src_bpp) {
+        return Err(BufferLengthError { len: src.len(), bytes_per_pixel: src_bpp });
+    }
+
+    dst.clear();
+    if src_format == dst_format {
+        dst.extend_from_slice(src);
+        return Ok(());
+    }
+
+    dst.reserve(src.len() / src_bpp * dst_format.bytes_per_pixel());
+    for pixel in src.chunks_exact(src_bpp) {
+        dst_format.write(src_format.read(pixel), dst);
+    }
+    Ok(())
+}
+
+/// What `dither_buffer` quantizes the colors to.
+#[derive(Copy, Clone, Debug)]
+pub enum DitherTarget<'a> {
+    /// Reduce every color channel to the given number of bits, e.g. 5 for RGB555.
+    Bits(u8),
+    /// Replace every color with the nearest color of the palette.
+    Palette(&'a [UniColor]),
+}
+
+#[cfg(feature = "std")]
+impl DitherTarget<'_> {
+
+    /// Returns the quantized version of the RGB channels (in [0, 255]), alpha is taken from `alpha`.
+    fn quantize(&self, rgb: [f32; 3], alpha: u8) -> UniColor {
+        match self {
+            DitherTarget::Bits(bits) => {
+                let levels = ((1u32 << (*bits).clamp(1, 8)) - 1) as f32;
+                let q = |c: f32| ((c.clamp(0.0, 255.0) / 255.0 * levels).round() / levels * 255.0).round() as u8;
+                UniColor::from_rgba(q(rgb[0]), q(rgb[1]), q(rgb[2]), alpha)
+            }
+            DitherTarget::Palette(palette) => {
+                let nearest = palette.iter()
+                    .min_by(|a, b| rgb_distance_squared(a, rgb).total_cmp(&rgb_distance_squared(b, rgb)))
+                    .copied()
+                    .unwrap_or(UniColor::from_rgba(0, 0, 0, alpha));
+                let (r, g, b) = nearest.to_rgb();
+                UniColor::from_rgba(r, g, b, alpha)
+            }
+        }
+    }
+
+    /// Returns the ordered dithering result for the RGB channels, `threshold` is the Bayer matrix entry in (-0.5, 0.5).
+    fn quantize_ordered(&self, rgb: [u8; 3], alpha: u8, threshold: f32) -> UniColor {
+        match self {
+            DitherTarget::Bits(bits) => {
+                let levels = (1u32 << (*bits).clamp(1, 8)) - 1;
+                let level = |k: u32| (k as f32 / levels as f32 * 255.0).round() as u8;
+                // Choosing between the representable values around the channel keeps the ones that already are representable,
+                // offsetting the byte value instead would push them over when the rounded levels are not evenly spaced.
+                let q = |c: u8| {
+                    let below = c as u32 * levels / 255;
+                    let (low, high) = (level(below), level((below + 1).min(levels)));
+                    if c == low || c == high {
+                        return c;
+                    }
+                    let fraction = (c - low) as f32 / (high - low) as f32;
+                    if fraction + threshold > 0.5 { high } else { low }
+                };
+                UniColor::from_rgba(q(rgb[0]), q(rgb[1]), q(rgb[2]), alpha)
+            }
+            DitherTarget::Palette(palette) => {
+                // A palette of n colors spread evenly over RGB has about n^(1/3) steps per channel.
+                let step = 255.0 / ((palette.len() as f32).cbrt().round() - 1.0).max(1.0);
+                let offset = threshold * step;
+                self.quantize(rgb.map(|c| c as f32 + offset), alpha)
+            }
+        }
+    }
+
+}
+
+/// The dithering algorithm used by `dither_buffer`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DitherMethod {
+    /// Ordered dithering with a 4x4 Bayer matrix. Fast, deterministic per pixel and stable under animation.
+    Bayer4,
+    /// Ordered dithering with an 8x8 Bayer matrix, giving finer patterns than `Bayer4`.
+    Bayer8,
+    /// Floyd–Steinberg error diffusion. Looks the most natural, but every pixel depends on the ones before it.
+    FloydSteinberg,
+}
+
+/// Quantizes an image with rows of `width` pixels to `target`, dithering with `method` to avoid banding.
+/// Alpha is left untouched. Does nothing if `width` is 0, a partial last row is dithered like a full one.
+#[cfg(feature = "std")]
+pub fn dither_buffer(pixels: &mut [UniColor], width: usize, target: DitherTarget, method: DitherMethod) {
+    if width == 0 {
+        return;
+    }
+
+    match method {
+        DitherMethod::Bayer4 | DitherMethod::Bayer8 => {
+            let order = if method == DitherMethod::Bayer4 { 2 } else { 3 };
+            let size = 1usize << (2 * order);
+            for (i, pixel) in pixels.iter_mut().enumerate() {
+                let threshold = (bayer_index(i % width, i / width, order) as f32 + 0.5) / size as f32 - 0.5;
+                let (r, g, b, a) = pixel.to_rgba();
+                *pixel = target.quantize_ordered([r, g, b], a, threshold);
+            }
+        }
+        DitherMethod::FloydSteinberg => {
+            // The error diffused into the current and the next row.
+            let mut current = vec![[0.0f32; 3]; width + 2];
+            let mut next = vec![[0.0f32; 3]; width + 2];
+            for row in pixels.chunks_mut(width) {
+                for (x, pixel) in row.iter_mut().enumerate() {
+                    let (r, g, b, a) = pixel.to_rgba();
+                    let e = current[x + 1];
+                    // Clamping keeps piled up error from pushing channels far outside of the representable range.
+                    let value = [
+                        (r as f32 + e[0]).clamp(0.0, 255.0),
+                        (g as f32 + e[1]).clamp(0.0, 255.0),
+                        (b as f32 + e[2]).clamp(0.0, 255.0),
+                    ];
+                    let quantized = target.quantize(value, a);
+                    *pixel = quantized;
+
+                    let (qr, qg, qb) = quantized.to_rgb();
+                    let error = [value[0] - qr as f32, value[1] - qg as f32, value[2] - qb as f32];
+                    for c in 0..3 {
+                        current[x + 2][c] += error[c] * 7.0 / 16.0;
+                        next[x][c] += error[c] * 3.0 / 16.0;
+                        next[x + 1][c] += error[c] * 5.0 / 16.0;
+                        next[x + 2][c] += error[c] / 16.0;
+                    }
+                }
+                core::mem::swap(&mut current, &mut next);
+                next.fill([0.0; 3]);
+            }
+        }
+    }
+}
+
+/// Returns the entry of the Bayer matrix of size 2^order at (x, y), built by interleaving the bits of the coordinates.
+#[cfg(feature = "std")]
+#[inline]
+fn bayer_index(x: usize, y: usize, order: u32) -> usize {
+    let mut index = 0;
+    for bit in 0..order {
+        let xb = (x >> bit) & 1;
+        let yb = (y >> bit) & 1;
+        index = (index << 2) | ((xb ^ yb) << 1) | yb;
+    }
+    index
+}
+
+/// Returns the squared distance between the RGB channels of `color` and `rgb`.
+#[cfg(feature = "std")]
+#[inline]
+fn rgb_distance_squared(color: &UniColor, rgb: [f32; 3]) -> f32 {
+    let (r, g, b) = color.to_rgb();
+    let (dr, dg, db) = (r as f32 - rgb[0], g as f32 - rgb[1], b as f32 - rgb[2]);
+    dr * dr + dg * dg + db * db
+}
+
+/// Converts the color into CIELAB (L, a, b) with a D65 white point.
+fn to_lab(color: &UniColor) -> (f32, f32, f32) {
+    let (r, g, b) = color.to_rgb();
+    let [r, g, b] = [r, g, b].map(|c| srgb_to_linear(c as f32 / 255.0));
+    let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+    let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+
+    let f = |t: f32| if t > 216.0 / 24389.0 { t.cbrt() } else { (24389.0 / 27.0 * t + 16.0) / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Strips `prefix` off `s`, ignoring the ASCII case of `s`.
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &s[prefix.len()..])
+}
+
+/// Returns `value` as `N - 1` lowercase hex digits prefixed with `#`.
+const fn hex_bytes<const N: usize>(mut value: u32) -> [u8; N] {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut bytes = [b'#'; N];
+    let mut i = N - 1;
+    while i > 0 {
+        bytes[i] = DIGITS[(value & 0xF) as usize];
+        value >>= 4;
+        i -= 1;
+    }
+    bytes
+}
+
+/// Parses a CSS `rgb()` argument, either a number where `max` means full intensity or a percentage.
+fn parse_css_component(s: &str, max: f32) -> Option<u8> {
+    let value = match s.strip_suffix('%') {
+        Some(percent) => percent.trim_end().parse::<f32>().ok()? / 100.0,
+        None => s.parse::<f32>().ok()? / max,
+    };
+    value.is_finite().then(|| to_channel(value))
+}
+
+/// Converts a channel value in [0, 1] back to a byte, clamping values outside the range.
+#[inline]
+fn to_channel(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Applies `f` to a channel as a value in [0, 1].
+#[inline]
+fn map_channel(c: u8, f: &impl Fn(f32) -> f32) -> u8 {
+    to_channel(f(c as f32 / 255.0))
+}
+
+/// Multiplies two channels as values in [0, 1], rounded to the nearest byte.
+/// The exact product `a * b / 255` is never halfway between two bytes, so adding 127 before the division rounds correctly.
+#[inline]
+fn multiply_channel(a: u8, b: u8) -> u8 {
+    ((a as u32 * b as u32 + 127) / 255) as u8
+}
+
+/// Decodes a single sRGB channel in [0, 1] into linear light.
+#[inline]
+pub(crate) fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a single linear light channel in [0, 1] into sRGB.
+#[inline]
+pub(crate) fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn max_channel_error(a: UniColor, b: UniColor) -> u8 {
+        let (a, b) = (a.to_rgb(), b.to_rgb());
+        a.0.abs_diff(b.0).max(a.1.abs_diff(b.1)).max(a.2.abs_diff(b.2))
+    }
+
+    #[test]
+    fn hsl_round_trips_the_whole_rgb_cube() {
+        let mut worst = 0;
+        for rgb in 0..1u32 << 24 {
+            let color = UniColor::from_rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8);
+            let (h, s, l) = color.to_hsl();
+            assert!((0.0..360.0).contains(&h) && (0.0..=1.0).contains(&s) && (0.0..=1.0).contains(&l), "{:?}", (h, s, l));
+            worst = worst.max(max_channel_error(color, UniColor::from_hsl(h, s, l)));
+        }
+        assert!(worst <= 1, "off by {}", worst);
+    }
+
+    #[test]
+    fn hsl_of_black_white_and_grays() {
+        assert_eq!(UniColor::from_rgb(0, 0, 0).to_hsl(), (0.0, 0.0, 0.0));
+        assert_eq!(UniColor::from_rgb(255, 255, 255).to_hsl(), (0.0, 0.0, 1.0));
+        for v in 0..=255 {
+            let gray = UniColor::from_rgb(v, v, v);
+            let (h, s, l) = gray.to_hsl();
+            assert_eq!((h, s), (0.0, 0.0));
+            assert_eq!(UniColor::from_hsl(h, s, l).to_rgb(), (v, v, v));
+            // The hue of a gray does not matter.
+            assert_eq!(UniColor::from_hsl(123.0, 0.0, l).to_rgb(), (v, v, v));
+        }
+    }
+
+    #[test]
+    fn from_hsl_wraps_hue_and_clamps_saturation_and_lightness() {
+        assert_eq!(UniColor::from_hsl(360.0, 1.0, 0.5).to_rgb(), (255, 0, 0));
+        assert_eq!(UniColor::from_hsl(-120.0, 1.0, 0.5).to_rgb(), (0, 0, 255));
+        assert_eq!(UniColor::from_hsl(480.0, 2.0, 0.5).to_rgb(), (0, 255, 0));
+        assert_eq!(UniColor::from_hsl(0.0, 1.0, -1.0).to_rgb(), (0, 0, 0));
+        assert_eq!(UniColor::from_hsl(0.0, -1.0, 2.0).to_rgb(), (255, 255, 255));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_writes_a_hex_string() {
+        let color = UniColor::from_rgba(0x12, 0x34, 0xab, 0x80);
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(json, r##""#1234ab80""##);
+        assert_eq!(serde_json::from_str::<UniColor>(&json).unwrap().to_rgba(), color.to_rgba());
+        assert_eq!(serde_json::from_str::<UniColor>(r##""#1234ab""##).unwrap().to_rgba(), (0x12, 0x34, 0xab, 0xff));
+        assert!(serde_json::from_str::<UniColor>(r##""#12345""##).is_err());
+        assert!(serde_json::from_str::<UniColor>("305441920").is_err());
+    }
+
+    #[test]
+    fn parse_reads_names_ignoring_case_and_whitespace() {
+        assert_eq!(UniColor::parse("cornflowerblue").unwrap().to_rgba(), (100, 149, 237, 255));
+        assert_eq!(UniColor::parse("  CornflowerBlue\t").unwrap().to_rgba(), (100, 149, 237, 255));
+        assert_eq!(UniColor::parse("rebeccapurple").unwrap().to_rgba(), (102, 51, 153, 255));
+        assert_eq!(UniColor::parse("WHITE").unwrap().to_rgba(), UniColor::WHITE.to_rgba());
+        assert_eq!(UniColor::parse(" Transparent ").unwrap().to_rgba(), (0, 0, 0, 0));
+        assert_eq!(UniColor::parse(" #FF8000 ").unwrap().to_rgba(), (255, 128, 0, 255));
+        assert_eq!(UniColor::parse("0x0f08").unwrap().to_rgba(), (0, 255, 0, 136));
+    }
+
+    #[test]
+    fn parse_reads_rgb_functions() {
+        assert_eq!(UniColor::parse("rgb(255, 0, 128)").unwrap().to_rgba(), (255, 0, 128, 255));
+        assert_eq!(UniColor::parse("RGB( 1 ,2, 3 )").unwrap().to_rgba(), (1, 2, 3, 255));
+        assert_eq!(UniColor::parse("rgba(10, 20, 30, 0.5)").unwrap().to_rgba(), (10, 20, 30, 128));
+        assert_eq!(UniColor::parse("rgba (10, 20, 30, 1)").unwrap().to_rgba(), (10, 20, 30, 255));
+        assert_eq!(UniColor::parse("rgb(100%, 50%, 0%)").unwrap().to_rgba(), (255, 128, 0, 255));
+        assert_eq!(UniColor::parse("rgba(20%, 40 %, 60%, 25%)").unwrap().to_rgba(), (51, 102, 153, 64));
+        assert_eq!(UniColor::parse("rgb(12.6, 0, 0)").unwrap().to_rgba(), (13, 0, 0, 255));
+        // Out of range values are clamped like in CSS.
+        assert_eq!(UniColor::parse("rgba(300, -5, 0, 2)").unwrap().to_rgba(), (255, 0, 0, 255));
+        assert_eq!(UniColor::parse("rgb(150%, 0, 0)").unwrap().to_rgba(), (255, 0, 0, 255));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_strings() {
+        let malformed = [
+            "", "   ", "notacolor", "cornflower blue", "#12345", "#gg0000", "#ff00000000",
+            "rgb", "rgb(", "rgb()", "rgb(1, 2)", "rgb(1, 2, 3, 4, 5)", "rgb(1, 2, 3", "rgb 1, 2, 3)",
+            "rgb(a, b, c)", "rgb(1,, 3)", "rgb(1, 2, 3,)", "rgb(NaN, 0, 0)", "rgb(inf, 0, 0)", "rgb(1e40, 0, 0)",
+            "rgb(%, 0, 0)", "rgba(1, 2, 3, x)", "rgb(1, 2, 3) x", "é", "rgbé(1, 2, 3)", "\u{1f308}",
+        ];
+        for s in malformed {
+            assert!(UniColor::parse(s).is_none(), "{:?} parsed as {:?}", s, UniColor::parse(s).map(|c| c.to_rgba()));
+        }
+    }
+
+    #[test]
+    fn lerp_hsl_takes_the_short_way_around_the_hue() {
+        let (red, blue) = (UniColor::from_rgb(255, 0, 0), UniColor::from_rgb(0, 0, 255));
+        // Magenta, where `lerp` gives a dark purple.
+        assert_eq!(red.lerp_hsl(&blue, 0.5).to_rgb(), (255, 0, 255));
+        assert_eq!(red.lerp(&blue, 0.5).to_rgb(), (128, 0, 128));
+        assert_eq!(blue.lerp_hsl(&red, 0.5).to_rgb(), (255, 0, 255));
+        assert_eq!(red.lerp_hsl(&blue, 0.0).to_rgb(), (255, 0, 0));
+        assert_eq!(red.lerp_hsl(&blue, 2.0).to_rgb(), (0, 0, 255));
+
+        // Grays only fade in saturation, so there is no detour through another hue.
+        let gray = UniColor::from_rgba(128, 128, 128, 0);
+        let (h, _, _) = UniColor::from_rgb(0, 255, 0).lerp_hsl(&gray, 0.5).to_hsl();
+        assert!((h - 120.0).abs() < 1.0, "{}", h);
+        assert_eq!(red.lerp_hsl(&gray, 0.5).to_rgba().3, 128);
+    }
+
+    #[test]
+    fn contrast_ratio_spans_1_to_21() {
+        let (black, white) = (UniColor::BLACK, UniColor::WHITE);
+        assert_eq!(black.contrast_ratio(&white), 21.0);
+        assert_eq!(white.contrast_ratio(&black), 21.0);
+        for color in [black, white, UniColor::from_rgb(119, 119, 119), UniColor::from_rgba(12, 200, 99, 0)] {
+            assert_eq!(color.contrast_ratio(&color), 1.0);
+        }
+        // #777 on white just misses WCAG AA for normal text.
+        let ratio = UniColor::from_rgb(119, 119, 119).contrast_ratio(&white);
+        assert!((ratio - 4.48).abs() < 0.01, "{}", ratio);
+        assert_eq!(white.luminance(), 1.0);
+        assert_eq!(black.luminance(), 0.0);
+    }
+
+    #[test]
+    fn lighten_darken_and_saturate_move_along_hsl() {
+        let color = UniColor::from_rgba(200, 60, 60, 77);
+        assert_eq!(color.lighten(1.0).to_rgba(), (255, 255, 255, 77));
+        assert_eq!(color.darken(1.0).to_rgba(), (0, 0, 0, 77));
+        assert_eq!(color.lighten(0.0).to_rgba(), color.to_rgba());
+        let (_, _, l) = color.to_hsl();
+        let (_, _, lighter) = color.lighten(0.1).to_hsl();
+        assert!((lighter - l - 0.1).abs() < 0.01, "{} -> {}", l, lighter);
+        assert!(color.darken(0.2).luminance() < color.luminance());
+
+        assert_eq!(color.desaturate(1.0).to_rgba(), (130, 130, 130, 77));
+        let gray = UniColor::from_rgb(90, 90, 90);
+        assert_eq!(gray.saturate(0.5).to_rgb(), (90, 90, 90));
+    }
+
+    #[test]
+    fn lerp_linear_blends_in_linear_light() {
+        let (black, white) = (UniColor::BLACK, UniColor::WHITE);
+        assert_eq!(black.lerp(&white, 0.5).to_rgb(), (128, 128, 128));
+        assert_eq!(black.lerp_linear(&white, 0.5).to_rgb(), (188, 188, 188));
+        // The endpoints and alpha are unaffected by the decoding.
+        let a = UniColor::from_rgba(10, 120, 250, 0);
+        let b = UniColor::from_rgba(240, 30, 90, 200);
+        assert_eq!(a.lerp_linear(&b, 0.0).to_rgba(), a.to_rgba());
+        assert_eq!(a.lerp_linear(&b, 1.0).to_rgba(), b.to_rgba());
+        assert_eq!(a.lerp_linear(&b, 0.5).to_rgba().3, 100);
+        assert_eq!(a.lerp_linear(&b, 7.0).to_rgba(), b.to_rgba());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn convert_buffer_round_trips_between_every_format() {
+        const FORMATS: [PixelFormat; 4] = [PixelFormat::Rgba8, PixelFormat::Bgra8, PixelFormat::Rgb8, PixelFormat::Rgba8Premultiplied];
+        let opaque: Vec<u8> = (0..64u32).flat_map(|i| [(i * 37) as u8, (i * 101) as u8, (i * 7 + 3) as u8, 255]).collect();
+        let (mut there, mut back, mut start) = (Vec::new(), Vec::new(), Vec::new());
+        for from in FORMATS {
+            convert_buffer(&opaque, PixelFormat::Rgba8, &mut start, from).unwrap();
+            for to in FORMATS {
+                convert_buffer(&start, from, &mut there, to).unwrap();
+                assert_eq!(there.len(), start.len() / from.bytes_per_pixel() * to.bytes_per_pixel());
+                convert_buffer(&there, to, &mut back, from).unwrap();
+                assert_eq!(back, start, "{:?} -> {:?}", from, to);
+            }
+        }
+
+        // Translucent pixels survive the swizzle exactly and the premultiplication up to rounding.
+        let translucent = [200, 100, 50, 128, 255, 255, 255, 0, 10, 20, 30, 1];
+        convert_buffer(&translucent, PixelFormat::Rgba8, &mut there, PixelFormat::Bgra8).unwrap();
+        assert_eq!(there, [50, 100, 200, 128, 255, 255, 255, 0, 30, 20, 10, 1]);
+        convert_buffer(&translucent, PixelFormat::Rgba8, &mut there, PixelFormat::Rgba8Premultiplied).unwrap();
+        assert_eq!(there[..8], [100, 50, 25, 128, 0, 0, 0, 0]);
+        convert_buffer(&there, PixelFormat::Rgba8Premultiplied, &mut back, PixelFormat::Rgba8).unwrap();
+        assert_eq!(back[..8], [199, 100, 50, 128, 0, 0, 0, 0]);
+        convert_buffer(&translucent, PixelFormat::Rgba8, &mut there, PixelFormat::Rgb8).unwrap();
+        assert_eq!(there, [200, 100, 50, 255, 255, 255, 10, 20, 30]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn convert_buffer_rejects_partial_pixels() {
+        let mut dst = vec![1, 2, 3];
+        assert_eq!(
+            convert_buffer(&[0; 7], PixelFormat::Rgba8, &mut dst, PixelFormat::Bgra8),
+            Err(BufferLengthError { len: 7, bytes_per_pixel: 4 }),
+        );
+        assert_eq!(
+            convert_buffer(&[0; 8], PixelFormat::Rgb8, &mut dst, PixelFormat::Rgba8),
+            Err(BufferLengthError { len: 8, bytes_per_pixel: 3 }),
+        );
+        // The destination is left alone on errors.
+        assert_eq!(dst, [1, 2, 3]);
+        assert_eq!(convert_buffer(&[], PixelFormat::Rgb8, &mut dst, PixelFormat::Rgba8), Ok(()));
+        assert!(dst.is_empty());
+        assert_eq!(
+            BufferLengthError { len: 7, bytes_per_pixel: 4 }.to_string(),
+            "buffer length 7 is not a multiple of 4 bytes per pixel",
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dither_buffer_keeps_the_average_of_a_flat_tone() {
+        const METHODS: [DitherMethod; 3] = [DitherMethod::Bayer4, DitherMethod::Bayer8, DitherMethod::FloydSteinberg];
+        let width = 32;
+        for method in METHODS {
+            for (bits, gray) in [(1, 128), (2, 100), (3, 77), (5, 13)] {
+                let mut pixels = vec![UniColor::from_rgba(gray, gray, gray, 200); width * width];
+                dither_buffer(&mut pixels, width, DitherTarget::Bits(bits), method);
+                // Only the two representable values around the tone are used.
+                let levels = ((1u32 << bits) - 1) as f32;
+                let below = (gray as f32 / 255.0 * levels).floor();
+                let around = [below, below + 1.0].map(|k| (k / levels * 255.0).round() as u8);
+                assert!(pixels.iter().all(|p| {
+                    let (r, g, b, a) = p.to_rgba();
+                    r == g && g == b && a == 200 && around.contains(&r)
+                }), "{:?} {}", method, bits);
+                let average = pixels.iter().map(|p| p.to_rgb().0 as f32).sum::<f32>() / pixels.len() as f32;
+                assert!((average - gray as f32).abs() < 1.0, "{:?} {} {}: {}", method, bits, gray, average);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dither_buffer_leaves_representable_colors_alone() {
+        let corners: Vec<UniColor> = (0..8).map(|i| {
+            let c = |bit: u32| if i & bit != 0 { 255 } else { 0 };
+            UniColor::from_rgb(c(1), c(2), c(4))
+        }).collect();
+        let original: Vec<UniColor> = (0..100u32).map(|i| {
+            let (r, g, b) = corners[(i * 5 % 8) as usize].to_rgb();
+            UniColor::from_rgba(r, g, b, i as u8)
+        }).collect();
+        let rgb555: Vec<UniColor> = (0..100u32).map(|i| {
+            let c = |k: u32| ((i * k % 32) as f32 / 31.0 * 255.0).round() as u8;
+            UniColor::from_rgba(c(3), c(7), c(11), 255)
+        }).collect();
+        for method in [DitherMethod::Bayer4, DitherMethod::Bayer8, DitherMethod::FloydSteinberg] {
+            let mut pixels = original.clone();
+            dither_buffer(&mut pixels, 10, DitherTarget::Palette(&corners), method);
+            assert!(pixels.iter().zip(&original).all(|(a, b)| a.to_rgba() == b.to_rgba()), "{:?}", method);
+            let mut pixels = rgb555.clone();
+            dither_buffer(&mut pixels, 10, DitherTarget::Bits(5), method);
+            assert!(pixels.iter().zip(&rgb555).all(|(a, b)| a.to_rgba() == b.to_rgba()), "{:?}", method);
+        }
+
+        // A width of 0 does nothing.
+        let mut pixels = vec![UniColor::from_rgb(128, 128, 128); 4];
+        dither_buffer(&mut pixels, 0, DitherTarget::Bits(1), DitherMethod::FloydSteinberg);
+        assert!(pixels.iter().all(|p| p.to_rgb() == (128, 128, 128)));
+    }
+
+    #[test]
+    fn simulate_cvd_keeps_grays_and_matches_the_reference() {
+        for kind in [CvdKind::Protanopia, CvdKind::Deuteranopia, CvdKind::Tritanopia] {
+            for gray in [0, 1, 60, 128, 200, 255] {
+                let (r, g, b, a) = UniColor::from_rgba(gray, gray, gray, 99).simulate_cvd(kind).to_rgba();
+                assert!([r, g, b].iter().all(|c| c.abs_diff(gray) <= 1) && a == 99, "{:?} {}: {:?}", kind, gray, (r, g, b));
+            }
+        }
+        // Linear red becomes the first column of the Machado et al. matrix, encoded to sRGB again.
+        assert_eq!(UniColor::RED.simulate_cvd(CvdKind::Protanopia).to_rgb(), (109, 95, 0));
+        assert_eq!(UniColor::RED.simulate_cvd(CvdKind::Deuteranopia).to_rgb(), (163, 144, 0));
+        assert_eq!(UniColor::RED.simulate_cvd(CvdKind::Tritanopia).to_rgb(), (255, 0, 15));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn palette_distinguishable_reports_confused_pairs() {
+        // Red and a dark green are on the same protanopic confusion line.
+        let palette = [UniColor::RED, UniColor::from_rgb(0, 107, 0), UniColor::BLUE, UniColor::WHITE];
+        assert!(palette[0].delta_e(&palette[1]) > 50.0);
+        assert_eq!(palette_distinguishable(&palette, CvdKind::Protanopia, 10.0), [(0, 1)]);
+        assert!(palette_distinguishable(&palette, CvdKind::Tritanopia, 10.0).is_empty());
+        assert_eq!(palette_distinguishable(&[UniColor::BLUE, UniColor::WHITE, UniColor::BLUE], CvdKind::Deuteranopia, 1.0), [(0, 2)]);
+        assert!(palette_distinguishable(&[], CvdKind::Protanopia, 10.0).is_empty());
+    }
+
+    #[test]
+    fn from_hsl_treats_a_non_finite_hue_as_zero() {
+        for hue in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(UniColor::from_hsl(hue, 0.0, 1.0).to_rgb(), (255, 255, 255));
+            assert_eq!(UniColor::from_hsl(hue, 0.0, 0.5).to_rgb(), (128, 128, 128));
+            assert_eq!(UniColor::from_hsl(hue, 1.0, 0.5).to_rgb(), UniColor::from_hsl(0.0, 1.0, 0.5).to_rgb());
+        }
+        assert_eq!(UniColor::from_hsl(720.0, 1.0, 0.5).to_rgb(), (255, 0, 0));
+        assert_eq!(UniColor::from_hsl(-120.0, 1.0, 0.5).to_rgb(), (0, 0, 255));
+    }
+
+    #[test]
+    fn named_constants() {
+        assert_eq!(UniColor::WHITE.to_rgba(), (255, 255, 255, 255));
+        assert_eq!(UniColor::BLACK.to_rgba(), (0, 0, 0, 255));
+        assert_eq!(UniColor::TRANSPARENT.to_rgba(), (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn mul_has_white_as_identity_and_transparent_as_absorbing_element() {
+        let color = UniColor::from_rgba(12, 130, 255, 77);
+        assert_eq!((color * UniColor::WHITE).to_rgba(), color.to_rgba());
+        assert_eq!((UniColor::WHITE * color).to_rgba(), color.to_rgba());
+        assert_eq!((color * UniColor::TRANSPARENT).to_rgba(), (0, 0, 0, 0));
+        // Opaque black only absorbs the color channels, alpha is multiplied like the rest.
+        assert_eq!((color * UniColor::BLACK).to_rgba(), (0, 0, 0, 77));
+
+        // 128 * 128 / 255 = 64.25 rounds down, 200 * 200 / 255 = 156.86 rounds up.
+        let tint = UniColor::from_rgba(128, 200, 255, 128);
+        assert_eq!((tint * tint).to_rgba(), (64, 157, 255, 64));
+
+        let mut assigned = color;
+        assigned *= tint;
+        assert_eq!(assigned.to_rgba(), (color * tint).to_rgba());
+    }
+
+    #[test]
+    fn mul_scalar_rounds_clamps_and_keeps_alpha() {
+        let color = UniColor::from_rgba(255, 3, 1, 77);
+        // 127.5, 1.5 and 0.5 round away from zero.
+        assert_eq!((color * 0.5).to_rgba(), (128, 2, 1, 77));
+        assert_eq!((color * 2.0).to_rgba(), (255, 6, 2, 77));
+        assert_eq!((color * -1.0).to_rgba(), (0, 0, 0, 77));
+        assert_eq!((color * 1.0).to_rgba(), color.to_rgba());
+
+        let mut assigned = color;
+        assigned *= 0.5;
+        assert_eq!(assigned.to_rgba(), (color * 0.5).to_rgba());
+    }
+
+    #[test]
+    fn add_saturates_every_channel() {
+        let a = UniColor::from_rgba(200, 10, 0, 128);
+        let b = UniColor::from_rgba(100, 20, 255, 200);
+        assert_eq!((a + b).to_rgba(), (255, 30, 255, 255));
+        assert_eq!((a + UniColor::TRANSPARENT).to_rgba(), a.to_rgba());
+        assert_eq!((UniColor::WHITE + b).to_rgba(), (255, 255, 255, 255));
+
+        let mut assigned = a;
+        assigned += b;
+        assert_eq!(assigned.to_rgba(), (a + b).to_rgba());
+    }
+
+    #[test]
+    fn map_rgb_with_identity_round_trips_every_byte() {
+        for c in 0..=255u8 {
+            let color = UniColor::from_rgba(c, 255 - c, c / 2, 77);
+            assert_eq!(color.map_rgb(|x| x).to_rgba(), color.to_rgba());
+            assert_eq!(color.map_rgba(|x| x).to_rgba(), color.to_rgba());
+        }
+
+        let color = UniColor::from_rgba(0, 128, 255, 77);
+        assert_eq!(color.map_rgb(|x| x * 2.0 - 0.25).to_rgba(), (0, 192, 255, 77));
+        assert_eq!(color.map_rgb(|x| 1.0 - x).to_rgba(), (255, 127, 0, 77));
+        assert_eq!(color.map_rgba(|x| 1.0 - x).to_rgba(), (255, 127, 0, 178));
+    }
+
+    #[test]
+    fn levels_stretches_the_range_and_applies_gamma() {
+        for c in 0..=255u8 {
+            let color = UniColor::from_rgba(c, 255 - c, c / 2, 77);
+            assert_eq!(color.levels(0.0, 1.0, 1.0).to_rgba(), color.to_rgba());
+        }
+
+        // (128 / 255)^2 * 255 = 64.25, so gamma 2 darkens the midtones while black and white stay.
+        let color = UniColor::from_rgba(0, 128, 255, 77);
+        assert_eq!(color.levels(0.0, 1.0, 2.0).to_rgba(), (0, 64, 255, 77));
+        assert_eq!(color.levels(0.0, 1.0, 0.5).to_rgba(), (0, 181, 255, 77));
+
+        let color = UniColor::from_rgba(51, 200, 10, 77);
+        assert_eq!(color.levels(0.0, 0.5, 1.0).to_rgba(), (102, 255, 20, 77));
+        assert_eq!(color.levels(0.5, 1.0, 1.0).to_rgba(), (0, 145, 0, 77));
+    }
+
+    #[test]
+    fn zip_rgb_multiply_matches_mul() {
+        for a in (0..=255u8).step_by(5) {
+            for b in (0..=255u8).step_by(3) {
+                let (x, y) = (UniColor::from_rgba(a, b, 255 - a, 77), UniColor::from_rgba(b, a, 255 - b, 255));
+                assert_eq!(x.zip_rgb(&y, |a, b| a * b).to_rgba(), (x * y).to_rgba(), "{:?}", (a, b));
+            }
+        }
+
+        let (x, y) = (UniColor::from_rgba(200, 10, 0, 77), UniColor::from_rgba(100, 20, 255, 255));
+        assert_eq!(x.zip_rgb(&y, |a, b| a + b).to_rgba(), (x + y).with_alpha(77).to_rgba());
+    }
+
+    #[test]
+    fn f32_array_round_trips_and_clamps() {
+        for c in 0..=255u8 {
+            let color = UniColor::from_rgba(c, 255 - c, c / 2, c / 3);
+            assert_eq!(UniColor::from_f32_array(color.to_f32_array()).to_rgba(), color.to_rgba());
+        }
+        assert_eq!(UniColor::from_rgba(255, 0, 51, 128).to_f32_array(), [1.0, 0.0, 0.2, 128.0 / 255.0]);
+        assert_eq!(UniColor::from_f32_array([1.5, -0.5, 0.5, 0.2]).to_rgba(), (255, 0, 128, 51));
+    }
+
+    #[test]
+    fn linear_conversions_match_the_srgb_reference() {
+        // sRGB 188 is about 0.5 in linear light, sRGB 0.5 is about 0.2140.
+        let [r, g, b, a] = UniColor::from_rgba(188, 0, 255, 128).to_linear();
+        assert!((r - 0.5029).abs() < 1e-4, "{:?}", r);
+        assert_eq!((g, b, a), (0.0, 1.0, 128.0 / 255.0));
+        assert!((srgb_to_linear(0.5) - 0.2140).abs() < 1e-4, "{:?}", srgb_to_linear(0.5));
+        // 0.2140 encodes to just below sRGB 0.5, i.e. 127.5, so it rounds down.
+        assert_eq!(UniColor::from_linear([0.5, 0.2140, 1.0, 0.5]).to_rgba(), (188, 127, 255, 128));
+        // Alpha stays linear and every channel is clamped.
+        assert_eq!(UniColor::from_linear([2.0, -1.0, 0.0, 1.5]).to_rgba(), (255, 0, 0, 255));
+
+        for c in 0..=255u8 {
+            let color = UniColor::from_rgba(c, 255 - c, c / 2, c / 3);
+            assert_eq!(UniColor::from_linear(color.to_linear()).to_rgba(), color.to_rgba());
+        }
+    }
+
+    #[test]
+    fn from_hex_parses_every_length_prefix_and_case() {
+        let rgba = |hex: &str| UniColor::from_hex(hex).map(|c| c.to_rgba());
+        assert_eq!(rgba("#f80"), Some((0xFF, 0x88, 0x00, 0xFF)));
+        assert_eq!(rgba("#f808"), Some((0xFF, 0x88, 0x00, 0x88)));
+        assert_eq!(rgba("#ff8001"), Some((0xFF, 0x80, 0x01, 0xFF)));
+        assert_eq!(rgba("#ff800120"), Some((0xFF, 0x80, 0x01, 0x20)));
+        assert_eq!(rgba("0xFf8001"), Some((0xFF, 0x80, 0x01, 0xFF)));
+        assert_eq!(rgba("0XaBc"), Some((0xAA, 0xBB, 0xCC, 0xFF)));
+        assert_eq!(rgba("ff800120"), Some((0xFF, 0x80, 0x01, 0x20)));
+
+        for invalid in ["", "#", "#ff", "#ff800", "#ff8001200", "#gg8001", "#ff 801", "##f80", "#0xf80", "+f80", "#ff80é"] {
+            assert_eq!(rgba(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn hex_bytes_round_trip_through_from_hex() {
+        for color in [UniColor::from_rgba(0, 0, 0, 0), UniColor::from_rgba(1, 2, 3, 4), UniColor::from_rgba(0xAB, 0xCD, 0xEF, 0x10), UniColor::WHITE] {
+            let hex = color.to_hex_alpha_bytes();
+            let parsed = UniColor::from_hex(core::str::from_utf8(&hex).unwrap()).unwrap();
+            assert_eq!(parsed.to_rgba(), color.to_rgba());
+
+            let hex = color.to_hex_bytes();
+            let (r, g, b, _) = color.to_rgba();
+            assert_eq!(UniColor::from_hex(core::str::from_utf8(&hex).unwrap()).unwrap().to_rgba(), (r, g, b, 255));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_hex_is_lowercase_zero_padded_and_round_trips() {
+        let color = UniColor::from_rgba(0x0A, 0xBC, 0x01, 0x02);
+        assert_eq!(color.to_hex(), "#0abc01");
+        assert_eq!(color.to_hex_alpha(), "#0abc0102");
+        assert_eq!(UniColor::from_rgba(0, 0, 0, 0).to_hex_alpha(), "#00000000");
+
+        // Regression: `to_hex` used to print a 0x prefixed value of varying width that `from_hex` could not parse.
+        let opaque = UniColor::from_rgba(0x0A, 0xBC, 0x01, 0xFF);
+        assert_eq!(UniColor::from_hex(&opaque.to_hex()).unwrap().to_rgba(), opaque.to_rgba());
+        assert_eq!(UniColor::from_hex(&color.to_hex_alpha()).unwrap().to_rgba(), color.to_rgba());
+        assert_eq!(color.to_hex().as_bytes(), color.to_hex_bytes());
+        assert_eq!(color.to_hex_alpha().as_bytes(), color.to_hex_alpha_bytes());
+    }
+
+    static PALETTE: [UniColor; 4] = [
+        UniColor::from_rgb(255, 128, 0),
+        UniColor::from_rgba(255, 128, 0, 64),
+        UniColor::from_hex_const(b"#ff8000"),
+        UniColor::from_hex_const(b"F80A"),
+    ];
+
+    #[test]
+    fn const_constructors_match_the_runtime_ones() {
+        let (r, g, b) = (255, 128, 0);
+        assert_eq!(PALETTE[0].to_rgba(), UniColor::from_rgb(r, g, b).to_rgba());
+        assert_eq!(PALETTE[1].to_rgba(), UniColor::from_rgba(r, g, b, 64).to_rgba());
+        assert_eq!(PALETTE[2].to_rgba(), UniColor::from_hex("#ff8000").unwrap().to_rgba());
+        assert_eq!(PALETTE[3].to_rgba(), UniColor::from_hex("F80A").unwrap().to_rgba());
+
+        for hex in ["#abc", "#abcd", "#a1b2c3", "#A1B2C3D4", "0123", "ffffff"] {
+            assert_eq!(UniColor::from_hex_const(hex.as_bytes()).to_rgba(), UniColor::from_hex(hex).unwrap().to_rgba(), "{:?}", hex);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex digit")]
+    fn from_hex_const_panics_on_invalid_digits() {
+        UniColor::from_hex_const(b"#ff80g0");
+    }
+
+    #[test]
+    #[should_panic(expected = "3, 4, 6 or 8 digits")]
+    fn from_hex_const_panics_on_invalid_lengths() {
+        UniColor::from_hex_const(b"#ff800");
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,459 @@
+use crate::rendering::color::UniColor;
+
+/// The CSS named colors, all opaque. `WHITE` and `BLACK` are defined next to `TRANSPARENT`.
+impl UniColor {
+
+    /// CSS `aliceblue`, `#f0f8ff`.
+    pub const ALICE_BLUE: UniColor = UniColor(0xF0F8FFFF);
+    /// CSS `antiquewhite`, `#faebd7`.
+    pub const ANTIQUE_WHITE: UniColor = UniColor(0xFAEBD7FF);
+    /// CSS `aqua`, `#00ffff`.
+    pub const AQUA: UniColor = UniColor(0x00FFFFFF);
+    /// CSS `aquamarine`, `#7fffd4`.
+    pub const AQUAMARINE: UniColor = UniColor(0x7FFFD4FF);
+    /// CSS `azure`, `#f0ffff`.
+    pub const AZURE: UniColor = UniColor(0xF0FFFFFF);
+    /// CSS `beige`, `#f5f5dc`.
+    pub const BEIGE: UniColor = UniColor(0xF5F5DCFF);
+    /// CSS `bisque`, `#ffe4c4`.
+    pub const BISQUE: UniColor = UniColor(0xFFE4C4FF);
+    /// CSS `blanchedalmond`, `#ffebcd`.
+    pub const BLANCHED_ALMOND: UniColor = UniColor(0xFFEBCDFF);
+    /// CSS `blue`, `#0000ff`.
+    pub const BLUE: UniColor = UniColor(0x0000FFFF);
+    /// CSS `blueviolet`, `#8a2be2`.
+    pub const BLUE_VIOLET: UniColor = UniColor(0x8A2BE2FF);
+    /// CSS `brown`, `#a52a2a`.
+    pub const BROWN: UniColor = UniColor(0xA52A2AFF);
+    /// CSS `burlywood`, `#deb887`.
+    pub const BURLYWOOD: UniColor = UniColor(0xDEB887FF);
+    /// CSS `cadetblue`, `#5f9ea0`.
+    pub const CADET_BLUE: UniColor = UniColor(0x5F9EA0FF);
+    /// CSS `chartreuse`, `#7fff00`.
+    pub const CHARTREUSE: UniColor = UniColor(0x7FFF00FF);
+    /// CSS `chocolate`, `#d2691e`.
+    pub const CHOCOLATE: UniColor = UniColor(0xD2691EFF);
+    /// CSS `coral`, `#ff7f50`.
+    pub const CORAL: UniColor = UniColor(0xFF7F50FF);
+    /// CSS `cornflowerblue`, `#6495ed`.
+    pub const CORNFLOWER_BLUE: UniColor = UniColor(0x6495EDFF);
+    /// CSS `cornsilk`, `#fff8dc`.
+    pub const CORNSILK: UniColor = UniColor(0xFFF8DCFF);
+    /// CSS `crimson`, `#dc143c`.
+    pub const CRIMSON: UniColor = UniColor(0xDC143CFF);
+    /// CSS `cyan`, `#00ffff`.
+    pub const CYAN: UniColor = UniColor(0x00FFFFFF);
+    /// CSS `darkblue`, `#00008b`.
+    pub const DARK_BLUE: UniColor = UniColor(0x00008BFF);
+    /// CSS `darkcyan`, `#008b8b`.
+    pub const DARK_CYAN: UniColor = UniColor(0x008B8BFF);
+    /// CSS `darkgoldenrod`, `#b8860b`.
+    pub const DARK_GOLDENROD: UniColor = UniColor(0xB8860BFF);
+    /// CSS `darkgray`, `#a9a9a9`.
+    pub const DARK_GRAY: UniColor = UniColor(0xA9A9A9FF);
+    /// CSS `darkgreen`, `#006400`.
+    pub const DARK_GREEN: UniColor = UniColor(0x006400FF);
+    /// CSS `darkgrey`, `#a9a9a9`.
+    pub const DARK_GREY: UniColor = UniColor(0xA9A9A9FF);
+    /// CSS `darkkhaki`, `#bdb76b`.
+    pub const DARK_KHAKI: UniColor = UniColor(0xBDB76BFF);
+    /// CSS `darkmagenta`, `#8b008b`.
+    pub const DARK_MAGENTA: UniColor = UniColor(0x8B008BFF);
+    /// CSS `darkolivegreen`, `#556b2f`.
+    pub const DARK_OLIVE_GREEN: UniColor = UniColor(0x556B2FFF);
+    /// CSS `darkorange`, `#ff8c00`.
+    pub const DARK_ORANGE: UniColor = UniColor(0xFF8C00FF);
+    /// CSS `darkorchid`, `#9932cc`.
+    pub const DARK_ORCHID: UniColor = UniColor(0x9932CCFF);
+    /// CSS `darkred`, `#8b0000`.
+    pub const DARK_RED: UniColor = UniColor(0x8B0000FF);
+    /// CSS `darksalmon`, `#e9967a`.
+    pub const DARK_SALMON: UniColor = UniColor(0xE9967AFF);
+    /// CSS `darkseagreen`, `#8fbc8f`.
+    pub const DARK_SEA_GREEN: UniColor = UniColor(0x8FBC8FFF);
+    /// CSS `darkslateblue`, `#483d8b`.
+    pub const DARK_SLATE_BLUE: UniColor = UniColor(0x483D8BFF);
+    /// CSS `darkslategray`, `#2f4f4f`.
+    pub const DARK_SLATE_GRAY: UniColor = UniColor(0x2F4F4FFF);
+    /// CSS `darkslategrey`, `#2f4f4f`.
+    pub const DARK_SLATE_GREY: UniColor = UniColor(0x2F4F4FFF);
+    /// CSS `darkturquoise`, `#00ced1`.
+    pub const DARK_TURQUOISE: UniColor = UniColor(0x00CED1FF);
+    /// CSS `darkviolet`, `#9400d3`.
+    pub const DARK_VIOLET: UniColor = UniColor(0x9400D3FF);
+    /// CSS `deeppink`, `#ff1493`.
+    pub const DEEP_PINK: UniColor = UniColor(0xFF1493FF);
+    /// CSS `deepskyblue`, `#00bfff`.
+    pub const DEEP_SKY_BLUE: UniColor = UniColor(0x00BFFFFF);
+    /// CSS `dimgray`, `#696969`.
+    pub const DIM_GRAY: UniColor = UniColor(0x696969FF);
+    /// CSS `dimgrey`, `#696969`.
+    pub const DIM_GREY: UniColor = UniColor(0x696969FF);
+    /// CSS `dodgerblue`, `#1e90ff`.
+    pub const DODGER_BLUE: UniColor = UniColor(0x1E90FFFF);
+    /// CSS `firebrick`, `#b22222`.
+    pub const FIREBRICK: UniColor = UniColor(0xB22222FF);
+    /// CSS `floralwhite`, `#fffaf0`.
+    pub const FLORAL_WHITE: UniColor = UniColor(0xFFFAF0FF);
+    /// CSS `forestgreen`, `#228b22`.
+    pub const FOREST_GREEN: UniColor = UniColor(0x228B22FF);
+    /// CSS `fuchsia`, `#ff00ff`.
+    pub const FUCHSIA: UniColor = UniColor(0xFF00FFFF);
+    /// CSS `gainsboro`, `#dcdcdc`.
+    pub const GAINSBORO: UniColor = UniColor(0xDCDCDCFF);
+    /// CSS `ghostwhite`, `#f8f8ff`.
+    pub const GHOST_WHITE: UniColor = UniColor(0xF8F8FFFF);
+    /// CSS `gold`, `#ffd700`.
+    pub const GOLD: UniColor = UniColor(0xFFD700FF);
+    /// CSS `goldenrod`, `#daa520`.
+    pub const GOLDENROD: UniColor = UniColor(0xDAA520FF);
+    /// CSS `gray`, `#808080`.
+    pub const GRAY: UniColor = UniColor(0x808080FF);
+    /// CSS `green`, `#008000`.
+    pub const GREEN: UniColor = UniColor(0x008000FF);
+    /// CSS `greenyellow`, `#adff2f`.
+    pub const GREEN_YELLOW: UniColor = UniColor(0xADFF2FFF);
+    /// CSS `grey`, `#808080`.
+    pub const GREY: UniColor = UniColor(0x808080FF);
+    /// CSS `honeydew`, `#f0fff0`.
+    pub const HONEYDEW: UniColor = UniColor(0xF0FFF0FF);
+    /// CSS `hotpink`, `#ff69b4`.
+    pub const HOT_PINK: UniColor = UniColor(0xFF69B4FF);
+    /// CSS `indianred`, `#cd5c5c`.
+    pub const INDIAN_RED: UniColor = UniColor(0xCD5C5CFF);
+    /// CSS `indigo`, `#4b0082`.
+    pub const INDIGO: UniColor = UniColor(0x4B0082FF);
+    /// CSS `ivory`, `#fffff0`.
+    pub const IVORY: UniColor = UniColor(0xFFFFF0FF);
+    /// CSS `khaki`, `#f0e68c`.
+    pub const KHAKI: UniColor = UniColor(0xF0E68CFF);
+    /// CSS `lavender`, `#e6e6fa`.
+    pub const LAVENDER: UniColor = UniColor(0xE6E6FAFF);
+    /// CSS `lavenderblush`, `#fff0f5`.
+    pub const LAVENDER_BLUSH: UniColor = UniColor(0xFFF0F5FF);
+    /// CSS `lawngreen`, `#7cfc00`.
+    pub const LAWN_GREEN: UniColor = UniColor(0x7CFC00FF);
+    /// CSS `lemonchiffon`, `#fffacd`.
+    pub const LEMON_CHIFFON: UniColor = UniColor(0xFFFACDFF);
+    /// CSS `lightblue`, `#add8e6`.
+    pub const LIGHT_BLUE: UniColor = UniColor(0xADD8E6FF);
+    /// CSS `lightcoral`, `#f08080`.
+    pub const LIGHT_CORAL: UniColor = UniColor(0xF08080FF);
+    /// CSS `lightcyan`, `#e0ffff`.
+    pub const LIGHT_CYAN: UniColor = UniColor(0xE0FFFFFF);
+    /// CSS `lightgoldenrodyellow`, `#fafad2`.
+    pub const LIGHT_GOLDENROD_YELLOW: UniColor = UniColor(0xFAFAD2FF);
+    /// CSS `lightgray`, `#d3d3d3`.
+    pub const LIGHT_GRAY: UniColor = UniColor(0xD3D3D3FF);
+    /// CSS `lightgreen`, `#90ee90`.
+    pub const LIGHT_GREEN: UniColor = UniColor(0x90EE90FF);
+    /// CSS `lightgrey`, `#d3d3d3`.
+    pub const LIGHT_GREY: UniColor = UniColor(0xD3D3D3FF);
+    /// CSS `lightpink`, `#ffb6c1`.
+    pub const LIGHT_PINK: UniColor = UniColor(0xFFB6C1FF);
+    /// CSS `lightsalmon`, `#ffa07a`.
+    pub const LIGHT_SALMON: UniColor = UniColor(0xFFA07AFF);
+    /// CSS `lightseagreen`, `#20b2aa`.
+    pub const LIGHT_SEA_GREEN: UniColor = UniColor(0x20B2AAFF);
+    /// CSS `lightskyblue`, `#87cefa`.
+    pub const LIGHT_SKY_BLUE: UniColor = UniColor(0x87CEFAFF);
+    /// CSS `lightslategray`, `#778899`.
+    pub const LIGHT_SLATE_GRAY: UniColor = UniColor(0x778899FF);
+    /// CSS `lightslategrey`, `#778899`.
+    pub const LIGHT_SLATE_GREY: UniColor = UniColor(0x778899FF);
+    /// CSS `lightsteelblue`, `#b0c4de`.
+    pub const LIGHT_STEEL_BLUE: UniColor = UniColor(0xB0C4DEFF);
+    /// CSS `lightyellow`, `#ffffe0`.
+    pub const LIGHT_YELLOW: UniColor = UniColor(0xFFFFE0FF);
+    /// CSS `lime`, `#00ff00`.
+    pub const LIME: UniColor = UniColor(0x00FF00FF);
+    /// CSS `limegreen`, `#32cd32`.
+    pub const LIME_GREEN: UniColor = UniColor(0x32CD32FF);
+    /// CSS `linen`, `#faf0e6`.
+    pub const LINEN: UniColor = UniColor(0xFAF0E6FF);
+    /// CSS `magenta`, `#ff00ff`.
+    pub const MAGENTA: UniColor = UniColor(0xFF00FFFF);
+    /// CSS `maroon`, `#800000`.
+    pub const MAROON: UniColor = UniColor(0x800000FF);
+    /// CSS `mediumaquamarine`, `#66cdaa`.
+    pub const MEDIUM_AQUAMARINE: UniColor = UniColor(0x66CDAAFF);
+    /// CSS `mediumblue`, `#0000cd`.
+    pub const MEDIUM_BLUE: UniColor = UniColor(0x0000CDFF);
+    /// CSS `mediumorchid`, `#ba55d3`.
+    pub const MEDIUM_ORCHID: UniColor = UniColor(0xBA55D3FF);
+    /// CSS `mediumpurple`, `#9370db`.
+    pub const MEDIUM_PURPLE: UniColor = UniColor(0x9370DBFF);
+    /// CSS `mediumseagreen`, `#3cb371`.
+    pub const MEDIUM_SEA_GREEN: UniColor = UniColor(0x3CB371FF);
+    /// CSS `mediumslateblue`, `#7b68ee`.
+    pub const MEDIUM_SLATE_BLUE: UniColor = UniColor(0x7B68EEFF);
+    /// CSS `mediumspringgreen`, `#00fa9a`.
+    pub const MEDIUM_SPRING_GREEN: UniColor = UniColor(0x00FA9AFF);
+    /// CSS `mediumturquoise`, `#48d1cc`.
+    pub const MEDIUM_TURQUOISE: UniColor = UniColor(0x48D1CCFF);
+    /// CSS `mediumvioletred`, `#c71585`.
+    pub const MEDIUM_VIOLET_RED: UniColor = UniColor(0xC71585FF);
+    /// CSS `midnightblue`, `#191970`.
+    pub const MIDNIGHT_BLUE: UniColor = UniColor(0x191970FF);
+    /// CSS `mintcream`, `#f5fffa`.
+    pub const MINT_CREAM: UniColor = UniColor(0xF5FFFAFF);
+    /// CSS `mistyrose`, `#ffe4e1`.
+    pub const MISTY_ROSE: UniColor = UniColor(0xFFE4E1FF);
+    /// CSS `moccasin`, `#ffe4b5`.
+    pub const MOCCASIN: UniColor = UniColor(0xFFE4B5FF);
+    /// CSS `navajowhite`, `#ffdead`.
+    pub const NAVAJO_WHITE: UniColor = UniColor(0xFFDEADFF);
+    /// CSS `navy`, `#000080`.
+    pub const NAVY: UniColor = UniColor(0x000080FF);
+    /// CSS `oldlace`, `#fdf5e6`.
+    pub const OLD_LACE: UniColor = UniColor(0xFDF5E6FF);
+    /// CSS `olive`, `#808000`.
+    pub const OLIVE: UniColor = UniColor(0x808000FF);
+    /// CSS `olivedrab`, `#6b8e23`.
+    pub const OLIVE_DRAB: UniColor = UniColor(0x6B8E23FF);
+    /// CSS `orange`, `#ffa500`.
+    pub const ORANGE: UniColor = UniColor(0xFFA500FF);
+    /// CSS `orangered`, `#ff4500`.
+    pub const ORANGE_RED: UniColor = UniColor(0xFF4500FF);
+    /// CSS `orchid`, `#da70d6`.
+    pub const ORCHID: UniColor = UniColor(0xDA70D6FF);
+    /// CSS `palegoldenrod`, `#eee8aa`.
+    pub const PALE_GOLDENROD: UniColor = UniColor(0xEEE8AAFF);
+    /// CSS `palegreen`, `#98fb98`.
+    pub const PALE_GREEN: UniColor = UniColor(0x98FB98FF);
+    /// CSS `paleturquoise`, `#afeeee`.
+    pub const PALE_TURQUOISE: UniColor = UniColor(0xAFEEEEFF);
+    /// CSS `palevioletred`, `#db7093`.
+    pub const PALE_VIOLET_RED: UniColor = UniColor(0xDB7093FF);
+    /// CSS `papayawhip`, `#ffefd5`.
+    pub const PAPAYA_WHIP: UniColor = UniColor(0xFFEFD5FF);
+    /// CSS `peachpuff`, `#ffdab9`.
+    pub const PEACH_PUFF: UniColor = UniColor(0xFFDAB9FF);
+    /// CSS `peru`, `#cd853f`.
+    pub const PERU: UniColor = UniColor(0xCD853FFF);
+    /// CSS `pink`, `#ffc0cb`.
+    pub const PINK: UniColor = UniColor(0xFFC0CBFF);
+    /// CSS `plum`, `#dda0dd`.
+    pub const PLUM: UniColor = UniColor(0xDDA0DDFF);
+    /// CSS `powderblue`, `#b0e0e6`.
+    pub const POWDER_BLUE: UniColor = UniColor(0xB0E0E6FF);
+    /// CSS `purple`, `#800080`.
+    pub const PURPLE: UniColor = UniColor(0x800080FF);
+    /// CSS `rebeccapurple`, `#663399`.
+    pub const REBECCA_PURPLE: UniColor = UniColor(0x663399FF);
+    /// CSS `red`, `#ff0000`.
+    pub const RED: UniColor = UniColor(0xFF0000FF);
+    /// CSS `rosybrown`, `#bc8f8f`.
+    pub const ROSY_BROWN: UniColor = UniColor(0xBC8F8FFF);
+    /// CSS `royalblue`, `#4169e1`.
+    pub const ROYAL_BLUE: UniColor = UniColor(0x4169E1FF);
+    /// CSS `saddlebrown`, `#8b4513`.
+    pub const SADDLE_BROWN: UniColor = UniColor(0x8B4513FF);
+    /// CSS `salmon`, `#fa8072`.
+    pub const SALMON: UniColor = UniColor(0xFA8072FF);
+    /// CSS `sandybrown`, `#f4a460`.
+    pub const SANDY_BROWN: UniColor = UniColor(0xF4A460FF);
+    /// CSS `seagreen`, `#2e8b57`.
+    pub const SEA_GREEN: UniColor = UniColor(0x2E8B57FF);
+    /// CSS `seashell`, `#fff5ee`.
+    pub const SEASHELL: UniColor = UniColor(0xFFF5EEFF);
+    /// CSS `sienna`, `#a0522d`.
+    pub const SIENNA: UniColor = UniColor(0xA0522DFF);
+    /// CSS `silver`, `#c0c0c0`.
+    pub const SILVER: UniColor = UniColor(0xC0C0C0FF);
+    /// CSS `skyblue`, `#87ceeb`.
+    pub const SKY_BLUE: UniColor = UniColor(0x87CEEBFF);
+    /// CSS `slateblue`, `#6a5acd`.
+    pub const SLATE_BLUE: UniColor = UniColor(0x6A5ACDFF);
+    /// CSS `slategray`, `#708090`.
+    pub const SLATE_GRAY: UniColor = UniColor(0x708090FF);
+    /// CSS `slategrey`, `#708090`.
+    pub const SLATE_GREY: UniColor = UniColor(0x708090FF);
+    /// CSS `snow`, `#fffafa`.
+    pub const SNOW: UniColor = UniColor(0xFFFAFAFF);
+    /// CSS `springgreen`, `#00ff7f`.
+    pub const SPRING_GREEN: UniColor = UniColor(0x00FF7FFF);
+    /// CSS `steelblue`, `#4682b4`.
+    pub const STEEL_BLUE: UniColor = UniColor(0x4682B4FF);
+    /// CSS `tan`, `#d2b48c`.
+    pub const TAN: UniColor = UniColor(0xD2B48CFF);
+    /// CSS `teal`, `#008080`.
+    pub const TEAL: UniColor = UniColor(0x008080FF);
+    /// CSS `thistle`, `#d8bfd8`.
+    pub const THISTLE: UniColor = UniColor(0xD8BFD8FF);
+    /// CSS `tomato`, `#ff6347`.
+    pub const TOMATO: UniColor = UniColor(0xFF6347FF);
+    /// CSS `turquoise`, `#40e0d0`.
+    pub const TURQUOISE: UniColor = UniColor(0x40E0D0FF);
+    /// CSS `violet`, `#ee82ee`.
+    pub const VIOLET: UniColor = UniColor(0xEE82EEFF);
+    /// CSS `wheat`, `#f5deb3`.
+    pub const WHEAT: UniColor = UniColor(0xF5DEB3FF);
+    /// CSS `whitesmoke`, `#f5f5f5`.
+    pub const WHITE_SMOKE: UniColor = UniColor(0xF5F5F5FF);
+    /// CSS `yellow`, `#ffff00`.
+    pub const YELLOW: UniColor = UniColor(0xFFFF00FF);
+    /// CSS `yellowgreen`, `#9acd32`.
+    pub const YELLOW_GREEN: UniColor = UniColor(0x9ACD32FF);
+
+}
+
+/// Every CSS color name with its color, sorted by name for binary search. Both the gray and grey spellings are listed.
+const NAMED_COLORS: [(&str, UniColor); 148] = [
+    ("aliceblue", UniColor::ALICE_BLUE),
+    ("antiquewhite", UniColor::ANTIQUE_WHITE),
+    ("aqua", UniColor::AQUA),
+    ("aquamarine", UniColor::AQUAMARINE),
+    ("azure", UniColor::AZURE),
+    ("beige", UniColor::BEIGE),
+    ("bisque", UniColor::BISQUE),
+    ("black", UniColor::BLACK),
+    ("blanchedalmond", UniColor::BLANCHED_ALMOND),
+    ("blue", UniColor::BLUE),
+    ("blueviolet", UniColor::BLUE_VIOLET),
+    ("brown", UniColor::BROWN),
+    ("burlywood", UniColor::BURLYWOOD),
+    ("cadetblue", UniColor::CADET_BLUE),
+    ("chartreuse", UniColor::CHARTREUSE),
+    ("chocolate", UniColor::CHOCOLATE),
+    ("coral", UniColor::CORAL),
+    ("cornflowerblue", UniColor::CORNFLOWER_BLUE),
+    ("cornsilk", UniColor::CORNSILK),
+    ("crimson", UniColor::CRIMSON),
+    ("cyan", UniColor::CYAN),
+    ("darkblue", UniColor::DARK_BLUE),
+    ("darkcyan", UniColor::DARK_CYAN),
+    ("darkgoldenrod", UniColor::DARK_GOLDENROD),
+    ("darkgray", UniColor::DARK_GRAY),
+    ("darkgreen", UniColor::DARK_GREEN),
+    ("darkgrey", UniColor::DARK_GREY),
+    ("darkkhaki", UniColor::DARK_KHAKI),
+    ("darkmagenta", UniColor::DARK_MAGENTA),
+    ("darkolivegreen", UniColor::DARK_OLIVE_GREEN),
+    ("darkorange", UniColor::DARK_ORANGE),
+    ("darkorchid", UniColor::DARK_ORCHID),
+    ("darkred", UniColor::DARK_RED),
+    ("darksalmon", UniColor::DARK_SALMON),
+    ("darkseagreen", UniColor::DARK_SEA_GREEN),
+    ("darkslateblue", UniColor::DARK_SLATE_BLUE),
+    ("darkslategray", UniColor::DARK_SLATE_GRAY),
+    ("darkslategrey", UniColor::DARK_SLATE_GREY),
+    ("darkturquoise", UniColor::DARK_TURQUOISE),
+    ("darkviolet", UniColor::DARK_VIOLET),
+    ("deeppink", UniColor::DEEP_PINK),
+    ("deepskyblue", UniColor::DEEP_SKY_BLUE),
+    ("dimgray", UniColor::DIM_GRAY),
+    ("dimgrey", UniColor::DIM_GREY),
+    ("dodgerblue", UniColor::DODGER_BLUE),
+    ("firebrick", UniColor::FIREBRICK),
+    ("floralwhite", UniColor::FLORAL_WHITE),
+    ("forestgreen", UniColor::FOREST_GREEN),
+    ("fuchsia", UniColor::FUCHSIA),
+    ("gainsboro", UniColor::GAINSBORO),
+    ("ghostwhite", UniColor::GHOST_WHITE),
+    ("gold", UniColor::GOLD),
+    ("goldenrod", UniColor::GOLDENROD),
+    ("gray", UniColor::GRAY),
+    ("green", UniColor::GREEN),
+    ("greenyellow", UniColor::GREEN_YELLOW),
+    ("grey", UniColor::GREY),
+    ("honeydew", UniColor::HONEYDEW),
+    ("hotpink", UniColor::HOT_PINK),
+    ("indianred", UniColor::INDIAN_RED),
+    ("indigo", UniColor::INDIGO),
+    ("ivory", UniColor::IVORY),
+    ("khaki", UniColor::KHAKI),
+    ("lavender", UniColor::LAVENDER),
+    ("lavenderblush", UniColor::LAVENDER_BLUSH),
+    ("lawngreen", UniColor::LAWN_GREEN),
+    ("lemonchiffon", UniColor::LEMON_CHIFFON),
+    ("lightblue", UniColor::LIGHT_BLUE),
+    ("lightcoral", UniColor::LIGHT_CORAL),
+    ("lightcyan", UniColor::LIGHT_CYAN),
+    ("lightgoldenrodyellow", UniColor::LIGHT_GOLDENROD_YELLOW),
+    ("lightgray", UniColor::LIGHT_GRAY),
+    ("lightgreen", UniColor::LIGHT_GREEN),
+    ("lightgrey", UniColor::LIGHT_GREY),
+    ("lightpink", UniColor::LIGHT_PINK),
+    ("lightsalmon", UniColor::LIGHT_SALMON),
+    ("lightseagreen", UniColor::LIGHT_SEA_GREEN),
+    ("lightskyblue", UniColor::LIGHT_SKY_BLUE),
+    ("lightslategray", UniColor::LIGHT_SLATE_GRAY),
+    ("lightslategrey", UniColor::LIGHT_SLATE_GREY),
+    ("lightsteelblue", UniColor::LIGHT_STEEL_BLUE),
+    ("lightyellow", UniColor::LIGHT_YELLOW),
+    ("lime", UniColor::LIME),
+    ("limegreen", UniColor::LIME_GREEN),
+    ("linen", UniColor::LINEN),
+    ("magenta", UniColor::MAGENTA),
+    ("maroon", UniColor::MAROON),
+    ("mediumaquamarine", UniColor::MEDIUM_AQUAMARINE),
+    ("mediumblue", UniColor::MEDIUM_BLUE),
+    ("mediumorchid", UniColor::MEDIUM_ORCHID),
+    ("mediumpurple", UniColor::MEDIUM_PURPLE),
+    ("mediumseagreen", UniColor::MEDIUM_SEA_GREEN),
+    ("mediumslateblue", UniColor::MEDIUM_SLATE_BLUE),
+    ("mediumspringgreen", UniColor::MEDIUM_SPRING_GREEN),
+    ("mediumturquoise", UniColor::MEDIUM_TURQUOISE),
+    ("mediumvioletred", UniColor::MEDIUM_VIOLET_RED),
+    ("midnightblue", UniColor::MIDNIGHT_BLUE),
+    ("mintcream", UniColor::MINT_CREAM),
+    ("mistyrose", UniColor::MISTY_ROSE),
+    ("moccasin", UniColor::MOCCASIN),
+    ("navajowhite", UniColor::NAVAJO_WHITE),
+    ("navy", UniColor::NAVY),
+    ("oldlace", UniColor::OLD_LACE),
+    ("olive", UniColor::OLIVE),
+    ("olivedrab", UniColor::OLIVE_DRAB),
+    ("orange", UniColor::ORANGE),
+    ("orangered", UniColor::ORANGE_RED),
+    ("orchid", UniColor::ORCHID),
+    ("palegoldenrod", UniColor::PALE_GOLDENROD),
+    ("palegreen", UniColor::PALE_GREEN),
+    ("paleturquoise", UniColor::PALE_TURQUOISE),
+    ("palevioletred", UniColor::PALE_VIOLET_RED),
+    ("papayawhip", UniColor::PAPAYA_WHIP),
+    ("peachpuff", UniColor::PEACH_PUFF),
+    ("peru", UniColor::PERU),
+    ("pink", UniColor::PINK),
+    ("plum", UniColor::PLUM),
+    ("powderblue", UniColor::POWDER_BLUE),
+    ("purple", UniColor::PURPLE),
+    ("rebeccapurple", UniColor::REBECCA_PURPLE),
+    ("red", UniColor::RED),
+    ("rosybrown", UniColor::ROSY_BROWN),
+    ("royalblue", UniColor::ROYAL_BLUE),
+    ("saddlebrown", UniColor::SADDLE_BROWN),
+    ("salmon", UniColor::SALMON),
+    ("sandybrown", UniColor::SANDY_BROWN),
+    ("seagreen", UniColor::SEA_GREEN),
+    ("seashell", UniColor::SEASHELL),
+    ("sienna", UniColor::SIENNA),
+    ("silver", UniColor::SILVER),
+    ("skyblue", UniColor::SKY_BLUE),
+    ("slateblue", UniColor::SLATE_BLUE),
+    ("slategray", UniColor::SLATE_GRAY),
+    ("slategrey", UniColor::SLATE_GREY),
+    ("snow", UniColor::SNOW),
+    ("springgreen", UniColor::SPRING_GREEN),
+    ("steelblue", UniColor::STEEL_BLUE),
+    ("tan", UniColor::TAN),
+    ("teal", UniColor::TEAL),
+    ("thistle", UniColor::THISTLE),
+    ("tomato", UniColor::TOMATO),
+    ("turquoise", UniColor::TURQUOISE),
+    ("violet", UniColor::VIOLET),
+    ("wheat", UniColor::WHEAT),
+    ("white", UniColor::WHITE),
+    ("whitesmoke", UniColor::WHITE_SMOKE),
+    ("yellow", UniColor::YELLOW),
+    ("yellowgreen", UniColor::YELLOW_GREEN),
+];
+
+/// Looks up a CSS color name, ignoring its ASCII case.
+pub(crate) fn by_name(name: &str) -> Option<UniColor> {
+    // The table is lowercase, so comparing against the lowercased name keeps its order.
+    NAMED_COLORS.binary_search_by(|(n, _)| n.bytes().cmp(name.bytes().map(|b| b.to_ascii_lowercase())))
+        .ok()
+        .map(|i| NAMED_COLORS[i].1)
+}
\ No newline at end of file
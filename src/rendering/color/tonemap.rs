@@ -0,0 +1,115 @@
+//! All operators work on the RGB channels of linear light colors and leave alpha untouched.
+//! Negative and NaN channels are treated as 0, so the results always lie in [0, 1].
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::rendering::color::ColorF;
+
+/// Scales the color by `2^ev`, like changing the exposure of a camera by `ev` stops.
+/// Apply it before the tone-mapping curve.
+#[inline]
+pub fn apply_exposure(c: ColorF, ev: f32) -> ColorF {
+    let scale = ev.exp2();
+    c.map_rgb(|x| x * scale)
+}
+
+/// The classic Reinhard operator `x / (1 + x)`. Approaches but never exceeds 1, so nothing is ever fully white.
+#[inline]
+pub fn reinhard(c: ColorF) -> ColorF {
+    c.map_rgb(|x| {
+        let x = sanitize(x);
+        x / (1.0 + x)
+    })
+}
+
+/// The extended Reinhard operator `x * (1 + x / white²) / (1 + x)`, which maps `white_point` to exactly 1.
+/// Values above the white point are clamped to 1. A non-positive white point gives black.
+#[inline]
+pub fn reinhard_extended(c: ColorF, white_point: f32) -> ColorF {
+    let inv_white_squared = 1.0 / (white_point * white_point);
+    c.map_rgb(|x| {
+        let x = sanitize(x);
+        if white_point > 0.0 {
+            (x * (1.0 + x * inv_white_squared) / (1.0 + x)).min(1.0)
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Krzysztof Narkowicz's fit of the ACES filmic curve, giving more contrast and softer highlights than Reinhard.
+/// Mid-gray (0.18) maps to about 0.27.
+#[inline]
+pub fn aces_approx(c: ColorF) -> ColorF {
+    const A: f32 = 2.51;
+    const B: f32 = 0.03;
+    const C: f32 = 2.43;
+    const D: f32 = 0.59;
+    const E: f32 = 0.14;
+    c.map_rgb(|x| {
+        // The curve is flat long before this, the limit only keeps the squares from overflowing.
+        let x = sanitize(x).min(1.0e4);
+        ((x * (A * x + B)) / (x * (C * x + D) + E)).clamp(0.0, 1.0)
+    })
+}
+
+/// Maps negative and NaN values to 0 and infinity to the largest finite float.
+#[inline]
+fn sanitize(x: f32) -> f32 {
+    if x.is_nan() { 0.0 } else { x.clamp(0.0, f32::MAX) }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn gray(x: f32) -> ColorF {
+        ColorF::new(x, x, x, 0.5)
+    }
+
+    type Operator = fn(ColorF) -> ColorF;
+
+    fn operators() -> [(&'static str, Operator); 4] {
+        [
+            ("reinhard", reinhard),
+            ("reinhard_extended", |c| reinhard_extended(c, 4.0)),
+            ("reinhard_extended without a white point", |c| reinhard_extended(c, 0.0)),
+            ("aces_approx", aces_approx),
+        ]
+    }
+
+    #[test]
+    fn zero_maps_to_zero_and_huge_values_stay_below_one() {
+        for (name, operator) in operators() {
+            assert_eq!(operator(gray(0.0)), gray(0.0), "{}", name);
+            for x in [1.0, 100.0, 1e20, f32::MAX, f32::INFINITY] {
+                let mapped = operator(gray(x));
+                assert!(mapped.r <= 1.0 && mapped.r >= 0.0, "{} maps {} to {}", name, x, mapped.r);
+                assert_eq!(mapped.a, 0.5);
+            }
+            for x in [-1.0, f32::NEG_INFINITY, f32::NAN] {
+                assert_eq!(operator(gray(x)), gray(0.0), "{} of {}", name, x);
+            }
+        }
+        assert_eq!(reinhard_extended(gray(4.0), 4.0).r, 1.0);
+        assert_eq!(reinhard_extended(gray(9.0), 4.0).r, 1.0);
+        assert_eq!(reinhard(gray(1.0)).r, 0.5);
+    }
+
+    #[test]
+    fn aces_maps_mid_gray_to_about_0_27() {
+        let mapped = aces_approx(gray(0.18));
+        assert!((mapped.r - 0.27).abs() < 0.005, "{}", mapped.r);
+        assert_eq!(mapped.r, mapped.b);
+    }
+
+    #[test]
+    fn exposure_scales_by_powers_of_two() {
+        let c = ColorF::new(0.1, 0.7, 3.0, 0.25);
+        assert_eq!(apply_exposure(c, 1.0), ColorF::new(0.2, 1.4, 6.0, 0.25));
+        assert_eq!(apply_exposure(c, -2.0), ColorF::new(0.025, 0.175, 0.75, 0.25));
+        assert_eq!(apply_exposure(c, 0.0), c);
+    }
+
+}
\ No newline at end of file
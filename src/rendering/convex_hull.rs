@@ -0,0 +1,373 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::rendering::color::UniColor;
+use crate::rendering::mesh::Mesh;
+use crate::rendering::vertex::Vertex;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+
+/// Computes the convex hull of `points` with the quickhull algorithm, e.g. to generate a collision shape from a render mesh.
+///
+/// The hull is returned as a mesh with counter-clockwise, outward facing triangles. Every triangle has its own three vertices
+/// carrying its flat face normal, so it can be rendered as is. Coplanar points on a hull face are merged into that face,
+/// so the 8 corners of a cube give exactly 12 triangles. The face planes are computed in `f64`, so only points closer to
+/// a plane than the `f64` rounding error count as coplanar. A larger tolerance would let thin faces bend the hull inward.
+///
+/// Fails on fewer than 4 points, non-finite coordinates and input without volume (coincident, collinear or coplanar points).
+pub fn convex_hull(points: &[Vector3]) -> Result<Mesh, HullError> {
+    if points.len() < 4 {
+        return Err(HullError::TooFewPoints { count: points.len() });
+    }
+    if let Some(index) = points.iter().position(|p| !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite())) {
+        return Err(HullError::NonFinitePoint { index });
+    }
+
+    let max_abs = points.iter().fold(Vector3::zero(), |m, p| Vector3::new(m.x.max(p.x.abs()), m.y.max(p.y.abs()), m.z.max(p.z.abs())));
+    let scale = max_abs.x + max_abs.y + max_abs.z;
+    let epsilon = 8.0 * f32::EPSILON * scale;
+
+    let simplex = initial_simplex(points, epsilon)?;
+    let mut hull = Hull { points, faces: Vec::new(), edges: HashMap::new(), pending: Vec::new(), epsilon: 8.0 * f64::EPSILON * scale as f64 };
+    hull.add_simplex(simplex);
+
+    while let Some(face) = hull.pending.pop() {
+        // Faces replaced since they were queued have handed their points on to the new faces.
+        if hull.faces[face].alive {
+            hull.add_point(face);
+        }
+    }
+
+    let mut vertices = Vec::new();
+    for face in hull.faces.iter().filter(|f| f.alive) {
+        for &v in &face.vertices {
+            vertices.push(Vertex::new(points[v], face.normal, Vector2::zero(), UniColor::from_rgb(255, 255, 255)));
+        }
+    }
+    let indices = (0..vertices.len() as u32).collect();
+    Ok(Mesh::new(vertices, indices))
+}
+
+/// Returned by [`convex_hull`] if the points do not span a volume.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HullError {
+    /// At least 4 points are needed for a hull with volume.
+    TooFewPoints { count: usize },
+    /// The point at `index` has a NaN or infinite coordinate.
+    NonFinitePoint { index: usize },
+    /// All points are at the same position or on one line.
+    Collinear,
+    /// All points lie in one plane, so the hull would be flat.
+    Coplanar,
+}
+
+impl Display for HullError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HullError::TooFewPoints { count } => write!(f, "a convex hull needs at least 4 points, got {}", count),
+            HullError::NonFinitePoint { index } => write!(f, "point {} has a non-finite coordinate", index),
+            HullError::Collinear => write!(f, "all points are on one line"),
+            HullError::Coplanar => write!(f, "all points are in one plane"),
+        }
+    }
+}
+
+impl Error for HullError {}
+
+/// A hull triangle with the points which are still outside of it.
+struct Face {
+    vertices: [usize; 3],
+    normal: Vector3,
+    /// The plane in `f64`, whose normals stay precise for the thin slivers a hull of many points consists of.
+    plane: [f64; 4],
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl Face {
+
+    /// Returns the signed distance of `point` to the plane of the face, positive outside.
+    #[inline]
+    fn distance(&self, point: Vector3) -> f64 {
+        let [x, y, z, offset] = self.plane;
+        x * point.x as f64 + y * point.y as f64 + z * point.z as f64 - offset
+    }
+
+}
+
+struct Hull<'a> {
+    points: &'a [Vector3],
+    faces: Vec<Face>,
+    /// The face left of every directed edge of the alive faces, the face across an edge (a, b) is at (b, a).
+    edges: HashMap<(usize, usize), usize>,
+    /// The faces whose outside set became non-empty, so the next face to expand is found without scanning all of them.
+    pending: Vec<usize>,
+    /// The plane distance up to which points count as coplanar, much smaller than the one of `initial_simplex`.
+    epsilon: f64,
+}
+
+impl Hull<'_> {
+
+    /// Builds the tetrahedron from 4 points spanning a volume, winding every face outward.
+    fn add_simplex(&mut self, [a, b, c, d]: [usize; 4]) {
+        let p = self.points;
+        let (a, b, c) = if (p[b] - p[a]).cross(&(p[c] - p[a])).dot(&(p[d] - p[a])) > 0.0 { (a, c, b) } else { (a, b, c) };
+        let faces = [
+            self.add_face([a, b, c]),
+            self.add_face([a, d, b]),
+            self.add_face([b, d, c]),
+            self.add_face([c, d, a]),
+        ];
+
+        let simplex = [a, b, c, d];
+        for point in (0..p.len()).filter(|i| !simplex.contains(i)) {
+            self.assign(point, &faces);
+        }
+    }
+
+    /// Adds the farthest outside point of `face` to the hull, replacing all faces it can see.
+    fn add_point(&mut self, face: usize) {
+        let eye = *self.faces[face].outside.iter()
+            .max_by(|&&a, &&b| self.faces[face].distance(self.points[a]).total_cmp(&self.faces[face].distance(self.points[b])))
+            .unwrap();
+        let eye_point = self.points[eye];
+
+        // Walk the faces visible from the eye, starting from `face`, which always sees it.
+        let mut visible = HashSet::from([face]);
+        let mut stack = vec![face];
+        let mut horizon = Vec::new();
+        while let Some(current) = stack.pop() {
+            let [a, b, c] = self.faces[current].vertices;
+            for edge in [(a, b), (b, c), (c, a)] {
+                let neighbor = self.edges[&(edge.1, edge.0)];
+                if visible.contains(&neighbor) {
+                    continue;
+                }
+                if self.faces[neighbor].distance(eye_point) > self.epsilon {
+                    visible.insert(neighbor);
+                    stack.push(neighbor);
+                } else {
+                    horizon.push(edge);
+                }
+            }
+        }
+
+        let mut orphans = Vec::new();
+        for &index in &visible {
+            let [a, b, c] = self.faces[index].vertices;
+            for edge in [(a, b), (b, c), (c, a)] {
+                self.edges.remove(&edge);
+            }
+            let face = &mut self.faces[index];
+            face.alive = false;
+            orphans.append(&mut face.outside);
+        }
+
+        let new_faces: Vec<usize> = horizon.iter().map(|&(a, b)| self.add_face([a, b, eye])).collect();
+        for point in orphans.into_iter().filter(|&point| point != eye) {
+            self.assign(point, &new_faces);
+        }
+    }
+
+    fn add_face(&mut self, vertices: [usize; 3]) -> usize {
+        let [a, b, c] = vertices.map(|v| {
+            let p = self.points[v];
+            [p.x as f64, p.y as f64, p.z as f64]
+        });
+        let (u, v) = ([b[0] - a[0], b[1] - a[1], b[2] - a[2]], [c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+        let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+        let length = (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt();
+        let [x, y, z] = cross.map(|c| if length > 0.0 { c / length } else { 0.0 });
+        let plane = [x, y, z, x * a[0] + y * a[1] + z * a[2]];
+        let normal = Vector3::new(x as f32, y as f32, z as f32);
+        let index = self.faces.len();
+        self.faces.push(Face { vertices, normal, plane, outside: Vec::new(), alive: true });
+        for edge in [(vertices[0], vertices[1]), (vertices[1], vertices[2]), (vertices[2], vertices[0])] {
+            self.edges.insert(edge, index);
+        }
+        index
+    }
+
+    /// Puts `point` into the outside set of the candidate face it is farthest above, or drops it if it is inside all of them.
+    fn assign(&mut self, point: usize, candidates: &[usize]) {
+        let position = self.points[point];
+        let best = candidates.iter()
+            .map(|&face| (face, self.faces[face].distance(position)))
+            .filter(|&(_, distance)| distance > self.epsilon)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((face, _)) = best {
+            let outside = &mut self.faces[face].outside;
+            if outside.is_empty() {
+                self.pending.push(face);
+            }
+            outside.push(point);
+        }
+    }
+
+}
+
+/// Picks 4 points spanning a large tetrahedron, starting from the two farthest apart axis extremes.
+fn initial_simplex(points: &[Vector3], epsilon: f32) -> Result<[usize; 4], HullError> {
+    let mut extremes = [0; 6];
+    for (i, p) in points.iter().enumerate() {
+        for axis in 0..3 {
+            if p[axis] < points[extremes[axis * 2]][axis] {
+                extremes[axis * 2] = i;
+            }
+            if p[axis] > points[extremes[axis * 2 + 1]][axis] {
+                extremes[axis * 2 + 1] = i;
+            }
+        }
+    }
+
+    let (mut a, mut b, mut longest) = (0, 0, 0.0);
+    for &i in &extremes {
+        for &j in &extremes {
+            let length = (points[i] - points[j]).magnitude_squared();
+            if length > longest {
+                (a, b, longest) = (i, j, length);
+            }
+        }
+    }
+    if longest.sqrt() <= epsilon {
+        return Err(HullError::Collinear);
+    }
+
+    let line = (points[b] - points[a]).normalized();
+    let line_distance = |p: Vector3| (p - points[a]).cross(&line).magnitude();
+    let c = farthest(points, line_distance);
+    if line_distance(points[c]) <= epsilon {
+        return Err(HullError::Collinear);
+    }
+
+    let normal = (points[b] - points[a]).cross(&(points[c] - points[a])).normalized();
+    let plane_distance = |p: Vector3| normal.dot(&(p - points[a])).abs();
+    let d = farthest(points, plane_distance);
+    if plane_distance(points[d]) <= epsilon {
+        return Err(HullError::Coplanar);
+    }
+
+    Ok([a, b, c, d])
+}
+
+/// Returns the index of the point with the largest `distance`.
+fn farthest(points: &[Vector3], distance: impl Fn(Vector3) -> f32) -> usize {
+    (0..points.len()).max_by(|&i, &j| distance(points[i]).total_cmp(&distance(points[j]))).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::matrix4x4::Matrix4x4;
+    use super::*;
+
+    /// A small xorshift generator, so the noise is the same on every run.
+    struct Rng(u32);
+
+    impl Rng {
+
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 >> 8) as f32 / (1 << 24) as f32
+        }
+
+        fn point(&mut self, scale: f32) -> Vector3 {
+            Vector3::new(self.next_f32() - 0.5, self.next_f32() - 0.5, self.next_f32() - 0.5) * scale
+        }
+
+    }
+
+    /// Checks that every point is inside or on every face of `hull` and that the faces point outward.
+    /// The planes are computed in `f64`, since the normals of sliver faces are too imprecise in `f32`.
+    fn assert_contains(hull: &Mesh, points: &[Vector3]) {
+        let wide = |v: Vector3| [v.x as f64, v.y as f64, v.z as f64];
+        let sub = |a: [f64; 3], b: [f64; 3]| [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let center = points.iter().fold(Vector3::zero(), |sum, &p| sum + p) * (1.0 / points.len() as f32);
+        for triangle in hull.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| wide(hull.vertices[triangle[k] as usize].position));
+            let (u, v) = (sub(b, a), sub(c, a));
+            let normal = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+            let length = dot(normal, normal).sqrt();
+            assert!(dot(normal, sub(a, wide(center))) > 0.0, "a face points inward");
+            for &point in points {
+                let distance = dot(normal, sub(wide(point), a)) / length;
+                assert!(distance <= 1e-5, "{:?} is outside by {}", point, distance);
+            }
+        }
+    }
+
+    #[test]
+    fn cube_with_interior_noise_gives_12_triangles() {
+        let mut rng = Rng(0x2545_f491);
+        let mut points: Vec<Vector3> = (0..8)
+            .map(|i| Vector3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32) * 2.0 - Vector3::new(1.0, 1.0, 1.0))
+            .collect();
+        points.extend((0..500).map(|_| rng.point(1.9)));
+        let hull = convex_hull(&points).unwrap();
+        assert_eq!(hull.indices.len(), 36);
+        assert_contains(&hull, &points);
+    }
+
+    #[test]
+    fn random_points_lie_inside_the_hull() {
+        let mut rng = Rng(0x1234_5678);
+        let points: Vec<Vector3> = (0..5000).map(|_| rng.point(10.0)).collect();
+        let hull = convex_hull(&points).unwrap();
+        assert_contains(&hull, &points);
+
+        // Points on a sphere are all on the hull, which exercises many horizon updates.
+        let sphere: Vec<Vector3> = (0..2000).map(|_| rng.point(2.0).normalized()).collect();
+        let hull = convex_hull(&sphere).unwrap();
+        assert_contains(&hull, &sphere);
+        assert!(hull.indices.len() / 3 > 1000);
+    }
+
+    #[test]
+    fn rotated_cube_with_points_on_its_faces_stays_closed() {
+        // Rotating rounds the face points off their planes, so the faces are not exactly flat anymore.
+        let rotation = Matrix4x4::rotation_x(0.3) * Matrix4x4::rotation_y(0.7) * Matrix4x4::rotation_z(1.1);
+        let mut points = Vec::new();
+        for axis in 0..3 {
+            for side in [-1.0, 1.0] {
+                for i in 0..=10 {
+                    for j in 0..=10 {
+                        let mut p = Vector3::zero();
+                        p[axis] = side;
+                        p[(axis + 1) % 3] = i as f32 / 5.0 - 1.0;
+                        p[(axis + 2) % 3] = j as f32 / 5.0 - 1.0;
+                        points.push(rotation.transform_point(p));
+                    }
+                }
+            }
+        }
+        let hull = convex_hull(&points).unwrap();
+        assert_contains(&hull, &points);
+
+        // Every edge is shared by exactly two triangles, once in each direction.
+        let mut edges = HashSet::new();
+        for triangle in hull.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| hull.vertices[triangle[k] as usize].position.to_array().map(f32::to_bits));
+            for edge in [(a, b), (b, c), (c, a)] {
+                assert!(edges.insert(edge), "an edge is used twice in the same direction");
+            }
+        }
+        assert!(edges.iter().all(|&(a, b)| edges.contains(&(b, a))), "the hull has a hole");
+    }
+
+    #[test]
+    fn degenerate_input_gives_the_documented_errors() {
+        let p = |x: f32, y: f32, z: f32| Vector3::new(x, y, z);
+        assert_eq!(convex_hull(&[p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(0.0, 1.0, 0.0)]).unwrap_err(), HullError::TooFewPoints { count: 3 });
+        assert_eq!(convex_hull(&[p(1.0, 2.0, 3.0); 6]).unwrap_err(), HullError::Collinear);
+        let line: Vec<Vector3> = (0..10).map(|i| p(i as f32, 2.0 * i as f32, 0.5)).collect();
+        assert_eq!(convex_hull(&line).unwrap_err(), HullError::Collinear);
+        let plane: Vec<Vector3> = (0..10).map(|i| p(i as f32, (i * i % 7) as f32, 3.0)).collect();
+        assert_eq!(convex_hull(&plane).unwrap_err(), HullError::Coplanar);
+        let with_nan = [p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(0.0, f32::NAN, 0.0), p(0.0, 0.0, 1.0)];
+        assert_eq!(convex_hull(&with_nan).unwrap_err(), HullError::NonFinitePoint { index: 2 });
+    }
+
+}
\ No newline at end of file
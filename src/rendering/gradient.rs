@@ -0,0 +1,157 @@
+use crate::rendering::color::UniColor;
+
+/// How a `Gradient` blends between two neighboring stops.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GradientInterpolation {
+    /// Interpolates the gamma-encoded bytes, see `UniColor::lerp`. Matches CSS gradients.
+    #[default]
+    Rgb,
+    /// Interpolates in linear light, see `UniColor::lerp_linear`.
+    Linear,
+    /// Interpolates hue, saturation and lightness, see `UniColor::lerp_hsl`. Keeps blends across hues vivid.
+    Hsl,
+}
+
+impl GradientInterpolation {
+
+    /// Blends `a` into `b` by `t` in [0, 1].
+    #[inline]
+    fn blend(&self, a: &UniColor, b: &UniColor, t: f32) -> UniColor {
+        match self {
+            GradientInterpolation::Rgb => a.lerp(b, t),
+            GradientInterpolation::Linear => a.lerp_linear(b, t),
+            GradientInterpolation::Hsl => a.lerp_hsl(b, t),
+        }
+    }
+
+}
+
+/// A color gradient over [0, 1] through a list of color stops, e.g. for heatmaps and UI theming.
+///
+/// Stops may be given in any order, they are sorted by position. Stops sharing a position form a hard edge:
+/// the gradient blends into the first of them and continues from the last, which is also the color at the position itself.
+/// Before the first stop the gradient keeps the color of the first stop, after the last stop the color of the last one.
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    stops: Vec<(f32, UniColor)>,
+    interpolation: GradientInterpolation,
+}
+
+impl Gradient {
+
+    /// Creates a gradient through the given `(position, color)` stops, interpolating in RGB.
+    /// Stops with a position that is not finite are dropped.
+    pub fn new(stops: Vec<(f32, UniColor)>) -> Self {
+        let mut stops = stops;
+        stops.retain(|(position, _)| position.is_finite());
+        // The sort is stable, so stops sharing a position keep their order.
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops, interpolation: GradientInterpolation::Rgb }
+    }
+
+    /// Creates a gradient through `colors` spread evenly over [0, 1], starting with the first color at 0.
+    pub fn evenly_spaced(colors: &[UniColor]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(colors.iter().enumerate().map(|(i, &color)| (i as f32 / last, color)).collect())
+    }
+
+    /// Returns a copy that blends between the stops with `interpolation`.
+    #[inline]
+    pub fn with_interpolation(&self, interpolation: GradientInterpolation) -> Self {
+        Self { interpolation, ..self.clone() }
+    }
+
+    /// Returns the stops sorted by position.
+    #[inline]
+    pub fn stops(&self) -> &[(f32, UniColor)] {
+        &self.stops
+    }
+
+    /// Returns how the gradient blends between the stops.
+    #[inline]
+    pub fn interpolation(&self) -> GradientInterpolation {
+        self.interpolation
+    }
+
+    /// Returns the color at `t`, which is clamped to [0, 1]. Exactly at a stop this is the color of the stop.
+    /// A gradient without stops is transparent everywhere.
+    pub fn sample(&self, t: f32) -> UniColor {
+        let t = t.clamp(0.0, 1.0);
+        // The first stop after `t`, so `t` lies in [stops[next - 1].0, stops[next].0). A NaN `t` lands before all stops.
+        let next = self.stops.partition_point(|(position, _)| *position <= t);
+        match (next.checked_sub(1).map(|i| self.stops[i]), self.stops.get(next)) {
+            (None, None) => UniColor::TRANSPARENT,
+            (None, Some(&(_, color))) | (Some((_, color)), None) => color,
+            (Some((start, from)), Some(&(end, to))) => {
+                if t == start {
+                    from
+                } else {
+                    self.interpolation.blend(&from, &to, (t - start) / (end - start))
+                }
+            }
+        }
+    }
+
+    /// Returns `n` colors sampled at evenly spaced positions from 0 to 1, e.g. as a palette for charts.
+    /// A single color is sampled at 0.
+    pub fn sample_n(&self, n: usize) -> Vec<UniColor> {
+        let last = n.saturating_sub(1).max(1) as f32;
+        (0..n).map(|i| self.sample(i as f32 / last)).collect()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sampling_at_a_stop_returns_its_color() {
+        let (red, lime, blue) = (UniColor::RED, UniColor::LIME, UniColor::BLUE);
+        for interpolation in [GradientInterpolation::Rgb, GradientInterpolation::Linear, GradientInterpolation::Hsl] {
+            let gradient = Gradient::new(vec![(0.1, red), (0.4, lime), (0.9, blue)]).with_interpolation(interpolation);
+            assert_eq!(gradient.sample(0.1).to_rgba(), red.to_rgba());
+            assert_eq!(gradient.sample(0.4).to_rgba(), lime.to_rgba());
+            assert_eq!(gradient.sample(0.9).to_rgba(), blue.to_rgba());
+            // Outside the stops the end colors are kept, NaN lands before all stops.
+            assert_eq!(gradient.sample(0.0).to_rgba(), red.to_rgba());
+            assert_eq!(gradient.sample(f32::NAN).to_rgba(), red.to_rgba());
+            assert_eq!(gradient.sample(5.0).to_rgba(), blue.to_rgba());
+        }
+        let gradient = Gradient::new(vec![(0.0, red), (0.5, lime), (1.0, blue)]);
+        assert_eq!(gradient.sample(0.25).to_rgba(), red.lerp(&lime, 0.5).to_rgba());
+        assert_eq!(gradient.sample_n(3).iter().map(UniColor::to_rgba).collect::<Vec<_>>(), [red, lime, blue].map(|c| c.to_rgba()));
+        let stops = |gradient: &Gradient| gradient.stops().iter().map(|(t, c)| (*t, c.to_rgba())).collect::<Vec<_>>();
+        assert_eq!(stops(&Gradient::evenly_spaced(&[red, lime, blue])), stops(&gradient));
+    }
+
+    #[test]
+    fn hsl_gradients_stay_vivid() {
+        let gradient = Gradient::new(vec![(0.0, UniColor::RED), (1.0, UniColor::BLUE)]);
+        assert_eq!(gradient.sample(0.5).to_rgb(), (128, 0, 128));
+        let hsl = gradient.with_interpolation(GradientInterpolation::Hsl);
+        assert_eq!(hsl.interpolation(), GradientInterpolation::Hsl);
+        assert_eq!(hsl.sample(0.5).to_rgb(), (255, 0, 255));
+    }
+
+    #[test]
+    fn stops_are_sorted_and_duplicates_form_a_hard_edge() {
+        let (black, red, blue, white) = (UniColor::BLACK, UniColor::RED, UniColor::BLUE, UniColor::WHITE);
+        let gradient = Gradient::new(vec![(1.0, white), (0.5, red), (f32::NAN, black), (0.0, black), (0.5, blue), (f32::INFINITY, red)]);
+        let positions: Vec<f32> = gradient.stops().iter().map(|stop| stop.0).collect();
+        assert_eq!(positions, [0.0, 0.5, 0.5, 1.0]);
+        // Stops sharing a position keep the order they were given in.
+        assert_eq!(gradient.stops()[1].1.to_rgba(), red.to_rgba());
+
+        // The gradient blends into the first stop of the edge and continues from the last, which is the color at the edge.
+        assert_eq!(gradient.sample(0.25).to_rgba(), black.lerp(&red, 0.5).to_rgba());
+        assert_eq!(gradient.sample(0.4999).to_rgb(), red.to_rgb());
+        assert_eq!(gradient.sample(0.5).to_rgba(), blue.to_rgba());
+        assert_eq!(gradient.sample(0.75).to_rgba(), blue.lerp(&white, 0.5).to_rgba());
+
+        assert_eq!(Gradient::new(Vec::new()).sample(0.5).to_rgba(), UniColor::TRANSPARENT.to_rgba());
+        assert_eq!(Gradient::new(vec![(0.3, red)]).sample(0.9).to_rgba(), red.to_rgba());
+    }
+
+}
\ No newline at end of file
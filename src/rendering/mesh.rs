@@ -0,0 +1,1616 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::f32::consts::{PI, TAU};
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read, Write};
+use crate::binary::{self, BinaryReadError};
+use crate::geometry::aabb::{Aabb, AabbBuilder};
+use crate::geometry::ray::Ray;
+use crate::math::Fnv1a;
+use crate::rendering::adjacency::{MeshAdjacency, NonManifoldError};
+use crate::rendering::color::UniColor;
+use crate::rendering::spatial_grid::SpatialGrid;
+use crate::rendering::vertex::{compute_tangents, Vertex};
+use crate::types::Axis;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
+use crate::vectors::vector4::Vector4;
+
+const MESH_MAGIC: [u8; 4] = *b"FGMS";
+const MESH_VERSION: u16 = 1;
+
+/// A triangle mesh made of shared vertices and a list of indices into them, three per triangle.
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+
+    /// Creates a new mesh from the given vertices and indices.
+    pub fn new(vertices: Vec<Vertex>, indices: Vec<u32>) -> Self {
+        Self { vertices, indices }
+    }
+
+    /// Creates a mesh from vertices and indices, checking that the indices form whole triangles and all point at a vertex.
+    /// Unlike `new`, which accepts anything and leaves broken indices to panic later.
+    pub fn indexed(vertices: Vec<Vertex>, indices: Vec<u32>) -> Result<Mesh, MeshIndexError> {
+        if !indices.len().is_multiple_of(3) {
+            return Err(MeshIndexError::IncompleteTriangle { index_count: indices.len() });
+        }
+        if let Some(&index) = indices.iter().find(|&&index| index as usize >= vertices.len()) {
+            return Err(MeshIndexError::OutOfRange { index, vertex_count: vertices.len() });
+        }
+        Ok(Mesh::new(vertices, indices))
+    }
+
+    /// Creates a mesh from a triangle list without shared vertices, every three consecutive vertices form a triangle.
+    /// Returns an error if the vertex count is not a multiple of 3.
+    pub fn non_indexed(vertices: Vec<Vertex>) -> Result<Mesh, MeshIndexError> {
+        let indices = (0..vertices.len() as u32).collect();
+        Mesh::indexed(vertices, indices)
+    }
+
+    /// Returns the box around all vertex positions, or `None` if the mesh has no vertices.
+    /// Vertices not used by any triangle are included.
+    pub fn compute_bounds(&self) -> Option<Aabb> {
+        let mut builder = AabbBuilder::new();
+        for vertex in &self.vertices {
+            builder.add_vertex(vertex);
+        }
+        builder.build()
+    }
+
+    /// Returns the interleaved vertex buffer (see `Vertex::layout`) and the index buffer as native-endian `u32`, ready for uploading.
+    pub fn to_bytes(&self) -> (Vec<u8>, Vec<u8>) {
+        let indices = self.indices.iter().flat_map(|index| index.to_ne_bytes()).collect();
+        (Vertex::bytes_of_slice(&self.vertices), indices)
+    }
+
+    /// Creates a regular grid on the XZ plane, centered at the origin and spanning `size`,
+    /// with `resolution` cells along x and z. The height (y) of every vertex comes from `height_fn(x, z)`.
+    ///
+    /// UVs span [0, 1] over the whole grid, colors are white and the triangles face +Y.
+    /// The normals are computed from central differences of `height_fn` instead of the triangles,
+    /// so they are smooth instead of faceted. A zero `size` component collapses the grid along that axis,
+    /// the normals then ignore the slope along it instead of dividing by zero.
+    pub fn heightmap(size: Vector2, resolution: (u32, u32), height_fn: impl Fn(f32, f32) -> f32) -> Mesh {
+        let nx = resolution.0.max(1);
+        let nz = resolution.1.max(1);
+        let ex = size.x / nx as f32 * 0.5;
+        let ez = size.y / nz as f32 * 0.5;
+
+        let slope = |ahead: f32, behind: f32, e: f32| if e == 0.0 { 0.0 } else { (ahead - behind) / (2.0 * e) };
+
+        let mut vertices = Vec::with_capacity((nx as usize + 1) * (nz as usize + 1));
+        for j in 0..=nz {
+            for i in 0..=nx {
+                let u = i as f32 / nx as f32;
+                let v = j as f32 / nz as f32;
+                let x = (u - 0.5) * size.x;
+                let z = (v - 0.5) * size.y;
+
+                let dx = slope(height_fn(x + ex, z), height_fn(x - ex, z), ex);
+                let dz = slope(height_fn(x, z + ez), height_fn(x, z - ez), ez);
+                let normal = Vector3::new(-dx, 1.0, -dz);
+
+                vertices.push(Vertex::new(
+                    Vector3::new(x, height_fn(x, z), z),
+                    normal * (1.0 / normal.magnitude_squared().sqrt()),
+                    Vector2::new(u, v),
+                    UniColor::from_rgb(255, 255, 255),
+                ));
+            }
+        }
+
+        Mesh::new(vertices, grid_indices(nx, nz))
+    }
+
+    /// Recomputes smooth vertex normals by summing up the normals of all faces using a vertex and normalizing the result.
+    /// Faces are weighted by their area, so degenerate (zero-area) triangles contribute nothing.
+    /// Vertices not used by any triangle keep a zero normal.
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vector3::zero(); self.vertices.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let a = self.vertices[triangle[0] as usize].position;
+            let b = self.vertices[triangle[1] as usize].position;
+            let c = self.vertices[triangle[2] as usize].position;
+            // The length of the cross product is twice the area of the triangle.
+            let face_normal = (b - a).cross(&(c - a));
+            for &i in triangle {
+                normals[i as usize] += face_normal;
+            }
+        }
+
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            vertex.normal = normalize_accumulated(normal);
+        }
+    }
+
+    /// Like `compute_normals`, but spreads the work over all cores with the `rayon` feature.
+    /// Every vertex still sums its face normals in triangle order, so the normals are bit for bit the same
+    /// as the ones of `compute_normals`. Without the feature this is `compute_normals`.
+    pub fn compute_normals_par(&mut self) {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            let vertices = &self.vertices;
+            let face_normals: Vec<Vector3> = self.indices.par_chunks_exact(3).map(|triangle| {
+                let a = vertices[triangle[0] as usize].position;
+                let b = vertices[triangle[1] as usize].position;
+                let c = vertices[triangle[2] as usize].position;
+                (b - a).cross(&(c - a))
+            }).collect();
+
+            let (offsets, vertex_triangles) = self.vertex_triangles();
+            self.vertices.par_iter_mut().enumerate().for_each(|(v, vertex)| {
+                let mut normal = Vector3::zero();
+                for &t in &vertex_triangles[offsets[v]..offsets[v + 1]] {
+                    normal += face_normals[t];
+                }
+                vertex.normal = normalize_accumulated(normal);
+            });
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.compute_normals();
+    }
+
+    /// Computes a tangent for every vertex for normal mapping, see `vertex::compute_tangents`.
+    #[inline]
+    pub fn compute_tangents(&self) -> Vec<Vector4> {
+        compute_tangents(&self.vertices, &self.indices)
+    }
+
+    /// Like `compute_tangents`, but spreads the work over all cores with the `rayon` feature.
+    /// Like `compute_normals_par`, the tangents are bit for bit the same as the ones of `compute_tangents`.
+    /// Without the feature this is `compute_tangents`.
+    pub fn compute_tangents_par(&self) -> Vec<Vector4> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            use crate::rendering::vertex::{orthonormalize_tangent, triangle_tangent};
+            let triangle_tangents: Vec<_> = self.indices.par_chunks_exact(3)
+                .map(|triangle| triangle_tangent(&self.vertices, triangle))
+                .collect();
+
+            let (offsets, vertex_triangles) = self.vertex_triangles();
+            self.vertices.par_iter().enumerate().map(|(v, vertex)| {
+                let (mut tangent, mut bitangent) = (Vector3::zero(), Vector3::zero());
+                for (t, b) in vertex_triangles[offsets[v]..offsets[v + 1]].iter().filter_map(|&t| triangle_tangents[t]) {
+                    tangent += t;
+                    bitangent += b;
+                }
+                orthonormalize_tangent(vertex.normal, tangent, bitangent)
+            }).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.compute_tangents()
+    }
+
+    /// Returns the triangles around every vertex in ascending order, packed into one array:
+    /// the triangles of vertex `v` are `vertex_triangles[offsets[v]..offsets[v + 1]]`.
+    /// A triangle using a vertex twice is listed twice, so sums over it match the ones over `indices`.
+    #[cfg(feature = "rayon")]
+    fn vertex_triangles(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut offsets = vec![0; self.vertices.len() + 1];
+        for triangle in self.indices.chunks_exact(3) {
+            for &i in triangle {
+                offsets[i as usize + 1] += 1;
+            }
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+        let mut vertex_triangles = vec![0; offsets[self.vertices.len()]];
+        let mut next = offsets.clone();
+        for (t, triangle) in self.indices.chunks_exact(3).enumerate() {
+            for &i in triangle {
+                vertex_triangles[next[i as usize]] = t;
+                next[i as usize] += 1;
+            }
+        }
+        (offsets, vertex_triangles)
+    }
+
+    /// Smooths the mesh by moving every vertex towards the average position of its neighbors by `lambda` (0 to 1),
+    /// repeated `iterations` times. If `preserve_boundary` is set, vertices on open borders stay where they are.
+    /// The normals are recomputed afterwards, UVs and colors are left untouched.
+    ///
+    /// Returns an error if the mesh is non-manifold, as its neighborhoods aren't well-defined then.
+    pub fn laplacian_smooth(&mut self, iterations: u32, lambda: f32, preserve_boundary: bool) -> Result<(), NonManifoldError> {
+        if iterations == 0 {
+            return Ok(());
+        }
+
+        let adjacency = MeshAdjacency::build(self)?;
+        let mut positions: Vec<Vector3> = self.vertices.iter().map(|v| v.position).collect();
+        let mut smoothed = positions.clone();
+
+        for _ in 0..iterations {
+            for (v, position) in smoothed.iter_mut().enumerate() {
+                if preserve_boundary && adjacency.is_boundary_vertex(v as u32) {
+                    continue;
+                }
+                let (sum, count) = adjacency.vertex_neighbors(v as u32)
+                    .fold((Vector3::zero(), 0), |(sum, count), n| (sum + positions[n as usize], count + 1));
+                if count > 0 {
+                    let average = sum * (1.0 / count as f32);
+                    *position = positions[v] + (average - positions[v]) * lambda;
+                }
+            }
+            positions.copy_from_slice(&smoothed);
+        }
+
+        for (vertex, position) in self.vertices.iter_mut().zip(positions) {
+            vertex.position = position;
+        }
+        self.compute_normals();
+        Ok(())
+    }
+
+    /// Reduces the mesh to (at most) `target_triangle_count` triangles by repeatedly collapsing the shortest edge
+    /// into its midpoint, with the vertex attributes interpolated by `Vertex::midpoint`.
+    ///
+    /// Collapses that would flip a triangle, create a degenerate one or make the mesh non-manifold are skipped.
+    /// If no legal collapse is left, decimation stops early, so the result may have more triangles than requested.
+    /// Unused vertices are removed from the result.
+    pub fn decimate(&self, target_triangle_count: usize) -> Mesh {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let mut vertices = self.vertices.clone();
+        let mut triangles: Vec<[u32; 3]> = self.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+        let mut alive = vec![true; triangles.len()];
+        let mut alive_count = triangles.len();
+        let mut vertex_triangles = vec![Vec::new(); vertices.len()];
+        for (t, triangle) in triangles.iter().enumerate() {
+            for &v in triangle {
+                vertex_triangles[v as usize].push(t);
+            }
+        }
+
+        // Squared lengths are never negative, so their bit patterns sort just like the floats.
+        let edge_key = |vertices: &[Vertex], a: u32, b: u32| {
+            Reverse((vertices[a as usize].position.distance_squared(&vertices[b as usize].position).to_bits(), a.min(b), a.max(b)))
+        };
+        let mut queue = BinaryHeap::new();
+        for triangle in &triangles {
+            for i in 0..3 {
+                queue.push(edge_key(&vertices, triangle[i], triangle[(i + 1) % 3]));
+            }
+        }
+
+        while alive_count > target_triangle_count {
+            let Some(Reverse((length, a, b))) = queue.pop() else {
+                break;
+            };
+            if a == b {
+                continue;
+            }
+
+            let shared: Vec<usize> = vertex_triangles[a as usize].iter().copied()
+                .filter(|t| triangles[*t].contains(&b))
+                .collect();
+            if shared.is_empty() {
+                // The edge doesn't exist anymore.
+                continue;
+            }
+            let current = edge_key(&vertices, a, b);
+            if current.0.0 != length {
+                // One of the vertices moved since the edge was queued.
+                queue.push(current);
+                continue;
+            }
+
+            // Link condition: the only common neighbors may be the tips of the triangles on the edge,
+            // otherwise the collapse would pinch the surface into a non-manifold shape.
+            let neighbors_b = vertex_neighbors(b, &vertex_triangles, &triangles);
+            let common = vertex_neighbors(a, &vertex_triangles, &triangles).into_iter().filter(|n| neighbors_b.binary_search(n).is_ok()).count();
+            if common != shared.len() {
+                continue;
+            }
+
+            let merged = vertices[a as usize].midpoint(&vertices[b as usize]);
+            let flips = vertex_triangles[a as usize].iter().chain(&vertex_triangles[b as usize])
+                .filter(|t| !shared.contains(t))
+                .any(|&t| {
+                    let positions = triangles[t].map(|v| vertices[v as usize].position);
+                    let moved = triangles[t].map(|v| if v == a || v == b { merged.position } else { vertices[v as usize].position });
+                    let before = (positions[1] - positions[0]).cross(&(positions[2] - positions[0]));
+                    let after = (moved[1] - moved[0]).cross(&(moved[2] - moved[0]));
+                    after.dot(&before) <= 0.0
+                });
+            if flips {
+                continue;
+            }
+
+            for &t in &shared {
+                alive[t] = false;
+                alive_count -= 1;
+                for v in triangles[t] {
+                    vertex_triangles[v as usize].retain(|&other| other != t);
+                }
+            }
+            let moved_triangles = std::mem::take(&mut vertex_triangles[b as usize]);
+            for t in moved_triangles {
+                if alive[t] {
+                    for v in triangles[t].iter_mut() {
+                        if *v == b {
+                            *v = a;
+                        }
+                    }
+                    vertex_triangles[a as usize].push(t);
+                }
+            }
+
+            let normal = merged.normal;
+            let len_squared = normal.magnitude_squared();
+            vertices[a as usize] = Vertex {
+                normal: if len_squared > 0.0 { normal * (1.0 / len_squared.sqrt()) } else { normal },
+                ..merged
+            };
+            // The neighborhood of every edge around the merged vertex changed,
+            // so collapses rejected earlier might be legal now.
+            for n in vertex_neighbors(a, &vertex_triangles, &triangles) {
+                queue.push(edge_key(&vertices, a, n));
+                for m in vertex_neighbors(n, &vertex_triangles, &triangles) {
+                    if m != a {
+                        queue.push(edge_key(&vertices, n, m));
+                    }
+                }
+            }
+        }
+
+        let mut remap = vec![u32::MAX; vertices.len()];
+        let mut result = Mesh::default();
+        for (t, triangle) in triangles.iter().enumerate() {
+            if !alive[t] {
+                continue;
+            }
+            for &v in triangle {
+                if remap[v as usize] == u32::MAX {
+                    remap[v as usize] = result.vertices.len() as u32;
+                    result.vertices.push(vertices[v as usize]);
+                }
+                result.indices.push(remap[v as usize]);
+            }
+        }
+        result
+    }
+
+    /// Reorders the triangles so consecutive triangles reuse vertices still in the GPU's post-transform vertex cache,
+    /// using Tom Forsyth's linear-speed vertex cache optimization with a simulated 32-entry cache.
+    ///
+    /// The set of triangles and their winding stay the same, only their order changes.
+    /// Trailing indices that don't form a whole triangle are kept at the end.
+    pub fn optimize_vertex_cache(&mut self) {
+        let triangle_count = self.indices.len() / 3;
+        if triangle_count == 0 {
+            return;
+        }
+
+        // The triangles every vertex is still used by.
+        let mut vertex_triangles = vec![Vec::new(); self.vertices.len()];
+        for (t, triangle) in self.indices.chunks_exact(3).enumerate() {
+            for &v in triangle {
+                vertex_triangles[v as usize].push(t);
+            }
+        }
+
+        let mut cache_position: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut vertex_score: Vec<f32> = vertex_triangles.iter()
+            .map(|triangles| forsyth_vertex_score(None, triangles.len()))
+            .collect();
+        let triangle_vertices = |t: usize| [self.indices[t * 3], self.indices[t * 3 + 1], self.indices[t * 3 + 2]];
+        let mut triangle_score: Vec<f32> = (0..triangle_count)
+            .map(|t| triangle_vertices(t).iter().map(|&v| vertex_score[v as usize]).sum())
+            .collect();
+        let mut emitted = vec![false; triangle_count];
+
+        let mut cache: Vec<u32> = Vec::with_capacity(FORSYTH_CACHE_SIZE + 3);
+        let mut optimized = Vec::with_capacity(self.indices.len());
+        let mut next_unemitted = 0;
+        let mut best = (0..triangle_count).fold(0, |best, t| if triangle_score[t] > triangle_score[best] { t } else { best });
+
+        for _ in 0..triangle_count {
+            let triangle = triangle_vertices(best);
+            emitted[best] = true;
+            optimized.extend_from_slice(&triangle);
+
+            for &v in &triangle {
+                let triangles = &mut vertex_triangles[v as usize];
+                if let Some(i) = triangles.iter().position(|&t| t == best) {
+                    triangles.swap_remove(i);
+                }
+            }
+
+            // Move the vertices of the triangle to the front of the cache, pushing the others back.
+            let mut touched = triangle.to_vec();
+            touched.extend(cache.iter().filter(|v| !triangle.contains(v)));
+            for (i, &v) in touched.iter().enumerate() {
+                let position = (i < FORSYTH_CACHE_SIZE).then_some(i);
+                cache_position[v as usize] = position;
+                vertex_score[v as usize] = forsyth_vertex_score(position, vertex_triangles[v as usize].len());
+            }
+            cache.clear();
+            cache.extend(touched.iter().take(FORSYTH_CACHE_SIZE));
+
+            // Only triangles of vertices whose score changed need to be rescored.
+            let mut best_score = f32::NEG_INFINITY;
+            let mut found = false;
+            for &v in &touched {
+                for &t in &vertex_triangles[v as usize] {
+                    let score = triangle_vertices(t).iter().map(|&v| vertex_score[v as usize]).sum();
+                    triangle_score[t] = score;
+                    if score > best_score {
+                        best_score = score;
+                        best = t;
+                        found = true;
+                    }
+                }
+            }
+
+            if !found {
+                while next_unemitted < triangle_count && emitted[next_unemitted] {
+                    next_unemitted += 1;
+                }
+                best = next_unemitted;
+            }
+        }
+
+        optimized.extend_from_slice(&self.indices[triangle_count * 3..]);
+        self.indices = optimized;
+    }
+
+    /// Reorders the vertices in the order they are first used by the indices, so the GPU reads vertex memory mostly linearly.
+    /// Best used after `optimize_vertex_cache`. Indices are remapped accordingly, unused vertices are moved to the end.
+    pub fn optimize_vertex_fetch(&mut self) {
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::with_capacity(self.vertices.len());
+
+        for index in self.indices.iter_mut() {
+            let old = *index as usize;
+            if remap[old] == u32::MAX {
+                remap[old] = vertices.len() as u32;
+                vertices.push(self.vertices[old]);
+            }
+            *index = remap[old];
+        }
+        for (old, vertex) in self.vertices.iter().enumerate() {
+            if remap[old] == u32::MAX {
+                vertices.push(*vertex);
+            }
+        }
+
+        self.vertices = vertices;
+    }
+
+    /// Simulates a FIFO post-transform vertex cache of the given size
+    /// and returns the average number of cache misses per triangle (ACMR).
+    /// Lower is better, 3.0 is the worst case and around 0.5 is optimal for big regular meshes.
+    pub fn average_cache_miss_ratio(&self, cache_size: usize) -> f32 {
+        let triangle_count = self.indices.len() / 3;
+        if triangle_count == 0 {
+            return 0.0;
+        }
+
+        let mut cache = std::collections::VecDeque::with_capacity(cache_size);
+        let mut misses = 0;
+        for &index in &self.indices[..triangle_count * 3] {
+            if !cache.contains(&index) {
+                misses += 1;
+                if cache.len() == cache_size {
+                    cache.pop_front();
+                }
+                cache.push_back(index);
+            }
+        }
+        misses as f32 / triangle_count as f32
+    }
+
+    /// Returns the indices as u16, which halves the size of the index buffer on the GPU.
+    /// Returns `None` if any index doesn't fit into a u16.
+    pub fn indices_u16(&self) -> Option<Vec<u16>> {
+        self.indices.iter().map(|&i| u16::try_from(i).ok()).collect()
+    }
+
+    /// Returns the indices in the smallest index type that can hold all of them.
+    pub fn shrink_indices(&self) -> IndexBuffer {
+        match self.indices_u16() {
+            Some(indices) => IndexBuffer::U16(indices),
+            None => IndexBuffer::U32(self.indices.clone()),
+        }
+    }
+
+    /// Splits the mesh into multiple meshes which have at most 65535 vertices each,
+    /// so all of them can use u16 indices without ever using 0xFFFF, the primitive restart index. Indices are remapped into the vertices of their chunk,
+    /// vertices used by triangles in several chunks are duplicated.
+    ///
+    /// Trailing indices that don't form a whole triangle are dropped.
+    pub fn split_for_u16(&self) -> Vec<Mesh> {
+        const MAX_VERTICES: usize = u16::MAX as usize;
+
+        let mut meshes = Vec::new();
+        let mut current = Mesh::default();
+        // Maps an index of this mesh to the index in the current chunk, u32::MAX if not yet in the chunk.
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut used = Vec::new();
+
+        for triangle in self.indices.chunks_exact(3) {
+            let new_vertices = triangle.iter().filter(|&&i| remap[i as usize] == u32::MAX).count();
+            if current.vertices.len() + new_vertices > MAX_VERTICES {
+                for &i in &used {
+                    remap[i as usize] = u32::MAX;
+                }
+                used.clear();
+                meshes.push(std::mem::take(&mut current));
+            }
+
+            for &i in triangle {
+                if remap[i as usize] == u32::MAX {
+                    remap[i as usize] = current.vertices.len() as u32;
+                    current.vertices.push(self.vertices[i as usize]);
+                    used.push(i);
+                }
+                current.indices.push(remap[i as usize]);
+            }
+        }
+
+        if !current.indices.is_empty() {
+            meshes.push(current);
+        }
+        meshes
+    }
+
+    /// Returns a stable 64-bit hash of the mesh contents, for skipping the processing of unchanged assets.
+    ///
+    /// The hash is 64-bit FNV-1a over the vertex count (as `u64`), then for every vertex the position (x, y, z),
+    /// normal (x, y, z) and texture coordinates (x, y) as float bit patterns followed by the color as r, g, b, a bytes,
+    /// then the index count (as `u64`) and the indices. Everything is fed in little-endian order,
+    /// so the hash does not depend on the host or on the capacity of the vectors and will not change between versions.
+    /// For reference, an empty mesh hashes to `0x88201fb960ff6465`.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_u64(self.vertices.len() as u64);
+        for vertex in &self.vertices {
+            let (p, n, uv) = (vertex.position, vertex.normal, vertex.tex_coords);
+            hasher.write_f32s(&[p.x, p.y, p.z, n.x, n.y, n.z, uv.x, uv.y]);
+            let (r, g, b, a) = vertex.color.to_rgba();
+            hasher.write(&[r, g, b, a]);
+        }
+        hasher.write_u64(self.indices.len() as u64);
+        for &index in &self.indices {
+            hasher.write_u32(index);
+        }
+        hasher.finish()
+    }
+
+    /// Returns the closest hit of `ray` with the triangles of this mesh, or `None` if it misses all of them.
+    /// Both sides of every triangle can be hit. If several triangles are hit at the same distance,
+    /// the one with the smallest index wins.
+    ///
+    /// This tests every triangle, build a [`MeshBvh`](crate::rendering::bvh::MeshBvh) for meshes that are raycast often.
+    pub fn raycast(&self, ray: &Ray) -> Option<MeshHit> {
+        let mut best: Option<MeshHit> = None;
+        for triangle in 0..self.indices.len() / 3 {
+            if let Some(hit) = self.raycast_triangle(ray, triangle) {
+                if best.is_none_or(|best| hit.is_closer_than(&best)) {
+                    best = Some(hit);
+                }
+            }
+        }
+        best
+    }
+
+    /// Intersects `ray` with a single triangle of this mesh.
+    pub(crate) fn raycast_triangle(&self, ray: &Ray, triangle: usize) -> Option<MeshHit> {
+        let [a, b, c] = [0, 1, 2].map(|i| &self.vertices[self.indices[triangle * 3 + i] as usize]);
+        let (t, u, v) = ray.intersect_triangle_barycentric(a.position, b.position, c.position, false)?;
+        let w = 1.0 - u - v;
+
+        let normal = a.normal * w + b.normal * u + c.normal * v;
+        let length_squared = normal.magnitude_squared();
+        let normal = if length_squared > 0.0 { normal * (1.0 / length_squared.sqrt()) } else { normal };
+
+        Some(MeshHit { triangle, t, barycentric: Vector3::new(w, u, v), normal })
+    }
+
+    /// Returns a copy of this mesh with nearby vertices merged into one, as decided by `policy`.
+    ///
+    /// Vertices are visited in order. Each one looks at the earlier vertices within `position_epsilon` in index order
+    /// and joins the cluster of the first one whose cluster is compatible with it, as decided by the vertex that started
+    /// that cluster. Without such a neighbour it starts a new cluster. An infinite epsilon makes every earlier vertex
+    /// a neighbour, a NaN one none.
+    /// The attributes of every cluster are averaged, normals are renormalized afterwards.
+    /// Triangles that collapse because two of their corners were merged are removed.
+    pub fn weld(&self, policy: &WeldPolicy) -> Mesh {
+        // Every position is within an infinite epsilon, so all clusters are candidates and no grid is needed.
+        let grid = (policy.position_epsilon != f32::INFINITY).then(|| {
+            let positions: Vec<Vector3> = self.vertices.iter().map(|v| v.position).collect();
+            let cell_size = if policy.position_epsilon > 0.0 { policy.position_epsilon } else { 1.0 };
+            SpatialGrid::build(&positions, cell_size).expect("the cell size is positive and finite")
+        });
+
+        let mut cluster_of: Vec<u32> = Vec::with_capacity(self.vertices.len());
+        let mut seeds: Vec<usize> = Vec::new();
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut counts: Vec<u32> = Vec::new();
+
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            let cluster = match &grid {
+                Some(grid) => grid.query_radius(vertex.position, policy.position_epsilon).into_iter()
+                    .take_while(|&j| j < i)
+                    .map(|j| cluster_of[j])
+                    .find(|&c| policy.can_merge(&self.vertices[seeds[c as usize]], vertex)),
+                // The clusters in the order of their first vertices, which is how the neighbours would reach them.
+                None => (0..seeds.len() as u32).find(|&c| policy.can_merge(&self.vertices[seeds[c as usize]], vertex)),
+            };
+
+            match cluster {
+                Some(c) => {
+                    let c = c as usize;
+                    counts[c] += 1;
+                    let merged = &mut vertices[c];
+                    let mut average = merged.lerp(vertex, 1.0 / counts[c] as f32);
+                    if policy.color_mode == ColorMergeMode::KeepFirst {
+                        average.color = merged.color;
+                    }
+                    *merged = average;
+                    cluster_of.push(c as u32);
+                }
+                None => {
+                    cluster_of.push(vertices.len() as u32);
+                    seeds.push(i);
+                    vertices.push(*vertex);
+                    counts.push(1);
+                }
+            }
+        }
+
+        for vertex in vertices.iter_mut() {
+            let length_squared = vertex.normal.magnitude_squared();
+            if length_squared > 0.0 {
+                vertex.normal *= 1.0 / length_squared.sqrt();
+            }
+        }
+
+        let indices = self.indices.chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|k| cluster_of[triangle[k] as usize]))
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .flatten()
+            .collect();
+        Mesh::new(vertices, indices)
+    }
+
+    /// Creates a flat grid on the XZ plane, centered at the origin and spanning `size`, with `resolution` cells along x and z.
+    /// This is `heightmap` with a height of zero everywhere, so all normals point up.
+    pub fn plane(size: Vector2, resolution: (u32, u32)) -> Mesh {
+        Mesh::heightmap(size, resolution, |_, _| 0.0)
+    }
+
+    /// Subdivides every cell of a grid mesh, as created by `plane` or `heightmap`, into `factor`² cells.
+    ///
+    /// Positions, normals, UVs and colors are interpolated bilinearly over each original cell, normals are renormalized.
+    /// The result has the same grid layout, so it can be subdivided again. Every new vertex on an original edge is
+    /// the linear interpolation of the two edge endpoints with `t = k / factor`, computed the same way for every mesh,
+    /// so chunks sharing an edge subdivide into bit-identical border vertices and no cracks open up between them.
+    ///
+    /// Returns `None` if `factor` is zero, the mesh does not have the vertex and index layout of `heightmap`
+    /// or the subdivided grid would have more vertices than `u32` indices can address.
+    pub fn subdivide_grid(&self, factor: u32) -> Option<Mesh> {
+        let (nx, nz) = self.grid_resolution()?;
+        if factor == 0 {
+            return None;
+        }
+
+        let (sx, sz) = (nx.checked_mul(factor)?, nz.checked_mul(factor)?);
+        let vertex_count = (sx as u64 + 1).checked_mul(sz as u64 + 1).filter(|&n| n <= u32::MAX as u64 + 1)?;
+        let corner = |i: u32, j: u32| &self.vertices[(j * (nx + 1) + i) as usize];
+        let mut vertices = Vec::with_capacity(vertex_count as usize);
+        for row in 0..=sz {
+            let j = (row / factor).min(nz - 1);
+            let t = (row - j * factor) as f32 / factor as f32;
+            for column in 0..=sx {
+                let i = (column / factor).min(nx - 1);
+                let s = (column - i * factor) as f32 / factor as f32;
+
+                let near = corner(i, j).lerp(corner(i, j + 1), t);
+                let far = corner(i + 1, j).lerp(corner(i + 1, j + 1), t);
+                let mut vertex = near.lerp(&far, s);
+                let length_squared = vertex.normal.magnitude_squared();
+                if length_squared > 0.0 {
+                    vertex.normal *= 1.0 / length_squared.sqrt();
+                }
+                vertices.push(vertex);
+            }
+        }
+
+        Some(Mesh::new(vertices, grid_indices(sx, sz)))
+    }
+
+    /// Returns the number of cells along x and z if this mesh has the layout created by `heightmap`.
+    fn grid_resolution(&self) -> Option<(u32, u32)> {
+        let nx = self.indices.get(1)?.checked_sub(1).filter(|&nx| nx > 0)?;
+        let cells = self.indices.len() / 6;
+        if cells == 0 || !cells.is_multiple_of(nx as usize) {
+            return None;
+        }
+        let nz = (cells / nx as usize) as u32;
+
+        let valid = self.vertices.len() == (nx as usize + 1) * (nz as usize + 1) && self.indices == grid_indices(nx, nz);
+        valid.then_some((nx, nz))
+    }
+
+    /// Writes the mesh in fgruc's versioned little-endian binary format, read back by `read_binary`.
+    ///
+    /// The layout of version 1 is the magic bytes `FGMS`, the version as `u16`, the vertex count and the index count as `u32`,
+    /// then per vertex the position, normal and texture coordinates as 8 `f32` followed by the color as r, g, b, a bytes,
+    /// then the indices as `u32`. An empty mesh is the 14 bytes `46 47 4d 53 01 00` followed by eight zeros.
+    pub fn write_binary(&self, w: &mut impl Write) -> io::Result<()> {
+        binary::write_header(w, MESH_MAGIC, MESH_VERSION)?;
+        binary::write_u32(w, self.vertices.len() as u32)?;
+        binary::write_u32(w, self.indices.len() as u32)?;
+        for vertex in &self.vertices {
+            let (p, n, t) = (vertex.position, vertex.normal, vertex.tex_coords);
+            binary::write_f32s(w, &[p.x, p.y, p.z, n.x, n.y, n.z, t.x, t.y])?;
+            let (r, g, b, a) = vertex.color.to_rgba();
+            w.write_all(&[r, g, b, a])?;
+        }
+        for &index in &self.indices {
+            binary::write_u32(w, index)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a mesh written by `write_binary`.
+    /// Fails cleanly on data of another type, newer format versions, truncated data and indices past the vertices.
+    pub fn read_binary(r: &mut impl Read) -> Result<Mesh, BinaryReadError> {
+        binary::read_header(r, MESH_MAGIC, MESH_VERSION)?;
+        let vertex_count = binary::read_u32(r)?;
+        let index_count = binary::read_u32(r)?;
+
+        // The counts are untrusted, so only reserve a bounded amount up front and let truncated data fail while reading.
+        let mut vertices = Vec::with_capacity((vertex_count as usize).min(1 << 16));
+        for _ in 0..vertex_count {
+            let [px, py, pz, nx, ny, nz, u, v] = binary::read_f32s::<8>(r)?;
+            let [red, green, blue, alpha] = binary::read_bytes::<4>(r)?;
+            vertices.push(Vertex::new(
+                Vector3::new(px, py, pz),
+                Vector3::new(nx, ny, nz),
+                Vector2::new(u, v),
+                UniColor::from_rgba(red, green, blue, alpha),
+            ));
+        }
+
+        let mut indices = Vec::with_capacity((index_count as usize).min(1 << 16));
+        for _ in 0..index_count {
+            let index = binary::read_u32(r)?;
+            if index >= vertex_count {
+                return Err(BinaryReadError::IndexOutOfRange { index, vertex_count });
+            }
+            indices.push(index);
+        }
+
+        Ok(Mesh::new(vertices, indices))
+    }
+
+    /// Sets the UVs of all vertices by projecting their positions onto the plane perpendicular to `axis`.
+    ///
+    /// The UVs are measured from the minimum corner of the mesh bounds and multiplied by `scale`,
+    /// so a mesh one unit wide gets exactly one 0 to 1 tile with a scale of one. Projecting along `Axis::X` maps (z, y)
+    /// to (u, v), `Axis::Y` maps (x, z) and `Axis::Z` maps (x, y). Faces parallel to `axis` get stretched UVs.
+    pub fn project_uvs_planar(&mut self, axis: Axis, scale: Vector2) {
+        let axis = axis.index();
+        let Some(bounds) = self.compute_bounds() else { return };
+        for vertex in &mut self.vertices {
+            vertex.tex_coords = planar_uv(vertex.position - bounds.min, axis, scale);
+        }
+    }
+
+    /// Sets the UVs with a box (triplanar) projection: every triangle is projected along the axis its face normal points
+    /// to the most, like `project_uvs_planar` does for a single axis. Each side of a cube gets a full 0 to 1 tile this way.
+    ///
+    /// Vertices shared by triangles projected along different axes are duplicated, so the UVs of every side stay intact.
+    pub fn project_uvs_box(&mut self, scale: Vector2) {
+        let Some(bounds) = self.compute_bounds() else { return };
+        let mut projected_axis: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut copies: HashMap<(u32, usize), u32> = HashMap::new();
+
+        for t in 0..self.indices.len() / 3 {
+            let triangle = [self.indices[t * 3], self.indices[t * 3 + 1], self.indices[t * 3 + 2]];
+            let [a, b, c] = triangle.map(|i| self.vertices[i as usize].position);
+            let normal = (b - a).cross(&(c - a));
+            let magnitudes = [normal.x.abs(), normal.y.abs(), normal.z.abs()];
+            let axis = (0..3).fold(0, |best, i| if magnitudes[i] > magnitudes[best] { i } else { best });
+
+            for (corner, index) in triangle.into_iter().enumerate() {
+                let new_index = match projected_axis[index as usize] {
+                    Some(projected) if projected == axis => index,
+                    Some(_) => *copies.entry((index, axis)).or_insert_with(|| {
+                        let mut vertex = self.vertices[index as usize];
+                        vertex.tex_coords = planar_uv(vertex.position - bounds.min, axis, scale);
+                        self.vertices.push(vertex);
+                        (self.vertices.len() - 1) as u32
+                    }),
+                    None => {
+                        let vertex = &mut self.vertices[index as usize];
+                        vertex.tex_coords = planar_uv(vertex.position - bounds.min, axis, scale);
+                        projected_axis[index as usize] = Some(axis);
+                        index
+                    }
+                };
+                self.indices[t * 3 + corner] = new_index;
+            }
+        }
+    }
+
+    /// Sets the UVs from the longitude (u) and latitude (v) of the vertex positions seen from the origin,
+    /// so it suits meshes centered at the origin like spheres. v is 0 at +Y and 1 at -Y, u goes around the Y axis.
+    ///
+    /// Triangles crossing the seam where u wraps from 1 to 0 get duplicated vertices with u past 1,
+    /// and vertices on the poles get duplicated per triangle with the u of their neighbors, so the texture doesn't smear.
+    pub fn project_uvs_spherical(&mut self) {
+        let mut poles = vec![false; self.vertices.len()];
+        for (vertex, pole) in self.vertices.iter_mut().zip(&mut poles) {
+            let p = vertex.position;
+            let horizontal = (p.x * p.x + p.z * p.z).sqrt();
+            vertex.tex_coords = Vector2::new(0.5 + p.z.atan2(p.x) / TAU, 0.5 - p.y.atan2(horizontal) / PI);
+            *pole = horizontal <= f32::EPSILON * p.y.abs();
+        }
+
+        let mut copies: HashMap<(u32, u32), u32> = HashMap::new();
+        for t in 0..self.indices.len() / 3 {
+            let triangle = [self.indices[t * 3], self.indices[t * 3 + 1], self.indices[t * 3 + 2]];
+            let mut us = triangle.map(|i| self.vertices[i as usize].tex_coords.x);
+
+            let around = || (0..3).filter(|&corner| !poles[triangle[corner] as usize]);
+            let min = around().map(|corner| us[corner]).fold(f32::INFINITY, f32::min);
+            let max = around().map(|corner| us[corner]).fold(f32::NEG_INFINITY, f32::max);
+            if max - min > 0.5 {
+                for corner in around() {
+                    if us[corner] < 0.5 {
+                        us[corner] += 1.0;
+                    }
+                }
+            }
+            let count = around().count();
+            if count > 0 {
+                let mean = around().map(|corner| us[corner]).sum::<f32>() / count as f32;
+                for corner in (0..3).filter(|&corner| poles[triangle[corner] as usize]) {
+                    us[corner] = mean;
+                }
+            }
+
+            for (corner, index) in triangle.into_iter().enumerate() {
+                let u = us[corner];
+                if u == self.vertices[index as usize].tex_coords.x {
+                    continue;
+                }
+                self.indices[t * 3 + corner] = *copies.entry((index, u.to_bits())).or_insert_with(|| {
+                    let mut vertex = self.vertices[index as usize];
+                    vertex.tex_coords.x = u;
+                    self.vertices.push(vertex);
+                    (self.vertices.len() - 1) as u32
+                });
+            }
+        }
+    }
+
+}
+
+/// Normalizes a sum of area weighted face normals for `Mesh::compute_normals`, vertices without area get a zero normal.
+#[inline]
+fn normalize_accumulated(normal: Vector3) -> Vector3 {
+    let len_squared = normal.magnitude_squared();
+    if len_squared > 0.0 {
+        normal * (1.0 / len_squared.sqrt())
+    } else {
+        Vector3::zero()
+    }
+}
+
+/// Projects `offset` along the axis with index `axis` (0 to 2) to UVs for `Mesh::project_uvs_planar`.
+#[inline]
+fn planar_uv(offset: Vector3, axis: usize, scale: Vector2) -> Vector2 {
+    let (u, v) = match axis {
+        0 => (offset.z, offset.y),
+        1 => (offset.x, offset.z),
+        _ => (offset.x, offset.y),
+    };
+    Vector2::new(u * scale.x, v * scale.y)
+}
+
+/// Returns the indices of a grid with `nx` by `nz` cells and `nx + 1` vertices per row, two triangles per cell facing +Y.
+fn grid_indices(nx: u32, nz: u32) -> Vec<u32> {
+    let mut indices = Vec::with_capacity(nx as usize * nz as usize * 6);
+    for j in 0..nz {
+        for i in 0..nx {
+            let a = j * (nx + 1) + i;
+            let b = a + 1;
+            let c = a + nx + 1;
+            let d = c + 1;
+            indices.extend_from_slice(&[a, c, b, b, c, d]);
+        }
+    }
+    indices
+}
+
+/// Returns the sorted vertices sharing a triangle with `v`, used by `Mesh::decimate`.
+fn vertex_neighbors(v: u32, vertex_triangles: &[Vec<usize>], triangles: &[[u32; 3]]) -> Vec<u32> {
+    let mut neighbors: Vec<u32> = vertex_triangles[v as usize].iter()
+        .flat_map(|&t| triangles[t])
+        .filter(|&n| n != v)
+        .collect();
+    neighbors.sort_unstable();
+    neighbors.dedup();
+    neighbors
+}
+
+/// The size of the vertex cache simulated by `Mesh::optimize_vertex_cache`.
+const FORSYTH_CACHE_SIZE: usize = 32;
+
+/// Scores a vertex for `Mesh::optimize_vertex_cache` by its position in the simulated cache and the number of triangles still using it.
+/// Vertices that were just used score a bit lower than the rest of the cache to avoid ping-ponging between strips,
+/// vertices with few remaining triangles get a boost so they are finished off and can leave the cache.
+fn forsyth_vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+    if remaining_triangles == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        None => 0.0,
+        Some(position) if position < 3 => 0.75,
+        Some(position) => (1.0 - (position - 3) as f32 / (FORSYTH_CACHE_SIZE - 3) as f32).powf(1.5),
+    };
+    cache_score + 2.0 / (remaining_triangles as f32).sqrt()
+}
+
+/// Returned by `Mesh::indexed` and `Mesh::non_indexed` for indices that do not describe a valid triangle list.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MeshIndexError {
+    /// The number of indices is not a multiple of 3, so the last triangle is incomplete.
+    IncompleteTriangle { index_count: usize },
+    /// An index points past the end of the vertices.
+    OutOfRange { index: u32, vertex_count: usize },
+}
+
+impl Display for MeshIndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshIndexError::IncompleteTriangle { index_count } =>
+                write!(f, "{} indices do not form whole triangles", index_count),
+            MeshIndexError::OutOfRange { index, vertex_count } =>
+                write!(f, "index {} is out of range for {} vertices", index, vertex_count),
+        }
+    }
+}
+
+impl Error for MeshIndexError {}
+
+/// An index buffer holding either u16 or u32 indices.
+#[derive(Clone, Debug)]
+pub enum IndexBuffer {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl IndexBuffer {
+
+    /// Returns the number of indices in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            IndexBuffer::U16(indices) => indices.len(),
+            IndexBuffer::U32(indices) => indices.len(),
+        }
+    }
+
+    /// Returns true if the buffer holds no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the byte representation of the indices, 2 or 4 bytes per index.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        match self {
+            IndexBuffer::U16(indices) => indices.iter().flat_map(|i| i.to_ne_bytes()).collect(),
+            IndexBuffer::U32(indices) => indices.iter().flat_map(|i| i.to_ne_bytes()).collect(),
+        }
+    }
+
+}
+
+/// A hit of a ray with a mesh, returned by [`Mesh::raycast`] and [`MeshBvh::raycast`](crate::rendering::bvh::MeshBvh::raycast).
+#[derive(Copy, Clone, Debug)]
+pub struct MeshHit {
+    /// The index of the hit triangle, its vertices are `indices[triangle * 3..triangle * 3 + 3]`.
+    pub triangle: usize,
+    /// The distance along the ray, in multiples of its direction.
+    pub t: f32,
+    /// The barycentric weights of the three triangle vertices at the hit point.
+    pub barycentric: Vector3,
+    /// The vertex normals interpolated at the hit point and normalized.
+    pub normal: Vector3,
+}
+
+impl MeshHit {
+
+    /// Returns true if this hit is closer than `other`, using the triangle index to break ties.
+    #[inline]
+    pub(crate) fn is_closer_than(&self, other: &MeshHit) -> bool {
+        self.t < other.t || (self.t == other.t && self.triangle < other.triangle)
+    }
+
+}
+
+/// Decides which vertices `Mesh::weld` merges and how their attributes are combined.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WeldPolicy {
+    /// Vertices closer than this (inclusive) are merge candidates. Zero merges exact duplicates only.
+    pub position_epsilon: f32,
+    /// The largest angle in radians between two normals that still merge, so hard edges above it stay split.
+    /// `None` ignores the normals and averages them.
+    pub normal_angle_threshold: Option<f32>,
+    /// Whether vertices with different texture coordinates merge (averaging the UVs).
+    /// If false, UV seams stay split, texture coordinates further apart than `position_epsilon` never merge.
+    pub merge_uvs: bool,
+    /// How the colors of merged vertices are combined.
+    pub color_mode: ColorMergeMode,
+}
+
+impl WeldPolicy {
+
+    /// Creates a policy that merges vertices within `position_epsilon`, keeping hard edges over
+    /// 30 degrees, UV seams and color borders split.
+    pub fn new(position_epsilon: f32) -> Self {
+        Self {
+            position_epsilon,
+            normal_angle_threshold: Some(30f32.to_radians()),
+            merge_uvs: false,
+            color_mode: ColorMergeMode::Split,
+        }
+    }
+
+    /// Returns true if `vertex` may join the cluster started by `seed`. The positions are checked by the caller.
+    fn can_merge(&self, seed: &Vertex, vertex: &Vertex) -> bool {
+        if let Some(threshold) = self.normal_angle_threshold {
+            if seed.normal.angle_between(&vertex.normal) > threshold {
+                return false;
+            }
+        }
+        if !self.merge_uvs && seed.tex_coords.distance_squared(&vertex.tex_coords) > self.position_epsilon * self.position_epsilon {
+            return false;
+        }
+        self.color_mode != ColorMergeMode::Split || seed.color.to_rgba() == vertex.color.to_rgba()
+    }
+
+}
+
+/// How `Mesh::weld` combines the colors of merged vertices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorMergeMode {
+    /// Vertices with different colors are not merged.
+    Split,
+    /// The colors are averaged.
+    Average,
+    /// The color of the first vertex of the cluster is kept.
+    KeepFirst,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// A unit cube with 4 vertices and a flat normal per face, so every corner appears 3 times.
+    fn cube() -> Mesh {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for axis in 0..3 {
+            for sign in [-1.0, 1.0] {
+                let mut normal = [0.0; 3];
+                normal[axis] = sign;
+                let base = vertices.len() as u32;
+                for (u, v) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                    let mut position = [0.0; 3];
+                    position[axis] = sign * 0.5;
+                    position[(axis + 1) % 3] = u - 0.5;
+                    position[(axis + 2) % 3] = v - 0.5;
+                    vertices.push(Vertex::new(
+                        Vector3::new(position[0], position[1], position[2]),
+                        Vector3::new(normal[0], normal[1], normal[2]),
+                        Vector2::new(u, v),
+                        UniColor::WHITE,
+                    ));
+                }
+                indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            }
+        }
+        Mesh::new(vertices, indices)
+    }
+
+    /// An open cylinder with `segments` quads around it and a UV seam, the first column repeated with u = 1.
+    fn cylinder(segments: u32) -> Mesh {
+        let mut vertices = Vec::new();
+        for column in 0..=segments {
+            let u = column as f32 / segments as f32;
+            let (sin, cos) = (u * TAU).sin_cos();
+            for height in [0.0, 1.0] {
+                vertices.push(Vertex::new(Vector3::new(cos, height, sin), Vector3::new(cos, 0.0, sin), Vector2::new(u, height), UniColor::WHITE));
+            }
+        }
+        let mut indices = Vec::new();
+        for column in 0..segments {
+            let (a, b) = (column * 2, column * 2 + 2);
+            indices.extend([a, b, a + 1, b, b + 1, a + 1]);
+        }
+        Mesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn weld_keeps_hard_edges() {
+        let cube = cube();
+        let policy = WeldPolicy { merge_uvs: true, ..WeldPolicy::new(1e-4) };
+        let welded = cube.weld(&policy);
+        assert_eq!(welded.vertices.len(), 24);
+        assert_eq!(welded.indices.len(), 36);
+
+        let welded = cube.weld(&WeldPolicy { normal_angle_threshold: None, ..policy });
+        assert_eq!(welded.vertices.len(), 8);
+        assert_eq!(welded.indices.len(), 36);
+        // The three face normals of every corner average to the diagonal.
+        for vertex in &welded.vertices {
+            // Loose enough for the `fast_math` magnitude.
+            assert!((vertex.normal.magnitude() - 1.0).abs() < 2e-3);
+            assert!(vertex.normal.dot(&vertex.position) > 0.0);
+        }
+    }
+
+    #[test]
+    fn weld_keeps_uv_seams() {
+        let cylinder = cylinder(16);
+        let split = cylinder.weld(&WeldPolicy::new(1e-4));
+        assert_eq!(split.vertices.len(), cylinder.vertices.len());
+        let merged = cylinder.weld(&WeldPolicy { merge_uvs: true, ..WeldPolicy::new(1e-4) });
+        assert_eq!(merged.vertices.len(), cylinder.vertices.len() - 2);
+        assert_eq!(merged.indices.len(), cylinder.indices.len());
+    }
+
+    #[test]
+    fn weld_with_an_infinite_epsilon_merges_everything_compatible() {
+        let cube = cube();
+        let everything = WeldPolicy {
+            position_epsilon: f32::INFINITY,
+            normal_angle_threshold: None,
+            merge_uvs: true,
+            color_mode: ColorMergeMode::Average,
+        };
+        let welded = cube.weld(&everything);
+        assert_eq!(welded.vertices.len(), 1);
+        assert!(welded.indices.is_empty());
+
+        // The normals still split the cube into its 6 faces.
+        let welded = cube.weld(&WeldPolicy { normal_angle_threshold: Some(0.1), ..everything });
+        assert_eq!(welded.vertices.len(), 6);
+
+        let welded = cube.weld(&WeldPolicy { position_epsilon: f32::NAN, ..everything });
+        assert_eq!(welded.vertices.len(), 24);
+    }
+
+    #[test]
+    fn content_hash_is_stable() {
+        let cube = cube();
+        let mut vertices = Vec::with_capacity(100);
+        vertices.extend(cube.vertices.iter().copied());
+        let mut indices = Vec::with_capacity(1000);
+        indices.extend(cube.indices.iter().copied());
+        let rebuilt = Mesh::indexed(vertices, indices).unwrap();
+        assert_eq!(rebuilt.content_hash(), cube.content_hash());
+        // Pinned, so an accidental change of the documented algorithm is caught.
+        assert_eq!(cube.content_hash(), 0x88f0_0815_257f_92d9);
+        assert_eq!(Mesh::new(Vec::new(), Vec::new()).content_hash(), 0x8820_1fb9_60ff_6465);
+
+        let mut flipped = cube.clone();
+        flipped.vertices[5].normal.y = f32::from_bits(flipped.vertices[5].normal.y.to_bits() ^ 1);
+        assert_ne!(flipped.content_hash(), cube.content_hash());
+    }
+
+    #[test]
+    fn binary_round_trips_generated_meshes() {
+        let heightmap = Mesh::heightmap(Vector2::new(4.0, 3.0), (9, 5), |x, z| (x * 1.7).sin() * z);
+        let mut colored = cylinder(7);
+        for (i, vertex) in colored.vertices.iter_mut().enumerate() {
+            vertex.color = UniColor::from_rgba(i as u8, (i * 7) as u8, 255 - i as u8, (i * 31) as u8);
+        }
+        for mesh in [Mesh::new(Vec::new(), Vec::new()), cube(), colored, heightmap] {
+            let mut bytes = Vec::new();
+            mesh.write_binary(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), 14 + mesh.vertices.len() * 36 + mesh.indices.len() * 4);
+            let read = Mesh::read_binary(&mut bytes.as_slice()).unwrap();
+            assert_eq!(read.content_hash(), mesh.content_hash());
+        }
+    }
+
+    #[test]
+    fn binary_fails_cleanly_on_bad_input() {
+        let mut bytes = Vec::new();
+        cube().write_binary(&mut bytes).unwrap();
+        for length in 0..bytes.len() {
+            let result = Mesh::read_binary(&mut &bytes[..length]);
+            assert!(matches!(result, Err(BinaryReadError::Truncated)), "{} bytes: {:?}", length, result.err());
+        }
+
+        let mut newer = bytes.clone();
+        newer[4] = 2;
+        assert!(matches!(Mesh::read_binary(&mut newer.as_slice()), Err(BinaryReadError::UnsupportedVersion { found: 2, supported: 1 })));
+        let mut transform = Vec::new();
+        crate::transform::Transform::identity().write_binary(&mut transform).unwrap();
+        assert!(matches!(Mesh::read_binary(&mut transform.as_slice()), Err(BinaryReadError::WrongMagic { found, .. }) if found == *b"FGTR"));
+        let last = bytes.len() - 4;
+        bytes[last..].copy_from_slice(&24u32.to_le_bytes());
+        assert!(matches!(Mesh::read_binary(&mut bytes.as_slice()), Err(BinaryReadError::IndexOutOfRange { index: 24, vertex_count: 24 })));
+    }
+
+    #[test]
+    fn binary_format_is_stable() {
+        let vertex = Vertex::new(Vector3::new(1.0, -2.0, 0.5), Vector3::new(0.0, 1.0, 0.0), Vector2::new(0.25, 1.0), UniColor::from_rgba(1, 2, 3, 4));
+        let mut bytes = Vec::new();
+        Mesh::new(vec![vertex], vec![0, 0, 0]).write_binary(&mut bytes).unwrap();
+        let expected: [u8; 62] = [
+            b'F', b'G', b'M', b'S', 1, 0,
+            1, 0, 0, 0, 3, 0, 0, 0,
+            0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x3f,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x80, 0x3e, 0x00, 0x00, 0x80, 0x3f,
+            1, 2, 3, 4,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(bytes, expected);
+    }
+
+    /// A mesh with random positions, UVs and triangles, some of them degenerate or using a vertex twice.
+    fn random_mesh(vertex_count: u32, triangle_count: usize) -> Mesh {
+        let mut state = 0x2545_f491u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+        let mut unit = move || (next() >> 8) as f32 / (1 << 24) as f32;
+        let vertices = (0..vertex_count)
+            .map(|_| Vertex::new(
+                Vector3::new(unit() * 10.0, unit() * 10.0, unit() * 10.0),
+                Vector3::new(0.0, 1.0, 0.0),
+                Vector2::new(unit(), unit()),
+                UniColor::WHITE,
+            ))
+            .collect();
+        let indices = (0..triangle_count * 3).map(|_| (unit() * vertex_count as f32) as u32 % vertex_count).collect();
+        Mesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn par_variants_match_the_serial_ones_bit_for_bit() {
+        let bits = |v: Vector3| v.to_array().map(f32::to_bits);
+        let mesh = random_mesh(5_000, 40_000);
+        let (mut serial, mut parallel) = (mesh.clone(), mesh);
+        serial.compute_normals();
+        parallel.compute_normals_par();
+        assert!(serial.vertices.iter().zip(&parallel.vertices).all(|(a, b)| bits(a.normal) == bits(b.normal)));
+
+        let (serial, parallel) = (serial.compute_tangents(), parallel.compute_tangents_par());
+        assert_eq!(serial.len(), parallel.len());
+        let bits = |v: &Vector4| [v.x, v.y, v.z, v.w].map(f32::to_bits);
+        assert!(serial.iter().zip(&parallel).all(|(a, b)| bits(a) == bits(b)));
+    }
+
+    #[test]
+    fn split_for_u16_keeps_every_triangle() {
+        let mesh = Mesh::plane(Vector2::new(10.0, 10.0), (300, 240));
+        assert_eq!(mesh.vertices.len(), 301 * 241);
+        assert!(matches!(mesh.shrink_indices(), IndexBuffer::U32(_)));
+        assert_eq!(mesh.shrink_indices().as_bytes().len(), mesh.indices.len() * 4);
+
+        let corners = |mesh: &Mesh| -> Vec<[[u32; 3]; 3]> {
+            mesh.indices.chunks_exact(3)
+                .map(|triangle| [0, 1, 2].map(|k| mesh.vertices[triangle[k] as usize].position.to_array().map(f32::to_bits)))
+                .collect()
+        };
+        let parts = mesh.split_for_u16();
+        assert!(parts.len() > 1);
+        let mut merged = Vec::new();
+        for part in &parts {
+            assert!(part.vertices.len() <= 65_535);
+            let indices = part.shrink_indices();
+            assert!(matches!(indices, IndexBuffer::U16(_)));
+            assert_eq!(indices.as_bytes().len(), part.indices.len() * 2);
+            merged.extend(corners(part));
+        }
+        assert_eq!(merged, corners(&mesh));
+    }
+
+    /// A closed unit sphere with `segments` around and `rings` from pole to pole, without a seam or duplicated poles.
+    /// The triangles wind counter-clockwise seen from outside.
+    fn uv_sphere(segments: u32, rings: u32) -> Mesh {
+        let vertex = |p: Vector3| Vertex::new(p, p, Vector2::new(0.0, 0.0), UniColor::WHITE);
+        let mut vertices = vec![vertex(Vector3::new(0.0, 1.0, 0.0))];
+        for ring in 1..rings {
+            let theta = PI * ring as f32 / rings as f32;
+            for segment in 0..segments {
+                let phi = TAU * segment as f32 / segments as f32;
+                vertices.push(vertex(Vector3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin())));
+            }
+        }
+        vertices.push(vertex(Vector3::new(0.0, -1.0, 0.0)));
+        let south = vertices.len() as u32 - 1;
+
+        let at = |ring: u32, segment: u32| 1 + (ring - 1) * segments + segment % segments;
+        let mut indices = Vec::new();
+        for s in 0..segments {
+            indices.extend([0, at(1, s + 1), at(1, s)]);
+            for ring in 1..rings - 1 {
+                let (a, b, c, d) = (at(ring, s), at(ring, s + 1), at(ring + 1, s), at(ring + 1, s + 1));
+                indices.extend([a, b, c, b, d, c]);
+            }
+            indices.extend([at(rings - 1, s), at(rings - 1, s + 1), south]);
+        }
+        Mesh::new(vertices, indices)
+    }
+
+    #[test]
+    fn decimate_keeps_a_sphere_closed_and_facing_outward() {
+        let sphere = uv_sphere(48, 24);
+        let target = sphere.indices.len() / 3 / 4;
+        let decimated = sphere.decimate(target);
+        let triangle_count = decimated.indices.len() / 3;
+        assert!(triangle_count <= target, "{} triangles left", triangle_count);
+
+        // A closed manifold sphere has V - E + F = 2 with E = 3F / 2, pinched vertices would break that.
+        let adjacency = MeshAdjacency::build(&decimated).unwrap();
+        assert!(adjacency.is_closed());
+        assert_eq!(decimated.vertices.len() as i64 - triangle_count as i64 / 2, 2);
+
+        for triangle in decimated.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| decimated.vertices[triangle[k] as usize].position);
+            let normal = (b - a).cross(&(c - a));
+            assert!(normal.dot(&(a + b + c)) > 0.0, "flipped triangle {:?}", triangle);
+        }
+
+        let (before, after) = (sphere.compute_bounds().unwrap(), decimated.compute_bounds().unwrap());
+        assert!(before.min.distance(&after.min) < 0.1 && before.max.distance(&after.max) < 0.1, "{:?}", after);
+    }
+
+    #[test]
+    fn optimize_vertex_cache_reorders_the_same_triangles() {
+        let mut mesh = Mesh::plane(Vector2::new(1.0, 1.0), (40, 40));
+        let mut triangles: Vec<[u32; 3]> = mesh.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+        let mut state = 0x2545_f491u32;
+        for i in (1..triangles.len()).rev() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            triangles.swap(i, state as usize % (i + 1));
+        }
+        mesh.indices = triangles.iter().flatten().copied().collect();
+        mesh.indices.push(7);
+
+        let shuffled = mesh.average_cache_miss_ratio(32);
+        mesh.optimize_vertex_cache();
+        let optimized = mesh.average_cache_miss_ratio(32);
+        assert!(optimized <= shuffled, "{} -> {}", shuffled, optimized);
+        assert!(optimized < 1.0, "{}", optimized);
+
+        // Only the order of the triangles changes, not their winding, and the dangling index stays at the end.
+        assert_eq!(mesh.indices.pop(), Some(7));
+        let mut reordered: Vec<[u32; 3]> = mesh.indices.chunks_exact(3).map(|t| [t[0], t[1], t[2]]).collect();
+        reordered.sort_unstable();
+        triangles.sort_unstable();
+        assert_eq!(reordered, triangles);
+    }
+
+    #[test]
+    fn laplacian_smooth_flattens_noise_and_keeps_the_border() {
+        let noise = |x: f32, z: f32| ((x * 12.9898 + z * 78.233).sin() * 43_758.547).fract() * 0.2;
+        let noisy = Mesh::heightmap(Vector2::new(4.0, 4.0), (20, 20), noise);
+        let roughness = |mesh: &Mesh| mesh.vertices.iter().map(|v| v.position.y * v.position.y).sum::<f32>();
+
+        let mut smoothed = noisy.clone();
+        smoothed.laplacian_smooth(5, 0.5, true).unwrap();
+        assert!(roughness(&smoothed) < roughness(&noisy) * 0.5, "{} -> {}", roughness(&noisy), roughness(&smoothed));
+
+        let adjacency = MeshAdjacency::build(&noisy).unwrap();
+        for (v, (before, after)) in noisy.vertices.iter().zip(&smoothed.vertices).enumerate() {
+            if adjacency.is_boundary_vertex(v as u32) {
+                assert_eq!(before.position.to_array(), after.position.to_array());
+            } else {
+                assert_ne!(before.position.to_array(), after.position.to_array());
+            }
+        }
+
+        let mut free = noisy.clone();
+        free.laplacian_smooth(5, 0.5, false).unwrap();
+        assert_ne!(free.vertices[0].position.to_array(), noisy.vertices[0].position.to_array());
+    }
+
+    #[test]
+    fn laplacian_smooth_rejects_non_manifold_meshes() {
+        let mut fan = cube();
+        // The faces of the cube are separate, so two more triangles make three on its first edge.
+        let (a, b) = (fan.indices[0], fan.indices[1]);
+        fan.indices.extend([a, b, 10, b, a, 11]);
+        let before = fan.vertices.clone();
+        assert_eq!(fan.laplacian_smooth(1, 0.5, false), Err(NonManifoldError { edge: (a.min(b), a.max(b)) }));
+        assert!(fan.vertices.iter().zip(&before).all(|(a, b)| a.position.to_array() == b.position.to_array()));
+    }
+
+    #[test]
+    fn heightmap_has_one_vertex_per_grid_corner() {
+        let flat = Mesh::heightmap(Vector2::new(4.0, 2.0), (8, 3), |_, _| 1.5);
+        assert_eq!((flat.vertices.len(), flat.indices.len()), (9 * 4, 8 * 3 * 6));
+        assert!(flat.vertices.iter().all(|v| v.normal == Vector3::UP && v.position.y == 1.5));
+        let bounds = flat.compute_bounds().unwrap();
+        assert_eq!((bounds.min.to_array(), bounds.max.to_array()), ([-2.0, 1.5, -1.0], [2.0, 1.5, 1.0]));
+        // The triangles face +Y.
+        let [a, b, c] = [0, 1, 2].map(|k| flat.vertices[flat.indices[k] as usize].position);
+        assert!((b - a).cross(&(c - a)).y > 0.0);
+
+        let slope = Mesh::heightmap(Vector2::new(4.0, 2.0), (8, 3), |x, _| 0.5 * x);
+        let expected = Vector3::new(-0.5, 1.0, 0.0).normalized();
+        // Loose enough for the `fast_math` magnitude.
+        assert!(slope.vertices.iter().all(|v| v.normal.approx_eq(&expected, 2e-3)));
+
+        // A zero size collapses the grid without producing NaN normals.
+        for size in [Vector2::new(0.0, 2.0), Vector2::new(4.0, 0.0), Vector2::new(0.0, 0.0)] {
+            let collapsed = Mesh::heightmap(size, (4, 4), |x, z| x - z);
+            assert!(collapsed.vertices.iter().all(|v| v.normal.to_array().iter().all(|c| c.is_finite())), "{:?}", size);
+            assert!(collapsed.vertices.iter().all(|v| (v.normal.magnitude_squared() - 1.0).abs() < 1e-3));
+        }
+    }
+
+    #[test]
+    fn subdivide_grid_multiplies_the_cells() {
+        let plane = Mesh::plane(Vector2::new(3.0, 2.0), (3, 2));
+        let once = plane.subdivide_grid(2).unwrap();
+        assert_eq!((once.vertices.len(), once.indices.len() / 3), (7 * 5, 6 * 4 * 2));
+        let twice = once.subdivide_grid(3).unwrap();
+        assert_eq!((twice.vertices.len(), twice.indices.len() / 3), (19 * 13, 18 * 12 * 2));
+        assert_eq!(plane.subdivide_grid(1).unwrap().content_hash(), plane.content_hash());
+        let bounds = |mesh: &Mesh| mesh.compute_bounds().map(|b| (b.min.to_array(), b.max.to_array()));
+        assert_eq!(bounds(&twice), bounds(&plane));
+        // The new vertices lie on the finer grid.
+        let fine = Mesh::plane(Vector2::new(3.0, 2.0), (6, 4));
+        assert_eq!(once.indices, fine.indices);
+        assert!(once.vertices.iter().zip(&fine.vertices).all(|(a, b)| a.position.approx_eq(&b.position, 1e-6) && a.normal == Vector3::UP));
+
+        assert!(plane.subdivide_grid(0).is_none());
+        assert!(cube().subdivide_grid(2).is_none());
+        assert!(Mesh::new(Vec::new(), Vec::new()).subdivide_grid(2).is_none());
+        // Too many vertices for u32 indices.
+        assert!(plane.subdivide_grid(u32::MAX).is_none());
+        assert!(plane.subdivide_grid(40_000).is_none());
+    }
+
+    #[test]
+    fn project_uvs_spherical_splits_the_seam_and_the_poles() {
+        let (segments, rings) = (16, 8);
+        let original = uv_sphere(segments, rings);
+        let mut sphere = original.clone();
+        sphere.project_uvs_spherical();
+
+        let positions = |mesh: &Mesh| -> Vec<[f32; 3]> { mesh.indices.iter().map(|&i| mesh.vertices[i as usize].position.to_array()).collect() };
+        assert_eq!(positions(&sphere), positions(&original));
+        assert!(sphere.vertices.len() > original.vertices.len());
+
+        for triangle in sphere.indices.chunks_exact(3) {
+            let uvs = [0, 1, 2].map(|k| sphere.vertices[triangle[k] as usize].tex_coords);
+            let (min, max) = uvs.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), uv| (min.min(uv.x), max.max(uv.x)));
+            // No triangle spans the whole texture at the seam.
+            assert!(max - min <= 1.0 / segments as f32 + 1e-5, "{:?}", uvs);
+            assert!(uvs.iter().all(|uv| (0.0..=1.0 + 1.0 / segments as f32).contains(&uv.x) && (0.0..=1.0).contains(&uv.y)), "{:?}", uvs);
+        }
+
+        // Every triangle touching a pole gets its own copy of the pole, with the u halfway between its other corners.
+        for (pole, v) in [(Vector3::new(0.0, 1.0, 0.0), 0.0), (Vector3::new(0.0, -1.0, 0.0), 1.0)] {
+            let mut copies: Vec<u32> = sphere.indices.iter().copied().filter(|&i| sphere.vertices[i as usize].position == pole).collect();
+            copies.sort_unstable();
+            copies.dedup();
+            assert_eq!(copies.len(), segments as usize);
+            assert!(copies.iter().all(|&i| sphere.vertices[i as usize].tex_coords.y == v));
+        }
+
+        // The equator at +X and +Z.
+        let equator = |segment: u32| sphere.vertices[(1 + (rings / 2 - 1) * segments + segment) as usize].tex_coords;
+        assert!(equator(0).approx_eq(&Vector2::new(0.5, 0.5), 1e-6), "{:?}", equator(0));
+        assert!(equator(segments / 4).approx_eq(&Vector2::new(0.75, 0.5), 1e-6), "{:?}", equator(segments / 4));
+    }
+
+    #[test]
+    fn project_uvs_box_gives_every_side_a_full_tile() {
+        // A cube sharing its 8 corners between the sides, corner i sits at the bits of i.
+        let corner = |i: u32| Vector3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2 & 1) as f32);
+        let vertices = (0..8).map(|i| Vertex::new(corner(i), Vector3::zero(), Vector2::new(0.0, 0.0), UniColor::WHITE)).collect();
+        let sides: [([u32; 4], usize); 6] = [
+            ([0, 2, 6, 4], 0), ([1, 3, 7, 5], 0),
+            ([0, 1, 5, 4], 1), ([2, 3, 7, 6], 1),
+            ([0, 1, 3, 2], 2), ([4, 5, 7, 6], 2),
+        ];
+        let indices = sides.iter().flat_map(|([a, b, c, d], _)| [*a, *b, *c, *a, *c, *d]).collect();
+        let mut mesh = Mesh::new(vertices, indices);
+        let scale = Vector2::new(2.0, 3.0);
+        mesh.project_uvs_box(scale);
+
+        // Each corner is used by three sides projected along different axes.
+        assert_eq!(mesh.vertices.len(), 24);
+        // The bounds start at the origin, so the UVs are the scaled coordinates across the side.
+        for (triangle, (_, axis)) in mesh.indices.chunks_exact(6).zip(sides) {
+            for &i in triangle {
+                let vertex = &mesh.vertices[i as usize];
+                assert_eq!(vertex.tex_coords, planar_uv(vertex.position, axis, scale));
+            }
+            let mut uvs: Vec<[f32; 2]> = triangle.iter().map(|&i| mesh.vertices[i as usize].tex_coords.to_array()).collect();
+            uvs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            uvs.dedup();
+            assert_eq!(uvs, [[0.0, 0.0], [0.0, 3.0], [2.0, 0.0], [2.0, 3.0]]);
+        }
+
+        // Separate sides need no copies.
+        let mut separate = cube();
+        separate.project_uvs_box(Vector2::new(1.0, 1.0));
+        assert_eq!(separate.vertices.len(), cube().vertices.len());
+    }
+
+    #[test]
+    fn non_indexed_and_welded_back_keeps_the_triangles() {
+        let cube = cube();
+        // The triangles by the bytes of their vertices, independent of the vertex order.
+        let triangles = |mesh: &Mesh| {
+            let mut triangles: Vec<Vec<u8>> = mesh.indices.chunks_exact(3)
+                .map(|t| t.iter().flat_map(|&i| mesh.vertices[i as usize].as_bytes()).collect())
+                .collect();
+            triangles.sort_unstable();
+            triangles
+        };
+        let expanded = cube.indices.iter().map(|&i| cube.vertices[i as usize]).collect();
+        let non_indexed = Mesh::non_indexed(expanded).unwrap();
+        assert_eq!((non_indexed.vertices.len(), non_indexed.indices.clone()), (36, (0..36).collect()));
+        assert_eq!(triangles(&non_indexed), triangles(&cube));
+
+        let indexed = non_indexed.weld(&WeldPolicy::new(0.0));
+        assert_eq!(indexed.vertices.len(), 24);
+        assert_eq!(triangles(&indexed), triangles(&cube));
+        let rebuilt = Mesh::indexed(indexed.vertices.clone(), indexed.indices.clone()).unwrap();
+        assert_eq!(rebuilt.content_hash(), indexed.content_hash());
+    }
+
+    #[test]
+    fn indexed_rejects_broken_triangle_lists() {
+        let vertices = cube().vertices;
+        assert_eq!(Mesh::non_indexed(vertices[..4].to_vec()).err(), Some(MeshIndexError::IncompleteTriangle { index_count: 4 }));
+        assert_eq!(Mesh::indexed(vertices.clone(), vec![0, 1]).err(), Some(MeshIndexError::IncompleteTriangle { index_count: 2 }));
+        let error = Mesh::indexed(vertices.clone(), vec![0, 1, 2, 3, 24, 5]).err().unwrap();
+        assert_eq!(error, MeshIndexError::OutOfRange { index: 24, vertex_count: 24 });
+        assert_eq!(error.to_string(), "index 24 is out of range for 24 vertices");
+        assert!(Mesh::indexed(Vec::new(), Vec::new()).is_ok());
+        assert!(Mesh::non_indexed(Vec::new()).unwrap().indices.is_empty());
+    }
+
+    #[test]
+    fn compute_bounds_matches_the_vertex_extents() {
+        assert!(Mesh::new(Vec::new(), Vec::new()).compute_bounds().is_none());
+        let mut mesh = random_mesh(50, 30);
+        // An unused vertex still counts.
+        mesh.vertices.push(Vertex::new(Vector3::new(-20.0, 0.0, 30.0), Vector3::UP, Vector2::new(0.0, 0.0), UniColor::WHITE));
+        let bounds = mesh.compute_bounds().unwrap();
+        let extent = |select: fn(f32, f32) -> f32, start: f32| mesh.vertices.iter().fold([start; 3], |acc, v| {
+            let p = v.position.to_array();
+            [select(acc[0], p[0]), select(acc[1], p[1]), select(acc[2], p[2])]
+        });
+        assert_eq!(bounds.min.to_array(), extent(f32::min, f32::INFINITY));
+        assert_eq!(bounds.max.to_array(), extent(f32::max, f32::NEG_INFINITY));
+        assert_eq!((bounds.min.x, bounds.max.z), (-20.0, 30.0));
+    }
+
+}
\ No newline at end of file
@@ -1,2 +1,15 @@
 pub mod vertex;
-pub mod color;
\ No newline at end of file
+pub mod color;
+pub mod camera;
+#[cfg(feature = "std")]
+pub mod mesh;
+#[cfg(feature = "std")]
+pub mod adjacency;
+#[cfg(feature = "std")]
+pub mod spatial_grid;
+#[cfg(feature = "std")]
+pub mod bvh;
+#[cfg(feature = "std")]
+pub mod convex_hull;
+#[cfg(feature = "std")]
+pub mod gradient;
\ No newline at end of file
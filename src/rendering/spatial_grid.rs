@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use crate::vectors::vector3::Vector3;
+
+/// A uniform hash grid over a point cloud, answering radius and nearest neighbor queries
+/// without scanning every point. Used for vertex welding, normal estimation and particle neighbor searches.
+///
+/// Points are bucketed into cubic cells of `cell_size`. Queries are fastest when the cell size is close to
+/// the typical query radius, or to the average point spacing for `nearest`.
+#[derive(Clone, Debug)]
+pub struct SpatialGrid {
+    points: Vec<Vector3>,
+    cells: HashMap<[i32; 3], Vec<usize>>,
+    cell_size: f32,
+    inv_cell_size: f32,
+    min_cell: [i32; 3],
+    max_cell: [i32; 3],
+}
+
+impl SpatialGrid {
+
+    /// Builds a grid over `points` with cubic cells of `cell_size`.
+    /// The points are copied, query results are indices into `points`.
+    /// Returns `None` if `cell_size` is not positive and finite.
+    pub fn build(points: &[Vector3], cell_size: f32) -> Option<Self> {
+        if cell_size <= 0.0 || !cell_size.is_finite() {
+            return None;
+        }
+
+        let mut grid = Self {
+            points: points.to_vec(),
+            cells: HashMap::new(),
+            cell_size,
+            inv_cell_size: 1.0 / cell_size,
+            min_cell: [i32::MAX; 3],
+            max_cell: [i32::MIN; 3],
+        };
+        for (i, point) in points.iter().enumerate() {
+            let cell = grid.cell_of(*point);
+            for (axis, &c) in cell.iter().enumerate() {
+                grid.min_cell[axis] = grid.min_cell[axis].min(c);
+                grid.max_cell[axis] = grid.max_cell[axis].max(c);
+            }
+            grid.cells.entry(cell).or_default().push(i);
+        }
+        Some(grid)
+    }
+
+    /// Returns the number of points in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns true if the grid contains no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the points the grid was built from.
+    #[inline]
+    pub fn points(&self) -> &[Vector3] {
+        &self.points
+    }
+
+    /// Returns the size of the cells.
+    #[inline]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the indices of all points within `radius` of `point` (inclusive), in ascending order.
+    /// A radius of zero finds exact duplicates of `point`, a negative or NaN radius finds nothing.
+    pub fn query_radius(&self, point: Vector3, radius: f32) -> Vec<usize> {
+        let mut result = Vec::new();
+        if radius.is_nan() || radius < 0.0 || self.is_empty() {
+            return result;
+        }
+
+        let radius_squared = radius * radius;
+        let mut check = |indices: &[usize]| {
+            for &i in indices {
+                if self.points[i].distance_squared(&point) <= radius_squared {
+                    result.push(i);
+                }
+            }
+        };
+
+        let min = self.cell_of(point - Vector3::from_one(radius));
+        let max = self.cell_of(point + Vector3::from_one(radius));
+        let (min, max) = (self.clamp_to_bounds(min), self.clamp_to_bounds(max));
+        let cell_count = (0..3).map(|axis| (max[axis] as i64 - min[axis] as i64 + 1).max(0) as u64).product::<u64>();
+
+        if cell_count > self.cells.len() as u64 {
+            // Visiting every occupied cell is cheaper than looking up mostly empty ones.
+            for (cell, indices) in &self.cells {
+                if (0..3).all(|axis| cell[axis] >= min[axis] && cell[axis] <= max[axis]) {
+                    check(indices);
+                }
+            }
+        } else {
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        if let Some(indices) = self.cells.get(&[x, y, z]) {
+                            check(indices);
+                        }
+                    }
+                }
+            }
+        }
+
+        result.sort_unstable();
+        result
+    }
+
+    /// Returns the index of the point closest to `point`, or `None` if the grid is empty.
+    /// If several points are equally close, the smallest index is returned.
+    ///
+    /// Searches shells of cells around `point` until no unvisited cell can contain a closer point,
+    /// so queries far outside the cloud start at the first shell that touches it.
+    pub fn nearest(&self, point: Vector3) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let center = self.cell_of(point);
+        let mut best: Option<(f32, usize)> = None;
+        let consider = |indices: &[usize], best: &mut Option<(f32, usize)>| {
+            for &i in indices {
+                let distance = self.points[i].distance_squared(&point);
+                if best.is_none_or(|(d, b)| distance < d || (distance == d && i < b)) {
+                    *best = Some((distance, i));
+                }
+            }
+        };
+
+        // The first and last shells that can contain occupied cells.
+        let (mut first, mut last) = (0i64, 0i64);
+        for (axis, &c) in center.iter().enumerate() {
+            let (c, lo, hi) = (c as i64, self.min_cell[axis] as i64, self.max_cell[axis] as i64);
+            first = first.max(lo - c).max(c - hi);
+            last = last.max((c - lo).abs()).max((hi - c).abs());
+        }
+
+        let mut visited = 0u64;
+        for k in first..=last {
+            // A shell of side 2k + 1 has (2k + 1)³ - (2k - 1)³ = 24k² + 2 cells, saturating for queries far outside the cloud.
+            let shell_cells = if k == 0 { 1 } else { (k as u64).saturating_mul(k as u64).saturating_mul(24).saturating_add(2) };
+            if visited.saturating_add(shell_cells) > self.cells.len() as u64 {
+                // The remaining shells are mostly empty, checking every occupied cell is cheaper.
+                for indices in self.cells.values() {
+                    consider(indices, &mut best);
+                }
+                break;
+            }
+            visited += shell_cells;
+
+            self.for_each_cell_in_shell(center, k, |indices| consider(indices, &mut best));
+
+            // Every cell in shell k + 1 or further is at least k cells away from `point`.
+            if let Some((distance, _)) = best {
+                let reach = k as f32 * self.cell_size;
+                if distance <= reach * reach {
+                    break;
+                }
+            }
+        }
+
+        best.map(|(_, i)| i)
+    }
+
+    /// Calls `f` with the points of every occupied cell at Chebyshev distance `k` from `center`.
+    fn for_each_cell_in_shell(&self, center: [i32; 3], k: i64, mut f: impl FnMut(&[usize])) {
+        let c = center.map(|v| v as i64);
+        for dx in -k..=k {
+            for dy in -k..=k {
+                let on_side = dx.abs() == k || dy.abs() == k;
+                let mut dz = -k;
+                while dz <= k {
+                    let cell = [c[0] + dx, c[1] + dy, c[2] + dz];
+                    if cell.iter().all(|&v| v >= i32::MIN as i64 && v <= i32::MAX as i64) {
+                        if let Some(indices) = self.cells.get(&cell.map(|v| v as i32)) {
+                            f(indices);
+                        }
+                    }
+                    // Inside the shell only the two z faces belong to it.
+                    dz += if on_side || k == 0 { 1 } else { 2 * k };
+                }
+            }
+        }
+    }
+
+    /// Returns the cell containing `point`, saturating for coordinates far outside the i32 range.
+    #[inline]
+    fn cell_of(&self, point: Vector3) -> [i32; 3] {
+        [
+            (point.x * self.inv_cell_size).floor() as i32,
+            (point.y * self.inv_cell_size).floor() as i32,
+            (point.z * self.inv_cell_size).floor() as i32,
+        ]
+    }
+
+    /// Clamps `cell` into the range of occupied cells, one past the bounds if it lies outside.
+    #[inline]
+    fn clamp_to_bounds(&self, cell: [i32; 3]) -> [i32; 3] {
+        [0, 1, 2].map(|axis| {
+            cell[axis].clamp(self.min_cell[axis].saturating_sub(1), self.max_cell[axis].saturating_add(1))
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// A small xorshift generator, so the points are the same on every run.
+    struct Rng(u32);
+
+    impl Rng {
+
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 >> 8) as f32 / (1 << 24) as f32
+        }
+
+        fn point(&mut self, scale: f32) -> Vector3 {
+            Vector3::new(self.next_f32() - 0.5, self.next_f32() - 0.5, self.next_f32() - 0.5) * scale
+        }
+
+    }
+
+    /// A random cloud with a few exact duplicates.
+    fn cloud(rng: &mut Rng) -> Vec<Vector3> {
+        let mut points: Vec<Vector3> = (0..500).map(|_| rng.point(10.0)).collect();
+        for i in 0..20 {
+            points.push(points[i * 7]);
+        }
+        points
+    }
+
+    /// The nearest point from checking every point, the smaller index winning ties.
+    fn brute_force_nearest(points: &[Vector3], point: Vector3) -> Option<usize> {
+        (0..points.len()).min_by(|&a, &b| points[a].distance_squared(&point).total_cmp(&points[b].distance_squared(&point)))
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let mut rng = Rng(0x1234_5678);
+        let points = cloud(&mut rng);
+        let mut queries: Vec<Vector3> = (0..200).map(|_| rng.point(14.0)).collect();
+        // Far outside of the populated cells and right on top of a duplicate.
+        queries.extend([Vector3::new(100.0, 0.0, 0.0), Vector3::new(-40.0, 60.0, -80.0), points[7]]);
+
+        for cell_size in [0.3, 1.0, 4.0] {
+            let grid = SpatialGrid::build(&points, cell_size).unwrap();
+            assert_eq!((grid.len(), grid.cell_size()), (points.len(), cell_size));
+            for &query in &queries {
+                for radius in [0.0, 0.5, 1.7, 30.0, 200.0] {
+                    let expected: Vec<usize> = (0..points.len()).filter(|&i| points[i].distance_squared(&query) <= radius * radius).collect();
+                    assert_eq!(grid.query_radius(query, radius), expected, "{:?} {} {}", query, radius, cell_size);
+                }
+                assert_eq!(grid.nearest(query), brute_force_nearest(&points, query), "{:?} {}", query, cell_size);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_inputs_find_nothing() {
+        for cell_size in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(SpatialGrid::build(&[Vector3::zero()], cell_size).is_none());
+        }
+
+        let empty = SpatialGrid::build(&[], 1.0).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.query_radius(Vector3::zero(), 10.0).is_empty());
+        assert_eq!(empty.nearest(Vector3::zero()), None);
+
+        let grid = SpatialGrid::build(&[Vector3::new(1.0, 2.0, 3.0), Vector3::new(1.0, 2.0, 3.0)], 1.0).unwrap();
+        assert_eq!(grid.query_radius(Vector3::new(1.0, 2.0, 3.0), 0.0), [0, 1]);
+        assert!(grid.query_radius(Vector3::new(1.0, 2.0, 3.0), -1.0).is_empty());
+        assert!(grid.query_radius(Vector3::new(1.0, 2.0, 3.0), f32::NAN).is_empty());
+        assert_eq!(grid.nearest(Vector3::new(1e30, -1e30, 0.0)), Some(0));
+    }
+
+}
\ No newline at end of file
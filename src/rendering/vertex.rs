@@ -1,10 +1,23 @@
-use crate::rendering::color::UniColor;
+use crate::angles::quaternion::Quaternion;
+use crate::math::Lerp;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::matrix4x4::Matrix4x4;
+use crate::rendering::color::{linear_to_srgb, srgb_to_linear, UniColor};
 use crate::vectors::vector2::Vector2;
 use crate::vectors::vector3::Vector3;
+#[cfg(feature = "std")]
+use crate::vectors::vector4::Vector4;
 
 /// A Vertex struct for representing a point in 3D space along with its associated attributes such as
 /// normal, texture coordinates, color, etc.
+///
+/// The layout is `repr(C)` without padding, 36 bytes in field order, so with the `bytemuck` feature
+/// a whole vertex buffer can be cast to bytes with `bytemuck::cast_slice`.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct Vertex {
     pub position: Vector3,
     pub normal: Vector3,
@@ -12,10 +25,18 @@ pub struct Vertex {
     pub color: UniColor,
 }
 
+// Vertex buffers are uploaded as is, so the layout has to match `as_bytes` exactly and contain no padding.
+const _: () = {
+    assert!(core::mem::size_of::<Vertex>() == 36 && core::mem::align_of::<Vertex>() == 4);
+    assert!(core::mem::offset_of!(Vertex, normal) == 12);
+    assert!(core::mem::offset_of!(Vertex, tex_coords) == 24);
+    assert!(core::mem::offset_of!(Vertex, color) == 32);
+};
+
 impl Vertex {
 
     /// Creates a new vertex from the given values
-    pub fn new(position: Vector3, normal: Vector3, tex_coords: Vector2, color: UniColor) -> Self {
+    pub const fn new(position: Vector3, normal: Vector3, tex_coords: Vector2, color: UniColor) -> Self {
         Self {
             position,
             normal,
@@ -25,8 +46,8 @@ impl Vertex {
     }
 
     /// Returns the byte representation of the vertex.
-    pub fn as_bytes(&self) -> [u8; 40] {
-        let mut bytes = [0u8; 40];
+    pub fn as_bytes(&self) -> [u8; 36] {
+        let mut bytes = [0u8; 36];
         bytes[..12].copy_from_slice(&self.position.as_bytes());
         bytes[12..24].copy_from_slice(&self.normal.as_bytes());
         bytes[24..32].copy_from_slice(&self.tex_coords.as_bytes());
@@ -34,13 +55,42 @@ impl Vertex {
         bytes
     }
 
-    /// Linearly interpolate between two vertices
+    /// Linearly interpolate between two vertices.
+    ///
+    /// `t` is not clamped, so values outside [0, 1] extrapolate every attribute consistently. The colors are extrapolated
+    /// per channel and only saturate at 0 or 255 when converted back to bytes, unlike `UniColor::lerp`, which clamps `t`.
     pub fn lerp(&self, other: &Self, t: f32) -> Self {
         Self {
             position: self.position.lerp(&other.position, t),
             normal: self.normal.lerp(&other.normal, t),
             tex_coords: self.tex_coords.lerp(&other.tex_coords, t),
-            color: self.color.lerp(&other.color, t),
+            color: lerp_color_unclamped(self.color, other.color, t),
+        }
+    }
+
+    /// Like `lerp`, but clamps `t` to [0, 1], so the result always lies between the two vertices.
+    #[inline]
+    pub fn lerp_clamped(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t.clamp(0.0, 1.0))
+    }
+
+    /// Extrapolates past the vertices, e.g. `t = 1.5` continues from `other` by half of the distance from `self`.
+    /// This is the same as `lerp`, which never clamps `t`, but states the intent at the call site.
+    #[inline]
+    pub fn extrapolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+
+    /// Like `lerp`, but blends the colors in linear light like `UniColor::lerp_linear`.
+    ///
+    /// `t` is not clamped either, every attribute extrapolates consistently. The colors are extrapolated per channel
+    /// in linear light and only saturate at 0 or 255 when converted back to bytes, unlike `UniColor::lerp_linear`, which clamps `t`.
+    pub fn lerp_linear(&self, other: &Self, t: f32) -> Self {
+        Self {
+            position: self.position.lerp(&other.position, t),
+            normal: self.normal.lerp(&other.normal, t),
+            tex_coords: self.tex_coords.lerp(&other.tex_coords, t),
+            color: lerp_color_linear_unclamped(self.color, other.color, t),
         }
     }
 
@@ -67,4 +117,459 @@ impl Vertex {
         self.distance_squared(other).sqrt()
     }
 
+    /// Returns the memory layout of a vertex for setting up vertex buffers in a render pipeline.
+    /// The offsets are taken from the struct itself, so they always match `as_bytes` and `bytes_of_slice`.
+    ///
+    /// The color is stored as one native-endian RGBA8888 `u32`, so on little-endian hosts its bytes are in a, b, g, r order
+    /// and a shader reading them as four unorm8 components has to swizzle with `.abgr` to get r, g, b, a back.
+    pub const fn layout() -> VertexLayout {
+        VertexLayout {
+            stride: core::mem::size_of::<Vertex>(),
+            attributes: [
+                VertexAttribute { name: "position", offset: core::mem::offset_of!(Vertex, position), components: 3, component_type: VertexComponentType::F32 },
+                VertexAttribute { name: "normal", offset: core::mem::offset_of!(Vertex, normal), components: 3, component_type: VertexComponentType::F32 },
+                VertexAttribute { name: "tex_coords", offset: core::mem::offset_of!(Vertex, tex_coords), components: 2, component_type: VertexComponentType::F32 },
+                VertexAttribute { name: "color", offset: core::mem::offset_of!(Vertex, color), components: 4, component_type: VertexComponentType::Unorm8 },
+            ],
+        }
+    }
+
+    /// Returns the bytes of all `vertices` back to back, `Vertex::layout().stride` bytes each, ready for uploading a vertex buffer.
+    #[cfg(feature = "std")]
+    pub fn bytes_of_slice(vertices: &[Vertex]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(core::mem::size_of_val(vertices));
+        for vertex in vertices {
+            bytes.extend_from_slice(&vertex.as_bytes());
+        }
+        bytes
+    }
+
+}
+
+impl Lerp for Vertex {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vertex::lerp(self, other, t)
+    }
+}
+
+/// Interpolates the color channels by `t` without clamping it, saturating every channel at 0 and 255.
+fn lerp_color_unclamped(a: UniColor, b: UniColor, t: f32) -> UniColor {
+    let (r1, g1, b1, a1) = a.to_rgba();
+    let (r2, g2, b2, a2) = b.to_rgba();
+    let mix = |c1: u8, c2: u8| (c1 as f32 * (1.0 - t) + c2 as f32 * t).clamp(0.0, 255.0).round() as u8;
+    UniColor::from_rgba(mix(r1, r2), mix(g1, g2), mix(b1, b2), mix(a1, a2))
+}
+
+/// Like `lerp_color_unclamped`, but interpolates the RGB channels in linear light like `UniColor::lerp_linear`.
+fn lerp_color_linear_unclamped(a: UniColor, b: UniColor, t: f32) -> UniColor {
+    let (r1, g1, b1, a1) = a.to_rgba();
+    let (r2, g2, b2, a2) = b.to_rgba();
+    let mix = |c1: u8, c2: u8| {
+        let linear = srgb_to_linear(c1 as f32 / 255.0) * (1.0 - t) + srgb_to_linear(c2 as f32 / 255.0) * t;
+        (linear_to_srgb(linear.clamp(0.0, 1.0)) * 255.0).round() as u8
+    };
+    let alpha = (a1 as f32 * (1.0 - t) + a2 as f32 * t).clamp(0.0, 255.0).round() as u8;
+    UniColor::from_rgba(mix(r1, r2), mix(g1, g2), mix(b1, b2), alpha)
+}
+
+/// Rotates the normals of all `vertices` by `rotation` and renormalizes them, leaving everything else untouched.
+/// Useful when positions are transformed on the GPU, but the normals are still needed on the CPU (e.g. for baked lighting).
+pub fn rotate_normals(vertices: &mut [Vertex], rotation: &Quaternion) {
+    for vertex in vertices.iter_mut() {
+        vertex.normal = normalize_or_zero(rotation.rotate_vector(vertex.normal));
+    }
+}
+
+/// Transforms the normals of all `vertices` by the normal matrix of `matrix` and renormalizes them,
+/// leaving everything else untouched.
+///
+/// The normal matrix is the inverse transpose of the upper-left 3x3, which keeps normals perpendicular
+/// to their surface under non-uniform scale. It also works for singular matrices.
+pub fn transform_normals(vertices: &mut [Vertex], matrix: &Matrix4x4) {
+    let normal_matrix = normal_matrix(matrix);
+    for vertex in vertices.iter_mut() {
+        vertex.normal = transform_normal(&normal_matrix, vertex.normal);
+    }
+}
+
+/// Transforms the positions of all `vertices` as points by `matrix` and their normals like `transform_normals`,
+/// e.g. to bake a model matrix into a mesh. Texture coordinates and colors are left untouched.
+pub fn transform_vertices(vertices: &mut [Vertex], matrix: &Matrix4x4) {
+    let normal_matrix = normal_matrix(matrix);
+    for vertex in vertices.iter_mut() {
+        vertex.position = matrix.transform_point(vertex.position);
+        vertex.normal = transform_normal(&normal_matrix, vertex.normal);
+    }
+}
+
+/// Like `transform_vertices`, but splits the vertices over all cores with the `rayon` feature.
+/// Every vertex is transformed on its own, so the result is identical to `transform_vertices`.
+/// Without the feature this is `transform_vertices`.
+pub fn transform_vertices_par(vertices: &mut [Vertex], matrix: &Matrix4x4) {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        let normal_matrix = normal_matrix(matrix);
+        vertices.par_iter_mut().for_each(|vertex| {
+            vertex.position = matrix.transform_point(vertex.position);
+            vertex.normal = transform_normal(&normal_matrix, vertex.normal);
+        });
+    }
+    #[cfg(not(feature = "rayon"))]
+    transform_vertices(vertices, matrix);
+}
+
+/// Computes a tangent for every vertex for normal mapping, pointing where the u texture coordinate grows along the surface.
+///
+/// The tangents are returned as an array parallel to `vertices`, which keeps the vertex layout as is. Their xyz is a unit vector
+/// perpendicular to the normal and w is the handedness, so the bitangent is `normal.cross(&tangent.truncate()) * tangent.w`.
+/// Every triangle adds its tangent from the UV deltas to its vertices, which are then orthonormalized against the vertex normal.
+/// Triangles without area in texture space add nothing, vertices left without a tangent get an arbitrary one perpendicular
+/// to their normal, so the result never contains NaN.
+#[cfg(feature = "std")]
+pub fn compute_tangents(vertices: &[Vertex], indices: &[u32]) -> Vec<Vector4> {
+    let mut tangents = vec![Vector3::zero(); vertices.len()];
+    let mut bitangents = vec![Vector3::zero(); vertices.len()];
+    for triangle in indices.chunks_exact(3) {
+        let Some((tangent, bitangent)) = triangle_tangent(vertices, triangle) else { continue };
+        for &i in triangle {
+            tangents[i as usize] += tangent;
+            bitangents[i as usize] += bitangent;
+        }
+    }
+
+    vertices.iter().zip(tangents.into_iter().zip(bitangents))
+        .map(|(vertex, (tangent, bitangent))| orthonormalize_tangent(vertex.normal, tangent, bitangent))
+        .collect()
+}
+
+/// Returns the tangent and bitangent of a triangle, solving the position deltas for the directions of the UV deltas.
+/// Returns `None` if the triangle has no area in texture space.
+#[cfg(feature = "std")]
+pub(crate) fn triangle_tangent(vertices: &[Vertex], triangle: &[u32]) -> Option<(Vector3, Vector3)> {
+    let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| &vertices[i as usize]);
+    let (e1, e2) = (b.position - a.position, c.position - a.position);
+    let (d1, d2) = (b.tex_coords - a.tex_coords, c.tex_coords - a.tex_coords);
+    let inv_det = 1.0 / (d1.x * d2.y - d2.x * d1.y);
+    let tangent = (e1 * d2.y - e2 * d1.y) * inv_det;
+    let bitangent = (e2 * d1.x - e1 * d2.x) * inv_det;
+    // Also catches tiny determinants, whose tangents overflow.
+    (tangent.is_finite() && bitangent.is_finite()).then_some((tangent, bitangent))
+}
+
+/// Turns the summed `tangent` into a unit vector perpendicular to `normal` (Gram-Schmidt)
+/// and stores the handedness of `bitangent` in w, see `compute_tangents`.
+#[cfg(feature = "std")]
+pub(crate) fn orthonormalize_tangent(normal: Vector3, tangent: Vector3, bitangent: Vector3) -> Vector4 {
+    let normal = normalize_or_zero(normal);
+    let perpendicular = tangent - normal * normal.dot(&tangent);
+    let len_squared = perpendicular.magnitude_squared();
+    let tangent = if len_squared > 0.0 && len_squared > 1e-12 * tangent.magnitude_squared() {
+        perpendicular * (1.0 / len_squared.sqrt())
+    } else if normal != Vector3::zero() {
+        normal.orthonormal_basis().0
+    } else {
+        Vector3::X
+    };
+    let handedness = if normal.cross(&tangent).dot(&bitangent) < 0.0 { -1.0 } else { 1.0 };
+    Vector4::new(tangent.x, tangent.y, tangent.z, handedness)
+}
+
+/// Returns the rows of the normal matrix used by `transform_normals`.
+///
+/// The normal matrix is the inverse transpose of the upper-left 3x3. Since the normals get renormalized anyway,
+/// the cofactor matrix is used instead, which avoids the division by the determinant and also works for singular matrices.
+fn normal_matrix(matrix: &Matrix4x4) -> [Vector3; 3] {
+    let d = &matrix.data;
+    let r0 = Vector3::new(d[0], d[1], d[2]);
+    let r1 = Vector3::new(d[4], d[5], d[6]);
+    let r2 = Vector3::new(d[8], d[9], d[10]);
+
+    // Mirroring matrices have a negative determinant, which would flip the normals inwards.
+    let sign = r0.dot(&r1.cross(&r2)).signum();
+    [r1.cross(&r2) * sign, r2.cross(&r0) * sign, r0.cross(&r1) * sign]
+}
+
+/// Transforms `normal` by the rows of a normal matrix and renormalizes it.
+#[inline]
+fn transform_normal([c0, c1, c2]: &[Vector3; 3], normal: Vector3) -> Vector3 {
+    normalize_or_zero(Vector3::new(c0.dot(&normal), c1.dot(&normal), c2.dot(&normal)))
+}
+
+/// Normalizes `v` exactly, returning zero for zero-length vectors.
+#[inline]
+fn normalize_or_zero(v: Vector3) -> Vector3 {
+    let len_squared = v.magnitude_squared();
+    if len_squared == 0.0 {
+        v
+    } else {
+        v * (1.0 / len_squared.sqrt())
+    }
+}
+
+/// The memory layout of a [`Vertex`], returned by `Vertex::layout`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VertexLayout {
+    /// The size of a vertex in bytes, the distance between two vertices in a buffer.
+    pub stride: usize,
+    /// The attributes in the order of their offsets.
+    pub attributes: [VertexAttribute; 4],
+}
+
+/// A single attribute of a [`VertexLayout`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VertexAttribute {
+    /// The name of the `Vertex` field holding the attribute.
+    pub name: &'static str,
+    /// The offset of the attribute from the start of the vertex in bytes.
+    pub offset: usize,
+    /// The number of components, e.g. 3 for a position.
+    pub components: usize,
+    /// The type of every component.
+    pub component_type: VertexComponentType,
+}
+
+impl VertexAttribute {
+
+    /// Returns the size of the attribute in bytes.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.components * self.component_type.size()
+    }
+
+}
+
+/// The type of the components of a [`VertexAttribute`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VertexComponentType {
+    /// A native-endian 32-bit float.
+    F32,
+    /// A byte mapping 0 to 255 onto [0, 1].
+    Unorm8,
+}
+
+impl VertexComponentType {
+
+    /// Returns the size of one component in bytes.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        match self {
+            VertexComponentType::F32 => 4,
+            VertexComponentType::Unorm8 => 1,
+        }
+    }
+
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn vertices() -> (Vertex, Vertex) {
+        (
+            Vertex::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector2::new(0.0, 0.0), UniColor::from_rgba(0, 100, 250, 255)),
+            Vertex::new(Vector3::new(2.0, 4.0, -2.0), Vector3::new(1.0, 0.0, 0.0), Vector2::new(1.0, 0.5), UniColor::from_rgba(250, 100, 50, 255)),
+        )
+    }
+
+    #[test]
+    fn lerp_extrapolates_every_attribute() {
+        let (a, b) = vertices();
+        for lerp in [Vertex::lerp, Vertex::lerp_linear, Vertex::extrapolate] {
+            let before = lerp(&a, &b, -0.5);
+            assert!(before.position.approx_eq(&Vector3::new(-1.0, -2.0, 1.0), 1e-6));
+            assert!(before.normal.approx_eq(&Vector3::new(-0.5, 1.5, 0.0), 1e-6));
+            assert!(before.tex_coords.approx_eq(&Vector2::new(-0.5, -0.25), 1e-6));
+            // Red and blue saturate instead of wrapping around, green stays.
+            let (r, g, b_, alpha) = before.color.to_rgba();
+            assert_eq!((r, b_, alpha), (0, 255, 255));
+            assert_eq!(g, 100);
+
+            let after = lerp(&a, &b, 1.5);
+            assert!(after.position.approx_eq(&Vector3::new(3.0, 6.0, -3.0), 1e-6));
+            let (r, g, b_, alpha) = after.color.to_rgba();
+            assert_eq!((r, b_, alpha), (255, 0, 255));
+            assert_eq!(g, 100);
+        }
+        // Halfway the byte blend and the linear light blend differ.
+        assert_eq!(a.lerp(&b, 0.5).color.to_rgba().0, 125);
+        assert!(a.lerp_linear(&b, 0.5).color.to_rgba().0 > 125);
+    }
+
+    #[test]
+    fn lerp_clamped_stays_between_the_vertices() {
+        let (a, b) = vertices();
+        let clamped = a.lerp_clamped(&b, 1.5);
+        assert!(clamped.position.approx_eq(&b.position, 0.0));
+        assert_eq!(clamped.color.to_rgba(), b.color.to_rgba());
+        let clamped = a.lerp_clamped(&b, -0.5);
+        assert!(clamped.position.approx_eq(&a.position, 0.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format_is_stable() {
+        let vertex = Vertex::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.0, 1.0, 0.0), Vector2::new(0.5, 0.25), UniColor::from_rgba(255, 0, 128, 255));
+        let json = serde_json::to_string(&vertex).unwrap();
+        assert_eq!(json, concat!(
+            r#"{"position":{"x":1.0,"y":2.0,"z":3.0},"normal":{"x":0.0,"y":1.0,"z":0.0},"#,
+            r##""tex_coords":{"x":0.5,"y":0.25},"color":"#ff0080ff"}"##,
+        ));
+        let back: Vertex = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.position, back.normal, back.tex_coords), (vertex.position, vertex.normal, vertex.tex_coords));
+        assert_eq!(back.color.to_rgba(), vertex.color.to_rgba());
+    }
+
+    #[test]
+    fn as_bytes_puts_every_field_at_its_layout_offset() {
+        let vertex = Vertex::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0), Vector2::new(7.0, 8.0), UniColor::from_rgba(9, 10, 11, 12));
+        let bytes = vertex.as_bytes();
+        let layout = Vertex::layout();
+        assert_eq!((layout.stride, core::mem::align_of::<Vertex>()), (bytes.len(), 4));
+
+        let floats = |values: &[f32]| -> Vec<u8> { values.iter().flat_map(|v| v.to_ne_bytes()).collect() };
+        let mut covered = [false; 36];
+        for attribute in layout.attributes {
+            let range = attribute.offset..attribute.offset + attribute.size();
+            let expected = match attribute.name {
+                "position" => floats(&[1.0, 2.0, 3.0]),
+                "normal" => floats(&[4.0, 5.0, 6.0]),
+                "tex_coords" => floats(&[7.0, 8.0]),
+                // The RGBA8888 value in native endianness.
+                "color" => 0x090a_0b0cu32.to_ne_bytes().to_vec(),
+                name => panic!("unknown attribute {}", name),
+            };
+            assert_eq!(bytes[range.clone()], expected, "{}", attribute.name);
+            // The attributes neither overlap nor leave gaps.
+            assert!(!covered[range.clone()].contains(&true), "{}", attribute.name);
+            covered[range].fill(true);
+        }
+        assert!(!covered.contains(&false));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts_a_vertex_buffer_without_copying() {
+        let (a, b) = vertices();
+        let buffer = [a, b, a.midpoint(&b)];
+        let bytes: &[u8] = bytemuck::cast_slice(&buffer);
+        assert_eq!(bytes, Vertex::bytes_of_slice(&buffer).as_slice());
+        assert_eq!(bytes.as_ptr(), buffer.as_ptr().cast());
+
+        let back: &[Vertex] = bytemuck::cast_slice(bytes);
+        assert_eq!(back.len(), 3);
+        assert_eq!(back[1].position, b.position);
+        assert_eq!(back[1].color.to_rgba(), b.color.to_rgba());
+    }
+
+    #[test]
+    fn transform_vertices_par_matches_the_serial_version() {
+        let matrix = Matrix4x4::translate(1.0, -2.0, 3.0) * Matrix4x4::rotation_x(0.7) * Matrix4x4::scale(2.0, 0.5, 1.5);
+        let (a, b) = vertices();
+        let mut serial: Vec<Vertex> = (0..20_000).map(|i| a.lerp(&b, (i as f32 * 0.37).sin())).collect();
+        let mut parallel = serial.clone();
+        transform_vertices(&mut serial, &matrix);
+        transform_vertices_par(&mut parallel, &matrix);
+        let bits = |v: &Vertex| [v.position.x, v.position.y, v.position.z, v.normal.x, v.normal.y, v.normal.z].map(f32::to_bits);
+        assert!(serial.iter().zip(&parallel).all(|(a, b)| bits(a) == bits(b)));
+    }
+
+    #[test]
+    fn lerp_linear_brightens_the_midpoint_color() {
+        let vertex = |color| Vertex::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0), Vector2::new(0.0, 0.0), color);
+        let (black, white) = (vertex(UniColor::BLACK), vertex(UniColor::WHITE));
+        assert_eq!(black.lerp(&white, 0.5).color.to_rgba(), (128, 128, 128, 255));
+        assert_eq!(black.lerp_linear(&white, 0.5).color.to_rgba(), (188, 188, 188, 255));
+        assert_eq!(black.lerp_linear(&white, 0.5).color.to_rgba(), UniColor::BLACK.lerp_linear(&UniColor::WHITE, 0.5).to_rgba());
+    }
+
+    #[test]
+    fn rotate_and_transform_normals_turn_x_into_minus_z() {
+        let (a, b) = vertices();
+        let mut rotated = [Vertex { normal: Vector3::new(1.0, 0.0, 0.0), ..a }, b];
+        let mut transformed = rotated;
+        rotate_normals(&mut rotated, &Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), core::f32::consts::FRAC_PI_2));
+        transform_normals(&mut transformed, &Matrix4x4::rotation_y(core::f32::consts::FRAC_PI_2));
+        for vertices in [rotated, transformed] {
+            assert!(vertices[0].normal.approx_eq(&Vector3::new(0.0, 0.0, -1.0), 1e-6), "{:?}", vertices[0].normal);
+            assert!(vertices[1].normal.approx_eq(&Vector3::new(0.0, 0.0, -1.0), 1e-6), "{:?}", vertices[1].normal);
+            // Only the normals change.
+            assert_eq!((vertices[0].position, vertices[0].tex_coords), (a.position, a.tex_coords));
+        }
+    }
+
+    #[test]
+    fn transform_normals_uses_the_inverse_transpose() {
+        // Stretching the plane x + y = 0 along x turns it into x + 2y = 0, whose normal is (1, 2, 0).
+        let (a, _) = vertices();
+        let normal = Vector3::new(1.0, 1.0, 0.0).normalized();
+        let mut vertices = [Vertex { normal, ..a }];
+        let matrix = Matrix4x4::translate(5.0, 0.0, 0.0) * Matrix4x4::scale(2.0, 1.0, 1.0);
+        transform_normals(&mut vertices, &matrix);
+        let expected = Vector3::new(1.0, 2.0, 0.0) * (1.0 / 5.0f32.sqrt());
+        assert!(vertices[0].normal.approx_eq(&expected, 1e-6), "{:?}", vertices[0].normal);
+        // It stays perpendicular to the transformed surface, unlike the normal transformed by the matrix itself.
+        let tangent = matrix.transform_point(Vector3::new(1.0, -1.0, 0.0)) - matrix.transform_point(Vector3::new(0.0, 0.0, 0.0));
+        assert!(vertices[0].normal.dot(&tangent).abs() < 1e-6);
+        let naive = Vector3::new(normal.x * 2.0, normal.y, normal.z);
+        assert!(naive.dot(&tangent).abs() > 0.5);
+
+        // Mirroring keeps normals facing outwards and flattening keeps them finite.
+        let mut vertices = [Vertex { normal: Vector3::new(0.0, 0.0, 1.0), ..a }];
+        transform_normals(&mut vertices, &Matrix4x4::scale(-1.0, 1.0, 1.0));
+        assert!(vertices[0].normal.approx_eq(&Vector3::new(0.0, 0.0, 1.0), 1e-6), "{:?}", vertices[0].normal);
+        transform_normals(&mut vertices, &Matrix4x4::scale(1.0, 1.0, 0.0));
+        assert!(vertices[0].normal.approx_eq(&Vector3::new(0.0, 0.0, 1.0), 1e-6), "{:?}", vertices[0].normal);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn compute_tangents_follows_the_texture_coordinates() {
+        // A quad in the XY plane facing +Z, the mapping turns the corner (x, y) into its texture coordinates.
+        type Mapping = fn(f32, f32) -> (f32, f32);
+        let quad = |uv: Mapping| -> Vec<Vertex> {
+            [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| {
+                let (u, v) = uv(x, y);
+                Vertex::new(Vector3::new(x, y, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector2::new(u, v), UniColor::WHITE)
+            }).to_vec()
+        };
+        let indices = [0, 1, 2, 0, 2, 3];
+        let cases: [(Mapping, Vector4); 3] = [
+            (|x, y| (x, y), Vector4::new(1.0, 0.0, 0.0, 1.0)),
+            // Flipping v mirrors the bitangent, mirroring u turns the tangent around instead.
+            (|x, y| (x, 1.0 - y), Vector4::new(1.0, 0.0, 0.0, -1.0)),
+            (|x, y| (1.0 - x, y), Vector4::new(-1.0, 0.0, 0.0, -1.0)),
+        ];
+        for (uv, expected) in cases {
+            let tangents = compute_tangents(&quad(uv), &indices);
+            assert_eq!(tangents.len(), 4);
+            assert!(tangents.iter().all(|t| t.approx_eq(&expected, 1e-6)), "{:?}", tangents);
+        }
+
+        // Without area in texture space the tangents are still unit length and perpendicular to the normal.
+        let tangents = compute_tangents(&quad(|_, _| (0.5, 0.5)), &indices);
+        for t in tangents {
+            assert!((t.x * t.x + t.y * t.y - 1.0).abs() < 1e-6 && t.z == 0.0 && t.w.abs() == 1.0, "{:?}", t);
+        }
+    }
+
+    static QUAD: [Vertex; 4] = [
+        Vertex::new(Vector3::new(-0.5, -0.5, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector2::new(0.0, 1.0), UniColor::WHITE),
+        Vertex::new(Vector3::new(0.5, -0.5, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector2::new(1.0, 1.0), UniColor::WHITE),
+        Vertex::new(Vector3::new(0.5, 0.5, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector2::new(1.0, 0.0), UniColor::from_rgb(255, 0, 0)),
+        Vertex::new(Vector3::new(-0.5, 0.5, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector2::new(0.0, 0.0), UniColor::from_hex_const(b"#00ff00")),
+    ];
+
+    #[test]
+    fn const_vertices_match_the_runtime_ones() {
+        let normal = Vector3::new(0.0, 0.0, 1.0);
+        let green = UniColor::from_hex("#00ff00").unwrap();
+        let runtime = Vertex::new(Vector3::new(-0.5, 0.5, 0.0), normal, Vector2::new(0.0, 0.0), green);
+        assert_eq!(QUAD[3].as_bytes(), runtime.as_bytes());
+        assert_eq!(QUAD[2].color.to_rgba(), (255, 0, 0, 255));
+        assert_eq!(QUAD[0].position + QUAD[2].position, Vector3::zero());
+    }
+
+}
\ No newline at end of file
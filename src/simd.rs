@@ -0,0 +1,249 @@
+//! SSE versions of the hottest `Matrix4x4` operations.
+//!
+//! Every lane does exactly the multiplications, additions and subtractions of the scalar code in `matrix4x4.rs`,
+//! in the same order and without fused multiply-adds, so both paths give bit-identical results.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector4::Vector4;
+
+/// `_mm_shuffle_ps` with the lanes spelled out in order: the first two are taken from `$a`, the last two from `$b`.
+macro_rules! shuffle {
+    ($a:expr, $b:expr, [$i0:literal, $i1:literal, $i2:literal, $i3:literal]) => {
+        _mm_shuffle_ps::<{ $i0 | $i1 << 2 | $i2 << 4 | $i3 << 6 }>($a, $b)
+    };
+}
+
+/// Loads the four rows of `m`.
+#[inline(always)]
+fn load_rows(m: &Matrix4x4) -> [__m128; 4] {
+    let p = m.data.as_ptr();
+    // SAFETY: SSE2 is enabled at compile time, the module is only compiled with it.
+    // Every row is 4 floats inside `data`, and the loads don't need any alignment.
+    unsafe { [_mm_loadu_ps(p), _mm_loadu_ps(p.add(4)), _mm_loadu_ps(p.add(8)), _mm_loadu_ps(p.add(12))] }
+}
+
+/// Builds a matrix from its four rows.
+#[inline(always)]
+fn store_rows(rows: [__m128; 4]) -> Matrix4x4 {
+    let mut data = [0.0; 16];
+    let p = data.as_mut_ptr();
+    // SAFETY: SSE2 is enabled at compile time and every row is stored to 4 floats inside `data`.
+    unsafe {
+        _mm_storeu_ps(p, rows[0]);
+        _mm_storeu_ps(p.add(4), rows[1]);
+        _mm_storeu_ps(p.add(8), rows[2]);
+        _mm_storeu_ps(p.add(12), rows[3]);
+    }
+    Matrix4x4 { data }
+}
+
+/// Returns `a * b`.
+#[inline]
+pub(crate) fn mul(a: &Matrix4x4, b: &Matrix4x4) -> Matrix4x4 {
+    let b = load_rows(b);
+    let a = &a.data;
+    // Row i of the product is the rows of `b` weighted by row i of `a`, summed from left to right.
+    // SAFETY: SSE2 is enabled at compile time.
+    let row = |i: usize| unsafe {
+        let sum = _mm_mul_ps(_mm_set1_ps(a[i * 4]), b[0]);
+        let sum = _mm_add_ps(sum, _mm_mul_ps(_mm_set1_ps(a[i * 4 + 1]), b[1]));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(_mm_set1_ps(a[i * 4 + 2]), b[2]));
+        _mm_add_ps(sum, _mm_mul_ps(_mm_set1_ps(a[i * 4 + 3]), b[3]))
+    };
+    store_rows([row(0), row(1), row(2), row(3)])
+}
+
+/// Returns `m * v`.
+#[inline]
+pub(crate) fn mul_vector4(m: &Matrix4x4, v: Vector4) -> Vector4 {
+    let [mut c0, mut c1, mut c2, mut c3] = load_rows(m);
+    let mut result = [0.0; 4];
+    // SAFETY: SSE2 is enabled at compile time and the result is stored to 4 floats.
+    unsafe {
+        _MM_TRANSPOSE4_PS(&mut c0, &mut c1, &mut c2, &mut c3);
+        // The columns weighted by the components, so lane i sums row i times `v` from left to right.
+        let sum = _mm_mul_ps(c0, _mm_set1_ps(v.x));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(c1, _mm_set1_ps(v.y)));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(c2, _mm_set1_ps(v.z)));
+        let sum = _mm_add_ps(sum, _mm_mul_ps(c3, _mm_set1_ps(v.w)));
+        _mm_storeu_ps(result.as_mut_ptr(), sum);
+    }
+    Vector4::new(result[0], result[1], result[2], result[3])
+}
+
+/// Returns the inverse of `m`, or `None` if its determinant is 0.
+///
+/// With the rows `(a, b, c, d)`, `(e, f, g, h)`, `(i, j, k, l)` and `(m, n, o, p)` this follows the scalar code:
+/// the 2x2 determinants `q` to `bb` of the top and bottom two rows, weighted by the columns, with the signs
+/// applied to the columns before multiplying like the negated elements of the scalar code.
+pub(crate) fn inverse(m: &Matrix4x4) -> Option<Matrix4x4> {
+    let [r0, r1, r2, r3] = load_rows(m);
+    // SAFETY: SSE2 is enabled at compile time and every store writes 4 floats into an array of 4.
+    unsafe {
+        // (q, r, s, t), (u, v, u, v), (w, x, y, z) and (aa, bb, aa, bb).
+        let qrst = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(r0, r0, [0, 0, 0, 1]), shuffle!(r1, r1, [1, 2, 3, 2])),
+            _mm_mul_ps(shuffle!(r0, r0, [1, 2, 3, 2]), shuffle!(r1, r1, [0, 0, 0, 1])),
+        );
+        let uvuv = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(r0, r0, [1, 2, 1, 2]), shuffle!(r1, r1, [3, 3, 3, 3])),
+            _mm_mul_ps(shuffle!(r0, r0, [3, 3, 3, 3]), shuffle!(r1, r1, [1, 2, 1, 2])),
+        );
+        let wxyz = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(r2, r2, [0, 0, 0, 1]), shuffle!(r3, r3, [1, 2, 3, 2])),
+            _mm_mul_ps(shuffle!(r2, r2, [1, 2, 3, 2]), shuffle!(r3, r3, [0, 0, 0, 1])),
+        );
+        let aabb = _mm_sub_ps(
+            _mm_mul_ps(shuffle!(r2, r2, [1, 2, 1, 2]), shuffle!(r3, r3, [3, 3, 3, 3])),
+            _mm_mul_ps(shuffle!(r2, r2, [3, 3, 3, 3]), shuffle!(r3, r3, [1, 2, 1, 2])),
+        );
+
+        let (mut top, mut top_uv, mut bottom, mut bottom_ab) = ([0.0f32; 4], [0.0f32; 4], [0.0f32; 4], [0.0f32; 4]);
+        _mm_storeu_ps(top.as_mut_ptr(), qrst);
+        _mm_storeu_ps(top_uv.as_mut_ptr(), uvuv);
+        _mm_storeu_ps(bottom.as_mut_ptr(), wxyz);
+        _mm_storeu_ps(bottom_ab.as_mut_ptr(), aabb);
+        let [q, r, s, t] = top;
+        let [u, v, ..] = top_uv;
+        let [w, x, y, z] = bottom;
+        let [aa, bb, ..] = bottom_ab;
+
+        let det = q * bb - r * aa + s * z + t * y - u * x + v * w;
+        if det == 0.0 {
+            return None;
+        }
+        let inv_det = _mm_set1_ps(1.0 / det);
+
+        // The columns (a, e, i, m) to (d, h, l, p), with the two halves swapped like (e, a, m, i).
+        let (mut c0, mut c1, mut c2, mut c3) = (r0, r1, r2, r3);
+        _MM_TRANSPOSE4_PS(&mut c0, &mut c1, &mut c2, &mut c3);
+        let swap_pairs = |c: __m128| shuffle!(c, c, [1, 0, 3, 2]);
+        let (p0, p1, p2, p3) = (swap_pairs(c0), swap_pairs(c1), swap_pairs(c2), swap_pairs(c3));
+
+        // Negating by flipping the sign bit, exactly like the unary minus of the scalar code.
+        let odd = _mm_setr_ps(0.0, -0.0, 0.0, -0.0);
+        let even = _mm_setr_ps(-0.0, 0.0, -0.0, 0.0);
+        let (p0_odd, p1_odd, p2_odd, p3_odd) = (_mm_xor_ps(p0, odd), _mm_xor_ps(p1, odd), _mm_xor_ps(p2, odd), _mm_xor_ps(p3, odd));
+        let (p0_even, p1_even, p2_even, p3_even) = (_mm_xor_ps(p0, even), _mm_xor_ps(p1, even), _mm_xor_ps(p2, even), _mm_xor_ps(p3, even));
+
+        let bb_v = shuffle!(aabb, uvuv, [1, 1, 1, 1]);
+        let aa_u = shuffle!(aabb, uvuv, [0, 0, 0, 0]);
+        let w_q = shuffle!(wxyz, qrst, [0, 0, 0, 0]);
+        let x_r = shuffle!(wxyz, qrst, [1, 1, 1, 1]);
+        let y_s = shuffle!(wxyz, qrst, [2, 2, 2, 2]);
+        let z_t = shuffle!(wxyz, qrst, [3, 3, 3, 3]);
+
+        // (a0 * b0 - a1 * b1 + a2 * b2) * inv_det, evaluated from left to right.
+        let row = |a0: __m128, b0: __m128, a1: __m128, b1: __m128, a2: __m128, b2: __m128| {
+            let sum = _mm_sub_ps(_mm_mul_ps(a0, b0), _mm_mul_ps(a1, b1));
+            _mm_mul_ps(_mm_add_ps(sum, _mm_mul_ps(a2, b2)), inv_det)
+        };
+        Some(store_rows([
+            row(p1_odd, bb_v, p2_odd, aa_u, p3_odd, z_t),
+            row(p0_even, bb_v, p2_even, y_s, p3_even, x_r),
+            row(p0_odd, aa_u, p1_odd, y_s, p3_odd, w_q),
+            row(p0_even, z_t, p1_even, x_r, p2_even, w_q),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix4x4::{inverse_scalar, mul_scalar, mul_vector4_scalar};
+
+    /// A xorshift generator, so every run compares the same matrices.
+    struct Rng(u32);
+
+    impl Rng {
+
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            (self.0 >> 8) as f32 / (1 << 24) as f32 * 20.0 - 10.0
+        }
+
+        fn matrix(&mut self) -> Matrix4x4 {
+            Matrix4x4 { data: core::array::from_fn(|_| self.next_f32()) }
+        }
+
+    }
+
+    /// Random matrices followed by singular ones (repeated, zero and dependent rows) and nearly singular ones.
+    fn matrices() -> Vec<Matrix4x4> {
+        let mut rng = Rng(0x9e37_79b9);
+        let mut matrices: Vec<Matrix4x4> = (0..2000).map(|_| rng.matrix()).collect();
+        for i in 0..200 {
+            let mut m = rng.matrix();
+            let (from, to) = (i % 4, (i + 1 + i / 4 % 3) % 4);
+            for column in 0..4 {
+                m.data[to * 4 + column] = match i % 3 {
+                    0 => m.data[from * 4 + column],
+                    1 => 0.0,
+                    _ => m.data[from * 4 + column] * 2.5,
+                };
+            }
+            matrices.push(m);
+            m.data[to * 4 + i % 4] += 1e-6 * (1 + i % 7) as f32;
+            matrices.push(m);
+        }
+        matrices.push(Matrix4x4 { data: [0.0; 16] });
+        matrices
+    }
+
+    fn bits(m: &Matrix4x4) -> [u32; 16] {
+        m.data.map(f32::to_bits)
+    }
+
+    #[test]
+    fn mul_matches_scalar() {
+        let matrices = matrices();
+        for (a, b) in matrices.iter().zip(matrices.iter().rev()) {
+            assert_eq!(bits(&mul(a, b)), bits(&mul_scalar(a, b)), "{:?} * {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn mul_vector4_matches_scalar() {
+        let mut rng = Rng(0x1234_5678);
+        for m in matrices() {
+            let v = Vector4::new(rng.next_f32(), rng.next_f32(), rng.next_f32(), rng.next_f32());
+            let (simd, scalar) = (mul_vector4(&m, v), mul_vector4_scalar(&m, v));
+            assert_eq!(
+                [simd.x, simd.y, simd.z, simd.w].map(f32::to_bits),
+                [scalar.x, scalar.y, scalar.z, scalar.w].map(f32::to_bits),
+                "{:?} * {:?}", m, v,
+            );
+        }
+    }
+
+    #[test]
+    fn inverse_matches_scalar() {
+        let mut singular = 0;
+        for m in matrices() {
+            let (simd, scalar) = (inverse(&m), inverse_scalar(&m));
+            assert_eq!(simd.as_ref().map(bits), scalar.as_ref().map(bits), "inverse of {:?}", m);
+            singular += usize::from(scalar.is_none());
+        }
+        // The zero matrix and the ones with a zero row are exactly singular.
+        assert!(singular > 0);
+    }
+
+    #[test]
+    fn mul_many_matches_scalar() {
+        let matrices = matrices();
+        let mut out = vec![Matrix4x4::new(); matrices.len()];
+        Matrix4x4::mul_many(&mut out, &matrices, &matrices[1..].iter().chain(&matrices[..1]).copied().collect::<Vec<_>>());
+        for (i, out) in out.iter().enumerate() {
+            let b = &matrices[(i + 1) % matrices.len()];
+            assert_eq!(bits(out), bits(&mul_scalar(&matrices[i], b)));
+        }
+    }
+
+}
\ No newline at end of file
@@ -0,0 +1,589 @@
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+use core::ops::Mul;
+use crate::angles::quaternion::Quaternion;
+#[cfg(feature = "std")]
+use crate::binary::{self, BinaryReadError};
+use crate::math::{damp, Fnv1a, Lerp};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::matrix4x4::Matrix4x4;
+use crate::vectors::vector3::Vector3;
+
+#[cfg(feature = "std")]
+const TRANSFORM_MAGIC: [u8; 4] = *b"FGTR";
+#[cfg(feature = "std")]
+const TRANSFORM_VERSION: u16 = 1;
+
+/// A transform made of a `position`, a `rotation` and a `scale`, applied in scale, rotate, translate order.
+#[derive(Copy, Clone, Debug)]
+pub struct Transform {
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    pub scale: Vector3,
+}
+
+impl Transform {
+
+    /// Creates a new Transform from the given position, rotation and scale.
+    #[inline]
+    pub fn new(position: Vector3, rotation: Quaternion, scale: Vector3) -> Self {
+        Self { position, rotation, scale }
+    }
+
+    /// Creates a Transform which leaves everything unchanged.
+    #[inline]
+    pub fn identity() -> Self {
+        Self::new(Vector3::zero(), Quaternion::identity(), Vector3::one())
+    }
+
+    /// Returns the matrix applying this transform, i.e. `translate * rotate * scale`.
+    pub fn to_matrix(&self) -> Matrix4x4 {
+        let mut m = Matrix4x4::from_quaternion(&self.rotation);
+        for row in 0..3 {
+            m[row * 4] *= self.scale.x;
+            m[row * 4 + 1] *= self.scale.y;
+            m[row * 4 + 2] *= self.scale.z;
+        }
+        m[3] = self.position.x;
+        m[7] = self.position.y;
+        m[11] = self.position.z;
+        m
+    }
+
+    /// Transforms `point` from the local space of this transform into its parent space: scale, rotate, then translate.
+    #[inline]
+    pub fn transform_point(&self, point: Vector3) -> Vector3 {
+        self.position + self.rotation.rotate_vector(point * self.scale)
+    }
+
+    /// Transforms `direction` like `transform_point`, but without the translation, like `Matrix4x4::transform_direction`.
+    /// The scale is applied, so the length changes with it, normalize the result if only the direction matters.
+    #[inline]
+    pub fn transform_direction(&self, direction: Vector3) -> Vector3 {
+        self.rotation.rotate_vector(direction * self.scale)
+    }
+
+    /// Returns the transform undoing this one, so `t * t.inverse()` is the identity.
+    ///
+    /// Exact for uniform scale. With non-uniform scale and a rotation, the true inverse applies the inverse scale
+    /// after the inverse rotation, which a `Transform` cannot express, so the result is only an approximation then.
+    /// A scale of zero on any axis gives infinite values.
+    pub fn inverse(&self) -> Transform {
+        let rotation = self.rotation.conjugate();
+        let scale = Vector3::new(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z);
+        let position = rotation.rotate_vector(-self.position) * scale;
+        Transform::new(position, rotation, scale)
+    }
+
+    /// Decomposes an affine `matrix` into position, rotation and scale.
+    /// Matrices with shear cannot be represented exactly, their rotation is only approximate then.
+    /// Mirroring is expressed as a negative x scale.
+    pub fn from_matrix(matrix: &Matrix4x4) -> Self {
+        let d = &matrix.data;
+        let column_length = |i: usize| Vector3::new(d[i], d[4 + i], d[8 + i]).magnitude_squared().sqrt();
+        let mut scale = Vector3::new(column_length(0), column_length(1), column_length(2));
+
+        let mut rotation = *matrix;
+        if matrix.determinant() < 0.0 {
+            scale.x = -scale.x;
+            for row in 0..3 {
+                rotation[row * 4] = -rotation[row * 4];
+            }
+        }
+
+        Self::new(Vector3::new(d[3], d[7], d[11]), rotation.to_quaternion(), scale)
+    }
+
+    /// Returns a stable 64-bit hash of this transform, for caching processed assets.
+    ///
+    /// The hash is 64-bit FNV-1a over the little-endian bit patterns of position (x, y, z),
+    /// rotation (w, x, y, z) and scale (x, y, z). It is the same on every platform and will not change between versions,
+    /// but it is bitwise, so `0.0` and `-0.0` or differently encoded NaNs hash differently.
+    /// For reference, the identity hashes to `0xf851660cf088f3b5`.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_f32s(&[
+            self.position.x, self.position.y, self.position.z,
+            self.rotation.w, self.rotation.x, self.rotation.y, self.rotation.z,
+            self.scale.x, self.scale.y, self.scale.z,
+        ]);
+        hasher.finish()
+    }
+
+    /// Writes the transform in fgruc's versioned little-endian binary format, read back by `read_binary`.
+    ///
+    /// The layout of version 1 is the magic bytes `FGTR`, the version as `u16`, then position (x, y, z),
+    /// rotation (w, x, y, z) and scale (x, y, z) as 10 `f32`, 46 bytes in total.
+    #[cfg(feature = "std")]
+    pub fn write_binary(&self, w: &mut impl Write) -> io::Result<()> {
+        binary::write_header(w, TRANSFORM_MAGIC, TRANSFORM_VERSION)?;
+        binary::write_f32s(w, &[
+            self.position.x, self.position.y, self.position.z,
+            self.rotation.w, self.rotation.x, self.rotation.y, self.rotation.z,
+            self.scale.x, self.scale.y, self.scale.z,
+        ])
+    }
+
+    /// Reads a transform written by `write_binary`.
+    #[cfg(feature = "std")]
+    pub fn read_binary(r: &mut impl Read) -> Result<Transform, BinaryReadError> {
+        binary::read_header(r, TRANSFORM_MAGIC, TRANSFORM_VERSION)?;
+        let [px, py, pz, rw, rx, ry, rz, sx, sy, sz] = binary::read_f32s::<10>(r)?;
+        Ok(Transform::new(Vector3::new(px, py, pz), Quaternion::new(rw, rx, ry, rz), Vector3::new(sx, sy, sz)))
+    }
+
+    /// Moves this transform towards `target` with `math::damp`, the position at the rate `position_lambda`
+    /// and the rotation along the shortest arc at the rate `rotation_lambda`. The scale is left unchanged.
+    ///
+    /// Call it once per frame with the frame time `dt`, the result does not depend on the framerate.
+    /// A rate of `f32::INFINITY` snaps that part to the target.
+    pub fn follow(&mut self, target: &Transform, position_lambda: f32, rotation_lambda: f32, dt: f32) {
+        self.position = damp(&self.position, &target.position, position_lambda, dt);
+        self.rotation = damp(&self.rotation, &target.rotation, rotation_lambda, dt);
+    }
+
+    /// Turns this transform towards facing `point` with `math::damp`, like `follow` does for the rotation.
+    /// Facing means the local -Z axis points at `point` with +Y kept as close to world up as possible, matching `Matrix4x4::look_at`.
+    /// Does nothing if `point` is the position itself.
+    pub fn look_at_smooth(&mut self, point: Vector3, lambda: f32, dt: f32) {
+        if (point - self.position).magnitude_squared() <= f32::EPSILON * f32::EPSILON {
+            return;
+        }
+        // The view matrix holds the inverse of the rotation that makes the transform face the point.
+        let facing = Matrix4x4::look_at(self.position, point, Vector3::UP).to_quaternion().conjugate();
+        self.rotation = damp(&self.rotation, &facing, lambda, dt);
+    }
+
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+/// Interpolates position and scale linearly and the rotation with `slerp`.
+impl Lerp for Transform {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Transform::new(
+            self.position.lerp(&other.position, t),
+            self.rotation.slerp(other.rotation, t),
+            self.scale.lerp(&other.scale, t),
+        )
+    }
+}
+
+/// Composes two transforms, so `parent * child` is the world transform of `child` placed in `parent`,
+/// and transforming a point by it is the same as transforming it by `child`, then by `parent`.
+///
+/// Exact if `parent` has a uniform scale or `child` is not rotated relative to the axes of the non-uniform scale.
+/// Otherwise the scale would shear the child, which a `Transform` cannot express, so the scales are just multiplied per axis.
+/// Use `to_matrix` and multiply the matrices where the shear matters.
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, child: Transform) -> Transform {
+        Transform::new(
+            self.transform_point(child.position),
+            self.rotation * child.rotation,
+            self.scale * child.scale,
+        )
+    }
+}
+
+/// Identifies a node in a [`TransformCache`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(u32);
+
+/// Returned when setting a parent would make a node its own ancestor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParentCycleError {
+    pub node: NodeId,
+    pub parent: NodeId,
+}
+
+impl Display for ParentCycleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "making node {} the parent of node {} would create a cycle", self.parent.0, self.node.0)
+    }
+}
+
+impl Error for ParentCycleError {}
+
+/// A hierarchy of local transforms which caches the world matrix of every node.
+///
+/// Changing a node only marks it and its subtree dirty, world matrices are recomputed lazily by `world_matrix`
+/// or all at once by `flush`, so untouched parts of the hierarchy never pay for an update.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct TransformCache {
+    nodes: Vec<CacheNode>,
+    recompute_count: u64,
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+struct CacheNode {
+    local: Transform,
+    world: Matrix4x4,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// If set, `world` is outdated. A dirty node's descendants are always dirty too.
+    dirty: bool,
+}
+
+#[cfg(feature = "std")]
+impl TransformCache {
+
+    /// Creates an empty cache.
+    #[inline]
+    pub fn new() -> Self {
+        Self { nodes: Vec::new(), recompute_count: 0 }
+    }
+
+    /// Adds a new root node with the given local transform.
+    pub fn add_node(&mut self, local: Transform) -> NodeId {
+        self.nodes.push(CacheNode {
+            local,
+            world: Matrix4x4::identity(),
+            parent: None,
+            children: Vec::new(),
+            dirty: true,
+        });
+        NodeId(self.nodes.len() as u32 - 1)
+    }
+
+    /// Returns the number of nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the cache has no nodes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the local transform of `node`.
+    #[inline]
+    pub fn local(&self, node: NodeId) -> &Transform {
+        &self.nodes[node.0 as usize].local
+    }
+
+    /// Returns the parent of `node`, or `None` for roots.
+    #[inline]
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes[node.0 as usize].parent
+    }
+
+    /// Returns how many world matrices have been computed so far, for profiling the cache.
+    #[inline]
+    pub fn recompute_count(&self) -> u64 {
+        self.recompute_count
+    }
+
+    /// Replaces the local transform of `node`, marking it and its subtree dirty.
+    pub fn set_local(&mut self, node: NodeId, local: Transform) {
+        self.nodes[node.0 as usize].local = local;
+        self.mark_dirty(node);
+    }
+
+    /// Moves `node` under `parent`, or makes it a root if `parent` is `None`. The local transform is kept,
+    /// so the node moves in world space along with its new parent.
+    /// Returns an error and changes nothing if `node` would become its own ancestor.
+    pub fn set_parent(&mut self, node: NodeId, parent: Option<NodeId>) -> Result<(), ParentCycleError> {
+        if let Some(parent) = parent {
+            let mut ancestor = Some(parent);
+            while let Some(current) = ancestor {
+                if current == node {
+                    return Err(ParentCycleError { node, parent });
+                }
+                ancestor = self.nodes[current.0 as usize].parent;
+            }
+        }
+
+        if let Some(old) = self.nodes[node.0 as usize].parent {
+            self.nodes[old.0 as usize].children.retain(|&child| child != node);
+        }
+        if let Some(parent) = parent {
+            self.nodes[parent.0 as usize].children.push(node);
+        }
+        self.nodes[node.0 as usize].parent = parent;
+        self.mark_dirty(node);
+        Ok(())
+    }
+
+    /// Like `set_parent`, but changes the local transform so the node keeps its current world transform.
+    /// If the new parent's world matrix is not invertible, the local transform is kept as is.
+    /// Shear introduced by non-uniformly scaled parents cannot be represented and is dropped.
+    pub fn set_parent_keep_world(&mut self, node: NodeId, parent: Option<NodeId>) -> Result<(), ParentCycleError> {
+        let world = self.world_matrix(node);
+        let parent_world = parent.map(|parent| self.world_matrix(parent));
+        self.set_parent(node, parent)?;
+
+        let local = match parent_world {
+            None => Some(world),
+            Some(parent_world) => parent_world.inverse().map(|inverse| inverse * world),
+        };
+        if let Some(local) = local {
+            self.set_local(node, Transform::from_matrix(&local));
+        }
+        Ok(())
+    }
+
+    /// Returns the world matrix of `node`, recomputing it and any dirty ancestors first.
+    pub fn world_matrix(&mut self, node: NodeId) -> Matrix4x4 {
+        // Collect the dirty chain up to the first clean ancestor, then compute it top-down.
+        let mut chain = Vec::new();
+        let mut current = Some(node);
+        while let Some(id) = current {
+            if !self.nodes[id.0 as usize].dirty {
+                break;
+            }
+            chain.push(id);
+            current = self.nodes[id.0 as usize].parent;
+        }
+        for &id in chain.iter().rev() {
+            self.recompute(id);
+        }
+        self.nodes[node.0 as usize].world
+    }
+
+    /// Recomputes the world matrices of all dirty nodes, e.g. once per frame before rendering.
+    pub fn flush(&mut self) {
+        for index in 0..self.nodes.len() {
+            if self.nodes[index].dirty {
+                self.world_matrix(NodeId(index as u32));
+            }
+        }
+    }
+
+    /// Computes the world matrix of `node`, whose parent has to be clean.
+    fn recompute(&mut self, node: NodeId) {
+        let entry = &self.nodes[node.0 as usize];
+        let local = entry.local.to_matrix();
+        let world = match entry.parent {
+            Some(parent) => self.nodes[parent.0 as usize].world * local,
+            None => local,
+        };
+        let entry = &mut self.nodes[node.0 as usize];
+        entry.world = world;
+        entry.dirty = false;
+        self.recompute_count += 1;
+    }
+
+    /// Marks `node` and its subtree dirty, skipping subtrees which already are.
+    fn mark_dirty(&mut self, node: NodeId) {
+        let mut stack = vec![node];
+        while let Some(id) = stack.pop() {
+            let entry = &mut self.nodes[id.0 as usize];
+            // The node itself is always marked, since its parent may have changed.
+            if entry.dirty && id != node {
+                continue;
+            }
+            entry.dirty = true;
+            stack.extend_from_slice(&entry.children);
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn content_hash_is_stable() {
+        let identity = Transform::identity();
+        assert_eq!(identity.content_hash(), 0xf851_660c_f088_f3b5);
+        let built = Transform::new(Vector3::new(0.0, 0.0, 0.0), Quaternion::new(1.0, 0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(built.content_hash(), identity.content_hash());
+
+        let mut moved = identity;
+        moved.position.y = f32::from_bits(1);
+        assert_ne!(moved.content_hash(), identity.content_hash());
+        // The hash is bitwise, so a negative zero is a change as well.
+        moved.position.y = -0.0;
+        assert_ne!(moved.content_hash(), identity.content_hash());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn binary_round_trips_and_is_stable() {
+        let transform = Transform::new(Vector3::new(1.0, -2.0, 0.5), Quaternion::new(0.5, 0.5, -0.5, 0.5), Vector3::new(2.0, 2.0, 0.25));
+        let mut bytes = Vec::new();
+        transform.write_binary(&mut bytes).unwrap();
+        let expected: [u8; 46] = [
+            b'F', b'G', b'T', b'R', 1, 0,
+            0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00, 0x3f,
+            0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x00, 0xbf, 0x00, 0x00, 0x00, 0x3f,
+            0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x80, 0x3e,
+        ];
+        assert_eq!(bytes, expected);
+        assert_eq!(Transform::read_binary(&mut bytes.as_slice()).unwrap().content_hash(), transform.content_hash());
+        for length in 0..bytes.len() {
+            assert!(matches!(Transform::read_binary(&mut &bytes[..length]), Err(BinaryReadError::Truncated)));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cache_only_recomputes_dirty_nodes() {
+        let at = |x: f32, y: f32| Transform::new(Vector3::new(x, y, 0.0), Quaternion::identity(), Vector3::one());
+        let mut cache = TransformCache::new();
+        let root = cache.add_node(at(1.0, 0.0));
+        let child = cache.add_node(at(0.0, 2.0));
+        let other = cache.add_node(at(0.0, 0.0));
+        cache.set_parent(child, Some(root)).unwrap();
+        cache.flush();
+        assert_eq!(cache.recompute_count(), 3);
+
+        // Nothing is dirty, so neither reading nor flushing recomputes anything.
+        cache.world_matrix(child);
+        cache.world_matrix(other);
+        cache.flush();
+        assert_eq!(cache.recompute_count(), 3);
+
+        // Moving the parent is lazy and only recomputes the parent and its child.
+        cache.set_local(root, at(5.0, 0.0));
+        assert_eq!(cache.recompute_count(), 3);
+        let world = cache.world_matrix(child);
+        assert_eq!(cache.recompute_count(), 5);
+        assert_eq!(world.transform_point(Vector3::zero()).to_array(), [5.0, 2.0, 0.0]);
+        cache.flush();
+        assert_eq!(cache.recompute_count(), 5);
+
+        assert_eq!(cache.set_parent(root, Some(child)), Err(ParentCycleError { node: root, parent: child }));
+        assert_eq!(cache.parent(root), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn set_parent_keep_world_keeps_the_world_matrix() {
+        let mut cache = TransformCache::new();
+        let parent = cache.add_node(Transform::new(
+            Vector3::new(3.0, -1.0, 2.0),
+            Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 0.5), 0.8),
+            Vector3::new(2.0, 2.0, 2.0),
+        ));
+        let node = cache.add_node(Transform::new(
+            Vector3::new(-1.0, 4.0, 0.5),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), -1.2),
+            Vector3::new(0.5, 1.0, 1.5),
+        ));
+
+        let before = cache.world_matrix(node);
+        cache.set_parent_keep_world(node, Some(parent)).unwrap();
+        assert_eq!(cache.parent(node), Some(parent));
+        let reparented = cache.world_matrix(node);
+        assert!(reparented.approx_eq(&before, 1e-3), "{}\n{}", reparented, before);
+
+        cache.set_parent_keep_world(node, None).unwrap();
+        let unparented = cache.world_matrix(node);
+        assert!(unparented.approx_eq(&before, 1e-3), "{}\n{}", unparented, before);
+    }
+
+    #[test]
+    fn follow_does_not_depend_on_the_framerate() {
+        let start = Transform::identity();
+        let target = Transform::new(
+            Vector3::new(10.0, -4.0, 2.0),
+            Quaternion::from_axis_angle(Vector3::new(0.3, 1.0, -0.2), 2.0),
+            Vector3::one(),
+        );
+        let run = |hz: u32| {
+            let mut transform = start;
+            for _ in 0..hz {
+                transform.follow(&target, 3.0, 2.0, 1.0 / hz as f32);
+            }
+            transform
+        };
+        let (slow, fast) = (run(30), run(240));
+        assert!(slow.position.approx_eq(&fast.position, 1e-3), "{:?} {:?}", slow.position, fast.position);
+        assert!(slow.rotation.approx_eq_rotation(&fast.rotation, 1e-3), "{:?} {:?}", slow.rotation, fast.rotation);
+        // Neither has arrived yet, a rate of 3 leaves e^-3 of the way after a second.
+        let remaining = (target.position - slow.position).magnitude() / target.position.magnitude();
+        assert!((remaining - (-3f32).exp()).abs() < 1e-3, "{}", remaining);
+        assert_eq!(slow.scale.to_array(), [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn look_at_smooth_converges_on_the_target() {
+        let mut transform = Transform::new(Vector3::new(1.0, 2.0, 3.0), Quaternion::identity(), Vector3::one());
+        let point = Vector3::new(-4.0, 0.0, 7.0);
+        for _ in 0..600 {
+            transform.look_at_smooth(point, 5.0, 1.0 / 60.0);
+        }
+        let forward = transform.transform_direction(Vector3::new(0.0, 0.0, -1.0));
+        let expected = (point - transform.position).normalized();
+        // Loose enough for the `fast_math` magnitude.
+        assert!(forward.approx_eq(&expected, 2e-3), "{:?} {:?}", forward, expected);
+        // Up stays as close to world up as possible, so the local right axis is horizontal.
+        assert!(transform.transform_direction(Vector3::new(1.0, 0.0, 0.0)).y.abs() < 1e-3);
+
+        // Looking at its own position changes nothing.
+        let before = transform.rotation;
+        transform.look_at_smooth(transform.position, 5.0, 1.0);
+        assert_eq!(transform.rotation.to_wxyz_array(), before.to_wxyz_array());
+    }
+
+    #[test]
+    fn lerp_midpoint_slerps_the_rotation() {
+        let a = Transform::new(Vector3::new(1.0, 2.0, 3.0), Quaternion::identity(), Vector3::new(1.0, 1.0, 1.0));
+        let turned = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 1.5);
+        let b = Transform::new(Vector3::new(-3.0, 6.0, 3.0), turned, Vector3::new(3.0, 2.0, 0.5));
+
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid.position, Vector3::new(-1.0, 4.0, 3.0));
+        assert_eq!(mid.scale, Vector3::new(2.0, 1.5, 0.75));
+        assert_eq!(mid.rotation, Quaternion::identity().slerp(turned, 0.5));
+        let halfway = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), 0.75);
+        assert!((mid.rotation.dot(&halfway).abs() - 1.0).abs() < 1e-5, "{:?}", mid.rotation);
+    }
+
+    #[test]
+    fn composition_matches_the_matrix_product() {
+        let parent = Transform::new(
+            Vector3::new(3.0, -1.0, 2.0),
+            Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 0.5), 0.8),
+            Vector3::new(2.0, 2.0, 2.0),
+        );
+        let child = Transform::new(
+            Vector3::new(-1.0, 4.0, 0.5),
+            Quaternion::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), -1.2),
+            Vector3::new(0.5, 1.0, 1.5),
+        );
+
+        let world = parent * child;
+        let product = parent.to_matrix() * child.to_matrix();
+        assert!(world.to_matrix().approx_eq(&product, 1e-4), "{}\n{}", world.to_matrix(), product);
+
+        let point = Vector3::new(0.3, -2.0, 1.0);
+        let nested = parent.transform_point(child.transform_point(point));
+        assert!((world.transform_point(point) - nested).magnitude() < 1e-4, "{:?}", world.transform_point(point));
+        let direction = Vector3::new(1.0, 1.0, -1.0);
+        let nested = parent.transform_direction(child.transform_direction(direction));
+        assert!((world.transform_direction(direction) - nested).magnitude() < 1e-4, "{:?}", world.transform_direction(direction));
+    }
+
+    #[test]
+    fn times_inverse_is_the_identity() {
+        let t = Transform::new(
+            Vector3::new(3.0, -1.0, 2.0),
+            Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 0.5), 0.8),
+            Vector3::new(2.0, 2.0, 2.0),
+        );
+        let identity = Matrix4x4::identity();
+        assert!((t * t.inverse()).to_matrix().approx_eq(&identity, 1e-5), "{}", (t * t.inverse()).to_matrix());
+        assert!((t.inverse() * t).to_matrix().approx_eq(&identity, 1e-5), "{}", (t.inverse() * t).to_matrix());
+
+        let point = Vector3::new(-4.0, 0.5, 7.0);
+        assert!((t.inverse().transform_point(t.transform_point(point)) - point).magnitude() < 1e-4);
+    }
+
+}
\ No newline at end of file
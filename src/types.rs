@@ -1,16 +1,220 @@
+use core::ops::Neg;
 use crate::angles::angle2::Angle2;
 use crate::angles::quaternion::Quaternion;
+#[cfg(feature = "std")]
 use crate::rendering::vertex::Vertex;
 use crate::vectors::vector2::Vector2;
 use crate::vectors::vector3::Vector3;
 
+/// One of the three coordinate axes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis {
     X,
     Y,
     Z,
 }
 
+impl Axis {
+
+    /// All axes in x, y, z order.
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+
+    /// Returns the index of the axis in x, y, z order, matching `Vector3`'s `Index` impl.
+    #[inline]
+    pub const fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+
+    /// Returns the unit vector pointing along the positive axis.
+    #[inline]
+    pub const fn unit_vector(self) -> Vector3 {
+        match self {
+            Axis::X => Vector3::X,
+            Axis::Y => Vector3::Y,
+            Axis::Z => Vector3::Z,
+        }
+    }
+
+}
+
+/// One of the six directions along the coordinate axes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignedAxis {
+    PositiveX,
+    NegativeX,
+    PositiveY,
+    NegativeY,
+    PositiveZ,
+    NegativeZ,
+}
+
+impl SignedAxis {
+
+    /// All directions, the positive one of every axis first.
+    pub const ALL: [SignedAxis; 6] = [
+        SignedAxis::PositiveX, SignedAxis::NegativeX,
+        SignedAxis::PositiveY, SignedAxis::NegativeY,
+        SignedAxis::PositiveZ, SignedAxis::NegativeZ,
+    ];
+
+    /// Creates the direction along `axis`, pointing the negative way if `negative` is set.
+    #[inline]
+    pub const fn new(axis: Axis, negative: bool) -> Self {
+        match (axis, negative) {
+            (Axis::X, false) => SignedAxis::PositiveX,
+            (Axis::X, true) => SignedAxis::NegativeX,
+            (Axis::Y, false) => SignedAxis::PositiveY,
+            (Axis::Y, true) => SignedAxis::NegativeY,
+            (Axis::Z, false) => SignedAxis::PositiveZ,
+            (Axis::Z, true) => SignedAxis::NegativeZ,
+        }
+    }
+
+    /// Returns the axis this direction lies on.
+    #[inline]
+    pub const fn axis(self) -> Axis {
+        match self {
+            SignedAxis::PositiveX | SignedAxis::NegativeX => Axis::X,
+            SignedAxis::PositiveY | SignedAxis::NegativeY => Axis::Y,
+            SignedAxis::PositiveZ | SignedAxis::NegativeZ => Axis::Z,
+        }
+    }
+
+    /// Returns true for the directions pointing the negative way.
+    #[inline]
+    pub const fn is_negative(self) -> bool {
+        matches!(self, SignedAxis::NegativeX | SignedAxis::NegativeY | SignedAxis::NegativeZ)
+    }
+
+    /// Returns 1 for the positive and -1 for the negative directions.
+    #[inline]
+    pub const fn sign(self) -> f32 {
+        if self.is_negative() { -1.0 } else { 1.0 }
+    }
+
+    /// Returns the unit vector pointing in this direction.
+    #[inline]
+    pub const fn unit_vector(self) -> Vector3 {
+        match self {
+            SignedAxis::PositiveX => Vector3::new(1.0, 0.0, 0.0),
+            SignedAxis::NegativeX => Vector3::new(-1.0, 0.0, 0.0),
+            SignedAxis::PositiveY => Vector3::new(0.0, 1.0, 0.0),
+            SignedAxis::NegativeY => Vector3::new(0.0, -1.0, 0.0),
+            SignedAxis::PositiveZ => Vector3::new(0.0, 0.0, 1.0),
+            SignedAxis::NegativeZ => Vector3::new(0.0, 0.0, -1.0),
+        }
+    }
+
+}
+
+impl From<Axis> for SignedAxis {
+    /// Returns the positive direction of `axis`.
+    fn from(axis: Axis) -> Self {
+        SignedAxis::new(axis, false)
+    }
+}
+
+impl Neg for SignedAxis {
+    type Output = SignedAxis;
+
+    /// Returns the opposite direction.
+    fn neg(self) -> SignedAxis {
+        SignedAxis::new(self.axis(), !self.is_negative())
+    }
+}
+
+/// The order in which the rotations around the three axes of an `Euler` are applied, see `Euler::to_quaternion_ordered`.
+///
+/// The rotations turn around the fixed axes of the parent space: `XYZ` rotates around x first, then around y and z,
+/// so its rotation matrix is `Rz * Ry * Rx`. This is the same as rotating around the already rotated axes in reverse order.
+/// Blender's Euler modes use the same naming, e.g. its default `XYZ` mode matches `RotationOrder::XYZ`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RotationOrder {
+    /// The order of `Euler::to_quaternion`.
+    #[default]
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+}
+
+impl RotationOrder {
+
+    /// All rotation orders.
+    pub const ALL: [RotationOrder; 6] = [
+        RotationOrder::XYZ, RotationOrder::XZY,
+        RotationOrder::YXZ, RotationOrder::YZX,
+        RotationOrder::ZXY, RotationOrder::ZYX,
+    ];
+
+    /// Returns the axes in the order their rotations are applied.
+    #[inline]
+    pub const fn axes(self) -> [Axis; 3] {
+        match self {
+            RotationOrder::XYZ => [Axis::X, Axis::Y, Axis::Z],
+            RotationOrder::XZY => [Axis::X, Axis::Z, Axis::Y],
+            RotationOrder::YXZ => [Axis::Y, Axis::X, Axis::Z],
+            RotationOrder::YZX => [Axis::Y, Axis::Z, Axis::X],
+            RotationOrder::ZXY => [Axis::Z, Axis::X, Axis::Y],
+            RotationOrder::ZYX => [Axis::Z, Axis::Y, Axis::X],
+        }
+    }
+
+    /// Returns true for the orders which are a cyclic shift of x, y, z: `XYZ`, `YZX` and `ZXY`.
+    #[inline]
+    pub const fn is_cyclic(self) -> bool {
+        matches!(self, RotationOrder::XYZ | RotationOrder::YZX | RotationOrder::ZXY)
+    }
+
+}
+
 pub type Point3 = (Quaternion, Vector3);
 pub type Point2 = (Angle2, Vector2);
 
+#[cfg(feature = "std")]
 pub type Vertices = Vec<Vertex>;
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn axis_indices_and_unit_vectors() {
+        for (i, axis) in Axis::ALL.into_iter().enumerate() {
+            assert_eq!(axis.index(), i);
+            assert_eq!(axis.unit_vector()[i], 1.0);
+            assert_eq!(axis.unit_vector().magnitude_squared(), 1.0);
+
+            let mut v = Vector3::new(1.0, 2.0, 3.0);
+            assert_eq!(v.component(axis), i as f32 + 1.0);
+            v.set_component(axis, -5.0);
+            assert_eq!(v[i], -5.0);
+        }
+    }
+
+    #[test]
+    fn signed_axes_pair_up_with_their_opposites() {
+        for signed in SignedAxis::ALL {
+            assert_eq!(SignedAxis::new(signed.axis(), signed.is_negative()), signed);
+            assert_eq!(signed.unit_vector(), signed.axis().unit_vector() * signed.sign());
+            assert_eq!(-(-signed), signed);
+            assert_eq!((-signed).axis(), signed.axis());
+            assert_eq!((-signed).unit_vector(), -signed.unit_vector());
+            assert_ne!(-signed, signed);
+        }
+        assert_eq!(SignedAxis::from(Axis::Y), SignedAxis::PositiveY);
+        assert_eq!(-SignedAxis::PositiveY, SignedAxis::NegativeY);
+        assert_eq!(SignedAxis::NegativeZ.unit_vector(), Vector3::FORWARD);
+    }
+
+}
\ No newline at end of file
@@ -1,3 +1,387 @@
 pub mod vector2;
 pub mod vector3;
-pub mod vector4;
\ No newline at end of file
+pub mod vector4;
+
+#[cfg(test)]
+mod tests {
+
+    use super::vector2::Vector2;
+    use super::vector3::Vector3;
+    use super::vector4::Vector4;
+
+    /// The documented relative error of the `_fast` variants.
+    const FAST_ERROR: f64 = 2e-3;
+
+    /// A small xorshift generator, so the inputs are the same on every run.
+    struct Rng(u32);
+
+    impl Rng {
+
+        /// Returns a float in [-100, 100), spread over several orders of magnitude.
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            let unit = (self.0 >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0;
+            unit * unit * unit * 100.0
+        }
+
+    }
+
+    /// Checks `magnitude`, `normalized` and their `_fast` variants against the magnitude computed in `f64`.
+    macro_rules! fast_and_precise_variants {
+        ($name:ident, $vector:ident, $($field:ident),+) => {
+            #[test]
+            fn $name() {
+                let mut rng = Rng(0x9e37_79b9);
+                for _ in 0..10_000 {
+                    let v = $vector { $($field: rng.next_f32()),+ };
+                    let exact = (0.0 $(+ (v.$field as f64) * (v.$field as f64))+).sqrt();
+                    if exact < 1e-6 {
+                        continue;
+                    }
+                    assert!((v.magnitude_fast() as f64 / exact - 1.0).abs() < FAST_ERROR, "{:?}", v);
+                    let n = v.normalized_fast();
+                    assert!(((0.0 $(+ (n.$field as f64) * (n.$field as f64))+).sqrt() - 1.0).abs() < FAST_ERROR, "{:?}", v);
+
+                    #[cfg(not(feature = "fast_math"))]
+                    {
+                        assert!((v.magnitude() as f64 / exact - 1.0).abs() < 1e-6, "{:?}", v);
+                        let n = v.normalized();
+                        assert!(((0.0 $(+ (n.$field as f64) * (n.$field as f64))+).sqrt() - 1.0).abs() < 1e-6, "{:?}", v);
+                    }
+                    // The feature switches the defaults over to the fast variants wholesale.
+                    #[cfg(feature = "fast_math")]
+                    assert_eq!(v.magnitude(), v.magnitude_fast());
+                }
+                let zero = $vector { $($field: 0.0),+ };
+                assert_eq!((zero.magnitude_fast(), zero.magnitude()), (0.0, 0.0));
+                assert_eq!(zero.normalized_fast(), zero);
+                assert_eq!(zero.normalized(), zero);
+            }
+        };
+    }
+
+    fast_and_precise_variants!(vector2_fast_and_precise_variants, Vector2, x, y);
+    fast_and_precise_variants!(vector3_fast_and_precise_variants, Vector3, x, y, z);
+    fast_and_precise_variants!(vector4_fast_and_precise_variants, Vector4, x, y, z, w);
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format_is_stable() {
+        let v2 = Vector2::new(1.5, -2.0);
+        let v3 = Vector3::new(1.5, -2.0, 0.25);
+        let v4 = Vector4::new(1.5, -2.0, 0.25, 1.0);
+        assert_eq!(serde_json::to_string(&v2).unwrap(), r#"{"x":1.5,"y":-2.0}"#);
+        assert_eq!(serde_json::to_string(&v3).unwrap(), r#"{"x":1.5,"y":-2.0,"z":0.25}"#);
+        assert_eq!(serde_json::to_string(&v4).unwrap(), r#"{"x":1.5,"y":-2.0,"z":0.25,"w":1.0}"#);
+        assert_eq!(serde_json::from_str::<Vector2>(&serde_json::to_string(&v2).unwrap()).unwrap(), v2);
+        assert_eq!(serde_json::from_str::<Vector3>(&serde_json::to_string(&v3).unwrap()).unwrap(), v3);
+        assert_eq!(serde_json::from_str::<Vector4>(&serde_json::to_string(&v4).unwrap()).unwrap(), v4);
+    }
+
+    /// Checks the component-wise operations against `f32` applied to every component, including the NaN semantics.
+    macro_rules! component_wise_operations {
+        ($name:ident, $vector:ident, $($field:ident),+) => {
+            #[test]
+            fn $name() {
+                let from = |values: [f32; 4]| {
+                    let mut values = values.into_iter();
+                    $vector { $($field: values.next().unwrap()),+ }
+                };
+                let components = |v: $vector| [$(v.$field),+];
+
+                let a = from([1.5, -2.5, 0.5, -0.5]);
+                let b = from([-1.0, 3.0, 0.5, 2.0]);
+                assert_eq!(components(a.min(&b)), components(from([-1.0, -2.5, 0.5, -0.5])));
+                assert_eq!(components(a.max(&b)), components(from([1.5, 3.0, 0.5, 2.0])));
+                let (lo, hi) = (from([-1.0; 4]), from([1.0; 4]));
+                assert_eq!(components(a.clamp(&lo, &hi)), components(from([1.0, -1.0, 0.5, -0.5])));
+                assert_eq!(components(a.abs()), components(from([1.5, 2.5, 0.5, 0.5])));
+                assert_eq!(components(a.floor()), components(from([1.0, -3.0, 0.0, -1.0])));
+                assert_eq!(components(a.ceil()), components(from([2.0, -2.0, 1.0, -0.0])));
+                assert_eq!(components(a.round()), components(from([2.0, -3.0, 1.0, -1.0])));
+                assert_eq!(a.min_element(), -2.5);
+                assert_eq!(b.max_element(), 3.0);
+                assert!(a.is_finite() && !a.is_nan());
+
+                // NaN loses against numbers like in `f32::min`, only NaN against NaN stays NaN.
+                let nan = from([f32::NAN, 2.0, f32::NAN, f32::NAN]);
+                assert_eq!(components(nan.min(&a))[..2], [1.5, -2.5]);
+                assert_eq!(components(a.max(&nan))[..2], [1.5, 2.0]);
+                assert!(nan.min(&nan).x.is_nan() && nan.max(&nan).x.is_nan());
+                assert!(nan.clamp(&lo, &hi).x.is_nan());
+                assert_eq!((nan.min_element(), nan.max_element()), (2.0, 2.0));
+                assert!(from([f32::NAN; 4]).min_element().is_nan() && from([f32::NAN; 4]).max_element().is_nan());
+                assert!(nan.is_nan() && !nan.is_finite());
+                let infinite = from([1.0, f32::INFINITY, 1.0, 1.0]);
+                assert!(!infinite.is_finite() && !infinite.is_nan());
+
+                assert!(std::panic::catch_unwind(|| a.clamp(&hi, &lo)).is_err());
+            }
+        };
+    }
+
+    component_wise_operations!(vector2_component_wise_operations, Vector2, x, y);
+    component_wise_operations!(vector3_component_wise_operations, Vector3, x, y, z);
+    component_wise_operations!(vector4_component_wise_operations, Vector4, x, y, z, w);
+
+    #[test]
+    fn magnitude_is_the_euclidean_length() {
+        // Loose enough for the `fast_math` magnitude.
+        let close = |actual: f32, expected: f32| (actual / expected - 1.0).abs() < FAST_ERROR as f32;
+        for magnitude in [Vector2::new(3.0, 4.0).magnitude(), Vector3::new(3.0, 4.0, 0.0).magnitude(), Vector3::new(0.0, -3.0, 4.0).magnitude(), Vector4::new(3.0, 0.0, 0.0, 4.0).magnitude()] {
+            assert!(close(magnitude, 5.0), "{}", magnitude);
+        }
+        assert!(close(Vector3::new(2.0, 3.0, 6.0).inv_magnitude(), 1.0 / 7.0));
+        assert!(close(Vector4::new(1.0, 1.0, 1.0, 1.0).magnitude_fast(), 2.0));
+        #[cfg(not(feature = "fast_math"))]
+        assert_eq!(Vector3::new(3.0, 4.0, 0.0).magnitude(), 5.0);
+    }
+
+    #[test]
+    fn normalized_leaves_zero_length_vectors_alone_in_every_size() {
+        // Too small to square, so they count as zero length instead of turning into NaN.
+        for value in [0.0, -0.0, 1e-30] {
+            assert_eq!(Vector2::new(value, 0.0).normalized(), Vector2::new(value, 0.0));
+            assert_eq!(Vector3::new(0.0, value, 0.0).normalized(), Vector3::new(0.0, value, 0.0));
+            assert_eq!(Vector4::new(0.0, 0.0, value, 0.0).normalized(), Vector4::new(0.0, 0.0, value, 0.0));
+            assert_eq!(Vector2::new(value, 0.0).normalized_fast(), Vector2::new(value, 0.0));
+            assert_eq!(Vector3::new(0.0, value, 0.0).normalized_fast(), Vector3::new(0.0, value, 0.0));
+            assert_eq!(Vector4::new(0.0, 0.0, value, 0.0).normalized_fast(), Vector4::new(0.0, 0.0, value, 0.0));
+        }
+    }
+
+    #[test]
+    fn distance_and_angles() {
+        use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        // Loose enough for the `fast_math` magnitude.
+        let close = |actual: f32, expected: f32| (actual - expected).abs() < 2e-3;
+
+        assert!(close(Vector2::new(1.0, 1.0).distance(&Vector2::new(4.0, 5.0)), 5.0));
+        assert!(close(Vector3::new(1.0, 1.0, 1.0).distance(&Vector3::new(3.0, 4.0, 7.0)), 7.0));
+
+        let (x, y) = (Vector2::new(2.0, 0.0), Vector2::new(0.0, 0.5));
+        assert_eq!(x.perp_dot(&y), 1.0);
+        assert!(close(x.angle_between(&y), FRAC_PI_2) && close(y.angle_between(&x), FRAC_PI_2));
+        assert!(close(x.signed_angle(&y), FRAC_PI_2) && close(y.signed_angle(&x), -FRAC_PI_2));
+        assert!(close(x.signed_angle(&Vector2::new(-1.0, -1.0)), -3.0 * FRAC_PI_4));
+        assert!(close(x.angle_between(&-x), PI));
+
+        let (x, y, z) = (Vector3::new(2.0, 0.0, 0.0), Vector3::new(0.0, 3.0, 0.0), Vector3::new(0.0, 0.0, 1.0));
+        assert!(close(x.angle_between(&y), FRAC_PI_2) && close(x.angle_between(&-x), PI));
+        assert!(close(x.angle_between(&(x + y * (2.0 / 3.0))), FRAC_PI_4));
+        assert!(close(x.signed_angle(&y, z), FRAC_PI_2) && close(x.signed_angle(&y, -z), -FRAC_PI_2));
+        assert!(close(y.signed_angle(&x, z), -FRAC_PI_2));
+
+        // Nearly parallel vectors still get a tiny angle instead of 0 or NaN, zero vectors get 0.
+        let angle = Vector3::new(1.0, 0.0, 0.0).angle_between(&Vector3::new(1.0, 1e-4, 0.0));
+        assert!((angle / 1e-4 - 1.0).abs() < 2e-3, "{}", angle);
+        assert_eq!(Vector3::zero().angle_between(&x), 0.0);
+        assert_eq!(Vector2::zero().signed_angle(&Vector2::new(0.0, 1.0)), 0.0);
+        assert_eq!(x.signed_angle(&Vector3::zero(), z), 0.0);
+    }
+
+    #[test]
+    fn extend_truncate_and_conversions_between_sizes() {
+        let v2 = Vector2::new(1.0, -2.0);
+        let v3 = Vector3::new(1.0, -2.0, 3.0);
+        let v4 = Vector4::new(1.0, -2.0, 3.0, 4.0);
+
+        assert_eq!(v2.extend(3.0), v3);
+        assert_eq!(v3.extend(4.0), v4);
+        assert_eq!(v3.truncate(), v2);
+        assert_eq!(v4.truncate(), v3);
+        assert_eq!((v4.xyz(), v4.xy()), (v3, v2));
+        assert_eq!(v2.extend(7.0).truncate(), v2);
+        assert_eq!(v3.extend(7.0).truncate(), v3);
+
+        // Growing fills in 0, so a Vector3 becomes a direction, and shrinking drops components without dividing by w.
+        assert_eq!(Vector3::from(v2), Vector3::new(1.0, -2.0, 0.0));
+        assert_eq!(Vector4::from(v3), Vector4::new(1.0, -2.0, 3.0, 0.0));
+        assert_eq!(Vector2::from(v3), v2);
+        assert_eq!(Vector3::from(Vector4::new(2.0, 4.0, 6.0, 2.0)), Vector3::new(2.0, 4.0, 6.0));
+    }
+
+    #[test]
+    fn index_array_and_tuple_conversions() {
+        let mut v2 = Vector2::from((1.0, 2.0));
+        let mut v3 = Vector3::from((1.0, 2.0, 3.0));
+        let mut v4 = Vector4::from([1.0, 2.0, 3.0, 4.0]);
+        for i in 0..4 {
+            if i < 2 {
+                assert_eq!(v2[i], i as f32 + 1.0);
+                v2[i] *= -1.0;
+            }
+            if i < 3 {
+                assert_eq!(v3[i], i as f32 + 1.0);
+                v3[i] *= -1.0;
+            }
+            assert_eq!(v4[i], i as f32 + 1.0);
+            v4[i] *= -1.0;
+        }
+        assert_eq!(<[f32; 2]>::from(v2), [-1.0, -2.0]);
+        assert_eq!(<[f32; 3]>::from(v3), [-1.0, -2.0, -3.0]);
+        assert_eq!(<[f32; 4]>::from(v4), [-1.0, -2.0, -3.0, -4.0]);
+        assert_eq!(Vector2::from([5.0, 6.0]), Vector2::new(5.0, 6.0));
+        assert_eq!(Vector3::from([5.0, 6.0, 7.0]), Vector3::new(5.0, 6.0, 7.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Vector2 index out of range: the index is 2 but there are only 2 components")]
+    fn vector2_index_out_of_range_panics() {
+        let _ = Vector2::zero()[2];
+    }
+
+    #[test]
+    #[should_panic(expected = "Vector3 index out of range: the index is 3 but there are only 3 components")]
+    fn vector3_index_out_of_range_panics() {
+        Vector3::zero()[3] = 1.0;
+    }
+
+    #[test]
+    #[should_panic(expected = "Vector4 index out of range: the index is 4 but there are only 4 components")]
+    fn vector4_index_out_of_range_panics() {
+        let _ = Vector4::zero()[4];
+    }
+
+    /// Checks every arithmetic operator against `f32` applied to every component, and the assign operators against the plain ones.
+    macro_rules! operators {
+        ($name:ident, $vector:ident, $($field:ident),+) => {
+            #[test]
+            fn $name() {
+                let from = |values: [f32; 4]| {
+                    let mut values = values.into_iter();
+                    $vector { $($field: values.next().unwrap()),+ }
+                };
+                let a = from([1.0, -2.0, 3.0, -4.0]);
+                let b = from([2.0, 4.0, -8.0, 0.5]);
+
+                assert_eq!(a + b, $vector { $($field: a.$field + b.$field),+ });
+                assert_eq!(a - b, $vector { $($field: a.$field - b.$field),+ });
+                assert_eq!(a * b, $vector { $($field: a.$field * b.$field),+ });
+                assert_eq!(a / b, $vector { $($field: a.$field / b.$field),+ });
+                assert_eq!(a * 3.0, $vector { $($field: a.$field * 3.0),+ });
+                assert_eq!(3.0 * a, a * 3.0);
+                assert_eq!(a / 4.0, $vector { $($field: a.$field / 4.0),+ });
+                assert_eq!(-a, $vector { $($field: -a.$field),+ });
+                assert_eq!(-a, a * -1.0);
+
+                let mut c = a;
+                c += b;
+                assert_eq!(c, a + b);
+                c -= b;
+                assert_eq!(c, a);
+                c *= b;
+                assert_eq!(c, a * b);
+                c /= b;
+                assert_eq!(c, a);
+                c *= 2.0;
+                assert_eq!(c, a * 2.0);
+                c /= 2.0;
+                assert_eq!(c, a);
+            }
+        };
+    }
+
+    operators!(vector2_operators, Vector2, x, y);
+    operators!(vector3_operators, Vector3, x, y, z);
+    operators!(vector4_operators, Vector4, x, y, z, w);
+
+    #[test]
+    fn constants_and_const_constructors() {
+        const V2: Vector2 = Vector2::from_one(2.0);
+        const V3: Vector3 = Vector3::new(1.0, 2.0, 3.0);
+        const V4: Vector4 = Vector4::one();
+        assert_eq!((V2, Vector2::zero(), Vector2::one()), (Vector2::new(2.0, 2.0), Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)));
+        assert_eq!(V3, Vector3::X + Vector3::Y * 2.0 + Vector3::Z * 3.0);
+        assert_eq!(V4, Vector4::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(Vector2::X.perp_dot(&Vector2::Y), 1.0);
+        assert_eq!((Vector3::ZERO, Vector3::ONE), (Vector3::zero(), Vector3::from_one(1.0)));
+
+        // Right-handed with y up and -z forward, like the cameras.
+        assert_eq!(Vector3::X.cross(&Vector3::Y), Vector3::Z);
+        assert_eq!(Vector3::RIGHT.cross(&Vector3::UP), Vector3::BACK);
+        assert_eq!((Vector3::RIGHT, Vector3::UP, Vector3::BACK), (Vector3::X, Vector3::Y, Vector3::Z));
+        assert_eq!((Vector3::LEFT, Vector3::DOWN, Vector3::FORWARD), (-Vector3::RIGHT, -Vector3::UP, -Vector3::BACK));
+    }
+
+    #[test]
+    fn try_project_handles_zero_and_tiny_targets() {
+        let v3 = Vector3::new(3.0, 4.0, 5.0);
+        assert_eq!(v3.try_project(Vector3::new(0.0, 2.0, 0.0)), Some(Vector3::new(0.0, 4.0, 0.0)));
+        assert_eq!(Vector2::new(3.0, 4.0).try_project(Vector2::new(1.0, 1.0)), Some(Vector2::new(3.5, 3.5)));
+        assert_eq!(Vector4::new(3.0, 4.0, 5.0, 6.0).try_project(&Vector4::new(0.0, 0.0, 0.0, -1.0)), Some(Vector4::new(0.0, 0.0, 0.0, 6.0)));
+
+        // Zero has no direction, so the projection falls back to zero.
+        assert_eq!(v3.try_project(Vector3::ZERO), None);
+        assert_eq!(v3.project(Vector3::ZERO), Vector3::ZERO);
+        assert_eq!(Vector2::new(3.0, 4.0).try_project(Vector2::zero()), None);
+        assert_eq!(Vector2::new(3.0, 4.0).project(Vector2::zero()), Vector2::zero());
+        assert_eq!(Vector4::one().try_project(&Vector4::zero()), None);
+        assert_eq!(Vector4::one().project(&Vector4::zero()), Vector4::zero());
+        assert_eq!(v3.try_project(Vector3::new(f32::INFINITY, 0.0, 0.0)), None);
+        assert_eq!(v3.try_project(Vector3::new(f32::NAN, 0.0, 0.0)), None);
+
+        // The square of 1e-30 underflows, but the projection is still exact.
+        assert_eq!(v3.try_project(Vector3::new(1e-30, 0.0, 0.0)), Some(Vector3::new(3.0, 0.0, 0.0)));
+        assert_eq!(Vector2::new(3.0, 4.0).project(Vector2::new(0.0, -1e-30)), Vector2::new(0.0, 4.0));
+        assert_eq!(Vector4::one().project(&Vector4::new(0.0, 0.0, 1e-30, 0.0)), Vector4::new(0.0, 0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn swizzles_and_with_methods() {
+        let v2 = Vector2::new(1.0, 2.0);
+        assert_eq!(v2.yx(), Vector2::new(2.0, 1.0));
+        assert_eq!((v2.with_x(5.0), v2.with_y(5.0)), (Vector2::new(5.0, 2.0), Vector2::new(1.0, 5.0)));
+
+        let v3 = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!((v3.xy(), v3.xz(), v3.yz()), (Vector2::new(1.0, 2.0), Vector2::new(1.0, 3.0), Vector2::new(2.0, 3.0)));
+        assert_eq!((v3.zyx(), v3.yzx(), v3.zxy()), (Vector3::new(3.0, 2.0, 1.0), Vector3::new(2.0, 3.0, 1.0), Vector3::new(3.0, 1.0, 2.0)));
+        // Rotating the components forwards and backwards cancels out.
+        assert_eq!(v3.yzx().zxy(), v3);
+        assert_eq!(v3.with_x(5.0), Vector3::new(5.0, 2.0, 3.0));
+        assert_eq!(v3.with_y(5.0), Vector3::new(1.0, 5.0, 3.0));
+        assert_eq!(v3.with_z(5.0), Vector3::new(1.0, 2.0, 5.0));
+
+        let v4 = Vector4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(v4.with_x(5.0), Vector4::new(5.0, 2.0, 3.0, 4.0));
+        assert_eq!(v4.with_y(5.0), Vector4::new(1.0, 5.0, 3.0, 4.0));
+        assert_eq!(v4.with_z(5.0), Vector4::new(1.0, 2.0, 5.0, 4.0));
+        assert_eq!(v4.with_w(5.0), Vector4::new(1.0, 2.0, 3.0, 5.0));
+        assert_eq!(v4, Vector4::new(1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn norm8_conversions_work_per_component() {
+        assert_eq!(Vector2::new(0.0, 1.0).to_unorm8(), [0, 255]);
+        assert_eq!(Vector3::new(-1.0, 0.0, 1.0).to_snorm8(), [-127, 0, 127]);
+        assert_eq!(Vector4::new(-1.0, 0.0, 0.5, 2.0).to_unorm8(), [0, 0, 128, 255]);
+        assert_eq!(Vector4::from_snorm8([-128, -127, 0, 127]), Vector4::new(-1.0, -1.0, 0.0, 1.0));
+        assert_eq!(Vector2::from_snorm8([-127, 127]), Vector2::new(-1.0, 1.0));
+        assert_eq!(Vector3::from_unorm8([0, 255, 51]), Vector3::new(0.0, 1.0, 0.2));
+
+        // A normal survives the round trip within half a step.
+        let normal = Vector3::new(0.6, -0.8, 0.0);
+        let decoded = Vector3::from_snorm8(normal.to_snorm8());
+        assert!(decoded.approx_eq(&normal, 0.5 / 127.0), "{:?}", decoded);
+        let color = Vector4::new(0.1, 0.2, 0.3, 0.4);
+        assert!(Vector4::from_unorm8(color.to_unorm8()).approx_eq(&color, 0.5 / 255.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn string_exact_round_trips() {
+        let v2 = Vector2::new(0.1, -0.0);
+        let v3 = Vector3::new(1.0 / 3.0, 1e-45, f32::INFINITY);
+        let v4 = Vector4::new(1.0, 2.5, -3.0, 16777216.0);
+        assert_eq!(v2.to_string_exact(), "0.1,-0.0");
+        assert_eq!(v3.to_string_exact(), "0.33333334,1e-45,inf");
+        assert_eq!(v4.to_string_exact(), "1.0,2.5,-3.0,16777216.0");
+        assert_eq!(Vector2::from_string_exact(&v2.to_string_exact()).unwrap().y.to_bits(), (-0.0f32).to_bits());
+        assert_eq!(Vector3::from_string_exact(&v3.to_string_exact()), Ok(v3));
+        assert_eq!(Vector4::from_string_exact(&v4.to_string_exact()), Ok(v4));
+        assert_eq!(Vector3::from_string_exact("1,2"), Err(crate::math::ParseExactError::WrongCount { expected: 3, found: 2 }));
+    }
+
+}
\ No newline at end of file
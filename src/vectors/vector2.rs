@@ -1,36 +1,54 @@
-use std::ops::{Add, Div, Mul, Sub};
-use crate::math::fast_inv_sqrt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::math::{default_sqrt, Lerp, fast_inv_sqrt, from_snorm8, from_unorm8, parse_exact, to_snorm8, to_unorm8, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector3::Vector3;
 
 /// A 2D vector for representing points or directions in 2D space.
-#[derive(Debug, Copy, Clone)]
+///
+/// All operators work component-wise: `+`, `-` and negation, `*` and `/` with a scalar or with another vector.
+/// Use `dot` for the dot product.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
 }
 
+const _: () = assert!(core::mem::size_of::<Vector2>() == 8 && core::mem::align_of::<Vector2>() == 4);
+
 impl Vector2 {
 
+    /// The unit x axis.
+    pub const X: Vector2 = Vector2::new(1.0, 0.0);
+    /// The unit y axis.
+    pub const Y: Vector2 = Vector2::new(0.0, 1.0);
+
     /// Create a Vector2 with x and y components.
     #[inline]
-    pub fn new(x: f32, y: f32) -> Vector2 {
+    pub const fn new(x: f32, y: f32) -> Vector2 {
         Vector2 { x, y }
     }
 
     /// Create a Vector2 with both x and y set to 0.
     #[inline]
-    pub fn zero() -> Vector2 {
+    pub const fn zero() -> Vector2 {
         Vector2::new(0.0, 0.0)
     }
 
     /// Create a Vector2 with both x and y set to 1.
     #[inline]
-    pub fn one() -> Vector2 {
+    pub const fn one() -> Vector2 {
         Vector2::new(1.0, 1.0)
     }
 
     /// Create a Vector2 with a single f32 as both x and y.
     #[inline]
-    pub fn from_one(x: f32) -> Vector2 {
+    pub const fn from_one(x: f32) -> Vector2 {
         Vector2::new(x,x)
     }
 
@@ -41,19 +59,58 @@ impl Vector2 {
     }
 
     /// Returns a normalized version of the vector.
+    /// A zero length vector is returned unchanged.
     #[inline]
     pub fn normalized(self) -> Self {
-        let mag = self.magnitude();
-        Self {
-            x: self.x / mag,
-            y: self.y / mag,
+        let magnitude_squared = self.magnitude_squared();
+
+        if magnitude_squared == 0.0 {
+            self
+        } else {
+            let mag = default_sqrt(magnitude_squared);
+            Self {
+                x: self.x / mag,
+                y: self.y / mag,
+            }
+        }
+    }
+
+    /// Normalizes the vector in place, see `normalized`.
+    #[inline]
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    /// Returns a normalized version of the vector using `fast_inv_sqrt`, the length of the result is within 0.2% of 1.
+    /// A zero length vector is returned unchanged.
+    #[inline]
+    pub fn normalized_fast(self) -> Self {
+        let magnitude_squared = self.magnitude_squared();
+
+        if magnitude_squared == 0.0 {
+            self
+        } else {
+            self * fast_inv_sqrt(magnitude_squared)
         }
     }
 
     /// Returns the magnitude (length) of the vector.
     #[inline]
     pub fn magnitude(&self) -> f32 {
-        1.0 / fast_inv_sqrt(self.x * self.x + self.y * self.y)
+        default_sqrt(self.magnitude_squared())
+    }
+
+    /// Returns an approximation of the magnitude using `fast_inv_sqrt`, with a relative error below 0.2% and exactly 0 for a zero vector.
+    #[inline]
+    pub fn magnitude_fast(&self) -> f32 {
+        let magnitude_squared = self.magnitude_squared();
+        magnitude_squared * fast_inv_sqrt(magnitude_squared)
+    }
+
+    /// Returns an approximation of 1 / magnitude using `fast_inv_sqrt`, with a relative error below 0.2%.
+    #[inline]
+    pub fn inv_magnitude(&self) -> f32 {
+        fast_inv_sqrt(self.magnitude_squared())
     }
 
     /// Returns the squared magnitude (length) of the vector.
@@ -72,9 +129,91 @@ impl Vector2 {
     }
 
     /// Projects the vector onto the vector other.
+    /// Returns zero if `other` is zero or the projection is not finite, see `try_project`.
     #[inline]
     pub fn project(&self, other: Vector2) -> Vector2 {
-        other * (self.dot(other) / other.magnitude_squared())
+        self.try_project(other).unwrap_or(Vector2::zero())
+    }
+
+    /// Projects the vector onto the vector other, or returns `None` if `other` is zero or the projection is not finite.
+    /// Tiny but nonzero vectors like `(1e-30, 0)` are rescaled first, so they still give the exact projection.
+    pub fn try_project(&self, other: Vector2) -> Option<Vector2> {
+        let largest = other.x.abs().max(other.y.abs());
+        if largest == 0.0 || !largest.is_finite() {
+            return None;
+        }
+        // Dividing by the largest component keeps the squared magnitude from underflowing to zero.
+        let direction = other / largest;
+        let projected = direction * (self.dot(direction) / direction.magnitude_squared());
+        projected.is_finite().then_some(projected)
+    }
+
+    /// Returns true if no component is infinite or NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    /// Returns true if any component is NaN.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan()
+    }
+
+    /// Returns the component-wise minimum of this and the other vector.
+    /// Like `f32::min`, a NaN component loses against a number, so only two NaNs give NaN.
+    #[inline]
+    pub fn min(&self, other: &Self) -> Vector2 {
+        Vector2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of this and the other vector, with NaN handled like in `min`.
+    #[inline]
+    pub fn max(&self, other: &Self) -> Vector2 {
+        Vector2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Clamps every component between the matching components of `min` and `max` with `f32::clamp`,
+    /// so NaN components stay NaN. Panics if a component of `min` is greater than the one of `max` or either is NaN.
+    #[inline]
+    pub fn clamp(&self, min: &Self, max: &Self) -> Vector2 {
+        Vector2::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y))
+    }
+
+    /// Returns the vector with the absolute value of every component.
+    #[inline]
+    pub fn abs(&self) -> Vector2 {
+        Vector2::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Rounds every component down.
+    #[inline]
+    pub fn floor(&self) -> Vector2 {
+        Vector2::new(self.x.floor(), self.y.floor())
+    }
+
+    /// Rounds every component up.
+    #[inline]
+    pub fn ceil(&self) -> Vector2 {
+        Vector2::new(self.x.ceil(), self.y.ceil())
+    }
+
+    /// Rounds every component to the nearest integer, with halfway cases away from zero like `f32::round`.
+    #[inline]
+    pub fn round(&self) -> Vector2 {
+        Vector2::new(self.x.round(), self.y.round())
+    }
+
+    /// Returns the smallest component. NaN components are skipped like in `min`, unless all of them are NaN.
+    #[inline]
+    pub fn min_element(&self) -> f32 {
+        self.x.min(self.y)
+    }
+
+    /// Returns the largest component, with NaN handled like in `min_element`.
+    #[inline]
+    pub fn max_element(&self) -> f32 {
+        self.x.max(self.y)
     }
 
     /// Scales the vector by the given scalar.
@@ -86,6 +225,62 @@ impl Vector2 {
         }
     }
 
+    /// Scales the vector in place by the given scalar.
+    #[inline]
+    pub fn scale_mut(&mut self, scalar: f32) {
+        *self = self.scale(scalar);
+    }
+
+    /// Returns a Vector3 with the components of this vector and the given z.
+    #[inline]
+    pub fn extend(&self, z: f32) -> Vector3 {
+        Vector3::new(self.x, self.y, z)
+    }
+
+    /// Returns a copy with x and y swapped.
+    #[inline]
+    pub fn yx(&self) -> Vector2 {
+        Vector2::new(self.y, self.x)
+    }
+
+    /// Returns a copy with x replaced by the given value.
+    #[inline]
+    pub fn with_x(&self, x: f32) -> Vector2 {
+        Vector2::new(x, self.y)
+    }
+
+    /// Returns a copy with y replaced by the given value.
+    #[inline]
+    pub fn with_y(&self, y: f32) -> Vector2 {
+        Vector2::new(self.x, y)
+    }
+
+    /// Encodes the components, expected in [0, 1], as unsigned normalized bytes (e.g. for vertex colors).
+    /// Values outside the range are clamped, values in between are rounded to the nearest step.
+    #[inline]
+    pub fn to_unorm8(&self) -> [u8; 2] {
+        [to_unorm8(self.x), to_unorm8(self.y)]
+    }
+
+    /// Decodes unsigned normalized bytes into a vector with components in [0, 1].
+    #[inline]
+    pub fn from_unorm8(bytes: [u8; 2]) -> Vector2 {
+        Vector2::new(from_unorm8(bytes[0]), from_unorm8(bytes[1]))
+    }
+
+    /// Encodes the components, expected in [-1, 1], as signed normalized bytes (e.g. for compressed normals).
+    /// Values outside the range are clamped. -1, 0 and 1 survive a round trip exactly.
+    #[inline]
+    pub fn to_snorm8(&self) -> [i8; 2] {
+        [to_snorm8(self.x), to_snorm8(self.y)]
+    }
+
+    /// Decodes signed normalized bytes into a vector with components in [-1, 1].
+    #[inline]
+    pub fn from_snorm8(bytes: [i8; 2]) -> Vector2 {
+        Vector2::new(from_snorm8(bytes[0]), from_snorm8(bytes[1]))
+    }
+
     /// Returns the byte representation of the vector.
     pub fn as_bytes(&self) -> [u8; 8] {
         let mut bytes = [0u8; 8];
@@ -99,6 +294,33 @@ impl Vector2 {
         (self.x - other.x).powi(2) + (self.y - other.y).powi(2)
     }
 
+    /// Returns the distance between this and other Vector2.
+    #[inline]
+    pub fn distance(&self, other: &Self) -> f32 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Returns the perp-dot product, the z component of the 3D cross product of this and other vector.
+    /// Positive when other is counter-clockwise from this vector.
+    #[inline]
+    pub fn perp_dot(&self, other: &Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the unsigned angle between this and other vector in radians, in [0, π].
+    /// If either vector has zero length the angle is 0.
+    #[inline]
+    pub fn angle_between(&self, other: &Self) -> f32 {
+        self.perp_dot(other).abs().atan2(self.dot(*other))
+    }
+
+    /// Returns the counter-clockwise angle from this to other vector in radians, in [-π, π].
+    /// If either vector has zero length the angle is 0.
+    #[inline]
+    pub fn signed_angle(&self, other: &Self) -> f32 {
+        self.perp_dot(other).atan2(self.dot(*other))
+    }
+
     /// Returns the midpoint between this and other Vector2.
     pub fn midpoint(&self, other: &Self) -> Self {
         Self::new((self.x + other.x) / 2.0, (self.y + other.y) / 2.0)
@@ -112,6 +334,41 @@ impl Vector2 {
         )
     }
 
+    /// Returns true if every component differs from the one of `other` by at most `epsilon`.
+    /// Always false if a component is NaN.
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+    }
+
+    /// Returns the components as an array in x, y order.
+    #[inline]
+    pub fn to_array(&self) -> [f32; 2] {
+        [self.x, self.y]
+    }
+
+    /// Formats this Vector2 as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&[self.x, self.y])
+    }
+
+    /// Parses a Vector2 written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let [x, y] = parse_exact::<2>(s)?;
+        Ok(Self::new(x, y))
+    }
+
+    /// Returns this position rounded to the nearest device pixel, with `device_pixel_ratio` device pixels per unit.
+    #[inline]
+    pub fn snapped_to_pixels(&self, device_pixel_ratio: f32) -> Vector2 {
+        Vector2::new(
+            (self.x * device_pixel_ratio).round() / device_pixel_ratio,
+            (self.y * device_pixel_ratio).round() / device_pixel_ratio,
+        )
+    }
+
 }
 
 impl Add for Vector2 {
@@ -156,4 +413,136 @@ impl Div<f32> for Vector2 {
             y: self.y / scalar,
         }
     }
+}
+
+impl Neg for Vector2 {
+    type Output = Vector2;
+
+    fn neg(self) -> Vector2 {
+        Vector2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Mul<Vector2> for Vector2 {
+    type Output = Vector2;
+
+    fn mul(self, other: Vector2) -> Vector2 {
+        Vector2 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+        }
+    }
+}
+
+impl Div<Vector2> for Vector2 {
+    type Output = Vector2;
+
+    fn div(self, other: Vector2) -> Vector2 {
+        Vector2 {
+            x: self.x / other.x,
+            y: self.y / other.y,
+        }
+    }
+}
+
+impl Mul<Vector2> for f32 {
+    type Output = Vector2;
+
+    fn mul(self, vector: Vector2) -> Vector2 {
+        vector * self
+    }
+}
+
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, other: Vector2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vector2 {
+    fn sub_assign(&mut self, other: Vector2) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vector2 {
+    fn mul_assign(&mut self, other: f32) {
+        *self = *self * other;
+    }
+}
+
+impl MulAssign<Vector2> for Vector2 {
+    fn mul_assign(&mut self, other: Vector2) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<f32> for Vector2 {
+    fn div_assign(&mut self, other: f32) {
+        *self = *self / other;
+    }
+}
+
+impl DivAssign<Vector2> for Vector2 {
+    fn div_assign(&mut self, other: Vector2) {
+        *self = *self / other;
+    }
+}
+
+/// Converts a Vector3 into a Vector2 by dropping z.
+impl From<Vector3> for Vector2 {
+    fn from(v: Vector3) -> Self {
+        v.truncate()
+    }
+}
+
+impl Index<usize> for Vector2 {
+    type Output = f32;
+
+    /// Returns the component at `index` in x, y order, panicking if it is 2 or more.
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Vector2 index out of range: the index is {index} but there are only 2 components"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vector2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Vector2 index out of range: the index is {index} but there are only 2 components"),
+        }
+    }
+}
+
+impl From<[f32; 2]> for Vector2 {
+    fn from([x, y]: [f32; 2]) -> Self {
+        Vector2::new(x, y)
+    }
+}
+
+impl From<Vector2> for [f32; 2] {
+    fn from(v: Vector2) -> Self {
+        v.to_array()
+    }
+}
+
+impl From<(f32, f32)> for Vector2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Vector2::new(x, y)
+    }
+}
+
+impl Lerp for Vector2 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vector2::lerp(self, other, t)
+    }
 }
\ No newline at end of file
@@ -1,39 +1,81 @@
-use std::ops::{Add, Div, Mul, Sub};
-use crate::math::fast_inv_sqrt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::math::{default_sqrt, Lerp, fast_inv_sqrt, from_snorm8, from_unorm8, parse_exact, to_snorm8, to_unorm8, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
 use crate::angles::quaternion::Quaternion;
+use crate::types::Axis;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector4::Vector4;
 
 /// A vector with x, y, and z components.
 /// They are used to represent a point or direction in 3d space.
-#[derive(Copy, Clone, Debug)]
+///
+/// All operators work component-wise: `+`, `-` and negation, `*` and `/` with a scalar or with another vector.
+/// Use `dot` and `cross` for the vector products.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
     pub z: f32,
 }
 
+const _: () = assert!(core::mem::size_of::<Vector3>() == 12 && core::mem::align_of::<Vector3>() == 4);
+
 impl Vector3 {
 
+    /// All components set to 0.
+    pub const ZERO: Vector3 = Vector3::zero();
+    /// All components set to 1.
+    pub const ONE: Vector3 = Vector3::one();
+    /// The unit x axis.
+    pub const X: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+    /// The unit y axis.
+    pub const Y: Vector3 = Vector3::new(0.0, 1.0, 0.0);
+    /// The unit z axis.
+    pub const Z: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
+    // The directions follow the right-handed, y up convention of `look_at` and the projections,
+    // where the camera looks down -z, so +x is right and -z is forward.
+
+    /// Up, +y.
+    pub const UP: Vector3 = Vector3::new(0.0, 1.0, 0.0);
+    /// Down, -y.
+    pub const DOWN: Vector3 = Vector3::new(0.0, -1.0, 0.0);
+    /// Left, -x.
+    pub const LEFT: Vector3 = Vector3::new(-1.0, 0.0, 0.0);
+    /// Right, +x.
+    pub const RIGHT: Vector3 = Vector3::new(1.0, 0.0, 0.0);
+    /// Forward, -z.
+    pub const FORWARD: Vector3 = Vector3::new(0.0, 0.0, -1.0);
+    /// Back, +z.
+    pub const BACK: Vector3 = Vector3::new(0.0, 0.0, 1.0);
+
     /// Creates a new vector with the given x, y, and z components.
     #[inline]
-    pub fn new(x: f32, y: f32, z: f32) -> Vector3 {
+    pub const fn new(x: f32, y: f32, z: f32) -> Vector3 {
         Vector3 { x, y, z }
     }
 
     /// Creates a new vector with all components set to 0.
     #[inline]
-    pub fn zero() -> Vector3 {
+    pub const fn zero() -> Vector3 {
         Vector3::new(0.0, 0.0, 0.0)
     }
 
     /// Creates a new vector with all components set to 1.
     #[inline]
-    pub fn one() -> Vector3 {
+    pub const fn one() -> Vector3 {
         Vector3::new(1.0, 1.0, 1.0)
     }
 
     /// Creates a new vector with all components set to the given value.
     #[inline]
-    pub fn from_one(x: f32) -> Vector3 {
+    pub const fn from_one(x: f32) -> Vector3 {
         Vector3::new(x, x, x)
     }
 
@@ -43,9 +85,66 @@ impl Vector3 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
+    /// Returns the cross product of this and other vector.
+    /// Right-handed, so the x axis crossed with the y axis gives the z axis. The result is perpendicular to both inputs.
+    #[inline]
+    pub fn cross(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Returns two unit vectors `(tangent, bitangent)` which form a right-handed orthonormal basis with this unit vector,
+    /// so `tangent.cross(&bitangent)` is `self`. Uses the branchless construction by Duff et al., which is continuous
+    /// everywhere except at `z = 0` with `z` changing sign. The vector has to be normalized.
+    pub fn orthonormal_basis(&self) -> (Vector3, Vector3) {
+        let sign = 1.0f32.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Vector3::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Vector3::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
+
+    /// Returns the component along `axis`.
+    #[inline]
+    pub fn component(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    /// Sets the component along `axis` to `value`.
+    #[inline]
+    pub fn set_component(&mut self, axis: Axis, value: f32) {
+        match axis {
+            Axis::X => self.x = value,
+            Axis::Y => self.y = value,
+            Axis::Z => self.z = value,
+        }
+    }
+
     /// Returns the magnitude (length) of the vector.
     #[inline]
     pub fn magnitude(&self) -> f32 {
+        default_sqrt(self.magnitude_squared())
+    }
+
+    /// Returns an approximation of the magnitude using `fast_inv_sqrt`, with a relative error below 0.2% and exactly 0 for a zero vector.
+    #[inline]
+    pub fn magnitude_fast(&self) -> f32 {
+        let magnitude_squared = self.magnitude_squared();
+        magnitude_squared * fast_inv_sqrt(magnitude_squared)
+    }
+
+    /// Returns an approximation of 1 / magnitude using `fast_inv_sqrt`, with a relative error below 0.2%.
+    #[inline]
+    pub fn inv_magnitude(&self) -> f32 {
         fast_inv_sqrt(self.magnitude_squared())
     }
 
@@ -56,13 +155,15 @@ impl Vector3 {
     }
 
     /// Returns a normalized copy of this vector.
+    /// A zero length vector is returned unchanged.
     #[inline]
     pub fn normalized(self) -> Vector3 {
-        let magnitude = self.magnitude();
+        let magnitude_squared = self.magnitude_squared();
 
-        if magnitude == 0.0 {
+        if magnitude_squared == 0.0 {
             self
         } else {
+            let magnitude = default_sqrt(magnitude_squared);
             Vector3 {
                 x: self.x / magnitude,
                 y: self.y / magnitude,
@@ -71,6 +172,25 @@ impl Vector3 {
         }
     }
 
+    /// Normalizes this vector in place, see `normalized`.
+    #[inline]
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    /// Returns a normalized copy of this vector using `fast_inv_sqrt`, the length of the result is within 0.2% of 1.
+    /// A zero length vector is returned unchanged.
+    #[inline]
+    pub fn normalized_fast(self) -> Vector3 {
+        let magnitude_squared = self.magnitude_squared();
+
+        if magnitude_squared == 0.0 {
+            self
+        } else {
+            self * fast_inv_sqrt(magnitude_squared)
+        }
+    }
+
     /// Reflects the vector about the given normal.
     #[inline]
     pub fn reflect(self, normal: Vector3) -> Self {
@@ -78,9 +198,91 @@ impl Vector3 {
     }
 
     /// Projects the vector onto the vector other.
+    /// Returns zero if `other` is zero or the projection is not finite, see `try_project`.
     #[inline]
     pub fn project(&self, other: Vector3) -> Self {
-        other.scale(self.dot(&other) /  other.magnitude_squared())
+        self.try_project(other).unwrap_or(Vector3::ZERO)
+    }
+
+    /// Projects the vector onto the vector other, or returns `None` if `other` is zero or the projection is not finite.
+    /// Tiny but nonzero vectors like `(1e-30, 0, 0)` are rescaled first, so they still give the exact projection.
+    pub fn try_project(&self, other: Vector3) -> Option<Self> {
+        let largest = other.x.abs().max(other.y.abs()).max(other.z.abs());
+        if largest == 0.0 || !largest.is_finite() {
+            return None;
+        }
+        // Dividing by the largest component keeps the squared magnitude from underflowing to zero.
+        let direction = other / largest;
+        let projected = direction.scale(self.dot(&direction) / direction.magnitude_squared());
+        projected.is_finite().then_some(projected)
+    }
+
+    /// Returns true if no component is infinite or NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    /// Returns true if any component is NaN.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    /// Returns the component-wise minimum of this and the other vector.
+    /// Like `f32::min`, a NaN component loses against a number, so only two NaNs give NaN.
+    #[inline]
+    pub fn min(&self, other: &Self) -> Vector3 {
+        Vector3::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// Returns the component-wise maximum of this and the other vector, with NaN handled like in `min`.
+    #[inline]
+    pub fn max(&self, other: &Self) -> Vector3 {
+        Vector3::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
+
+    /// Clamps every component between the matching components of `min` and `max` with `f32::clamp`,
+    /// so NaN components stay NaN. Panics if a component of `min` is greater than the one of `max` or either is NaN.
+    #[inline]
+    pub fn clamp(&self, min: &Self, max: &Self) -> Vector3 {
+        Vector3::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y), self.z.clamp(min.z, max.z))
+    }
+
+    /// Returns the vector with the absolute value of every component.
+    #[inline]
+    pub fn abs(&self) -> Vector3 {
+        Vector3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Rounds every component down.
+    #[inline]
+    pub fn floor(&self) -> Vector3 {
+        Vector3::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+
+    /// Rounds every component up.
+    #[inline]
+    pub fn ceil(&self) -> Vector3 {
+        Vector3::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+
+    /// Rounds every component to the nearest integer, with halfway cases away from zero like `f32::round`.
+    #[inline]
+    pub fn round(&self) -> Vector3 {
+        Vector3::new(self.x.round(), self.y.round(), self.z.round())
+    }
+
+    /// Returns the smallest component. NaN components are skipped like in `min`, unless all of them are NaN.
+    #[inline]
+    pub fn min_element(&self) -> f32 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Returns the largest component, with NaN handled like in `min_element`.
+    #[inline]
+    pub fn max_element(&self) -> f32 {
+        self.x.max(self.y).max(self.z)
     }
 
     /// Scales this vector by the given scalar.
@@ -93,6 +295,104 @@ impl Vector3 {
         }
     }
 
+    /// Scales this vector in place by the given scalar.
+    #[inline]
+    pub fn scale_mut(&mut self, scalar: f32) {
+        *self = self.scale(scalar);
+    }
+
+    /// Returns a Vector4 with the components of this vector and the given w.
+    #[inline]
+    pub fn extend(&self, w: f32) -> Vector4 {
+        Vector4::new(self.x, self.y, self.z, w)
+    }
+
+    /// Returns the x and y components as a Vector2, dropping z.
+    #[inline]
+    pub fn truncate(&self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Returns the x and y components as a Vector2.
+    #[inline]
+    pub fn xy(&self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Returns the x and z components as a Vector2, e.g. to project a position onto the ground plane.
+    #[inline]
+    pub fn xz(&self) -> Vector2 {
+        Vector2::new(self.x, self.z)
+    }
+
+    /// Returns the y and z components as a Vector2.
+    #[inline]
+    pub fn yz(&self) -> Vector2 {
+        Vector2::new(self.y, self.z)
+    }
+
+    /// Returns a copy with the components in reversed order.
+    #[inline]
+    pub fn zyx(&self) -> Vector3 {
+        Vector3::new(self.z, self.y, self.x)
+    }
+
+    /// Returns a copy with the components rotated one to the left.
+    #[inline]
+    pub fn yzx(&self) -> Vector3 {
+        Vector3::new(self.y, self.z, self.x)
+    }
+
+    /// Returns a copy with the components rotated one to the right.
+    #[inline]
+    pub fn zxy(&self) -> Vector3 {
+        Vector3::new(self.z, self.x, self.y)
+    }
+
+    /// Returns a copy with x replaced by the given value.
+    #[inline]
+    pub fn with_x(&self, x: f32) -> Vector3 {
+        Vector3::new(x, self.y, self.z)
+    }
+
+    /// Returns a copy with y replaced by the given value.
+    #[inline]
+    pub fn with_y(&self, y: f32) -> Vector3 {
+        Vector3::new(self.x, y, self.z)
+    }
+
+    /// Returns a copy with z replaced by the given value.
+    #[inline]
+    pub fn with_z(&self, z: f32) -> Vector3 {
+        Vector3::new(self.x, self.y, z)
+    }
+
+    /// Encodes the components, expected in [0, 1], as unsigned normalized bytes (e.g. for vertex colors).
+    /// Values outside the range are clamped, values in between are rounded to the nearest step.
+    #[inline]
+    pub fn to_unorm8(&self) -> [u8; 3] {
+        [to_unorm8(self.x), to_unorm8(self.y), to_unorm8(self.z)]
+    }
+
+    /// Decodes unsigned normalized bytes into a vector with components in [0, 1].
+    #[inline]
+    pub fn from_unorm8(bytes: [u8; 3]) -> Vector3 {
+        Vector3::new(from_unorm8(bytes[0]), from_unorm8(bytes[1]), from_unorm8(bytes[2]))
+    }
+
+    /// Encodes the components, expected in [-1, 1], as signed normalized bytes (e.g. for compressed normals).
+    /// Values outside the range are clamped. -1, 0 and 1 survive a round trip exactly.
+    #[inline]
+    pub fn to_snorm8(&self) -> [i8; 3] {
+        [to_snorm8(self.x), to_snorm8(self.y), to_snorm8(self.z)]
+    }
+
+    /// Decodes signed normalized bytes into a vector with components in [-1, 1].
+    #[inline]
+    pub fn from_snorm8(bytes: [i8; 3]) -> Vector3 {
+        Vector3::new(from_snorm8(bytes[0]), from_snorm8(bytes[1]), from_snorm8(bytes[2]))
+    }
+
     /// Converts this Vector into a Quaternion using the given scalar (w) component.
     #[inline]
     pub fn to_quaternion(&self, w: f32) -> Quaternion {
@@ -111,6 +411,30 @@ impl Vector3 {
         (other.x - self.x).powi(2) + (other.y - self.y).powi(2) + (other.z - self.z).powi(2)
     }
 
+    /// Returns the distance between this and other Vector3.
+    #[inline]
+    pub fn distance(&self, other: &Self) -> f32 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Returns the unsigned angle between this and other vector in radians, in [0, π].
+    /// Computed with atan2 instead of acos, so it stays accurate for nearly parallel vectors and never returns NaN.
+    /// If either vector has zero length the angle is 0.
+    #[inline]
+    pub fn angle_between(&self, other: &Self) -> f32 {
+        self.cross(other).magnitude().atan2(self.dot(other))
+    }
+
+    /// Returns the angle from this to other vector in radians, in [-π, π].
+    /// The angle is positive when the rotation is counter-clockwise looking down `axis`,
+    /// i.e. when the cross product points the same way as `axis`.
+    /// If either vector has zero length the angle is 0.
+    pub fn signed_angle(&self, other: &Self, axis: Vector3) -> f32 {
+        let cross = self.cross(other);
+        let angle = cross.magnitude().atan2(self.dot(other));
+        if cross.dot(&axis) < 0.0 { -angle } else { angle }
+    }
+
     pub fn midpoint(&self, other: &Self) -> Self {
         Self {
             x: (self.x + other.x) / 2.0,
@@ -123,6 +447,33 @@ impl Vector3 {
         *self * (1.0 - t) + *other * t
     }
 
+    /// Returns true if every component differs from the one of `other` by at most `epsilon`.
+    /// Always false if a component is NaN.
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
+
+    /// Returns the components as an array in x, y, z order.
+    #[inline]
+    pub fn to_array(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Formats this Vector3 as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&[self.x, self.y, self.z])
+    }
+
+    /// Parses a Vector3 written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let [x, y, z] = parse_exact::<3>(s)?;
+        Ok(Self::new(x, y, z))
+    }
+
 }
 
 impl Mul<f32> for Vector3 {
@@ -179,4 +530,140 @@ impl Div for Vector3 {
             z: self.z / other.z,
         }
     }
+}
+
+impl Neg for Vector3 {
+    type Output = Vector3;
+
+    fn neg(self) -> Vector3 {
+        Vector3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Mul<Vector3> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, other: Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+        }
+    }
+}
+
+impl Div<f32> for Vector3 {
+    type Output = Vector3;
+
+    fn div(self, scalar: f32) -> Vector3 {
+        Vector3 {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+        }
+    }
+}
+
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Vector3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Vector3) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vector3 {
+    fn mul_assign(&mut self, other: f32) {
+        *self = *self * other;
+    }
+}
+
+impl MulAssign<Vector3> for Vector3 {
+    fn mul_assign(&mut self, other: Vector3) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<f32> for Vector3 {
+    fn div_assign(&mut self, other: f32) {
+        *self = *self / other;
+    }
+}
+
+impl DivAssign<Vector3> for Vector3 {
+    fn div_assign(&mut self, other: Vector3) {
+        *self = *self / other;
+    }
+}
+
+/// Converts a Vector2 into a Vector3 with z set to 0.0.
+impl From<Vector2> for Vector3 {
+    fn from(v: Vector2) -> Self {
+        v.extend(0.0)
+    }
+}
+
+/// Converts a Vector4 into a Vector3 by dropping w, without dividing by it.
+impl From<Vector4> for Vector3 {
+    fn from(v: Vector4) -> Self {
+        v.truncate()
+    }
+}
+
+impl Index<usize> for Vector3 {
+    type Output = f32;
+
+    /// Returns the component at `index` in x, y, z order, panicking if it is 3 or more.
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vector3 index out of range: the index is {index} but there are only 3 components"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vector3 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vector3 index out of range: the index is {index} but there are only 3 components"),
+        }
+    }
+}
+
+impl From<[f32; 3]> for Vector3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Vector3::new(x, y, z)
+    }
+}
+
+impl From<Vector3> for [f32; 3] {
+    fn from(v: Vector3) -> Self {
+        v.to_array()
+    }
+}
+
+impl From<(f32, f32, f32)> for Vector3 {
+    fn from((x, y, z): (f32, f32, f32)) -> Self {
+        Vector3::new(x, y, z)
+    }
+}
+
+impl Lerp for Vector3 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vector3::lerp(self, other, t)
+    }
 }
\ No newline at end of file
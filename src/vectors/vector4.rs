@@ -1,34 +1,48 @@
-use std::ops::{Add, Sub, Mul, Div, Neg};
+use core::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Index, IndexMut, Neg};
 use crate::angles::quaternion::Quaternion;
-use crate::math::fast_inv_sqrt;
+use crate::math::{default_sqrt, Lerp, fast_inv_sqrt, from_snorm8, from_unorm8, parse_exact, to_snorm8, to_unorm8, ParseExactError};
+#[cfg(feature = "std")]
+use crate::math::format_exact;
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::Float;
+use crate::vectors::vector2::Vector2;
+use crate::vectors::vector3::Vector3;
 
 /// A vector with x, y, z and w components.
 /// They are used to represent a point or direction in 4d space.
-#[derive(Debug, Copy, Clone)]
+///
+/// All operators work component-wise: `+`, `-` and negation, `*` and `/` with a scalar or with another vector.
+/// Use `dot` for the dot product.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct Vector4 {
-    x: f32,
-    y: f32,
-    z: f32,
-    w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
 }
 
+const _: () = assert!(core::mem::size_of::<Vector4>() == 16 && core::mem::align_of::<Vector4>() == 4);
+
 impl Vector4 {
 
     /// Creates a new vector with the given x, y, z, and w components.
     #[inline]
-    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
         Vector4 { x, y, z, w }
     }
 
     /// Creates a new vector with all components set to 0.
     #[inline]
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Vector4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 }
     }
 
     /// Creates a new vector with all components set to 1.
     #[inline]
-    pub fn one() -> Self {
+    pub const fn one() -> Self {
         Vector4 { x: 1.0, y: 1.0, z: 1.0, w: 1.0 }
     }
 
@@ -40,7 +54,7 @@ impl Vector4 {
 
     /// Creates a new vector with all components set to the given value.
     #[inline]
-    pub fn from_one(one: f32) -> Self {
+    pub const fn from_one(one: f32) -> Self {
         Vector4::new(one, one, one, one)
     }
 
@@ -58,6 +72,19 @@ impl Vector4 {
     /// Returns the magnitude (length) of the vector.
     #[inline]
     pub fn magnitude(&self) -> f32 {
+        default_sqrt(self.squared_magnitude())
+    }
+
+    /// Returns an approximation of the magnitude using `fast_inv_sqrt`, with a relative error below 0.2% and exactly 0 for a zero vector.
+    #[inline]
+    pub fn magnitude_fast(&self) -> f32 {
+        let squared_magnitude = self.squared_magnitude();
+        squared_magnitude * fast_inv_sqrt(squared_magnitude)
+    }
+
+    /// Returns an approximation of 1 / magnitude using `fast_inv_sqrt`, with a relative error below 0.2%.
+    #[inline]
+    pub fn inv_magnitude(&self) -> f32 {
         fast_inv_sqrt(self.squared_magnitude())
     }
 
@@ -68,20 +95,115 @@ impl Vector4 {
     }
 
     /// Returns a normalized copy of this vector.
+    /// A zero length vector is returned unchanged.
+    #[inline]
+    pub fn normalized(&self) -> Self {
+        let squared_magnitude = self.squared_magnitude();
+
+        if squared_magnitude == 0.0 {
+            *self
+        } else {
+            *self / default_sqrt(squared_magnitude)
+        }
+    }
+
+    /// Normalizes this vector in place, see `normalized`.
+    #[inline]
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    /// Returns a normalized copy of this vector using `fast_inv_sqrt`, the length of the result is within 0.2% of 1.
+    /// A zero length vector is returned unchanged.
+    #[inline]
+    pub fn normalized_fast(&self) -> Self {
+        let squared_magnitude = self.squared_magnitude();
+
+        if squared_magnitude == 0.0 {
+            *self
+        } else {
+            self.scale(fast_inv_sqrt(squared_magnitude))
+        }
+    }
+
+    /// Returns a copy of this vector scaled by the given factor.
+    #[inline]
+    pub fn scale(&self, factor: f32) -> Self {
+        *self * factor
+    }
+
+    /// Scales this vector in place by the given factor.
+    #[inline]
+    pub fn scale_mut(&mut self, factor: f32) {
+        *self *= factor;
+    }
+
+    /// Returns the x, y and z components as a Vector3, dropping w.
+    #[inline]
+    pub fn truncate(&self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the x, y and z components as a Vector3.
+    #[inline]
+    pub fn xyz(&self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the x and y components as a Vector2.
+    #[inline]
+    pub fn xy(&self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+
+    /// Returns a copy with x replaced by the given value.
+    #[inline]
+    pub fn with_x(&self, x: f32) -> Self {
+        Vector4 { x, ..*self }
+    }
+
+    /// Returns a copy with y replaced by the given value.
+    #[inline]
+    pub fn with_y(&self, y: f32) -> Self {
+        Vector4 { y, ..*self }
+    }
+
+    /// Returns a copy with z replaced by the given value.
+    #[inline]
+    pub fn with_z(&self, z: f32) -> Self {
+        Vector4 { z, ..*self }
+    }
+
+    /// Returns a copy with w replaced by the given value.
+    #[inline]
+    pub fn with_w(&self, w: f32) -> Self {
+        Vector4 { w, ..*self }
+    }
+
+    /// Encodes the components, expected in [0, 1], as unsigned normalized bytes (e.g. for vertex colors).
+    /// Values outside the range are clamped, values in between are rounded to the nearest step.
     #[inline]
-    pub fn normalized(&mut self) -> Self {
-        let inv_mag = fast_inv_sqrt(self.squared_magnitude());
-        self.scale(inv_mag)
+    pub fn to_unorm8(&self) -> [u8; 4] {
+        [to_unorm8(self.x), to_unorm8(self.y), to_unorm8(self.z), to_unorm8(self.w)]
     }
 
-    /// Scales this vector by the given factor.
-    pub fn scale(&mut self, factor: f32) -> Self {
-        let mut copy = self.clone();
-        copy.x *= factor;
-        copy.y *= factor;
-        copy.z *= factor;
-        copy.w *= factor;
-        copy
+    /// Decodes unsigned normalized bytes into a vector with components in [0, 1].
+    #[inline]
+    pub fn from_unorm8(bytes: [u8; 4]) -> Vector4 {
+        Vector4::new(from_unorm8(bytes[0]), from_unorm8(bytes[1]), from_unorm8(bytes[2]), from_unorm8(bytes[3]))
+    }
+
+    /// Encodes the components, expected in [-1, 1], as signed normalized bytes (e.g. for compressed normals).
+    /// Values outside the range are clamped. -1, 0 and 1 survive a round trip exactly.
+    #[inline]
+    pub fn to_snorm8(&self) -> [i8; 4] {
+        [to_snorm8(self.x), to_snorm8(self.y), to_snorm8(self.z), to_snorm8(self.w)]
+    }
+
+    /// Decodes signed normalized bytes into a vector with components in [-1, 1].
+    #[inline]
+    pub fn from_snorm8(bytes: [i8; 4]) -> Vector4 {
+        Vector4::new(from_snorm8(bytes[0]), from_snorm8(bytes[1]), from_snorm8(bytes[2]), from_snorm8(bytes[3]))
     }
 
     /// Converts this vector to a quaternion
@@ -92,16 +214,96 @@ impl Vector4 {
 
     /// Reflects the vector around the given normal.
     #[inline]
-    pub fn reflect(&self, normal: &mut Vector4) -> Vector4 {
+    pub fn reflect(&self, normal: &Vector4) -> Vector4 {
         *self - normal.scale(2.0 * self.dot(normal))
     }
 
     /// Projects the vector onto the given vector.
-    pub fn project(&self, other: &mut Vector4) -> Vector4 {
-        let dot_product = self.dot(other);
-        let other_squared_magnitude = other.squared_magnitude();
-        let scale_factor = dot_product / other_squared_magnitude;
-        other.scale(scale_factor)
+    /// Returns zero if `other` is zero or the projection is not finite, see `try_project`.
+    pub fn project(&self, other: &Vector4) -> Vector4 {
+        self.try_project(other).unwrap_or(Vector4::zero())
+    }
+
+    /// Projects the vector onto the given vector, or returns `None` if `other` is zero or the projection is not finite.
+    /// Tiny but nonzero vectors like `(1e-30, 0, 0, 0)` are rescaled first, so they still give the exact projection.
+    pub fn try_project(&self, other: &Vector4) -> Option<Vector4> {
+        let largest = other.x.abs().max(other.y.abs()).max(other.z.abs()).max(other.w.abs());
+        if largest == 0.0 || !largest.is_finite() {
+            return None;
+        }
+        // Dividing by the largest component keeps the squared magnitude from underflowing to zero.
+        let direction = *other / largest;
+        let scale_factor = self.dot(&direction) / direction.squared_magnitude();
+        let projected = direction.scale(scale_factor);
+        projected.is_finite().then_some(projected)
+    }
+
+    /// Returns true if no component is infinite or NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
+    /// Returns true if any component is NaN.
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan() || self.w.is_nan()
+    }
+
+    /// Returns the component-wise minimum of this and the other vector.
+    /// Like `f32::min`, a NaN component loses against a number, so only two NaNs give NaN.
+    #[inline]
+    pub fn min(&self, other: &Self) -> Vector4 {
+        Vector4::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z), self.w.min(other.w))
+    }
+
+    /// Returns the component-wise maximum of this and the other vector, with NaN handled like in `min`.
+    #[inline]
+    pub fn max(&self, other: &Self) -> Vector4 {
+        Vector4::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z), self.w.max(other.w))
+    }
+
+    /// Clamps every component between the matching components of `min` and `max` with `f32::clamp`,
+    /// so NaN components stay NaN. Panics if a component of `min` is greater than the one of `max` or either is NaN.
+    #[inline]
+    pub fn clamp(&self, min: &Self, max: &Self) -> Vector4 {
+        Vector4::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y), self.z.clamp(min.z, max.z), self.w.clamp(min.w, max.w))
+    }
+
+    /// Returns the vector with the absolute value of every component.
+    #[inline]
+    pub fn abs(&self) -> Vector4 {
+        Vector4::new(self.x.abs(), self.y.abs(), self.z.abs(), self.w.abs())
+    }
+
+    /// Rounds every component down.
+    #[inline]
+    pub fn floor(&self) -> Vector4 {
+        Vector4::new(self.x.floor(), self.y.floor(), self.z.floor(), self.w.floor())
+    }
+
+    /// Rounds every component up.
+    #[inline]
+    pub fn ceil(&self) -> Vector4 {
+        Vector4::new(self.x.ceil(), self.y.ceil(), self.z.ceil(), self.w.ceil())
+    }
+
+    /// Rounds every component to the nearest integer, with halfway cases away from zero like `f32::round`.
+    #[inline]
+    pub fn round(&self) -> Vector4 {
+        Vector4::new(self.x.round(), self.y.round(), self.z.round(), self.w.round())
+    }
+
+    /// Returns the smallest component. NaN components are skipped like in `min`, unless all of them are NaN.
+    #[inline]
+    pub fn min_element(&self) -> f32 {
+        self.x.min(self.y).min(self.z).min(self.w)
+    }
+
+    /// Returns the largest component, with NaN handled like in `min_element`.
+    #[inline]
+    pub fn max_element(&self) -> f32 {
+        self.x.max(self.y).max(self.z).max(self.w)
     }
 
     /// Returns the middle of this vector and the given vector.
@@ -113,13 +315,18 @@ impl Vector4 {
         Vector4 { x, y, z, w }
     }
 
-    /// Converts the Vectors components to a byte array
-    pub fn as_bytes(&self) -> &[u8; 16] {
-        unsafe { &*(self as *const Self as *const [u8; 16]) }
+    /// Returns the components in x, y, z, w order as bytes in native endianness.
+    pub fn as_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..4].copy_from_slice(&self.x.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&self.y.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&self.z.to_ne_bytes());
+        bytes[12..].copy_from_slice(&self.w.to_ne_bytes());
+        bytes
     }
 
     /// Computes the squared distance between two vectors
-    fn distance_squared(self, other: Vector4) -> f32 {
+    pub fn distance_squared(&self, other: &Self) -> f32 {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
         let dz = self.z - other.z;
@@ -129,7 +336,7 @@ impl Vector4 {
 
     /// Performs a linear interpolation between two vectors
     #[inline]
-    fn lerp(self, other: Vector4, t: f32) -> Vector4 {
+    pub fn lerp(&self, other: &Self, t: f32) -> Vector4 {
         let one_minus_t = 1.0 - t;
         Vector4 {
             x: self.x * one_minus_t + other.x * t,
@@ -138,6 +345,34 @@ impl Vector4 {
             w: self.w * one_minus_t + other.w * t,
         }
     }
+
+    /// Returns true if every component differs from the one of `other` by at most `epsilon`.
+    /// Always false if a component is NaN.
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+            && (self.w - other.w).abs() <= epsilon
+    }
+
+    /// Returns the components as an array in x, y, z, w order.
+    #[inline]
+    pub fn to_array(&self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Formats this Vector4 as comma separated components that `from_string_exact` parses back losslessly.
+    #[cfg(feature = "std")]
+    pub fn to_string_exact(&self) -> String {
+        format_exact(&[self.x, self.y, self.z, self.w])
+    }
+
+    /// Parses a Vector4 written by `to_string_exact`, reproducing every component bit for bit.
+    pub fn from_string_exact(s: &str) -> Result<Self, ParseExactError> {
+        let [x, y, z, w] = parse_exact::<4>(s)?;
+        Ok(Self::new(x, y, z, w))
+    }
 }
 
 impl Neg for Vector4 {
@@ -153,15 +388,6 @@ impl Neg for Vector4 {
     }
 }
 
-impl PartialEq for Vector4 {
-    fn eq(&self, other: &Self) -> bool {
-        (self.x - other.x).abs() < f32::EPSILON
-            && (self.y - other.y).abs() < f32::EPSILON
-            && (self.z - other.z).abs() < f32::EPSILON
-            && (self.w - other.w).abs() < f32::EPSILON
-    }
-}
-
 // Overloading the '+' operator for adding two vectors
 impl Add for Vector4 {
     type Output = Self;
@@ -189,12 +415,21 @@ impl Mul<f32> for Vector4 {
     }
 }
 
-// Overloading the '*' operator for dot product of two vectors
+// Overloading the '*' operator for component-wise multiplication, use `dot` for the dot product
 impl Mul for Vector4 {
-    type Output = f32;
+    type Output = Self;
 
-    fn mul(self, other: Self) -> f32 {
-        self.dot(&other)
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.x * other.x, self.y * other.y, self.z * other.z, self.w * other.w)
+    }
+}
+
+// Overloading the '*' operator for scalar multiplication with the scalar on the left
+impl Mul<Vector4> for f32 {
+    type Output = Vector4;
+
+    fn mul(self, vector: Vector4) -> Vector4 {
+        vector * self
     }
 }
 
@@ -205,4 +440,152 @@ impl Div<f32> for Vector4 {
     fn div(self, scalar: f32) -> Self {
         Self::new(self.x / scalar, self.y / scalar, self.z / scalar, self.w / scalar)
     }
+}
+
+// Overloading the '/' operator for component-wise division
+impl Div for Vector4 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self::new(self.x / other.x, self.y / other.y, self.z / other.z, self.w / other.w)
+    }
+}
+
+impl AddAssign for Vector4 {
+    fn add_assign(&mut self, other: Vector4) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vector4 {
+    fn sub_assign(&mut self, other: Vector4) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vector4 {
+    fn mul_assign(&mut self, other: f32) {
+        *self = *self * other;
+    }
+}
+
+impl MulAssign<Vector4> for Vector4 {
+    fn mul_assign(&mut self, other: Vector4) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<f32> for Vector4 {
+    fn div_assign(&mut self, other: f32) {
+        *self = *self / other;
+    }
+}
+
+impl DivAssign<Vector4> for Vector4 {
+    fn div_assign(&mut self, other: Vector4) {
+        *self = *self / other;
+    }
+}
+
+/// Converts a Vector3 into a Vector4 with w set to 0.0, i.e. a direction. Use `Vector3::extend(1.0)` for points.
+impl From<Vector3> for Vector4 {
+    fn from(v: Vector3) -> Self {
+        v.extend(0.0)
+    }
+}
+
+impl Index<usize> for Vector4 {
+    type Output = f32;
+
+    /// Returns the component at `index` in x, y, z, w order, panicking if it is 4 or more.
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Vector4 index out of range: the index is {index} but there are only 4 components"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vector4 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Vector4 index out of range: the index is {index} but there are only 4 components"),
+        }
+    }
+}
+
+impl From<[f32; 4]> for Vector4 {
+    fn from([x, y, z, w]: [f32; 4]) -> Self {
+        Vector4::new(x, y, z, w)
+    }
+}
+
+impl From<Vector4> for [f32; 4] {
+    fn from(v: Vector4) -> Self {
+        v.to_array()
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Vector4 {
+    fn from((x, y, z, w): (f32, f32, f32, f32)) -> Self {
+        Vector4::new(x, y, z, w)
+    }
+}
+
+impl Lerp for Vector4 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vector4::lerp(self, other, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use core::mem::offset_of;
+    use super::*;
+
+    #[test]
+    fn as_bytes_is_the_components_in_order() {
+        let v = Vector4::new(1.0, -2.0, 0.5, f32::from_bits(0x0102_0304));
+        let bytes = v.as_bytes();
+        for (i, component) in v.to_array().into_iter().enumerate() {
+            assert_eq!(bytes[i * 4..i * 4 + 4], component.to_ne_bytes());
+        }
+        assert_eq!([offset_of!(Vector4, x), offset_of!(Vector4, y), offset_of!(Vector4, z), offset_of!(Vector4, w)], [0, 4, 8, 12]);
+    }
+
+    #[test]
+    fn in_place_methods_match_their_copying_versions() {
+        let v = Vector4::new(3.0, 0.0, -4.0, 12.0);
+        let normalized = v.normalized();
+        assert_eq!(v, Vector4::new(3.0, 0.0, -4.0, 12.0));
+        let mut in_place = v;
+        in_place.normalize();
+        assert_eq!(in_place, normalized);
+        // Loose enough for the `fast_math` magnitude.
+        assert!((in_place.magnitude() - 1.0).abs() < 2e-3);
+        let mut zero = Vector4::zero();
+        zero.normalize();
+        assert_eq!(zero, Vector4::zero());
+
+        let mut scaled = v;
+        scaled.scale_mut(-0.5);
+        assert_eq!(scaled, v.scale(-0.5));
+        assert_eq!(scaled, Vector4::new(-1.5, 0.0, 2.0, -6.0));
+
+        let other = Vector4::new(1.0, 1.0, 0.0, 0.0);
+        assert_eq!(v.lerp(&other, 0.5), Vector4::new(2.0, 0.5, -2.0, 6.0));
+        assert_eq!(v.distance_squared(&other), 4.0 + 1.0 + 16.0 + 144.0);
+        assert_eq!(v.project(&other), Vector4::new(1.5, 1.5, 0.0, 0.0));
+        assert_eq!(other.reflect(&Vector4::new(0.0, 1.0, 0.0, 0.0)), Vector4::new(1.0, -1.0, 0.0, 0.0));
+    }
+
 }
\ No newline at end of file